@@ -14,58 +14,184 @@ pub struct Args {
 pub enum DfftCommand {
     /// Run dfft's TUI
     Run {
-        /// Path of the directory to watch (defaults to current directory)
-        #[arg(short = 'p', long = "path")]
-        path: Option<String>,
+        /// Path of a directory to watch (defaults to current directory); repeat to watch several
+        #[arg(short = 'p', long = "path", value_name = "PATH")]
+        paths: Vec<String>,
+        /// Only watch files matching this glob (eg. "src/**/*.rs"); can be repeated
+        #[arg(long = "include", value_name = "GLOB")]
+        include: Vec<String>,
+        /// Don't watch files matching this glob (eg. "docs"); can be repeated
+        #[arg(long = "exclude", value_name = "GLOB")]
+        exclude: Vec<String>,
+        /// Poll for changes every MILLIS (defaults to 1000), eg. on network or container file systems
+        #[arg(
+            long = "poll",
+            value_name = "MILLIS",
+            num_args = 0..=1,
+            default_missing_value = "1000",
+            value_parser = clap::value_parser!(u64).range(1..)
+        )]
+        poll_millis: Option<u64>,
         /// Start with the setting "follow changes" enabled
-        #[arg(short = 'f', long = "follow-changes")]
+        #[arg(
+            short = 'f',
+            long = "follow-changes",
+            overrides_with = "no_follow_changes"
+        )]
         follow_changes: bool,
+        /// Start with the setting "follow changes" disabled, eg. if the config file enables it
+        #[arg(long = "no-follow-changes", overrides_with = "follow_changes")]
+        no_follow_changes: bool,
+        /// Show repeated appends to a file (eg. a log) as a single, updating change
+        #[arg(long = "collapse-appends", overrides_with = "no_collapse_appends")]
+        collapse_appends: bool,
+        /// Show every append to a file as a change of its own, eg. if the config file collapses them
+        #[arg(long = "no-collapse-appends", overrides_with = "collapse_appends")]
+        no_collapse_appends: bool,
+        /// Prepopulate cache with file snapshots, eg. if the config file skips it
+        #[arg(long = "prepop", overrides_with = "no_prepopulation")]
+        prepopulation: bool,
         /// Skip prepopulating cache with file snapshots
-        #[arg(long = "no-prepop")]
+        #[arg(long = "no-prepop", overrides_with = "prepopulation")]
         no_prepopulation: bool,
+        /// Start with file watching enabled, eg. if the config file disables it
+        #[arg(long = "watch", overrides_with = "no_watch")]
+        watch: bool,
         /// Start with file watching disabled
-        #[arg(long = "no-watch")]
+        #[arg(long = "no-watch", overrides_with = "watch")]
         no_watch: bool,
+        /// Mark a change as reviewed after viewing it for this many seconds
+        #[arg(long = "auto-review", value_name = "SECONDS")]
+        auto_review_after_secs: Option<u64>,
+        /// Path of the config file (defaults to config.toml in dfft's config directory)
+        #[arg(long = "config", value_name = "PATH")]
+        config_path: Option<String>,
+        /// Color theme (auto, dark, light, high-contrast, no-color, or one from the config file)
+        #[arg(long = "theme", value_name = "NAME")]
+        theme: Option<String>,
+        /// Capture mouse events, eg. if the config file turns them off
+        #[arg(long = "mouse", overrides_with = "no_mouse")]
+        mouse: bool,
+        /// Don't capture mouse events (leaves text selection to the terminal)
+        #[arg(long = "no-mouse", overrides_with = "mouse")]
+        no_mouse: bool,
+        /// Start with sound notifications enabled, eg. if the config file disables them
+        #[cfg(feature = "sound")]
+        #[arg(long = "sound", overrides_with = "no_sound")]
+        sound: bool,
         /// Start with sound notifications disabled
         #[cfg(feature = "sound")]
-        #[arg(long = "no-sound")]
+        #[arg(long = "no-sound", overrides_with = "sound")]
         no_sound: bool,
     },
 }
 
+// The value set by a pair of flags that turn a setting on and off, if either was passed; the one
+// passed last wins, so the other one is never set
+pub fn flag_value(on: bool, off: bool) -> Option<bool> {
+    match (on, off) {
+        (true, _) => Some(true),
+        (_, true) => Some(false),
+        _ => None,
+    }
+}
+
 impl std::fmt::Display for Args {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let output = match &self.command {
             DfftCommand::Run {
-                path,
+                paths,
+                include,
+                exclude,
+                poll_millis,
                 follow_changes,
+                no_follow_changes,
+                collapse_appends,
+                no_collapse_appends,
+                prepopulation,
                 no_prepopulation,
+                watch,
                 no_watch,
+                auto_review_after_secs,
+                config_path,
+                theme,
+                mouse,
+                no_mouse,
+                #[cfg(feature = "sound")]
+                sound,
                 #[cfg(feature = "sound")]
                 no_sound,
             } => {
+                let describe_flags = |on: &bool, off: &bool| {
+                    flag_value(*on, *off)
+                        .map(|value| value.to_string())
+                        .unwrap_or_else(|| "not set".to_string())
+                };
+                let follow_changes = describe_flags(follow_changes, no_follow_changes);
+                let collapse_appends = describe_flags(collapse_appends, no_collapse_appends);
+                let prepopulation = describe_flags(prepopulation, no_prepopulation);
+                let watch = describe_flags(watch, no_watch);
+                let mouse = describe_flags(mouse, no_mouse);
+                #[cfg(feature = "sound")]
+                let sound = describe_flags(sound, no_sound);
+                let config_path = config_path.as_deref().unwrap_or("default location");
+                let theme = theme.as_deref().unwrap_or("not set");
+                let paths = if paths.is_empty() {
+                    "current directory".to_string()
+                } else {
+                    paths.join(", ")
+                };
+                let describe_globs = |globs: &[String]| {
+                    if globs.is_empty() {
+                        "none".to_string()
+                    } else {
+                        globs.join(", ")
+                    }
+                };
+                let include = describe_globs(include);
+                let exclude = describe_globs(exclude);
+                let poll = poll_millis
+                    .map(|millis| format!("every {millis}ms"))
+                    .unwrap_or_else(|| "off".to_string());
+                let auto_review = auto_review_after_secs
+                    .map(|secs| format!("after {secs}s"))
+                    .unwrap_or_else(|| "off".to_string());
                 #[cfg(feature = "sound")]
                 let output = format!(
                     r#"
 command:            run TUI
-path:               {}
+path:               {paths}
+include:            {include}
+exclude:            {exclude}
+poll:               {poll}
 follow changes:     {follow_changes}
-no prepopulation:   {no_prepopulation}
-no watch:           {no_watch}
-no sound:           {no_sound}
+collapse appends:   {collapse_appends}
+prepopulate:        {prepopulation}
+watch:              {watch}
+auto review:        {auto_review}
+config file:        {config_path}
+theme:              {theme}
+mouse:              {mouse}
+sound:              {sound}
 "#,
-                    path.as_deref().unwrap_or("current directory"),
                 );
                 #[cfg(not(feature = "sound"))]
                 let output = format!(
                     r#"
 command:            run TUI
-path:               {}
+path:               {paths}
+include:            {include}
+exclude:            {exclude}
+poll:               {poll}
 follow changes:     {follow_changes}
-no prepopulation:   {no_prepopulation}
-no watch:           {no_watch}
+collapse appends:   {collapse_appends}
+prepopulate:        {prepopulation}
+watch:              {watch}
+auto review:        {auto_review}
+config file:        {config_path}
+theme:              {theme}
+mouse:              {mouse}
 "#,
-                    path.as_deref().unwrap_or("current directory"),
                 );
                 output
             }
@@ -0,0 +1,632 @@
+use crate::tui::{KeymapConfig, ThemeConfig};
+use crate::utils::get_config_dir;
+use crate::watcher::WatchSettings;
+use anyhow::Context;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const CONFIG_FILE_NAME: &str = "config.toml";
+pub const PROJECT_CONFIG_FILE_NAME: &str = ".dfft.toml";
+
+// The contents of a config file; anything that's not set falls back to the layer below it
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    follow_changes: Option<bool>,
+    collapse_appends: Option<bool>,
+    prepopulate: Option<bool>,
+    watch: Option<bool>,
+    auto_review_secs: Option<u64>,
+    mouse: Option<bool>,
+    sound: Option<bool>,
+    theme: Option<String>,
+    limits: LimitsConfig,
+    files: FilesConfig,
+    themes: BTreeMap<String, ThemeConfig>,
+    keymap: KeymapConfig,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct LimitsConfig {
+    max_file_size: Option<u64>,
+    max_diffed_file_size: Option<u64>,
+    max_prepopulated_files: Option<usize>,
+    max_cache_size: Option<u64>,
+    compress_snapshots: Option<bool>,
+    persist_snapshots: Option<bool>,
+    debounce_millis: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FilesConfig {
+    text_extensions: Option<Vec<String>>,
+    ignored_extensions: Option<Vec<String>>,
+}
+
+// Values provided via command line flags, which take precedence over config files
+#[derive(Debug, Default)]
+pub struct ConfigOverrides {
+    pub follow_changes: Option<bool>,
+    pub collapse_appends: Option<bool>,
+    pub prepopulate: Option<bool>,
+    pub watch: Option<bool>,
+    pub auto_review_secs: Option<u64>,
+    pub mouse: Option<bool>,
+    pub sound: Option<bool>,
+    pub theme: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    Default,
+    ConfigFile,
+    ProjectConfigFile,
+    Flag,
+}
+
+impl std::fmt::Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::Default => write!(f, "default"),
+            Source::ConfigFile => write!(f, "config file"),
+            Source::ProjectConfigFile => write!(f, "project config file"),
+            Source::Flag => write!(f, "flag"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Setting<T> {
+    pub value: T,
+    pub source: Source,
+}
+
+impl<T> Setting<T> {
+    fn new(value: T) -> Self {
+        Self {
+            value,
+            source: Source::Default,
+        }
+    }
+
+    fn apply(&mut self, value: Option<T>, source: Source) {
+        if let Some(v) = value {
+            self.value = v;
+            self.source = source;
+        }
+    }
+}
+
+// The effective configuration, after merging the config file in dfft's config directory, the
+// project's config file, and command line flags (in increasing order of precedence)
+#[derive(Debug)]
+pub struct Config {
+    pub files: Vec<PathBuf>,
+    // project config files in the other watched directories, which aren't read
+    pub skipped_files: Vec<PathBuf>,
+    pub follow_changes: Setting<bool>,
+    pub collapse_appends: Setting<bool>,
+    pub prepopulate: Setting<bool>,
+    pub watch: Setting<bool>,
+    pub auto_review_secs: Setting<Option<u64>>,
+    pub mouse: Setting<bool>,
+    #[cfg_attr(not(feature = "sound"), allow(dead_code))]
+    pub sound: Setting<bool>,
+    pub theme: Setting<Option<String>>,
+    pub max_file_size: Setting<u64>,
+    pub max_diffed_file_size: Setting<u64>,
+    pub max_prepopulated_files: Setting<usize>,
+    pub max_cache_size: Setting<u64>,
+    pub compress_snapshots: Setting<bool>,
+    pub persist_snapshots: Setting<bool>,
+    pub debounce_millis: Setting<u64>,
+    pub text_extensions: Setting<Vec<String>>,
+    pub ignored_extensions: Setting<Vec<String>>,
+    pub themes: BTreeMap<String, ThemeConfig>,
+    pub keymap: KeymapConfig,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        let watch_settings = WatchSettings::default();
+
+        Self {
+            files: vec![],
+            skipped_files: vec![],
+            follow_changes: Setting::new(false),
+            collapse_appends: Setting::new(false),
+            prepopulate: Setting::new(true),
+            watch: Setting::new(true),
+            auto_review_secs: Setting::new(None),
+            mouse: Setting::new(true),
+            sound: Setting::new(true),
+            theme: Setting::new(None),
+            max_file_size: Setting::new(watch_settings.max_file_size),
+            max_diffed_file_size: Setting::new(watch_settings.max_diffed_file_size),
+            max_prepopulated_files: Setting::new(watch_settings.max_prepopulated_files),
+            max_cache_size: Setting::new(watch_settings.max_cache_size),
+            compress_snapshots: Setting::new(watch_settings.compress_snapshots),
+            persist_snapshots: Setting::new(watch_settings.persist_snapshots),
+            debounce_millis: Setting::new(watch_settings.debounce.as_millis() as u64),
+            text_extensions: Setting::new(watch_settings.text_extensions),
+            ignored_extensions: Setting::new(watch_settings.ignored_extensions),
+            themes: BTreeMap::new(),
+            keymap: KeymapConfig::default(),
+        }
+    }
+}
+
+impl Config {
+    fn merge(&mut self, file: ConfigFile, source: Source) {
+        self.follow_changes.apply(file.follow_changes, source);
+        self.collapse_appends.apply(file.collapse_appends, source);
+        self.prepopulate.apply(file.prepopulate, source);
+        self.watch.apply(file.watch, source);
+        self.auto_review_secs
+            .apply(file.auto_review_secs.map(Some), source);
+        self.mouse.apply(file.mouse, source);
+        self.sound.apply(file.sound, source);
+        self.theme.apply(file.theme.map(Some), source);
+        self.max_file_size.apply(file.limits.max_file_size, source);
+        self.max_diffed_file_size
+            .apply(file.limits.max_diffed_file_size, source);
+        self.max_prepopulated_files
+            .apply(file.limits.max_prepopulated_files, source);
+        self.max_cache_size
+            .apply(file.limits.max_cache_size, source);
+        self.compress_snapshots
+            .apply(file.limits.compress_snapshots, source);
+        self.persist_snapshots
+            .apply(file.limits.persist_snapshots, source);
+        self.debounce_millis
+            .apply(file.limits.debounce_millis, source);
+        self.text_extensions
+            .apply(file.files.text_extensions, source);
+        self.ignored_extensions
+            .apply(file.files.ignored_extensions, source);
+        self.themes.extend(file.themes);
+        self.keymap.merge(file.keymap);
+    }
+
+    fn apply_overrides(&mut self, overrides: ConfigOverrides) {
+        let source = Source::Flag;
+        self.follow_changes.apply(overrides.follow_changes, source);
+        self.collapse_appends
+            .apply(overrides.collapse_appends, source);
+        self.prepopulate.apply(overrides.prepopulate, source);
+        self.watch.apply(overrides.watch, source);
+        self.auto_review_secs
+            .apply(overrides.auto_review_secs.map(Some), source);
+        self.mouse.apply(overrides.mouse, source);
+        self.sound.apply(overrides.sound, source);
+        self.theme.apply(overrides.theme.map(Some), source);
+    }
+
+    pub fn watch_settings(&self) -> WatchSettings {
+        WatchSettings {
+            max_file_size: self.max_file_size.value,
+            max_diffed_file_size: self.max_diffed_file_size.value,
+            max_prepopulated_files: self.max_prepopulated_files.value,
+            max_cache_size: self.max_cache_size.value,
+            compress_snapshots: self.compress_snapshots.value,
+            persist_snapshots: self.persist_snapshots.value,
+            debounce: Duration::from_millis(self.debounce_millis.value),
+            text_extensions: self.text_extensions.value.clone(),
+            ignored_extensions: self.ignored_extensions.value.clone(),
+            poll_interval: None,
+        }
+    }
+
+    // how the project config files that were skipped are referred to in warnings
+    pub fn describe_skipped_files(&self) -> String {
+        self.skipped_files
+            .iter()
+            .map(|p| p.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    // how the config files that were read are referred to in error messages
+    pub fn describe_files(&self) -> String {
+        let paths = self
+            .files
+            .iter()
+            .map(|p| p.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        match self.files.len() {
+            0 => "defaults".to_string(),
+            1 => format!("config file {paths}"),
+            _ => format!("config files {paths}"),
+        }
+    }
+}
+
+impl std::fmt::Display for Config {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let files = if self.files.is_empty() {
+            "none".to_string()
+        } else {
+            self.files
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let auto_review = self
+            .auto_review_secs
+            .value
+            .map(|secs| format!("after {secs}s"))
+            .unwrap_or_else(|| "off".to_string());
+
+        writeln!(f, "config files read:  {files}")?;
+        if !self.skipped_files.is_empty() {
+            writeln!(f, "skipped configs:    {}", self.describe_skipped_files())?;
+        }
+        writeln!(
+            f,
+            "follow changes:     {} ({})",
+            self.follow_changes.value, self.follow_changes.source
+        )?;
+        writeln!(
+            f,
+            "collapse appends:   {} ({})",
+            self.collapse_appends.value, self.collapse_appends.source
+        )?;
+        writeln!(
+            f,
+            "prepopulate:        {} ({})",
+            self.prepopulate.value, self.prepopulate.source
+        )?;
+        writeln!(
+            f,
+            "watch:              {} ({})",
+            self.watch.value, self.watch.source
+        )?;
+        writeln!(
+            f,
+            "auto review:        {auto_review} ({})",
+            self.auto_review_secs.source
+        )?;
+        writeln!(
+            f,
+            "mouse:              {} ({})",
+            self.mouse.value, self.mouse.source
+        )?;
+        #[cfg(feature = "sound")]
+        writeln!(
+            f,
+            "sound:              {} ({})",
+            self.sound.value, self.sound.source
+        )?;
+        writeln!(
+            f,
+            "theme:              {} ({})",
+            self.theme.value.as_deref().unwrap_or("auto"),
+            self.theme.source
+        )?;
+        writeln!(
+            f,
+            "max file size:      {} bytes ({})",
+            self.max_file_size.value, self.max_file_size.source
+        )?;
+        writeln!(
+            f,
+            "max diffed size:    {} bytes ({})",
+            self.max_diffed_file_size.value, self.max_diffed_file_size.source
+        )?;
+        writeln!(
+            f,
+            "max prepop files:   {} ({})",
+            self.max_prepopulated_files.value, self.max_prepopulated_files.source
+        )?;
+        writeln!(
+            f,
+            "max cache size:     {} bytes ({})",
+            self.max_cache_size.value, self.max_cache_size.source
+        )?;
+        writeln!(
+            f,
+            "compress snapshots: {} ({})",
+            self.compress_snapshots.value, self.compress_snapshots.source
+        )?;
+        writeln!(
+            f,
+            "persist snapshots:  {} ({})",
+            self.persist_snapshots.value, self.persist_snapshots.source
+        )?;
+        writeln!(
+            f,
+            "debounce:           {}ms ({})",
+            self.debounce_millis.value, self.debounce_millis.source
+        )?;
+        writeln!(
+            f,
+            "text extensions:    {} ({})",
+            describe_list(&self.text_extensions.value),
+            self.text_extensions.source
+        )?;
+        writeln!(
+            f,
+            "ignored extensions: {} ({})",
+            describe_list(&self.ignored_extensions.value),
+            self.ignored_extensions.source
+        )?;
+
+        if !self.themes.is_empty() {
+            writeln!(
+                f,
+                "custom themes:      {}",
+                self.themes.keys().cloned().collect::<Vec<_>>().join(", ")
+            )?;
+        }
+
+        let keymap_overrides = self.keymap.overrides();
+        if !keymap_overrides.is_empty() {
+            writeln!(f, "keymap overrides:")?;
+            for entry in keymap_overrides {
+                writeln!(f, "    {entry}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn describe_list(values: &[String]) -> String {
+    if values.is_empty() {
+        "none".to_string()
+    } else {
+        values.join(", ")
+    }
+}
+
+// Builds the effective config from the config file (the one at the provided path, or the one in
+// dfft's config directory), the project's config file in the first root directory, and the
+// overrides from flags. Only a config file whose path is provided explicitly needs to exist.
+// Settings apply to every root, so project config files in the other roots are skipped (and
+// recorded as such, so that they can be warned about).
+pub async fn load_config(
+    path: Option<PathBuf>,
+    roots: &[PathBuf],
+    overrides: ConfigOverrides,
+) -> anyhow::Result<Config> {
+    let (path, required) = match path {
+        Some(p) => (p, true),
+        None => (get_config_dir()?.join(CONFIG_FILE_NAME), false),
+    };
+
+    let mut project_paths = roots.iter().map(|root| root.join(PROJECT_CONFIG_FILE_NAME));
+    let mut layers = vec![(path, required, Source::ConfigFile)];
+    if let Some(project_path) = project_paths.next() {
+        layers.push((project_path, false, Source::ProjectConfigFile));
+    }
+
+    let mut config = Config::default();
+    for (path, required, source) in layers {
+        if let Some(file) = read_config_file(&path, required).await? {
+            config.merge(file, source);
+            config.files.push(path);
+        }
+    }
+    for path in project_paths {
+        if tokio::fs::try_exists(&path).await.unwrap_or(false) {
+            config.skipped_files.push(path);
+        }
+    }
+    config.apply_overrides(overrides);
+
+    Ok(config)
+}
+
+async fn read_config_file(path: &Path, required: bool) -> anyhow::Result<Option<ConfigFile>> {
+    let contents = match tokio::fs::read_to_string(path).await {
+        Ok(c) => c,
+        // the watched path is validated separately, so a project config file that can't exist
+        // (eg. if the path is a file) is skipped
+        Err(e)
+            if matches!(
+                e.kind(),
+                std::io::ErrorKind::NotFound | std::io::ErrorKind::NotADirectory
+            ) && !required =>
+        {
+            return Ok(None);
+        }
+        Err(e) => {
+            return Err(e).with_context(|| format!("couldn't read config file {}", path.display()));
+        }
+    };
+
+    let file = toml::from_str(&contents)
+        .with_context(|| format!("couldn't parse config file {}", path.display()))?;
+
+    Ok(Some(file))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_file(contents: &str) -> ConfigFile {
+        toml::from_str(contents).expect("config should've been parsed")
+    }
+
+    #[test]
+    fn parsing_config_works() {
+        // GIVEN
+        let contents = r##"
+follow_changes = true
+auto_review_secs = 10
+theme = "mine"
+
+[limits]
+max_file_size = 2048
+debounce_millis = 200
+
+[themes.mine]
+base = "light"
+
+[themes.mine.colors]
+primary = "#40a02b"
+
+[keymap.diff]
+"<c-n>" = "next_hunk"
+
+[keymap.changes]
+x = "toggle_reviewed"
+"##;
+
+        // WHEN
+        let result = toml::from_str::<ConfigFile>(contents);
+
+        // THEN
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn parsing_config_with_unknown_sections_fails() {
+        // GIVEN
+        let contents = r#"
+[keymaps.diff]
+"<c-n>" = "next_hunk"
+"#;
+
+        // WHEN
+        let result = toml::from_str::<ConfigFile>(contents);
+
+        // THEN
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn later_layers_take_precedence() {
+        // GIVEN
+        let global = config_file(
+            r#"
+follow_changes = true
+watch = false
+mouse = false
+
+[limits]
+debounce_millis = 200
+
+[keymap.diff]
+"<c-n>" = "next_hunk"
+x = "toggle_reviewed"
+"#,
+        );
+        let project = config_file(
+            r#"
+watch = true
+
+[limits]
+debounce_millis = 100
+
+[keymap.diff]
+x = "copy_path"
+"#,
+        );
+        let overrides = ConfigOverrides {
+            mouse: Some(true),
+            ..Default::default()
+        };
+
+        // WHEN
+        let mut config = Config::default();
+        config.merge(global, Source::ConfigFile);
+        config.merge(project, Source::ProjectConfigFile);
+        config.apply_overrides(overrides);
+
+        // THEN
+        assert_eq!(
+            config.follow_changes,
+            Setting {
+                value: true,
+                source: Source::ConfigFile
+            }
+        );
+        assert_eq!(
+            config.watch,
+            Setting {
+                value: true,
+                source: Source::ProjectConfigFile
+            }
+        );
+        assert_eq!(
+            config.mouse,
+            Setting {
+                value: true,
+                source: Source::Flag
+            }
+        );
+        assert_eq!(
+            config.debounce_millis,
+            Setting {
+                value: 100,
+                source: Source::ProjectConfigFile
+            }
+        );
+        assert_eq!(
+            config.prepopulate,
+            Setting {
+                value: true,
+                source: Source::Default
+            }
+        );
+        assert_eq!(
+            config.keymap.overrides(),
+            vec![
+                r#"[keymap.diff] "<c-n>" = "next_hunk""#.to_string(),
+                r#"[keymap.diff] "x" = "copy_path""#.to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn watch_settings_are_built_from_limits_and_files() {
+        // GIVEN
+        let mut config = Config::default();
+        config.merge(
+            config_file(
+                r#"
+[limits]
+max_file_size = 2048
+max_diffed_file_size = 4096
+max_prepopulated_files = 50
+max_cache_size = 8192
+compress_snapshots = false
+persist_snapshots = true
+
+[files]
+text_extensions = ["dat"]
+"#,
+            ),
+            Source::ConfigFile,
+        );
+
+        // WHEN
+        let settings = config.watch_settings();
+
+        // THEN
+        assert_eq!(
+            settings,
+            WatchSettings {
+                max_file_size: 2048,
+                max_diffed_file_size: 4096,
+                max_prepopulated_files: 50,
+                max_cache_size: 8192,
+                compress_snapshots: false,
+                persist_snapshots: true,
+                debounce: WatchSettings::default().debounce,
+                text_extensions: vec!["dat".to_string()],
+                ignored_extensions: vec![],
+                poll_interval: None,
+            }
+        );
+    }
+}
@@ -1,34 +1,164 @@
-use std::collections::HashMap;
+use super::contents::{Fingerprint, stable_hash};
+use super::encoding::TextEncoding;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 
+// Snapshots of the files being watched. Identical files share a single copy of their contents, and
+// when a memory budget is set, snapshots of the files that changed the longest time ago are
+// compressed (if enabled), and then evicted, to stay within it.
 #[derive(Debug)]
 pub struct FileCache {
-    cache: HashMap<String, Arc<str>>,
+    files: HashMap<String, Entry>,
+    // contents of text files, keyed by their hash
+    blobs: HashMap<u64, Blob>,
+    // paths by when they were last changed, oldest first
+    changed: BTreeMap<u64, String>,
+    // uncompressed blobs by when they were last used, oldest first
+    uncompressed: BTreeMap<u64, u64>,
+    clock: u64,
+    bytes_used: usize,
+    budget: Option<usize>,
+    compress: bool,
+}
+
+#[derive(Clone, Debug)]
+pub enum CachedFile {
+    Text {
+        contents: Arc<str>,
+        encoding: TextEncoding,
+    },
+    Fingerprint(Fingerprint),
+}
+
+#[derive(Debug)]
+struct Entry {
+    snapshot: Snapshot,
+    changed_at: u64,
+}
+
+// What's cached for a file; the contents of text files are kept separately, as blobs, so that
+// identical files can share them
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Snapshot {
+    Text { blob: u64, encoding: TextEncoding },
+    Fingerprint(Fingerprint),
+}
+
+#[derive(Debug)]
+struct Blob {
+    contents: BlobContents,
+    refs: usize,
+    used_at: u64,
+}
+
+#[derive(Debug)]
+enum BlobContents {
+    Plain(Arc<str>),
+    Compressed(Box<[u8]>),
+}
+
+impl BlobContents {
+    fn size(&self) -> usize {
+        match self {
+            BlobContents::Plain(contents) => contents.len(),
+            BlobContents::Compressed(bytes) => bytes.len(),
+        }
+    }
+
+    fn get(&self) -> Arc<str> {
+        match self {
+            BlobContents::Plain(contents) => Arc::clone(contents),
+            // the bytes were compressed from a valid string, so they can only turn out to be invalid
+            // if something's really wrong; an empty snapshot is the least harmful way to handle that
+            BlobContents::Compressed(bytes) => lz4_flex::decompress_size_prepended(bytes)
+                .ok()
+                .and_then(|bytes| String::from_utf8(bytes).ok())
+                .unwrap_or_default()
+                .into(),
+        }
+    }
+}
+
+impl CachedFile {
+    #[cfg(test)]
+    pub fn text(&self) -> Option<&str> {
+        match self {
+            CachedFile::Text { contents, .. } => Some(contents),
+            CachedFile::Fingerprint(_) => None,
+        }
+    }
+
+    // what the file looked like as a binary file, eg. when it went from being text to binary
+    pub fn fingerprint(&self) -> Fingerprint {
+        match self {
+            CachedFile::Text { contents, .. } => Fingerprint::new(contents.as_bytes()),
+            CachedFile::Fingerprint(info) => *info,
+        }
+    }
+}
+
+// How much memory the cache uses for file contents, and how much it may use
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheUsage {
+    pub num_files: usize,
+    pub bytes_used: usize,
+    pub budget: Option<usize>,
 }
 
 impl FileCache {
     pub fn new() -> Self {
         Self {
-            cache: HashMap::new(),
+            files: HashMap::new(),
+            blobs: HashMap::new(),
+            changed: BTreeMap::new(),
+            uncompressed: BTreeMap::new(),
+            clock: 0,
+            bytes_used: 0,
+            budget: None,
+            compress: false,
         }
     }
 
-    pub fn insert<P, C>(&mut self, path: P, contents: C) -> Option<Arc<str>>
+    pub fn with_budget(mut self, budget: usize, compress: bool) -> Self {
+        self.budget = Some(budget);
+        self.compress = compress;
+        self
+    }
+
+    pub fn insert<P, C>(
+        &mut self,
+        path: P,
+        contents: C,
+        encoding: TextEncoding,
+    ) -> Option<CachedFile>
     where
         P: AsRef<str>,
         C: AsRef<str>,
     {
-        let arc_contents: Arc<str> = contents.as_ref().into();
-        let normalized_path = Self::normalize_path(path);
-        self.cache.insert(normalized_path, arc_contents)
+        let blob = self.add_blob(contents.as_ref());
+        let previous = self.put(path, Snapshot::Text { blob, encoding });
+        self.enforce_budget(blob);
+
+        previous
     }
 
-    pub fn remove<P>(&mut self, path: P) -> Option<Arc<str>>
+    pub fn insert_fingerprint<P>(&mut self, path: P, info: Fingerprint) -> Option<CachedFile>
+    where
+        P: AsRef<str>,
+    {
+        self.put(path, Snapshot::Fingerprint(info))
+    }
+
+    pub fn remove<P>(&mut self, path: P) -> Option<CachedFile>
     where
         P: AsRef<str>,
     {
         let normalized_path = Self::normalize_path(path);
-        self.cache.remove(&normalized_path)
+        let entry = self.files.remove(&normalized_path)?;
+        self.changed.remove(&entry.changed_at);
+        self.tick();
+
+        Some(self.release(entry.snapshot))
     }
 
     pub fn remove_directory<P>(&mut self, dir_path: P) -> bool
@@ -48,30 +178,235 @@ impl FileCache {
             format!("{normalized_path}/")
         };
 
-        let len_before = self.cache.len();
-        self.cache.retain(|path, _| !path.starts_with(&dir_prefix));
-        self.cache.len() < len_before
+        let paths = self
+            .files
+            .keys()
+            .filter(|path| path.starts_with(&dir_prefix))
+            .cloned()
+            .collect::<Vec<_>>();
+        for path in &paths {
+            self.remove(path);
+        }
+
+        !paths.is_empty()
     }
 
+    #[cfg(test)]
     pub fn len(&self) -> usize {
-        self.cache.len()
+        self.files.len()
+    }
+
+    pub fn usage(&self) -> CacheUsage {
+        CacheUsage {
+            num_files: self.files.len(),
+            bytes_used: self.bytes_used,
+            budget: self.budget,
+        }
+    }
+
+    // Changes whenever the cache does, so that whoever keeps a copy of it knows when to update it
+    pub fn generation(&self) -> u64 {
+        self.clock
+    }
+
+    pub fn snapshots(&self) -> Vec<(String, Snapshot)> {
+        self.files
+            .iter()
+            .map(|(path, entry)| (path.clone(), entry.snapshot))
+            .collect()
+    }
+
+    pub fn contains<P: AsRef<str>>(&self, path: P) -> bool {
+        self.files.contains_key(&Self::normalize_path(path))
+    }
+
+    // The contents of a text file; for a snapshot that isn't compressed, they're shared rather than
+    // copied
+    pub fn text_contents<P: AsRef<str>>(&self, path: P) -> Option<Arc<str>> {
+        match self.files.get(&Self::normalize_path(path))?.snapshot {
+            Snapshot::Text { blob, .. } => self.blob(blob),
+            Snapshot::Fingerprint(_) => None,
+        }
+    }
+
+    pub fn blob(&self, key: u64) -> Option<Arc<str>> {
+        self.blobs.get(&key).map(|blob| blob.contents.get())
     }
 
     fn normalize_path<P: AsRef<str>>(path: P) -> String {
         path.as_ref().replace('\\', "/")
     }
 
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    // Stores the entry for a path, returning what was stored for it before
+    fn put<P: AsRef<str>>(&mut self, path: P, snapshot: Snapshot) -> Option<CachedFile> {
+        let normalized_path = Self::normalize_path(path);
+        let changed_at = self.tick();
+        self.changed.insert(changed_at, normalized_path.clone());
+
+        let previous = self.files.insert(
+            normalized_path,
+            Entry {
+                snapshot,
+                changed_at,
+            },
+        )?;
+        self.changed.remove(&previous.changed_at);
+
+        Some(self.release(previous.snapshot))
+    }
+
+    // Returns the key of the blob holding these contents, adding one if there's none yet. Keys are
+    // content hashes (stable ones, since they name the blobs saved with snapshots); in the unlikely
+    // case of a collision, the next free key is used.
+    fn add_blob(&mut self, contents: &str) -> u64 {
+        let mut key = stable_hash(contents.as_bytes());
+        let used_at = self.tick();
+
+        loop {
+            match self.blobs.get_mut(&key) {
+                Some(blob) if *blob.contents.get() == *contents => {
+                    blob.refs += 1;
+                    if let BlobContents::Plain(_) = blob.contents {
+                        self.uncompressed.remove(&blob.used_at);
+                        self.uncompressed.insert(used_at, key);
+                    }
+                    blob.used_at = used_at;
+                    return key;
+                }
+                Some(_) => key = key.wrapping_add(1),
+                None => break,
+            }
+        }
+
+        self.bytes_used += contents.len();
+        self.uncompressed.insert(used_at, key);
+        self.blobs.insert(
+            key,
+            Blob {
+                contents: BlobContents::Plain(contents.into()),
+                refs: 1,
+                used_at,
+            },
+        );
+
+        key
+    }
+
+    // Drops an entry's hold on its contents, returning what the entry stood for
+    fn release(&mut self, snapshot: Snapshot) -> CachedFile {
+        let (key, encoding) = match snapshot {
+            Snapshot::Fingerprint(info) => return CachedFile::Fingerprint(info),
+            Snapshot::Text { blob, encoding } => (blob, encoding),
+        };
+
+        let Some(blob) = self.blobs.get_mut(&key) else {
+            return CachedFile::Text {
+                contents: "".into(),
+                encoding,
+            };
+        };
+        let contents = blob.contents.get();
+
+        blob.refs -= 1;
+        if blob.refs == 0 {
+            self.bytes_used -= blob.contents.size();
+            if let BlobContents::Plain(_) = blob.contents {
+                self.uncompressed.remove(&blob.used_at);
+            }
+            self.blobs.remove(&key);
+        }
+
+        CachedFile::Text { contents, encoding }
+    }
+
+    // Compresses the snapshots that were used the longest time ago, and then evicts the files that
+    // changed the longest time ago, until the cache fits within its budget. The contents that were
+    // just added are left alone.
+    fn enforce_budget(&mut self, just_added: u64) {
+        let Some(budget) = self.budget.filter(|&budget| self.bytes_used > budget) else {
+            return;
+        };
+
+        if self.compress {
+            let cold = self
+                .uncompressed
+                .iter()
+                .map(|(&used_at, &key)| (used_at, key))
+                .filter(|&(_, key)| key != just_added)
+                .collect::<Vec<_>>();
+            for (used_at, key) in cold {
+                if self.bytes_used <= budget {
+                    return;
+                }
+                self.compress_blob(used_at, key);
+            }
+        }
+
+        while self.bytes_used > budget {
+            let Some(path) = self
+                .changed
+                .values()
+                .find(|path| {
+                    !matches!(
+                        self.files.get(*path).map(|entry| &entry.snapshot),
+                        Some(Snapshot::Text { blob, .. }) if *blob == just_added
+                    )
+                })
+                .cloned()
+            else {
+                return;
+            };
+            self.remove(path);
+        }
+    }
+
+    fn compress_blob(&mut self, used_at: u64, key: u64) {
+        let Some(blob) = self.blobs.get_mut(&key) else {
+            return;
+        };
+        let BlobContents::Plain(contents) = &blob.contents else {
+            return;
+        };
+
+        let compressed = lz4_flex::compress_prepend_size(contents.as_bytes());
+        self.uncompressed.remove(&used_at);
+        // there's no point in keeping contents that don't compress well around in compressed form
+        if compressed.len() >= contents.len() {
+            return;
+        }
+
+        self.bytes_used -= contents.len() - compressed.len();
+        blob.contents = BlobContents::Compressed(compressed.into_boxed_slice());
+    }
+
     #[cfg(test)]
     fn paths(&self) -> Vec<String> {
-        let mut keys = self.cache.keys().cloned().collect::<Vec<_>>();
+        let mut keys = self.files.keys().cloned().collect::<Vec<_>>();
         keys.sort();
         keys
     }
+
+    #[cfg(test)]
+    fn is_compressed<P: AsRef<str>>(&self, path: P) -> bool {
+        match self.files.get(path.as_ref()).map(|entry| &entry.snapshot) {
+            Some(Snapshot::Text { blob, .. }) => matches!(
+                self.blobs.get(blob).map(|b| &b.contents),
+                Some(BlobContents::Compressed(_))
+            ),
+            _ => false,
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    const UTF8: TextEncoding = TextEncoding::UTF8;
     use insta::assert_yaml_snapshot;
 
     #[test]
@@ -92,10 +427,10 @@ mod tests {
 
         // WHEN
         // THEN
-        cache.insert("file1.txt", "content");
+        cache.insert("file1.txt", "content", UTF8);
         assert_eq!(cache.len(), 1);
 
-        cache.insert("file2.txt", "content");
+        cache.insert("file2.txt", "content", UTF8);
         assert_eq!(cache.len(), 2);
 
         cache.remove("file1.txt");
@@ -111,7 +446,7 @@ mod tests {
         let mut cache = FileCache::new();
 
         // WHEN
-        let result = cache.insert("file.txt", "content");
+        let result = cache.insert("file.txt", "content", UTF8);
 
         // THEN
         assert!(result.is_none());
@@ -122,26 +457,47 @@ mod tests {
     fn inserting_file_returns_previous_content_when_overwriting() {
         // GIVEN
         let mut cache = FileCache::new();
-        cache.insert("file.txt", "old content");
+        cache.insert("file.txt", "old content", UTF8);
 
         // WHEN
         let result = cache
-            .insert("file.txt", "new content")
+            .insert("file.txt", "new content", UTF8)
             .expect("insert should've returned previous content");
 
         // THEN
-        assert_eq!(result.as_ref(), "old content");
+        assert_eq!(result.text(), Some("old content"));
         assert_yaml_snapshot!(cache.paths(), @"- file.txt");
     }
 
+    #[test]
+    fn inserting_binary_file_over_text_returns_previous_content() {
+        // GIVEN
+        let mut cache = FileCache::new();
+        cache.insert("file.bin", "old content", UTF8);
+        let info = Fingerprint::new(b"\0\x01");
+
+        // WHEN
+        let result = cache
+            .insert_fingerprint("file.bin", info)
+            .expect("insert should've returned previous content");
+
+        // THEN
+        assert_eq!(result.text(), Some("old content"));
+        assert_eq!(result.fingerprint(), Fingerprint::new(b"old content"));
+        assert_eq!(
+            cache.remove("file.bin").map(|c| c.fingerprint()),
+            Some(info)
+        );
+    }
+
     #[test]
     fn inserting_path_with_backslashes_normalizes_to_forward_slashes() {
         // GIVEN
         let mut cache = FileCache::new();
 
         // WHEN
-        cache.insert("src\\main.rs", "content");
-        cache.insert("src\\lib.rs", "lib content");
+        cache.insert("src\\main.rs", "content", UTF8);
+        cache.insert("src\\lib.rs", "lib content", UTF8);
 
         // THEN
         assert_yaml_snapshot!(cache.paths(), @r"
@@ -154,7 +510,7 @@ mod tests {
     fn removing_existing_file_returns_content() {
         // GIVEN
         let mut cache = FileCache::new();
-        cache.insert("file.txt", "content");
+        cache.insert("file.txt", "content", UTF8);
 
         // WHEN
         let result = cache
@@ -162,7 +518,7 @@ mod tests {
             .expect("remove should've returned content");
 
         // THEN
-        assert_eq!(result.as_ref(), "content");
+        assert_eq!(result.text(), Some("content"));
         assert_yaml_snapshot!(cache.paths(), @"[]");
     }
 
@@ -182,10 +538,10 @@ mod tests {
     fn removing_directory_removes_all_files_in_it() {
         // GIVEN
         let mut cache = FileCache::new();
-        cache.insert("src/main.rs", "fn main() {}");
-        cache.insert("src/lib.rs", "pub mod test;");
-        cache.insert("src/utils/mod.rs", "pub fn helper() {}");
-        cache.insert("tests/test.rs", "#[test] fn test() {}");
+        cache.insert("src/main.rs", "fn main() {}", UTF8);
+        cache.insert("src/lib.rs", "pub mod test;", UTF8);
+        cache.insert("src/utils/mod.rs", "pub fn helper() {}", UTF8);
+        cache.insert("tests/test.rs", "#[test] fn test() {}", UTF8);
 
         // WHEN
         let result = cache.remove_directory("src/");
@@ -199,10 +555,10 @@ mod tests {
     fn removing_directory_with_exact_prefix_match_works() {
         // GIVEN
         let mut cache = FileCache::new();
-        cache.insert("src/main.rs", "content");
-        cache.insert("src_backup/main.rs", "backup content");
-        cache.insert("some_src/main.rs", "some file");
-        cache.insert("project/src/main.rs", "some file");
+        cache.insert("src/main.rs", "content", UTF8);
+        cache.insert("src_backup/main.rs", "backup content", UTF8);
+        cache.insert("some_src/main.rs", "some file", UTF8);
+        cache.insert("project/src/main.rs", "some file", UTF8);
 
         // WHEN
         let result = cache.remove_directory("src/");
@@ -220,7 +576,7 @@ mod tests {
     fn removing_directory_returns_false_when_no_files_removed() {
         // GIVEN
         let mut cache = FileCache::new();
-        cache.insert("file.txt", "content");
+        cache.insert("file.txt", "content", UTF8);
 
         // WHEN
         let result = cache.remove_directory("nonexistent/");
@@ -247,11 +603,11 @@ mod tests {
     fn removing_directory_with_nested_paths_works() {
         // GIVEN
         let mut cache = FileCache::new();
-        cache.insert("project/src/main.rs", "main");
-        cache.insert("project/src/lib.rs", "lib");
-        cache.insert("project/tests/test.rs", "test");
-        cache.insert("project/Cargo.toml", "toml");
-        cache.insert("other/file.rs", "other");
+        cache.insert("project/src/main.rs", "main", UTF8);
+        cache.insert("project/src/lib.rs", "lib", UTF8);
+        cache.insert("project/tests/test.rs", "test", UTF8);
+        cache.insert("project/Cargo.toml", "toml", UTF8);
+        cache.insert("other/file.rs", "other", UTF8);
 
         // WHEN
         let result = cache.remove_directory("project/");
@@ -265,11 +621,11 @@ mod tests {
     fn removing_a_nested_directory_works() {
         // GIVEN
         let mut cache = FileCache::new();
-        cache.insert("project/src/main.rs", "main");
-        cache.insert("project/src/lib.rs", "lib");
-        cache.insert("project/tests/test.rs", "test");
-        cache.insert("project/Cargo.toml", "toml");
-        cache.insert("other/file.rs", "other");
+        cache.insert("project/src/main.rs", "main", UTF8);
+        cache.insert("project/src/lib.rs", "lib", UTF8);
+        cache.insert("project/tests/test.rs", "test", UTF8);
+        cache.insert("project/Cargo.toml", "toml", UTF8);
+        cache.insert("other/file.rs", "other", UTF8);
 
         // WHEN
         let result = cache.remove_directory("project/src/");
@@ -287,8 +643,8 @@ mod tests {
     fn removing_directory_with_root_path_doesnt_do_anything() {
         // GIVEN
         let mut cache = FileCache::new();
-        cache.insert("file1.txt", "content1");
-        cache.insert("dir/file2.txt", "content2");
+        cache.insert("file1.txt", "content1", UTF8);
+        cache.insert("dir/file2.txt", "content2", UTF8);
 
         // WHEN
         // THEN
@@ -311,9 +667,9 @@ mod tests {
     fn removing_directory_is_case_sensitive() {
         // GIVEN
         let mut cache = FileCache::new();
-        cache.insert("SRC/main.rs", "content");
-        cache.insert("sRc/main.rs", "content");
-        cache.insert("src/main.rs", "content");
+        cache.insert("SRC/main.rs", "content", UTF8);
+        cache.insert("sRc/main.rs", "content", UTF8);
+        cache.insert("src/main.rs", "content", UTF8);
 
         // WHEN
         let result = cache.remove_directory("src/");
@@ -330,11 +686,11 @@ mod tests {
     fn removing_directory_matches_path_exactly() {
         // GIVEN
         let mut cache = FileCache::new();
-        cache.insert("app/src/main.rs", "main");
-        cache.insert("app/src/lib.rs", "lib");
-        cache.insert("app_test/src/test.rs", "test");
-        cache.insert("myapp/src/other.rs", "other");
-        cache.insert("project/app/src/other.rs", "other");
+        cache.insert("app/src/main.rs", "main", UTF8);
+        cache.insert("app/src/lib.rs", "lib", UTF8);
+        cache.insert("app_test/src/test.rs", "test", UTF8);
+        cache.insert("myapp/src/other.rs", "other", UTF8);
+        cache.insert("project/app/src/other.rs", "other", UTF8);
 
         // WHEN
         let result = cache.remove_directory("app/");
@@ -352,9 +708,9 @@ mod tests {
     fn removing_directory_without_trailing_separator_works() {
         // GIVEN
         let mut cache = FileCache::new();
-        cache.insert("src/main.rs", "fn main() {}");
-        cache.insert("src/lib.rs", "pub mod test;");
-        cache.insert("tests/test.rs", "#[test] fn test() {}");
+        cache.insert("src/main.rs", "fn main() {}", UTF8);
+        cache.insert("src/lib.rs", "pub mod test;", UTF8);
+        cache.insert("tests/test.rs", "#[test] fn test() {}", UTF8);
 
         // WHEN
         let result = cache.remove_directory("src");
@@ -368,11 +724,11 @@ mod tests {
     fn removing_directory_without_trailing_separator_matches_path_exactly() {
         // GIVEN
         let mut cache = FileCache::new();
-        cache.insert("app/main.rs", "main");
-        cache.insert("app/lib.rs", "lib");
-        cache.insert("myapp/config.rs", "config");
-        cache.insert("application/config.rs", "config");
-        cache.insert("project/app/config.rs", "config");
+        cache.insert("app/main.rs", "main", UTF8);
+        cache.insert("app/lib.rs", "lib", UTF8);
+        cache.insert("myapp/config.rs", "config", UTF8);
+        cache.insert("application/config.rs", "config", UTF8);
+        cache.insert("project/app/config.rs", "config", UTF8);
 
         // WHEN
         let result = cache.remove_directory("app");
@@ -390,7 +746,7 @@ mod tests {
     fn removing_windows_path_works() {
         // GIVEN
         let mut cache = FileCache::new();
-        cache.insert("src\\main.rs", "content");
+        cache.insert("src\\main.rs", "content", UTF8);
 
         // WHEN
         let result = cache
@@ -398,7 +754,7 @@ mod tests {
             .expect("removing should have returned content");
 
         // THEN
-        assert_eq!(result.as_ref(), "content");
+        assert_eq!(result.text(), Some("content"));
         assert_yaml_snapshot!(cache.paths(), @"[]");
     }
 
@@ -406,9 +762,9 @@ mod tests {
     fn removing_windows_directory_works() {
         // GIVEN
         let mut cache = FileCache::new();
-        cache.insert("src\\main.rs", "main");
-        cache.insert("src\\lib.rs", "lib");
-        cache.insert("tests\\test.rs", "test");
+        cache.insert("src\\main.rs", "main", UTF8);
+        cache.insert("src\\lib.rs", "lib", UTF8);
+        cache.insert("tests\\test.rs", "test", UTF8);
 
         // WHEN
         let result = cache.remove_directory("src\\");
@@ -422,9 +778,9 @@ mod tests {
     fn removing_windows_directory_without_trailing_separator_works() {
         // GIVEN
         let mut cache = FileCache::new();
-        cache.insert("src\\main.rs", "main");
-        cache.insert("src\\lib.rs", "lib");
-        cache.insert("src_backup\\main.rs", "backup");
+        cache.insert("src\\main.rs", "main", UTF8);
+        cache.insert("src\\lib.rs", "lib", UTF8);
+        cache.insert("src_backup\\main.rs", "backup", UTF8);
 
         // WHEN
         let result = cache.remove_directory("src");
@@ -439,9 +795,9 @@ mod tests {
     fn mixed_path_separators_work_consistently() {
         // GIVEN
         let mut cache = FileCache::new();
-        cache.insert("project\\src\\main.rs", "main");
-        cache.insert("project/src/lib.rs", "lib");
-        cache.insert("project\\tests\\test.rs", "test");
+        cache.insert("project\\src\\main.rs", "main", UTF8);
+        cache.insert("project/src/lib.rs", "lib", UTF8);
+        cache.insert("project\\tests\\test.rs", "test", UTF8);
 
         // WHEN
         let result = cache.remove_directory("project/src");
@@ -450,4 +806,101 @@ mod tests {
         assert!(result);
         assert_yaml_snapshot!(cache.paths(), @"- project/tests/test.rs");
     }
+
+    #[test]
+    fn identical_files_share_their_contents() {
+        // GIVEN
+        let mut cache = FileCache::new();
+
+        // WHEN
+        cache.insert("a/LICENSE", "MIT License", UTF8);
+        cache.insert("b/LICENSE", "MIT License", UTF8);
+
+        // THEN
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.usage().bytes_used, "MIT License".len());
+        let removed = cache
+            .remove("a/LICENSE")
+            .map(|c| c.text().map(String::from));
+        assert_eq!(removed, Some(Some("MIT License".to_string())));
+        assert_eq!(cache.usage().bytes_used, "MIT License".len());
+        cache.remove("b/LICENSE");
+        assert_eq!(cache.usage().bytes_used, 0);
+    }
+
+    #[test]
+    fn overwriting_a_file_updates_memory_used() {
+        // GIVEN
+        let mut cache = FileCache::new();
+        cache.insert("file.txt", "12345", UTF8);
+
+        // WHEN
+        cache.insert("file.txt", "123", UTF8);
+
+        // THEN
+        assert_eq!(cache.usage().bytes_used, 3);
+    }
+
+    #[test]
+    fn files_changed_the_longest_time_ago_are_evicted_to_stay_within_budget() {
+        // GIVEN
+        let mut cache = FileCache::new().with_budget(10, false);
+        cache.insert("a.txt", "aaaa", UTF8);
+        cache.insert("b.txt", "bbbb", UTF8);
+        cache.insert("a.txt", "AAAA", UTF8);
+
+        // WHEN
+        cache.insert("c.txt", "cccc", UTF8);
+
+        // THEN
+        assert_yaml_snapshot!(cache.paths(), @r"
+        - a.txt
+        - c.txt
+        ");
+        assert_eq!(
+            cache.usage(),
+            CacheUsage {
+                num_files: 2,
+                bytes_used: 8,
+                budget: Some(10),
+            }
+        );
+    }
+
+    #[test]
+    fn a_file_larger_than_the_budget_is_still_kept() {
+        // GIVEN
+        let mut cache = FileCache::new().with_budget(4, false);
+        cache.insert("a.txt", "aa", UTF8);
+
+        // WHEN
+        cache.insert("b.txt", "bbbbbbbb", UTF8);
+
+        // THEN
+        assert_yaml_snapshot!(cache.paths(), @"- b.txt");
+    }
+
+    #[test]
+    fn cold_snapshots_are_compressed_before_anything_is_evicted() {
+        // GIVEN
+        let compressible = "let x = 1;\n".repeat(100);
+        let mut cache = FileCache::new().with_budget(compressible.len(), true);
+        cache.insert("old.rs", &compressible, UTF8);
+
+        // WHEN
+        cache.insert("new.rs", "x".repeat(100), UTF8);
+
+        // THEN
+        assert_yaml_snapshot!(cache.paths(), @r"
+        - new.rs
+        - old.rs
+        ");
+        assert!(cache.is_compressed("old.rs"));
+        assert!(!cache.is_compressed("new.rs"));
+        assert!(cache.usage().bytes_used < compressible.len());
+        let previous = cache
+            .insert("old.rs", "let x = 2;\n", UTF8)
+            .expect("insert should've returned previous content");
+        assert_eq!(previous.text(), Some(compressible.as_str()));
+    }
 }
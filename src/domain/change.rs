@@ -1,15 +1,26 @@
-use super::diff::Diff;
+use super::contents::Fingerprint;
+use super::diff::{Diff, DiffContents, DiffStats};
+use super::encoding::TextEncoding;
+use std::borrow::Cow;
 
 pub enum WatchUpdate {
     ChangeReceived(Change),
+    // a change made while dfft wasn't running, found by comparing files with the last session's
+    // snapshots
+    ChangedSinceLastSession(Change),
     PrepopulationFailed(String),
     PrepopulationFinished,
+    // the root is on a file system (eg. NFS, or a Docker bind mount) where file system events
+    // might not fire; contains a description of it
+    PollingSuggested(String),
 }
 
 #[derive(Clone, Debug)]
 pub struct Change {
     pub path: String,
     pub kind: ChangeKind,
+    // the encoding of the file's contents, for changes to text files
+    pub encoding: Option<TextEncoding>,
 }
 
 #[derive(Clone, Debug)]
@@ -18,10 +29,297 @@ pub enum ChangeKind {
     Modified(Result<Modification, String>),
     RemovedFile,
     RemovedDir,
+    Binary(FingerprintChange),
+    // the file is above the size limit for diffing
+    TooLarge(FingerprintChange),
+    Reencoded(EncodingChange),
+}
+
+// A text file's encoding (or BOM) changed; this is reported separately from any changes to its
+// contents
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EncodingChange {
+    pub before: TextEncoding,
+    pub after: TextEncoding,
+}
+
+// Files that can't be diffed (binary files, or ones that are too large) are only compared by
+// their size and hash
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FingerprintChange {
+    // None if the file didn't exist (or wasn't known) before
+    pub before: Option<Fingerprint>,
+    pub after: Fingerprint,
 }
 
 #[derive(Clone, Debug)]
 pub enum Modification {
     InitialSnapshot,
     Diff(Diff),
+    // contents ended up the same as they were at the start, eg. across several changes
+    NoNetChange,
+}
+
+impl ChangeKind {
+    // Lines of a newly created file count as insertions; removals have no stats since the removed
+    // contents aren't known at this point
+    pub fn stats(&self) -> Option<DiffStats> {
+        match self {
+            ChangeKind::Created(Ok(contents)) => Some(DiffStats {
+                insertions: contents.lines().count(),
+                deletions: 0,
+            }),
+            ChangeKind::Modified(Ok(Modification::Diff(diff))) => Some(diff.stats()),
+            _ => None,
+        }
+    }
+
+    // Combines several changes to the same file (in the order they happened) into one, going from
+    // the earliest known contents to the latest ones. Returns None if the changes don't carry
+    // enough information to do that.
+    pub fn net<'a, I>(kinds: I) -> Option<ChangeKind>
+    where
+        I: IntoIterator<Item = &'a ChangeKind>,
+    {
+        let kinds = kinds.into_iter().collect::<Vec<_>>();
+        if let Some(net) = Self::net_append(&kinds) {
+            return Some(net);
+        }
+
+        // None means the file didn't exist
+        let mut base: Option<Option<String>> = None;
+        let mut latest: Option<Option<Cow<str>>> = None;
+        // for files that can't be diffed, along with whether the latest such change was for a file
+        // that was too large (rather than binary)
+        let mut fingerprinted: Option<FingerprintChange> = None;
+        let mut too_large = false;
+        let mut large_file = false;
+
+        for kind in kinds {
+            match kind {
+                ChangeKind::Created(Ok(contents)) => {
+                    base.get_or_insert(None);
+                    latest = Some(Some(Cow::Borrowed(contents)));
+                }
+                ChangeKind::Modified(Ok(Modification::Diff(diff))) => match &diff.contents {
+                    DiffContents::Full(contents) => {
+                        if base.is_none() {
+                            base = Some(Some(diff.old_contents()?));
+                        }
+                        latest = Some(Some(Cow::Borrowed(contents)));
+                        large_file |= diff.large_file;
+                    }
+                    // only what was appended is kept, so the contents before it need to be known already
+                    DiffContents::Appended { old_len, .. } => match &mut latest {
+                        Some(Some(contents)) if contents.len() == *old_len => {
+                            contents.to_mut().push_str(&diff.appended_contents()?);
+                        }
+                        _ => return None,
+                    },
+                },
+                ChangeKind::RemovedFile => latest = Some(None),
+                ChangeKind::Binary(change) | ChangeKind::TooLarge(change) => {
+                    fingerprinted = Some(FingerprintChange {
+                        before: fingerprinted.map_or(change.before, |c| c.before),
+                        after: change.after,
+                    });
+                    too_large = matches!(kind, ChangeKind::TooLarge(_));
+                    latest = None;
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(change) = fingerprinted {
+            // these can only be combined with each other, since their contents aren't known
+            if base.is_some() {
+                return None;
+            }
+
+            let net = match latest {
+                Some(None) => ChangeKind::RemovedFile,
+                _ if change.before == Some(change.after) => {
+                    ChangeKind::Modified(Ok(Modification::NoNetChange))
+                }
+                _ if too_large => ChangeKind::TooLarge(change),
+                _ => ChangeKind::Binary(change),
+            };
+
+            return Some(net);
+        }
+
+        let net = match (base?, latest?) {
+            (None, Some(new)) => ChangeKind::Created(Ok(new.to_string())),
+            (Some(old), Some(new)) => match Diff::new_for(&old, new.into(), large_file) {
+                Some(diff) => ChangeKind::Modified(Ok(Modification::Diff(diff))),
+                None => ChangeKind::Modified(Ok(Modification::NoNetChange)),
+            },
+            (Some(_), None) => ChangeKind::RemovedFile,
+            (None, None) => ChangeKind::Modified(Ok(Modification::NoNetChange)),
+        };
+
+        Some(net)
+    }
+
+    // A file that was only appended to comes down to a single append
+    fn net_append(kinds: &[&ChangeKind]) -> Option<ChangeKind> {
+        let mut diffs = kinds.iter().map(|kind| match kind {
+            ChangeKind::Modified(Ok(Modification::Diff(diff))) if diff.is_append() => Some(diff),
+            _ => None,
+        });
+        let first = diffs.next()??.clone();
+        let merged = diffs.try_fold(first, |merged, diff| merged.merge_append(diff?))?;
+
+        Some(ChangeKind::Modified(Ok(Modification::Diff(merged))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn modified(old: &str, new: &str) -> ChangeKind {
+        ChangeKind::Modified(Ok(Modification::Diff(
+            Diff::new(old, new).expect("diff should've been created"),
+        )))
+    }
+
+    #[test]
+    fn net_change_across_modifications_is_a_single_diff() {
+        // GIVEN
+        let kinds = [
+            modified("a\nb\nc\n", "a\nB\nc\n"),
+            modified("a\nB\nc\n", "a\nB\nc\nd\n"),
+        ];
+
+        // WHEN
+        let net = ChangeKind::net(&kinds);
+
+        // THEN
+        let Some(ChangeKind::Modified(Ok(Modification::Diff(diff)))) = net else {
+            panic!("net change should've been a diff, got: {net:?}");
+        };
+        assert_eq!(diff.old_contents().as_deref(), Some("a\nb\nc\n"));
+        assert_eq!(diff.contents, DiffContents::Full("a\nB\nc\nd\n".into()));
+    }
+
+    #[test]
+    fn net_change_across_appends_is_a_single_append() {
+        // GIVEN
+        let appended = |old: &str, new: &str| {
+            ChangeKind::Modified(Ok(Modification::Diff(
+                Diff::new_for_append(old, new).expect("diff should've been created"),
+            )))
+        };
+        let kinds = [appended("a\n", "a\nb\n"), appended("a\nb\n", "a\nb\nc\n")];
+
+        // WHEN
+        let net = ChangeKind::net(&kinds);
+
+        // THEN
+        let Some(ChangeKind::Modified(Ok(Modification::Diff(diff)))) = net else {
+            panic!("net change should've been a diff, got: {net:?}");
+        };
+        assert_eq!(diff.appended_contents().as_deref(), Some("b\nc\n"));
+    }
+
+    #[test]
+    fn appends_to_a_file_created_during_session_are_added_to_its_contents() {
+        // GIVEN
+        let kinds = [
+            ChangeKind::Created(Ok("a\n".to_string())),
+            ChangeKind::Modified(Ok(Modification::Diff(
+                Diff::new_for_append("a\n", "a\nb\n").expect("diff should've been created"),
+            ))),
+        ];
+
+        // WHEN
+        let net = ChangeKind::net(&kinds);
+
+        // THEN
+        assert!(matches!(net, Some(ChangeKind::Created(Ok(c))) if c == "a\nb\n"));
+    }
+
+    #[test]
+    fn net_change_for_file_created_during_session_is_a_creation() {
+        // GIVEN
+        let kinds = [
+            ChangeKind::Created(Ok("a\n".to_string())),
+            modified("a\n", "a\nb\n"),
+        ];
+
+        // WHEN
+        let net = ChangeKind::net(&kinds);
+
+        // THEN
+        assert!(matches!(net, Some(ChangeKind::Created(Ok(c))) if c == "a\nb\n"));
+    }
+
+    #[test]
+    fn net_change_for_reverted_modifications_is_no_change() {
+        // GIVEN
+        let kinds = [modified("a\n", "b\n"), modified("b\n", "a\n")];
+
+        // WHEN
+        let net = ChangeKind::net(&kinds);
+
+        // THEN
+        assert!(matches!(
+            net,
+            Some(ChangeKind::Modified(Ok(Modification::NoNetChange)))
+        ));
+    }
+
+    #[test]
+    fn net_change_for_removed_file_is_a_removal() {
+        // GIVEN
+        let kinds = [modified("a\n", "b\n"), ChangeKind::RemovedFile];
+
+        // WHEN
+        let net = ChangeKind::net(&kinds);
+
+        // THEN
+        assert!(matches!(net, Some(ChangeKind::RemovedFile)));
+    }
+
+    #[test]
+    fn net_change_across_binary_changes_spans_all_of_them() {
+        // GIVEN
+        let (first, second, third) = (
+            Fingerprint::new(b"\x01"),
+            Fingerprint::new(b"\x02"),
+            Fingerprint::new(b"\x03"),
+        );
+        let kinds = [
+            ChangeKind::Binary(FingerprintChange {
+                before: Some(first),
+                after: second,
+            }),
+            ChangeKind::Binary(FingerprintChange {
+                before: Some(second),
+                after: third,
+            }),
+        ];
+
+        // WHEN
+        let net = ChangeKind::net(&kinds);
+
+        // THEN
+        assert!(matches!(
+            net,
+            Some(ChangeKind::Binary(FingerprintChange { before: Some(b), after: a })) if b == first && a == third
+        ));
+    }
+
+    #[test]
+    fn net_change_needs_known_contents() {
+        // GIVEN
+        let kinds = [ChangeKind::Modified(Ok(Modification::InitialSnapshot))];
+
+        // WHEN
+        let net = ChangeKind::net(&kinds);
+
+        // THEN
+        assert!(net.is_none());
+    }
 }
@@ -0,0 +1,280 @@
+use super::encoding::{TextEncoding, decode_text};
+use encoding_rs::Encoding;
+
+// Only the start of a file is inspected when deciding whether it's binary
+pub const SNIFF_LEN: usize = 8 * 1024;
+// Files where more than this percentage of the inspected bytes aren't valid UTF-8 might be binary
+const MAX_INVALID_UTF8_PERCENT: usize = 30;
+// ... and are, if more than this percentage of them are control characters
+const MAX_CONTROL_CHAR_PERCENT: usize = 1;
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+#[derive(Clone, Debug)]
+pub enum FileContents {
+    Text {
+        contents: String,
+        encoding: TextEncoding,
+    },
+    Binary(Fingerprint),
+    // files above the size limit for diffing aren't read into memory
+    TooLarge(Fingerprint),
+}
+
+// What's known about a file whose contents aren't kept around (eg. a binary file)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Fingerprint {
+    pub size: u64,
+    pub hash: u64,
+}
+
+// Builds a fingerprint from a file's contents, a chunk at a time. Fingerprints are saved along with
+// snapshots, so the hash needs to be the same across builds.
+pub struct FingerprintHasher {
+    hash: u64,
+    size: u64,
+}
+
+impl Default for FingerprintHasher {
+    fn default() -> Self {
+        Self {
+            hash: FNV_OFFSET_BASIS,
+            size: 0,
+        }
+    }
+}
+
+impl FingerprintHasher {
+    pub fn update(&mut self, chunk: &[u8]) {
+        self.hash = extend_stable_hash(self.hash, chunk);
+        self.size += chunk.len() as u64;
+    }
+
+    pub fn finish(&self) -> Fingerprint {
+        Fingerprint {
+            size: self.size,
+            hash: self.hash,
+        }
+    }
+}
+
+// FNV-1a, which (unlike the standard library's hasher) is guaranteed to give the same hash for the
+// same bytes across builds
+pub fn stable_hash(bytes: &[u8]) -> u64 {
+    extend_stable_hash(FNV_OFFSET_BASIS, bytes)
+}
+
+fn extend_stable_hash(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+impl Fingerprint {
+    pub fn new(bytes: &[u8]) -> Self {
+        let mut hasher = FingerprintHasher::default();
+        hasher.update(bytes);
+        hasher.finish()
+    }
+
+    pub fn short_hash(&self) -> String {
+        format!("{:016x}", self.hash)[..8].to_string()
+    }
+}
+
+impl FileContents {
+    // Treats the bytes as text if they look like text (or if forced to), in which case they need to
+    // be decodable
+    pub fn from_bytes(bytes: Vec<u8>, force_text: bool) -> Result<Self, String> {
+        if !force_text && is_binary(&bytes) {
+            return Ok(FileContents::Binary(Fingerprint::new(&bytes)));
+        }
+
+        decode_text(bytes).map(|(contents, encoding)| FileContents::Text { contents, encoding })
+    }
+}
+
+// Only needs the start of a file. Contents with a BOM are always text, since UTF-16 text is full of
+// NUL bytes.
+pub fn is_binary(bytes: &[u8]) -> bool {
+    Encoding::for_bom(bytes).is_none() && looks_binary(bytes)
+}
+
+// A file is considered binary if the first block contains a NUL byte, or if too much of it isn't
+// valid UTF-8 (a small amount is tolerated, eg. for text files with a stray latin-1 character).
+// Text in legacy encodings (eg. Shift-JIS) isn't valid UTF-8 either, but it rarely contains
+// control characters, which is what sets it apart from binary data.
+fn looks_binary(bytes: &[u8]) -> bool {
+    let block = &bytes[..bytes.len().min(SNIFF_LEN)];
+    if block.is_empty() {
+        return false;
+    }
+
+    if block.contains(&0) {
+        return true;
+    }
+
+    let mut invalid = 0;
+    let mut rest = block;
+    while let Err(e) = std::str::from_utf8(rest) {
+        match e.error_len() {
+            Some(len) => {
+                invalid += len;
+                rest = &rest[e.valid_up_to() + len..];
+            }
+            // a multi-byte character cut off at the end of the block
+            None => break,
+        }
+    }
+
+    if invalid * 100 <= block.len() * MAX_INVALID_UTF8_PERCENT {
+        return false;
+    }
+
+    let control_chars = block
+        .iter()
+        .filter(|&&b| (b < 0x20 && !b.is_ascii_whitespace() && b != 0x1b) || b == 0x7f)
+        .count();
+
+    control_chars * 100 > block.len() * MAX_CONTROL_CHAR_PERCENT
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_is_not_binary() {
+        // GIVEN
+        let bytes = "fn main() {\n    println!(\"héllo 👋\");\n}\n".as_bytes();
+
+        // WHEN
+        // THEN
+        assert!(!looks_binary(bytes));
+        assert!(!looks_binary(b""));
+    }
+
+    #[test]
+    fn nul_bytes_mean_binary() {
+        // GIVEN
+        let bytes = b"\0asm\x01\0\0\0";
+
+        // WHEN
+        // THEN
+        assert!(looks_binary(bytes));
+    }
+
+    #[test]
+    fn a_few_invalid_utf8_bytes_are_tolerated() {
+        // GIVEN
+        let mut bytes = "name,city\n".repeat(10).into_bytes();
+        bytes.extend_from_slice(b"Jos\xe9,M\xe1laga\n");
+
+        // WHEN
+        // THEN
+        assert!(!looks_binary(&bytes));
+    }
+
+    #[test]
+    fn mostly_invalid_utf8_with_control_chars_means_binary() {
+        // GIVEN
+        let bytes = (0x01..=0xff).cycle().take(512).collect::<Vec<u8>>();
+
+        // WHEN
+        // THEN
+        assert!(looks_binary(&bytes));
+    }
+
+    #[test]
+    fn text_in_legacy_encodings_is_not_binary() {
+        // GIVEN
+        let text = "日本語のテキストです。\n".repeat(20);
+        let (bytes, _, _) = encoding_rs::SHIFT_JIS.encode(&text);
+
+        // WHEN
+        // THEN
+        assert!(!looks_binary(&bytes));
+    }
+
+    #[test]
+    fn utf16_text_is_decoded_despite_nul_bytes() {
+        // GIVEN
+        let mut bytes = vec![0xff, 0xfe];
+        bytes.extend("hi\n".encode_utf16().flat_map(|u| u.to_le_bytes()));
+
+        // WHEN
+        let result = FileContents::from_bytes(bytes, false);
+
+        // THEN
+        assert!(matches!(result, Ok(FileContents::Text { contents, .. }) if contents == "hi\n"));
+    }
+
+    #[test]
+    fn multi_byte_char_cut_off_by_the_block_is_not_counted_as_invalid() {
+        // GIVEN
+        let mut bytes = "a".repeat(SNIFF_LEN - 1).into_bytes();
+        bytes.extend_from_slice("é".as_bytes());
+
+        // WHEN
+        // THEN
+        assert!(!looks_binary(&bytes));
+    }
+
+    #[test]
+    fn forcing_text_skips_detection() {
+        // GIVEN
+        let bytes = b"a\0b".to_vec();
+
+        // WHEN
+        let result = FileContents::from_bytes(bytes, true);
+
+        // THEN
+        assert!(matches!(result, Ok(FileContents::Text { contents, .. }) if contents == "a\0b"));
+    }
+
+    #[test]
+    fn fingerprint_changes_with_contents() {
+        // GIVEN
+        let before = Fingerprint::new(b"\0\x01\x02");
+
+        // WHEN
+        let after = Fingerprint::new(b"\0\x01\x03");
+
+        // THEN
+        assert_eq!(before.size, after.size);
+        assert_ne!(before.hash, after.hash);
+        assert_eq!(before, Fingerprint::new(b"\0\x01\x02"));
+    }
+
+    #[test]
+    fn fingerprint_doesnt_depend_on_how_contents_are_chunked() {
+        // GIVEN
+        let contents = (0..=255).cycle().take(10_000).collect::<Vec<u8>>();
+        let mut hasher = FingerprintHasher::default();
+
+        // WHEN
+        for chunk in contents.chunks(777) {
+            hasher.update(chunk);
+        }
+
+        // THEN
+        assert_eq!(hasher.finish(), Fingerprint::new(&contents));
+    }
+
+    #[test]
+    fn stable_hash_doesnt_change() {
+        assert_eq!(stable_hash(b""), 0xcbf29ce484222325);
+        assert_eq!(stable_hash(b"a"), 0xaf63dc4c8601ec8c);
+    }
+
+    #[test]
+    fn fingerprint_doesnt_change_across_builds() {
+        assert_eq!(
+            Fingerprint::new(b"a"),
+            Fingerprint {
+                size: 1,
+                hash: 0xaf63dc4c8601ec8c,
+            }
+        );
+    }
+}
@@ -1,13 +1,35 @@
 use crate::utils::num_digits;
 use std::cmp::max;
 use std::fmt::Display;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use similar::ChangeTag;
 use similar::TextDiff;
+use similar::{Algorithm, capture_diff_slices_deadline, group_diff_ops};
+use unicode_width::UnicodeWidthChar;
+
+// How long diffing a large file may take; past this, the diff might not be minimal
+const LARGE_FILE_DIFF_BUDGET: Duration = Duration::from_millis(250);
 
 #[derive(Clone, Debug)]
 pub struct Diff {
     pub hunks: Vec<DiffHunk>,
+    pub contents: DiffContents,
+    // computed in the cheaper mode for large files, without highlighting changes within lines
+    pub large_file: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DiffContents {
+    // the whole new file, shared with the snapshot of the file, if there's one, so that it isn't
+    // held twice
+    Full(Arc<str>),
+    // the file was only appended to, so the diff just has the new lines at its end, preceded by
+    // some context; a file that keeps growing (eg. a log) would otherwise be copied whole for
+    // every append, so only its size (in bytes) before and after is kept
+    Appended { old_len: usize, new_len: usize },
 }
 
 #[derive(Clone, Debug)]
@@ -29,6 +51,19 @@ pub struct InlineChange {
     pub emphasized: bool,
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DiffStats {
+    pub insertions: usize,
+    pub deletions: usize,
+}
+
+impl DiffStats {
+    #[cfg(test)]
+    pub fn churn(&self) -> usize {
+        self.insertions + self.deletions
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum DiffOperation {
     Insert,
@@ -57,9 +92,135 @@ impl From<ChangeTag> for DiffOperation {
     }
 }
 
+impl DiffLine {
+    pub fn unchanged(line: &str, old_line_num: usize, new_line_num: usize) -> Self {
+        Self {
+            kind: DiffOperation::Equal,
+            old_line_num: Some(old_line_num),
+            new_line_num: Some(new_line_num),
+            inline_changes: vec![InlineChange {
+                value: line.to_string(),
+                emphasized: false,
+            }],
+        }
+    }
+
+    pub fn inserted(line: &str, new_line_num: usize) -> Self {
+        Self {
+            kind: DiffOperation::Insert,
+            old_line_num: None,
+            new_line_num: Some(new_line_num),
+            inline_changes: vec![InlineChange {
+                value: line.to_string(),
+                emphasized: false,
+            }],
+        }
+    }
+
+    // Every line of a newly created file counts as an insertion
+    pub fn all_inserted(contents: &str) -> Vec<Self> {
+        contents
+            .split_inclusive('\n')
+            .enumerate()
+            .map(|(i, line)| Self::inserted(line, i))
+            .collect()
+    }
+
+    pub fn content(&self) -> String {
+        let content = self
+            .inline_changes
+            .iter()
+            .map(|inline_change| inline_change.value.as_str())
+            .collect::<String>();
+
+        content.trim_end_matches(['\n', '\r']).to_string()
+    }
+
+    fn to_plain_string(&self, line_number_padding: usize) -> String {
+        let sign = self.kind.sign();
+        let old_line = self
+            .old_line_num
+            .map(|n| format!("{:<padding$}", n + 1, padding = line_number_padding))
+            .unwrap_or_else(|| " ".repeat(line_number_padding));
+
+        let new_line = self
+            .new_line_num
+            .map(|n| format!("{:<padding$}", n + 1, padding = line_number_padding))
+            .unwrap_or_else(|| " ".repeat(line_number_padding));
+
+        let mut line_spans = vec![old_line, new_line, format!("|{sign}")];
+
+        for inline_change in &self.inline_changes {
+            if inline_change.emphasized {
+                line_spans.push(format!("⸢{}⸣", inline_change.value));
+            } else {
+                line_spans.push(inline_change.value.clone());
+            }
+        }
+
+        line_spans.join("")
+    }
+}
+
+impl DiffHunk {
+    // Renders the hunk in the unified diff format, including its "@@" header. `lines_added_before`
+    // is how many more lines the new file has than the old one ahead of the hunk; it's needed to
+    // place a hunk that has no lines on one of its sides.
+    fn to_unified(&self, lines_added_before: isize) -> String {
+        let old_line_nums = self.lines.iter().filter_map(|l| l.old_line_num);
+        let new_line_nums = self.lines.iter().filter_map(|l| l.new_line_num);
+        let (old_start, new_start) =
+            match (old_line_nums.clone().next(), new_line_nums.clone().next()) {
+                (Some(old), Some(new)) => (old + 1, new + 1),
+                // an empty side starts at the line the hunk comes after, as in `diff -u`
+                (Some(old), None) => (old + 1, old.saturating_add_signed(lines_added_before)),
+                (None, Some(new)) => (new.saturating_add_signed(-lines_added_before), new + 1),
+                (None, None) => (0, 0),
+            };
+
+        let mut output = format!(
+            "@@ -{old_start},{} +{new_start},{} @@\n",
+            old_line_nums.count(),
+            new_line_nums.count()
+        );
+        for line in &self.lines {
+            // the line is written as is, so that its line ending (eg. "\r\n") is kept
+            let value = line
+                .inline_changes
+                .iter()
+                .map(|inline_change| inline_change.value.as_str())
+                .collect::<String>();
+            output.push_str(&line.kind.sign());
+            output.push_str(&value);
+            if !value.ends_with('\n') {
+                output.push_str("\n\\ No newline at end of file\n");
+            }
+        }
+
+        output
+    }
+
+    // How many lines the hunk adds to the file; negative if it removes more than it adds
+    fn lines_added(&self) -> isize {
+        self.lines
+            .iter()
+            .map(|line| match line.kind {
+                DiffOperation::Insert => 1,
+                DiffOperation::Delete => -1,
+                DiffOperation::Equal => 0,
+            })
+            .sum()
+    }
+}
+
 impl Diff {
+    #[cfg(test)]
     pub fn new(old: &str, new: &str) -> Option<Self> {
-        let diff = TextDiff::from_lines(old, new);
+        Self::new_with_contents(old, new.into())
+    }
+
+    fn new_with_contents(old: &str, new: Arc<str>) -> Option<Self> {
+        let diff = TextDiff::from_lines(old, &*new);
 
         if diff.ops().is_empty() {
             return None;
@@ -97,41 +258,402 @@ impl Diff {
             return None;
         }
 
-        Some(Diff { hunks })
+        Some(Diff {
+            hunks,
+            contents: DiffContents::Full(new),
+            large_file: false,
+        })
     }
 
-    pub fn line_num_padding(&self) -> usize {
-        let largest_line_num = self
+    pub fn new_for(old: &str, new: Arc<str>, large_file: bool) -> Option<Self> {
+        if let Some(diff) = Self::new_for_append(old, &new) {
+            return Some(diff);
+        }
+
+        if large_file {
+            Self::new_for_large_file(old, new)
+        } else {
+            Self::new_with_contents(old, new)
+        }
+    }
+
+    // Files that only grow (eg. logs) don't need to be diffed at all: if the new contents start with
+    // the old ones, the diff is just the lines at the end, preceded by some context. Returns None
+    // if the file wasn't appended to, or if the append didn't start on a new line.
+    pub fn new_for_append(old: &str, new: &str) -> Option<Self> {
+        if new.len() == old.len()
+            || !new.starts_with(old)
+            || !(old.is_empty() || old.ends_with('\n'))
+        {
+            return None;
+        }
+
+        let num_old_lines = old.split_inclusive('\n').count();
+        let context_start = num_old_lines.saturating_sub(3);
+        let context = old
+            .split_inclusive('\n')
+            .enumerate()
+            .skip(context_start)
+            .map(|(i, line)| DiffLine::unchanged(line, i, i));
+        let appended = new[old.len()..]
+            .split_inclusive('\n')
+            .enumerate()
+            .map(|(i, line)| DiffLine::inserted(line, num_old_lines + i));
+
+        Some(Diff {
+            hunks: vec![DiffHunk {
+                lines: context.chain(appended).collect(),
+            }],
+            contents: DiffContents::Appended {
+                old_len: old.len(),
+                new_len: new.len(),
+            },
+            large_file: false,
+        })
+    }
+
+    // Combines this append with the one that was made to the file right after it, so that the
+    // result goes from the contents before this one to the contents after the next one. Returns
+    // None if either isn't an append, or if the next one didn't start where this one ended.
+    pub fn merge_append(&self, next: &Diff) -> Option<Self> {
+        let (
+            DiffContents::Appended { old_len, new_len },
+            DiffContents::Appended {
+                old_len: next_old_len,
+                new_len: next_new_len,
+            },
+        ) = (&self.contents, &next.contents)
+        else {
+            return None;
+        };
+        if new_len != next_old_len {
+            return None;
+        }
+
+        // the context of the next append is made up of lines this one appended
+        let appended = next
             .hunks
             .iter()
             .flat_map(|hunk| hunk.lines.iter())
-            .map(|diff_line| {
-                max(
-                    diff_line.old_line_num.unwrap_or_default(),
-                    diff_line.new_line_num.unwrap_or_default(),
-                )
+            .filter(|line| line.kind == DiffOperation::Insert)
+            .cloned();
+        let lines = self
+            .hunks
+            .iter()
+            .flat_map(|hunk| hunk.lines.iter())
+            .cloned()
+            .chain(appended)
+            .collect();
+
+        Some(Diff {
+            hunks: vec![DiffHunk { lines }],
+            contents: DiffContents::Appended {
+                old_len: *old_len,
+                new_len: *next_new_len,
+            },
+            large_file: false,
+        })
+    }
+
+    pub fn is_append(&self) -> bool {
+        matches!(self.contents, DiffContents::Appended { .. })
+    }
+
+    // The lines that were appended to the file, if it was only appended to
+    pub fn appended_contents(&self) -> Option<String> {
+        if !self.is_append() {
+            return None;
+        }
+
+        let appended = self
+            .hunks
+            .iter()
+            .flat_map(|hunk| hunk.lines.iter())
+            .filter(|line| line.kind == DiffOperation::Insert)
+            .flat_map(|line| line.inline_changes.iter())
+            .map(|inline_change| inline_change.value.as_str())
+            .collect();
+
+        Some(appended)
+    }
+
+    // A cheaper way of diffing large files: lines are compared by their hashes, changes within
+    // lines aren't looked for, and diffing gives up on finding a minimal diff once it runs out of
+    // time
+    pub fn new_for_large_file(old: &str, new: Arc<str>) -> Option<Self> {
+        if old == &*new {
+            return None;
+        }
+
+        let old_lines = old.split_inclusive('\n').collect::<Vec<_>>();
+        let new_lines = new.split_inclusive('\n').collect::<Vec<_>>();
+        let line_hash = |line: &&str| {
+            let mut hasher = DefaultHasher::new();
+            line.hash(&mut hasher);
+            hasher.finish()
+        };
+        let old_hashes = old_lines.iter().map(line_hash).collect::<Vec<_>>();
+        let new_hashes = new_lines.iter().map(line_hash).collect::<Vec<_>>();
+
+        let ops = capture_diff_slices_deadline(
+            Algorithm::Myers,
+            &old_hashes,
+            &new_hashes,
+            Some(Instant::now() + LARGE_FILE_DIFF_BUDGET),
+        );
+
+        let hunks = group_diff_ops(ops, 3)
+            .into_iter()
+            .map(|group| DiffHunk {
+                lines: group
+                    .iter()
+                    .flat_map(|op| op.iter_changes(&old_lines[..], &new_lines[..]))
+                    .map(|change| DiffLine {
+                        kind: DiffOperation::from(change.tag()),
+                        old_line_num: change.old_index(),
+                        new_line_num: change.new_index(),
+                        inline_changes: vec![InlineChange {
+                            value: change.value().to_string(),
+                            emphasized: false,
+                        }],
+                    })
+                    .collect(),
+            })
+            .collect::<Vec<_>>();
+
+        if hunks.is_empty() {
+            return None;
+        }
+
+        Some(Diff {
+            hunks,
+            contents: DiffContents::Full(new),
+            large_file: true,
+        })
+    }
+
+    // The contents the diff was computed against, reconstructed from the unchanged and deleted
+    // lines; None for an append, as most of the file isn't kept then
+    pub fn old_contents(&self) -> Option<String> {
+        if self.is_append() {
+            return None;
+        }
+
+        let old = self
+            .full_file_lines()
+            .iter()
+            .filter(|line| line.kind != DiffOperation::Insert)
+            .flat_map(|line| line.inline_changes.iter())
+            .map(|inline_change| inline_change.value.as_str())
+            .collect();
+
+        Some(old)
+    }
+
+    // Renders the diff in the unified diff format, as produced by `diff -u` or `git diff`
+    pub fn to_unified(&self, path: &str) -> String {
+        let mut output = format!("--- a/{path}\n+++ b/{path}\n");
+        let mut lines_added_before = 0;
+        for hunk in &self.hunks {
+            output.push_str(&hunk.to_unified(lines_added_before));
+            lines_added_before += hunk.lines_added();
+        }
+
+        output
+    }
+
+    // Renders a single hunk in the unified diff format, including its "@@" header
+    pub fn hunk_to_unified(&self, index: usize) -> Option<String> {
+        let hunk = self.hunks.get(index)?;
+        let lines_added_before = self.hunks[..index].iter().map(DiffHunk::lines_added).sum();
+
+        Some(hunk.to_unified(lines_added_before))
+    }
+
+    pub fn stats(&self) -> DiffStats {
+        self.hunks.iter().flat_map(|hunk| hunk.lines.iter()).fold(
+            DiffStats::default(),
+            |mut stats, line| {
+                match line.kind {
+                    DiffOperation::Insert => stats.insertions += 1,
+                    DiffOperation::Delete => stats.deletions += 1,
+                    DiffOperation::Equal => {}
+                }
+                stats
+            },
+        )
+    }
+
+    // Returns the (1-indexed) line in the new file where the first change is. For a removal,
+    // that's the line now in place of the removed one.
+    pub fn first_changed_line_num(&self) -> Option<usize> {
+        let lines = &self.hunks.first()?.lines;
+        let index = lines
+            .iter()
+            .position(|line| line.kind != DiffOperation::Equal)?;
+
+        let line_num = lines[index..]
+            .iter()
+            .find_map(|line| line.new_line_num)
+            .or_else(|| {
+                lines[..index]
+                    .iter()
+                    .rev()
+                    .find_map(|line| line.new_line_num)
+                    .map(|n| n + 1)
             })
-            .max()
             .unwrap_or_default();
 
-        max(num_digits(largest_line_num) + 2, 4)
+        Some(line_num + 1)
+    }
+
+    pub fn line_num_padding(&self) -> usize {
+        line_num_padding(self.hunks.iter().flat_map(|hunk| hunk.lines.iter()))
+    }
+
+    // Returns every line of the new file in order, with deleted lines interleaved as "ghost"
+    // lines at the position they were removed from. Lines outside of hunks are reported as
+    // unchanged, with their old line numbers derived from the offset of the preceding hunk.
+    // Only the lines of the diff itself are known for an append.
+    pub fn full_file_lines(&self) -> Vec<DiffLine> {
+        let hunk_lines = self
+            .hunks
+            .iter()
+            .flat_map(|hunk| hunk.lines.iter())
+            .collect::<Vec<_>>();
+        let new_contents = match &self.contents {
+            DiffContents::Full(contents) => contents,
+            DiffContents::Appended { .. } => {
+                return hunk_lines.into_iter().cloned().collect();
+            }
+        };
+
+        let mut lines = Vec::new();
+        let mut hunk_line_index = 0;
+        // old_line_num - new_line_num for lines outside of hunks
+        let mut offset: isize = 0;
+
+        for (new_index, line) in new_contents.split_inclusive('\n').enumerate() {
+            while let Some(hunk_line) = hunk_lines.get(hunk_line_index) {
+                match hunk_line.new_line_num {
+                    Some(n) if n >= new_index => break,
+                    _ => {
+                        if hunk_line.kind == DiffOperation::Delete {
+                            lines.push((*hunk_line).clone());
+                        }
+                        hunk_line_index += 1;
+                    }
+                }
+            }
+
+            match hunk_lines.get(hunk_line_index) {
+                Some(hunk_line) if hunk_line.new_line_num == Some(new_index) => {
+                    if let Some(old) = hunk_line.old_line_num {
+                        offset = old as isize - new_index as isize;
+                    }
+                    lines.push((*hunk_line).clone());
+                    hunk_line_index += 1;
+                }
+                _ => {
+                    let old_index = (new_index as isize + offset).max(0) as usize;
+                    lines.push(DiffLine::unchanged(line, old_index, new_index));
+                }
+            }
+        }
+
+        // deletions at the very end of the file
+        for hunk_line in &hunk_lines[hunk_line_index.min(hunk_lines.len())..] {
+            if hunk_line.kind == DiffOperation::Delete {
+                lines.push((*hunk_line).clone());
+            }
+        }
+
+        lines
     }
 
     // This method is kinda weird, as the number of lines in a diff is a concern of the "view" side
     // of things. However, since computing "max available scroll" for the diff pane requires the
-    // knowledge of the number of lines in a diff, this method is needed
-    pub fn num_lines(&self) -> usize {
+    // knowledge of the number of lines in a diff, this method is needed.
+    // When wrap_width is provided, the count reflects the number of visual lines that the diff
+    // will take up when wrapped to fit in that many columns (line numbers included).
+    pub fn num_lines(&self, wrap_width: Option<usize>) -> usize {
+        let content_width =
+            wrap_width.map(|w| w.saturating_sub(gutter_width(self.line_num_padding())));
+
         let mut num = 0;
         for (idx, hunk) in self.hunks.iter().enumerate() {
             if idx > 0 {
                 num += 1;
             }
 
-            num += hunk.lines.len();
+            num += match content_width {
+                Some(w) => hunk
+                    .lines
+                    .iter()
+                    .map(|line| wrapped_line_count(&line.content(), w))
+                    .sum(),
+                None => hunk.lines.len(),
+            };
         }
 
         num
     }
+
+    pub fn max_line_width(&self) -> usize {
+        self.hunks
+            .iter()
+            .flat_map(|hunk| hunk.lines.iter())
+            .map(|line| line_width(&line.content()))
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+// width taken up by the old/new line numbers and the "|+" marker before a diff line's content
+pub fn gutter_width(line_number_padding: usize) -> usize {
+    2 * line_number_padding + 2
+}
+
+pub fn line_width(line: &str) -> usize {
+    line.chars().map(|c| c.width().unwrap_or_default()).sum()
+}
+
+// Lines are hard wrapped at character boundaries; a wide character that doesn't fit at the end
+// of a row is moved over to the next one
+pub fn wrapped_line_count(line: &str, available_width: usize) -> usize {
+    if available_width == 0 {
+        return 1;
+    }
+
+    let mut rows = 1;
+    let mut row_width = 0;
+    for c in line.chars() {
+        let char_width = c.width().unwrap_or_default();
+        if row_width + char_width > available_width && row_width > 0 {
+            rows += 1;
+            row_width = 0;
+        }
+        row_width += char_width;
+    }
+
+    rows
+}
+
+pub fn line_num_padding<'a, I>(lines: I) -> usize
+where
+    I: Iterator<Item = &'a DiffLine>,
+{
+    let largest_line_num = lines
+        .map(|diff_line| {
+            max(
+                diff_line.old_line_num.unwrap_or_default(),
+                diff_line.new_line_num.unwrap_or_default(),
+            )
+        })
+        .max()
+        .unwrap_or_default();
+
+    max(num_digits(largest_line_num) + 2, 4)
 }
 
 impl Display for Diff {
@@ -150,28 +672,7 @@ impl Display for Diff {
             }
 
             for diff_line in &hunk.lines {
-                let sign = diff_line.kind.sign();
-                let old_line = diff_line
-                    .old_line_num
-                    .map(|n| format!("{:<padding$}", n + 1, padding = line_number_padding))
-                    .unwrap_or_else(|| " ".repeat(line_number_padding));
-
-                let new_line = diff_line
-                    .new_line_num
-                    .map(|n| format!("{:<padding$}", n + 1, padding = line_number_padding))
-                    .unwrap_or_else(|| " ".repeat(line_number_padding));
-
-                let mut line_spans = vec![old_line, new_line, format!("|{sign}")];
-
-                for inline_change in &diff_line.inline_changes {
-                    if inline_change.emphasized {
-                        line_spans.push(format!("⸢{}⸣", inline_change.value));
-                    } else {
-                        line_spans.push(inline_change.value.clone());
-                    }
-                }
-
-                lines.push(line_spans.join(""));
+                lines.push(diff_line.to_plain_string(line_number_padding));
             }
         }
 
@@ -213,7 +714,187 @@ line 2
         4       |-line 3
             5   |+⸢(prefix) ⸣line 3⸢ ( changed)⸣
         ");
-        assert_snapshot!(diff.num_lines(), @"7");
+        assert_snapshot!(diff.num_lines(None), @"7");
+    }
+
+    #[test]
+    fn old_contents_are_reconstructed_correctly() {
+        // GIVEN
+        let cases = [
+            ("a\nb\nc\n", "a\nB\nc\nd\n"),
+            ("a\nb\nc", "a\nb\nc\n"),
+            (
+                "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\n",
+                "x\nb\nc\nd\ne\nf\ng\nh\ni\n",
+            ),
+            ("a\n", "b\nc\na\n"),
+        ];
+
+        for (old, new) in cases {
+            // WHEN
+            let diff = Diff::new(old, new).expect("diff should've been created");
+
+            // THEN
+            assert_eq!(diff.old_contents().as_deref(), Some(old));
+        }
+    }
+
+    #[test]
+    fn stats_count_insertions_and_deletions() {
+        // GIVEN
+        let diff = Diff::new(
+            "line 1\nline 2\nline 3\nline 4\n",
+            "line 1\nline 2 (changed)\nnew line\nline 3\n",
+        )
+        .expect("diff should've been created");
+
+        // WHEN
+        let stats = diff.stats();
+
+        // THEN
+        assert_eq!(
+            stats,
+            DiffStats {
+                insertions: 2,
+                deletions: 2,
+            }
+        );
+        assert_eq!(stats.churn(), 4);
+    }
+
+    #[test]
+    fn rendering_diff_in_unified_format_works() {
+        // GIVEN
+        let old = (1..=12).map(|i| format!("line {i}\n")).collect::<String>();
+        let new = old
+            .replace("line 2\n", "line 2 (changed)\n")
+            .replace("line 11\n", "");
+        let diff = Diff::new(&old, &new).expect("diff should've been created");
+
+        // WHEN
+        let unified = diff.to_unified("src/main.rs");
+
+        // THEN
+        insta::assert_snapshot!(unified, @"
+        --- a/src/main.rs
+        +++ b/src/main.rs
+        @@ -1,5 +1,5 @@
+         line 1
+        -line 2
+        +line 2 (changed)
+         line 3
+         line 4
+         line 5
+        @@ -8,5 +8,4 @@
+         line 8
+         line 9
+         line 10
+        -line 11
+         line 12
+        ");
+    }
+
+    #[test]
+    fn rendering_diff_in_unified_format_places_hunks_with_an_empty_side() {
+        // GIVEN
+        let created = Diff::new("", "a\nb\n").expect("diff should've been created");
+        let emptied = Diff::new("a\nb\n", "").expect("diff should've been created");
+        let old = (1..=12).map(|i| format!("line {i}\n")).collect::<String>();
+        let new = old.replace("line 2\n", "").replace("line 3\n", "");
+        let mut without_context = Diff::new(&old, &new).expect("diff should've been created");
+        for hunk in &mut without_context.hunks {
+            hunk.lines.retain(|line| line.kind != DiffOperation::Equal);
+        }
+        let later_hunk = DiffHunk {
+            lines: vec![DiffLine::inserted("new line\n", 8)],
+        };
+        without_context.hunks.push(later_hunk);
+
+        // WHEN
+        let created = created.to_unified("created.txt");
+        let emptied = emptied.to_unified("emptied.txt");
+        let later_hunk = without_context.hunk_to_unified(1);
+        let without_context = without_context.to_unified("src/main.rs");
+
+        // THEN
+        insta::assert_snapshot!(created, @"
+        --- a/created.txt
+        +++ b/created.txt
+        @@ -0,0 +1,2 @@
+        +a
+        +b
+        ");
+        insta::assert_snapshot!(emptied, @"
+        --- a/emptied.txt
+        +++ b/emptied.txt
+        @@ -1,2 +0,0 @@
+        -a
+        -b
+        ");
+        insta::assert_snapshot!(without_context, @"
+        --- a/src/main.rs
+        +++ b/src/main.rs
+        @@ -2,2 +1,0 @@
+        -line 2
+        -line 3
+        @@ -10,0 +9,1 @@
+        +new line
+        ");
+        assert_eq!(later_hunk.as_deref(), Some("@@ -10,0 +9,1 @@\n+new line\n"));
+    }
+
+    #[test]
+    fn rendering_diff_in_unified_format_keeps_crlf_line_endings() {
+        // GIVEN
+        let diff =
+            Diff::new("a\r\nb\r\nc\r\n", "a\r\nB\r\nc\r\n").expect("diff should've been created");
+
+        // WHEN
+        let unified = diff.to_unified("windows.txt");
+
+        // THEN
+        assert_eq!(
+            unified,
+            "--- a/windows.txt\n+++ b/windows.txt\n@@ -1,3 +1,3 @@\n a\r\n-b\r\n+B\r\n c\r\n"
+        );
+    }
+
+    #[test]
+    fn rendering_diff_in_unified_format_marks_missing_newline_at_end_of_file() {
+        // GIVEN
+        let diff = Diff::new("a\nb", "a\nb\nc").expect("diff should've been created");
+
+        // WHEN
+        let unified = diff.to_unified("notes.txt");
+
+        // THEN
+        insta::assert_snapshot!(unified, @r"
+        --- a/notes.txt
+        +++ b/notes.txt
+        @@ -1,2 +1,3 @@
+         a
+        -b
+        \ No newline at end of file
+        +b
+        +c
+        \ No newline at end of file
+        ");
+    }
+
+    #[test]
+    fn first_changed_line_num_points_at_the_first_change() {
+        // GIVEN
+        let modified =
+            Diff::new("a\nb\nc\nd\n", "a\nb\nC\nd\n").expect("diff should've been created");
+        let removed_in_middle =
+            Diff::new("a\nb\nc\nd\n", "a\nb\nd\n").expect("diff should've been created");
+        let removed_at_end = Diff::new("a\nb\nc\n", "a\nb\n").expect("diff should've been created");
+
+        // WHEN
+        // THEN
+        assert_eq!(modified.first_changed_line_num(), Some(3));
+        assert_eq!(removed_in_middle.first_changed_line_num(), Some(3));
+        assert_eq!(removed_at_end.first_changed_line_num(), Some(3));
     }
 
     #[test]
@@ -261,7 +942,7 @@ line 8
         10      |-line 9
             10  |+⸢(prefix) ⸣line 9⸢ (changed)⸣
         ");
-        assert_snapshot!(diff.num_lines(), @"12");
+        assert_snapshot!(diff.num_lines(None), @"12");
     }
 
     #[test]
@@ -316,7 +997,126 @@ line 8
                10000  |+line 10000⸢ (modified)⸣
         10001  10001  | line 10001
         ");
-        assert_snapshot!(diff.num_lines(), @"30");
+        assert_snapshot!(diff.num_lines(None), @"30");
+    }
+
+    #[test]
+    fn num_lines_accounts_for_wrapping() {
+        // GIVEN
+        let diff = Diff::new(
+            "short line\nanother line\n",
+            "short line\na much longer line that will need to wrap around\n",
+        )
+        .expect("diff should've been created");
+
+        // WHEN
+        // THEN
+        // gutter width = 4 + 4 + 2 = 10, leaving 20 columns for content
+        // the longer line (48 columns) takes up 3 rows when wrapped
+        assert_eq!(diff.num_lines(None), 3);
+        assert_eq!(diff.num_lines(Some(30)), 5);
+        assert_eq!(diff.num_lines(Some(200)), 3);
+    }
+
+    #[test]
+    fn wrapped_line_count_works() {
+        // GIVEN
+        let test_cases = vec![
+            ("", 10, 1),
+            ("abcde", 5, 1),
+            ("abcdef", 5, 2),
+            ("abcdefghijk", 5, 3),
+            ("abc", 0, 1),
+            // wide characters that don't fit at the end of a row move to the next one
+            ("ab日本", 3, 3),
+        ];
+
+        // WHEN
+        // THEN
+        for (line, width, expected) in test_cases {
+            assert_eq!(
+                wrapped_line_count(line, width),
+                expected,
+                "line: {line:?}, width: {width}"
+            );
+        }
+    }
+
+    fn full_file_view(lines: &[DiffLine]) -> String {
+        let padding = line_num_padding(lines.iter());
+        lines
+            .iter()
+            .map(|line| line.to_plain_string(padding))
+            .collect::<Vec<_>>()
+            .join("")
+    }
+
+    #[test]
+    fn full_file_lines_interleave_deletions_with_new_contents() {
+        // GIVEN
+        let mut lines = (1..=12).map(|n| format!("line {n}")).collect::<Vec<_>>();
+        let old = lines.join("\n");
+
+        lines[1] = "line 2 (modified)".to_string();
+        lines.remove(9);
+        lines.insert(5, "new line".to_string());
+
+        let new = lines.join("\n");
+        let diff = Diff::new(&old, &new).expect("diff should've been created");
+
+        // WHEN
+        let full_lines = diff.full_file_lines();
+
+        // THEN
+        assert_snapshot!(full_file_view(&full_lines), @r"
+        1   1   | line 1
+        2       |-line 2
+            2   |+line 2⸢ (modified)⸣
+        3   3   | line 3
+        4   4   | line 4
+        5   5   | line 5
+            6   |+new line
+        6   7   | line 6
+        7   8   | line 7
+        8   9   | line 8
+        9   10  | line 9
+        10      |-line 10
+        11  11  | line 11
+        12  12  | line 12
+        ");
+    }
+
+    #[test]
+    fn full_file_lines_include_deletions_at_the_end_of_the_file() {
+        // GIVEN
+        let diff =
+            Diff::new("line 1\nline 2\nline 3\n", "line 1\n").expect("diff should've been created");
+
+        // WHEN
+        let full_lines = diff.full_file_lines();
+
+        // THEN
+        assert_snapshot!(full_file_view(&full_lines), @r"
+        1   1   | line 1
+        2       |-line 2
+        3       |-line 3
+        ");
+    }
+
+    #[test]
+    fn all_inserted_marks_every_line_as_an_insertion() {
+        // GIVEN
+        let contents = "line 1\nline 2\nline 3";
+
+        // WHEN
+        let lines = DiffLine::all_inserted(contents);
+
+        // THEN
+        assert_snapshot!(full_file_view(&lines), @r"
+            1   |+line 1
+            2   |+line 2
+            3   |+line 3
+        ");
     }
 
     #[test]
@@ -328,4 +1128,118 @@ line 8
         // THEN
         assert!(diff.is_none());
     }
+
+    #[test]
+    fn large_file_diff_has_the_same_hunks_without_inline_changes() {
+        // GIVEN
+        let old = (1..=40).map(|i| format!("row {i}\n")).collect::<String>();
+        let new = old
+            .replace("row 3\n", "row three\n")
+            .replace("row 30\n", "")
+            .replace("row 40\n", "row 40\nrow 41\n");
+
+        // WHEN
+        let diff = Diff::new_for_large_file(&old, new.as_str().into())
+            .expect("diff should've been created");
+
+        // THEN
+        let regular = Diff::new(&old, &new).expect("diff should've been created");
+        assert!(diff.large_file);
+        assert_eq!(diff.to_unified("rows.sql"), regular.to_unified("rows.sql"));
+        assert!(
+            diff.hunks
+                .iter()
+                .flat_map(|hunk| hunk.lines.iter())
+                .all(|line| line.inline_changes.iter().all(|c| !c.emphasized))
+        );
+        assert_eq!(diff.old_contents(), Some(old));
+    }
+
+    #[test]
+    fn large_file_diff_with_no_changes_is_none() {
+        // GIVEN
+        let contents = "a\nb\n";
+
+        // WHEN
+        let diff = Diff::new_for_large_file(contents, contents.into());
+
+        // THEN
+        assert!(diff.is_none());
+    }
+
+    #[test]
+    fn appended_diff_has_the_same_hunks_as_a_regular_diff() {
+        // GIVEN
+        let old = (1..=10).map(|i| format!("line {i}\n")).collect::<String>();
+        let new = format!("{old}line 11\nline 12\n");
+
+        // WHEN
+        let diff = Diff::new_for_append(&old, &new).expect("diff should've been created");
+
+        // THEN
+        let regular = Diff::new(&old, &new).expect("diff should've been created");
+        assert_eq!(diff.to_unified("app.log"), regular.to_unified("app.log"));
+        assert_eq!(
+            diff.contents,
+            DiffContents::Appended {
+                old_len: old.len(),
+                new_len: new.len(),
+            }
+        );
+        assert_eq!(
+            diff.appended_contents().as_deref(),
+            Some("line 11\nline 12\n")
+        );
+    }
+
+    #[test]
+    fn merging_appends_works() {
+        // GIVEN
+        let first = (1..=10).map(|i| format!("line {i}\n")).collect::<String>();
+        let second = format!("{first}line 11\n");
+        let third = format!("{second}line 12\nline 13\n");
+        let earlier = Diff::new_for_append(&first, &second).expect("diff should've been created");
+        let later = Diff::new_for_append(&second, &third).expect("diff should've been created");
+
+        // WHEN
+        let merged = earlier
+            .merge_append(&later)
+            .expect("appends should've been merged");
+
+        // THEN
+        let expected = Diff::new_for_append(&first, &third).expect("diff should've been created");
+        assert_eq!(merged.to_unified("app.log"), expected.to_unified("app.log"));
+        assert_eq!(merged.contents, expected.contents);
+        assert!(later.merge_append(&earlier).is_none());
+    }
+
+    #[test]
+    fn appending_to_an_empty_file_counts_as_an_append() {
+        // GIVEN
+        let new = "line 1\nline 2\n";
+
+        // WHEN
+        let diff = Diff::new_for_append("", new).expect("diff should've been created");
+
+        // THEN
+        assert_eq!(
+            diff.stats(),
+            DiffStats {
+                insertions: 2,
+                deletions: 0
+            }
+        );
+    }
+
+    #[test]
+    fn changes_other_than_appending_lines_arent_appends() {
+        // GIVEN
+        let old = "line 1\nline 2\n";
+
+        // WHEN
+        // THEN
+        assert!(Diff::new_for_append(old, "line 1\nline two\nline 3\n").is_none());
+        assert!(Diff::new_for_append(old, old).is_none());
+        assert!(Diff::new_for_append("line 1\nline 2", "line 1\nline 2 and more\n").is_none());
+    }
 }
@@ -0,0 +1,187 @@
+use encoding_rs::{Encoding, UTF_8};
+
+// The encoding a text file was decoded from, and whether it started with a byte order mark
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TextEncoding {
+    pub encoding: &'static Encoding,
+    pub bom: bool,
+}
+
+impl TextEncoding {
+    pub const UTF8: Self = Self {
+        encoding: UTF_8,
+        bom: false,
+    };
+
+    // UTF-8 without a BOM is what most files use, so it's not worth pointing out
+    pub fn is_default(&self) -> bool {
+        *self == Self::UTF8
+    }
+}
+
+impl std::fmt::Display for TextEncoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.encoding.name())?;
+        if self.bom {
+            write!(f, " with BOM")?;
+        }
+
+        Ok(())
+    }
+}
+
+// Decodes the contents of a text file, going by its BOM if it has one, and guessing the encoding
+// otherwise (UTF-8 is preferred whenever the contents are valid UTF-8)
+pub fn decode_text(bytes: Vec<u8>) -> Result<(String, TextEncoding), String> {
+    if let Some((encoding, bom_len)) = Encoding::for_bom(&bytes) {
+        let encoding = TextEncoding {
+            encoding,
+            bom: true,
+        };
+        return decode_with(&bytes[bom_len..], encoding);
+    }
+
+    let bytes = match String::from_utf8(bytes) {
+        Ok(contents) => return Ok((contents, TextEncoding::UTF8)),
+        Err(e) => e.into_bytes(),
+    };
+
+    let mut detector = chardetng::EncodingDetector::new();
+    detector.feed(&bytes, true);
+    let encoding = TextEncoding {
+        encoding: detector.guess(None, true),
+        bom: false,
+    };
+
+    decode_with(&bytes, encoding)
+}
+
+fn decode_with(bytes: &[u8], encoding: TextEncoding) -> Result<(String, TextEncoding), String> {
+    let (contents, had_errors) = encoding.encoding.decode_without_bom_handling(bytes);
+    if had_errors {
+        return Err(format!(
+            "contents aren't valid {}",
+            encoding.encoding.name()
+        ));
+    }
+
+    Ok((contents.into_owned(), encoding))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use encoding_rs::{SHIFT_JIS, UTF_16LE, WINDOWS_1252};
+
+    #[test]
+    fn utf8_is_decoded_as_is() {
+        // GIVEN
+        let bytes = "héllo\n".as_bytes().to_vec();
+
+        // WHEN
+        let result = decode_text(bytes);
+
+        // THEN
+        assert_eq!(result, Ok(("héllo\n".to_string(), TextEncoding::UTF8)));
+    }
+
+    #[test]
+    fn utf8_bom_is_detected_and_stripped() {
+        // GIVEN
+        let bytes = b"\xef\xbb\xbfhello\n".to_vec();
+
+        // WHEN
+        let result = decode_text(bytes);
+
+        // THEN
+        assert_eq!(
+            result,
+            Ok((
+                "hello\n".to_string(),
+                TextEncoding {
+                    encoding: UTF_8,
+                    bom: true
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn utf16_with_bom_is_decoded() {
+        // GIVEN
+        let mut bytes = vec![0xff, 0xfe];
+        bytes.extend("hi\n".encode_utf16().flat_map(|u| u.to_le_bytes()));
+
+        // WHEN
+        let result = decode_text(bytes);
+
+        // THEN
+        assert_eq!(
+            result,
+            Ok((
+                "hi\n".to_string(),
+                TextEncoding {
+                    encoding: UTF_16LE,
+                    bom: true
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn latin1_is_decoded_as_windows_1252() {
+        // GIVEN
+        let bytes = b"Caf\xe9 cr\xe8me br\xfbl\xe9e, na\xefve fa\xe7ade\n".to_vec();
+
+        // WHEN
+        let result = decode_text(bytes);
+
+        // THEN
+        assert_eq!(
+            result,
+            Ok((
+                "Café crème brûlée, naïve façade\n".to_string(),
+                TextEncoding {
+                    encoding: WINDOWS_1252,
+                    bom: false
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn shift_jis_is_decoded() {
+        // GIVEN
+        let text = "これは日本語のテキストです。ファイルの変更を表示します。\n";
+        let (bytes, _, _) = SHIFT_JIS.encode(text);
+
+        // WHEN
+        let result = decode_text(bytes.into_owned());
+
+        // THEN
+        assert_eq!(
+            result,
+            Ok((
+                text.to_string(),
+                TextEncoding {
+                    encoding: SHIFT_JIS,
+                    bom: false
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn displaying_encoding_works() {
+        // GIVEN
+        let with_bom = TextEncoding {
+            encoding: UTF_16LE,
+            bom: true,
+        };
+
+        // WHEN
+        // THEN
+        assert_eq!(with_bom.to_string(), "UTF-16LE with BOM");
+        assert_eq!(TextEncoding::UTF8.to_string(), "UTF-8");
+    }
+}
@@ -0,0 +1,184 @@
+use anyhow::Context;
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+
+// Which files are watched, going by globs passed via --include/--exclude (or changed from the TUI),
+// on top of the ignore files. A glob matching a directory applies to everything in it, so that
+// "docs" excludes all of docs/. Paths are relative to the root they're in.
+#[derive(Debug, Clone, Default)]
+pub struct PathFilter {
+    include: Vec<String>,
+    exclude: Vec<String>,
+    include_set: Option<GlobSet>,
+    exclude_set: Option<GlobSet>,
+}
+
+impl PathFilter {
+    pub fn new(include: Vec<String>, exclude: Vec<String>) -> anyhow::Result<Self> {
+        let include_set = build_glob_set(&include)?;
+        let exclude_set = build_glob_set(&exclude)?;
+
+        Ok(Self {
+            include,
+            exclude,
+            include_set,
+            exclude_set,
+        })
+    }
+
+    // Parses globs separated by whitespace, with the ones to exclude starting with "!"; the
+    // inverse of this filter's Display
+    pub fn parse(input: &str) -> anyhow::Result<Self> {
+        let mut include = vec![];
+        let mut exclude = vec![];
+        for glob in input.split_whitespace() {
+            match glob.strip_prefix('!') {
+                Some(glob) => exclude.push(glob.to_string()),
+                None => include.push(glob.to_string()),
+            }
+        }
+
+        Self::new(include, exclude)
+    }
+
+    pub fn is_active(&self) -> bool {
+        !self.include.is_empty() || !self.exclude.is_empty()
+    }
+
+    pub fn matches(&self, path: &str) -> bool {
+        let path = path.trim_start_matches("./");
+        // the path itself, and every directory it's in
+        let candidates = std::iter::once(path)
+            .chain(path.match_indices('/').map(|(i, _)| &path[..i]))
+            .collect::<Vec<_>>();
+        let any_match = |set: &GlobSet| candidates.iter().any(|c| set.is_match(c));
+
+        if self.exclude_set.as_ref().is_some_and(any_match) {
+            return false;
+        }
+
+        self.include_set.as_ref().is_none_or(any_match)
+    }
+}
+
+impl std::fmt::Display for PathFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let globs = self
+            .include
+            .iter()
+            .cloned()
+            .chain(self.exclude.iter().map(|glob| format!("!{glob}")))
+            .collect::<Vec<_>>();
+
+        write!(f, "{}", globs.join(" "))
+    }
+}
+
+// "*" doesn't match across directories, while "**" does
+fn build_glob_set(globs: &[String]) -> anyhow::Result<Option<GlobSet>> {
+    if globs.is_empty() {
+        return Ok(None);
+    }
+
+    let mut builder = GlobSetBuilder::new();
+    for glob in globs {
+        let pattern = glob.trim_start_matches("./").trim_end_matches('/');
+        builder.add(
+            GlobBuilder::new(pattern)
+                .literal_separator(true)
+                .build()
+                .with_context(|| format!(r#"invalid glob "{glob}""#))?,
+        );
+    }
+
+    Ok(Some(
+        builder.build().context("couldn't build glob matcher")?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(input: &str) -> PathFilter {
+        PathFilter::parse(input).expect("filter should've been parsed")
+    }
+
+    #[test]
+    fn everything_matches_an_empty_filter() {
+        // GIVEN
+        let filter = filter("");
+
+        // WHEN
+        // THEN
+        assert!(!filter.is_active());
+        assert!(filter.matches("src/main.rs"));
+        assert!(filter.matches("README.md"));
+    }
+
+    #[test]
+    fn only_included_paths_match() {
+        // GIVEN
+        let filter = filter("src/**/*.rs");
+
+        // WHEN
+        // THEN
+        assert!(filter.matches("src/main.rs"));
+        assert!(filter.matches("src/tui/model.rs"));
+        assert!(!filter.matches("build.rs"));
+        assert!(!filter.matches("src/assets/logo.png"));
+    }
+
+    #[test]
+    fn globs_for_directories_apply_to_everything_in_them() {
+        // GIVEN
+        let filter = filter("src !docs/ !src/generated");
+
+        // WHEN
+        // THEN
+        assert!(filter.matches("src/main.rs"));
+        assert!(!filter.matches("src/generated/schema.rs"));
+        assert!(!filter.matches("docs/guide/intro.md"));
+        assert!(!filter.matches("README.md"));
+    }
+
+    #[test]
+    fn single_stars_dont_match_across_directories() {
+        // GIVEN
+        let filter = filter("!*.log");
+
+        // WHEN
+        // THEN
+        assert!(!filter.matches("debug.log"));
+        assert!(filter.matches("logs/debug.log"));
+    }
+
+    #[test]
+    fn filter_is_shown_the_way_its_parsed() {
+        // GIVEN
+        let filter = PathFilter::new(vec!["src/**/*.rs".to_string()], vec!["docs".to_string()])
+            .expect("filter should've been built");
+
+        // WHEN
+        let shown = filter.to_string();
+
+        // THEN
+        assert_eq!(shown, "src/**/*.rs !docs");
+        assert_eq!(
+            PathFilter::parse(&shown)
+                .expect("filter should've been parsed")
+                .to_string(),
+            shown
+        );
+    }
+
+    #[test]
+    fn invalid_globs_are_reported() {
+        // GIVEN
+        // WHEN
+        let result = PathFilter::parse("src/[a-");
+
+        // THEN
+        let error = result.expect_err("glob should've been invalid");
+        assert_eq!(error.to_string(), r#"invalid glob "src/[a-""#);
+    }
+}
@@ -0,0 +1,52 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// Counters for the work the watcher has queued up, shared between the watcher and the TUI (which
+// shows them in the status line when debugging)
+#[derive(Debug, Default)]
+pub struct WatchMetrics {
+    queued: AtomicUsize,
+    max_queued: AtomicUsize,
+}
+
+impl WatchMetrics {
+    pub fn job_queued(&self) {
+        let queued = self.queued.fetch_add(1, Ordering::Relaxed) + 1;
+        self.max_queued.fetch_max(queued, Ordering::Relaxed);
+    }
+
+    pub fn job_done(&self) {
+        self.queued.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    // jobs that are either waiting for a worker, or being worked on
+    pub fn queued(&self) -> usize {
+        self.queued.load(Ordering::Relaxed)
+    }
+
+    pub fn max_queued(&self) -> usize {
+        self.max_queued.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queue_depth_is_tracked() {
+        // GIVEN
+        let metrics = WatchMetrics::default();
+
+        // WHEN
+        metrics.job_queued();
+        metrics.job_queued();
+        metrics.job_queued();
+        metrics.job_done();
+        metrics.job_queued();
+        metrics.job_done();
+
+        // THEN
+        assert_eq!(metrics.queued(), 2);
+        assert_eq!(metrics.max_queued(), 3);
+    }
+}
@@ -1,7 +1,15 @@
 mod cache;
 mod change;
+mod contents;
 mod diff;
+mod encoding;
+mod globs;
+mod metrics;
 
 pub use cache::*;
 pub use change::*;
+pub use contents::*;
 pub use diff::*;
+pub use encoding::*;
+pub use globs::*;
+pub use metrics::*;
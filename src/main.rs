@@ -1,4 +1,5 @@
 mod args;
+mod config;
 mod domain;
 mod log;
 mod notifs;
@@ -7,12 +8,16 @@ mod utils;
 mod watcher;
 
 use std::path::PathBuf;
+use std::time::Duration;
 
 use anyhow::Context;
-use args::{Args, DfftCommand};
+use args::{Args, DfftCommand, flag_value};
 use clap::Parser;
+use config::{Config, ConfigOverrides, PROJECT_CONFIG_FILE_NAME, load_config};
+use domain::PathFilter;
 use log::setup_logging;
-use tui::TuiBehaviours;
+use tui::{Keymap, Theme, TuiBehaviours};
+use watcher::WatchSettings;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -20,58 +25,149 @@ async fn main() -> anyhow::Result<()> {
 
     if args.debug {
         print_debug_info(&args);
-        return Ok(());
     }
 
     match args.command {
         DfftCommand::Run {
-            path: maybe_path_str,
+            paths: path_strs,
+            include,
+            exclude,
+            poll_millis,
             follow_changes,
+            no_follow_changes,
+            collapse_appends,
+            no_collapse_appends,
+            prepopulation,
             no_prepopulation,
+            watch,
             no_watch,
+            auto_review_after_secs,
+            config_path,
+            theme,
+            mouse,
+            no_mouse,
+            #[cfg(feature = "sound")]
+            sound,
             #[cfg(feature = "sound")]
             no_sound,
         } => {
-            setup_logging().context("couldn't set up logging")?;
+            let path_strs = if path_strs.is_empty() {
+                vec![".".to_string()]
+            } else {
+                path_strs
+            };
 
-            let path_str = maybe_path_str.unwrap_or(".".to_string());
-            let path = PathBuf::from(&path_str);
-
-            let metadata = match tokio::fs::metadata(&path).await {
-                Ok(m) => m,
-                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
-                    anyhow::bail!("path doesn't exist: {}", &path_str);
-                }
-                Err(e) => {
-                    return Err(anyhow::anyhow!("couldn't check if path exists: {e}"));
-                }
+            // a setting whose flags aren't passed is left to the config files
+            let overrides = ConfigOverrides {
+                follow_changes: flag_value(follow_changes, no_follow_changes),
+                collapse_appends: flag_value(collapse_appends, no_collapse_appends),
+                prepopulate: flag_value(prepopulation, no_prepopulation),
+                watch: flag_value(watch, no_watch),
+                auto_review_secs: auto_review_after_secs,
+                mouse: flag_value(mouse, no_mouse),
+                #[cfg(feature = "sound")]
+                sound: flag_value(sound, no_sound),
+                #[cfg(not(feature = "sound"))]
+                sound: None,
+                theme,
             };
+            // the project config file is read from the first directory
+            let root_paths = path_strs.iter().map(PathBuf::from).collect::<Vec<_>>();
+            let config =
+                load_config(config_path.map(PathBuf::from), &root_paths, overrides).await?;
 
-            if !metadata.is_dir() {
-                anyhow::bail!("path is not a directory: {}", &path_str);
+            if args.debug {
+                print_effective_config(&config);
+                return Ok(());
             }
 
-            let root = tokio::fs::canonicalize(path)
-                .await
-                .context("couldn't canonicalize directory path")?;
+            // printed before the TUI takes over the screen, so that it's still there once it exits
+            if !config.skipped_files.is_empty() {
+                eprintln!(
+                    "warning: only the first directory's {PROJECT_CONFIG_FILE_NAME} is read; skipping {}",
+                    config.describe_skipped_files()
+                );
+            }
+
+            setup_logging().context("couldn't set up logging")?;
+
+            let roots = get_roots(&path_strs).await?;
+            let path_filter = PathFilter::new(include, exclude)?;
+
+            let keymap = Keymap::from_config(&config.keymap)
+                .with_context(|| format!("invalid keymap in {}", config.describe_files()))?;
+            let theme = Theme::from_config(config.theme.value.as_deref(), &config.themes)
+                .context("couldn't load theme")?;
 
             let behaviours = TuiBehaviours {
-                watch: !no_watch,
-                follow_changes,
-                prepopulate_cache: !no_prepopulation,
+                watch: config.watch.value,
+                follow_changes: config.follow_changes.value,
+                collapse_appends: config.collapse_appends.value,
+                prepopulate_cache: config.prepopulate.value,
+                auto_review_after: config.auto_review_secs.value.map(Duration::from_secs),
+                mouse: config.mouse.value,
                 #[cfg(feature = "sound")]
-                play_sound: !no_sound,
+                play_sound: config.sound.value,
+                watch_settings: WatchSettings {
+                    poll_interval: poll_millis.map(Duration::from_millis),
+                    ..config.watch_settings()
+                },
+                path_filter,
             };
-            tui::run(root, behaviours).await?;
+            tui::run(roots, behaviours, keymap, theme).await?;
         }
     };
 
     Ok(())
 }
 
+// Directories that are the same as, or inside, another one would have their changes reported twice
+async fn get_roots(path_strs: &[String]) -> anyhow::Result<Vec<PathBuf>> {
+    let mut roots: Vec<(PathBuf, &str)> = vec![];
+    for path_str in path_strs {
+        let metadata = match tokio::fs::metadata(path_str).await {
+            Ok(m) => m,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                anyhow::bail!("path doesn't exist: {}", path_str);
+            }
+            Err(e) => {
+                return Err(anyhow::anyhow!("couldn't check if path exists: {e}"));
+            }
+        };
+
+        if !metadata.is_dir() {
+            anyhow::bail!("path is not a directory: {}", path_str);
+        }
+
+        let root = tokio::fs::canonicalize(path_str)
+            .await
+            .context("couldn't canonicalize directory path")?;
+
+        if let Some((_, other)) = roots
+            .iter()
+            .find(|(other, _)| root.starts_with(other) || other.starts_with(&root))
+        {
+            anyhow::bail!("paths overlap: {} and {}", other, path_str);
+        }
+
+        roots.push((root, path_str));
+    }
+
+    Ok(roots.into_iter().map(|(root, _)| root).collect())
+}
+
 fn print_debug_info(args: &Args) {
     print!(
         r#"DEBUG INFO:
 {args}"#
     )
 }
+
+fn print_effective_config(config: &Config) {
+    print!(
+        r#"
+EFFECTIVE CONFIG:
+
+{config}"#
+    )
+}
@@ -26,7 +26,15 @@ impl AudioPlayer {
     pub fn play_change_sound(&self, change_kind: &ChangeKind) {
         let sound_data = match change_kind {
             ChangeKind::Created(_) => CREATE_SOUND,
-            ChangeKind::Modified { .. } => MODIFY_SOUND,
+            ChangeKind::Binary(change) | ChangeKind::TooLarge(change)
+                if change.before.is_none() =>
+            {
+                CREATE_SOUND
+            }
+            ChangeKind::Modified { .. }
+            | ChangeKind::Binary(_)
+            | ChangeKind::TooLarge(_)
+            | ChangeKind::Reencoded(_) => MODIFY_SOUND,
             ChangeKind::RemovedFile | ChangeKind::RemovedDir => REMOVE_SOUND,
         };
 
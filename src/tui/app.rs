@@ -1,25 +1,38 @@
 use super::TuiBehaviours;
 use super::cmd::Cmd;
 use super::common::*;
+use super::editor::EditorCommand;
 use super::handle::handle_command;
+use super::keymap::Keymap;
 use super::model::*;
 use super::msg::{Msg, get_event_handling_msg};
+use super::theme::Theme;
 use super::update::update;
 use super::view::view;
 use crate::domain::WatchUpdate;
 use anyhow::Context;
 use ratatui::backend::CrosstermBackend;
-use ratatui::crossterm::event::poll;
+use ratatui::crossterm::event::{DisableMouseCapture, EnableMouseCapture, poll};
+use ratatui::crossterm::execute;
+use ratatui::crossterm::terminal::{EnterAlternateScreen, enable_raw_mode};
 use ratatui::{Terminal, try_restore};
-use std::path::PathBuf;
-use std::time::Duration;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 use tokio::sync::mpsc::{Receiver, Sender};
 
 const EVENT_POLL_DURATION_MS: u64 = 16;
-
-pub async fn run(root: PathBuf, behaviours: TuiBehaviours) -> anyhow::Result<()> {
-    let mut tui = AppTui::new(root, behaviours)?;
+const REVIEW_TICK_INTERVAL_MS: u64 = 500;
+
+pub async fn run(
+    roots: Vec<PathBuf>,
+    behaviours: TuiBehaviours,
+    keymap: Keymap,
+    theme: Theme,
+) -> anyhow::Result<()> {
+    let mut tui = AppTui::new(roots, behaviours, keymap, theme)?;
     tui.run().await
 }
 
@@ -31,8 +44,19 @@ struct AppTui {
 }
 
 impl AppTui {
-    pub fn new(root: PathBuf, behaviours: TuiBehaviours) -> anyhow::Result<Self> {
+    pub fn new(
+        roots: Vec<PathBuf>,
+        behaviours: TuiBehaviours,
+        keymap: Keymap,
+        theme: Theme,
+    ) -> anyhow::Result<Self> {
         let terminal = ratatui::try_init()?;
+        if behaviours.mouse
+            && let Err(e) = execute!(std::io::stdout(), EnableMouseCapture)
+        {
+            let _ = try_restore();
+            return Err(e).context("couldn't enable mouse capture");
+        }
         let (event_tx, event_rx) = mpsc::channel(10);
 
         let (width, height) = ratatui::crossterm::terminal::size()?;
@@ -41,7 +65,9 @@ impl AppTui {
 
         let debug = std::env::var("DFFT_DEBUG").unwrap_or_default().trim() == "1";
 
-        let model = Model::new(behaviours, root, terminal_dimensions, debug);
+        let model = Model::new(behaviours, roots, terminal_dimensions, debug)
+            .with_keymap(keymap)
+            .with_theme(theme);
 
         Ok(Self {
             terminal,
@@ -55,6 +81,10 @@ impl AppTui {
         let result = self.run_inner().await;
         self.model.pause_watching();
 
+        if self.model.behaviours.mouse {
+            let _ = execute!(std::io::stdout(), DisableMouseCapture);
+        }
+
         if let Err(restore_err) = try_restore()
             && result.is_ok()
         {
@@ -73,45 +103,42 @@ impl AppTui {
 
         let mut initial_cmds = vec![];
         if self.model.behaviours.watch {
-            let changes_tx = self.model.watch_updates_tx.clone();
-            initial_cmds.push(Cmd::WatchForChanges {
-                root: self.model.root.clone(), // TODO: prevent cloning here
-                cache: self.model.cache(),
-                sender: changes_tx,
-                cancellation_token: self.model.get_cancellation_token(),
-                prepopulate_cache: self.model.behaviours.prepopulate_cache,
-            });
+            initial_cmds.extend(self.model.watch_cmds());
         }
 
         for cmd in initial_cmds {
             handle_command(cmd.clone(), self.event_tx.clone()).await;
         }
 
+        let mut review_ticker =
+            tokio::time::interval(Duration::from_millis(REVIEW_TICK_INTERVAL_MS));
+
         loop {
             tokio::select! {
                 Some(message) = self.event_rx.recv() => {
-                    let cmds = update(&mut self.model, message);
-
-                    if self.model.running_state == RunningState::Done {
+                    if self.handle_msg(message).await? {
                         break;
                     }
-
-                        self.model.render_counter += 1;
-                        self.terminal.draw(|f| view(&mut self.model, f))?;
-
-                    for cmd in cmds {
-                        handle_command(cmd.clone(), self.event_tx.clone()).await;
-                    }
                 }
 
                 Some(watch_update) = self.model.watch_updates_rx.recv() => {
                     self.model.watch_counter += 1;
                     let msg = match watch_update {
                         WatchUpdate::ChangeReceived(change) => Msg::ChangeReceived(change),
+                        WatchUpdate::ChangedSinceLastSession(change) => Msg::ChangedSinceLastSession(change),
                         WatchUpdate::PrepopulationFinished => Msg::PrepopulationFinished,
                         WatchUpdate::PrepopulationFailed(e) => Msg::PrepopulationFailed(e),
+                        WatchUpdate::PollingSuggested(description) => Msg::PollingSuggested(description),
                     };
-                    let _ = self.event_tx.try_send(msg);
+                    // handled right away rather than forwarded through the event channel, which
+                    // would drop bursts of updates once it's full
+                    if self.handle_msg(msg).await? {
+                        break;
+                    }
+                }
+
+                _ = review_ticker.tick(), if self.model.is_awaiting_auto_review() => {
+                    let _ = self.event_tx.try_send(Msg::Tick(Instant::now()));
                 }
 
                 Ok(ready) = tokio::task::spawn_blocking(|| poll(Duration::from_millis(EVENT_POLL_DURATION_MS))) => {
@@ -135,4 +162,72 @@ impl AppTui {
 
         Ok(())
     }
+
+    // Returns whether the app is done
+    async fn handle_msg(&mut self, msg: Msg) -> anyhow::Result<bool> {
+        let mut msgs = VecDeque::from([msg]);
+
+        while let Some(msg) = msgs.pop_front() {
+            let cmds = update(&mut self.model, msg);
+
+            if self.model.running_state == RunningState::Done {
+                return Ok(true);
+            }
+
+            self.model.render_counter += 1;
+            self.terminal.draw(|f| view(&mut self.model, f))?;
+
+            for cmd in cmds {
+                match cmd {
+                    Cmd::OpenInEditor { editor, dir } => {
+                        let result = self.open_in_editor(&editor, &dir).await?;
+                        msgs.push_back(Msg::EditorClosed(result));
+                    }
+                    cmd => handle_command(cmd, self.event_tx.clone()).await,
+                }
+            }
+        }
+
+        Ok(false)
+    }
+
+    // Suspends the TUI while the editor runs, and restores it afterwards. Watch updates aren't
+    // handled in the meantime; they wait in their channel (holding up the watcher's workers once
+    // it's full), and are handled once the editor is closed.
+    async fn open_in_editor(
+        &mut self,
+        editor: &EditorCommand,
+        dir: &Path,
+    ) -> anyhow::Result<Result<(), String>> {
+        if self.model.behaviours.mouse {
+            execute!(std::io::stdout(), DisableMouseCapture)
+                .context("couldn't disable mouse capture before opening editor")?;
+        }
+        try_restore().context("couldn't restore terminal before opening editor")?;
+
+        // waiting for the editor blocks, so it's done off the runtime's worker threads
+        let mut command = Command::new(&editor.program);
+        command.args(&editor.args).current_dir(dir);
+        let status = tokio::task::spawn_blocking(move || command.status())
+            .await
+            .map_err(std::io::Error::other)
+            .flatten();
+
+        let result = match status {
+            Ok(status) if status.success() => Ok(()),
+            Ok(status) => Err(format!("{} exited with {status}", editor.program)),
+            Err(e) => Err(format!("couldn't run {}: {e}", editor.program)),
+        };
+
+        enable_raw_mode().context("couldn't re-enable raw mode after editor exited")?;
+        execute!(std::io::stdout(), EnterAlternateScreen)
+            .context("couldn't re-enter alternate screen after editor exited")?;
+        if self.model.behaviours.mouse {
+            execute!(std::io::stdout(), EnableMouseCapture)
+                .context("couldn't re-enable mouse capture after editor exited")?;
+        }
+        self.terminal.clear()?;
+
+        Ok(result)
+    }
 }
@@ -1,10 +1,19 @@
+use crate::domain::PathFilter;
+use crate::watcher::WatchSettings;
+use std::time::Duration;
+
 #[derive(Debug, Clone)]
 pub struct TuiBehaviours {
     pub watch: bool,
     pub follow_changes: bool,
+    pub collapse_appends: bool,
     pub prepopulate_cache: bool,
+    pub auto_review_after: Option<Duration>,
+    pub mouse: bool,
     #[cfg(feature = "sound")]
     pub play_sound: bool,
+    pub watch_settings: WatchSettings,
+    pub path_filter: PathFilter,
 }
 
 #[cfg(test)]
@@ -13,9 +22,14 @@ impl TuiBehaviours {
         Self {
             watch: true,
             follow_changes: false,
+            collapse_appends: false,
             prepopulate_cache: true,
+            auto_review_after: None,
+            mouse: true,
             #[cfg(feature = "sound")]
             play_sound: false,
+            watch_settings: WatchSettings::default(),
+            path_filter: PathFilter::default(),
         }
     }
 
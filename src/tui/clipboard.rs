@@ -0,0 +1,67 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use std::io::Write;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Multiplexer {
+    Tmux,
+    Screen,
+}
+
+impl Multiplexer {
+    fn detect() -> Option<Self> {
+        if std::env::var_os("TMUX").is_some() {
+            Some(Self::Tmux)
+        } else if std::env::var_os("STY").is_some() {
+            Some(Self::Screen)
+        } else {
+            None
+        }
+    }
+}
+
+// Builds the OSC 52 escape sequence that asks the terminal to set the system clipboard. Since
+// the terminal does the copying, this works over SSH too. Terminal multiplexers swallow the
+// sequence unless it's wrapped in their passthrough sequence.
+pub(super) fn osc52_sequence(contents: &str, multiplexer: Option<Multiplexer>) -> String {
+    let osc52 = format!("\x1b]52;c;{}\x07", STANDARD.encode(contents));
+
+    match multiplexer {
+        Some(Multiplexer::Tmux) => format!("\x1bPtmux;{}\x1b\\", osc52.replace('\x1b', "\x1b\x1b")),
+        Some(Multiplexer::Screen) => format!("\x1bP{osc52}\x1b\\"),
+        None => osc52,
+    }
+}
+
+pub(super) fn copy_to_clipboard(contents: &str) -> std::io::Result<()> {
+    let mut stdout = std::io::stdout();
+    stdout.write_all(osc52_sequence(contents, Multiplexer::detect()).as_bytes())?;
+    stdout.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn osc52_sequence_contains_base64_encoded_contents() {
+        // GIVEN
+        // WHEN
+        let sequence = osc52_sequence("src/main.rs", None);
+
+        // THEN
+        assert_eq!(sequence, "\x1b]52;c;c3JjL21haW4ucnM=\x07");
+    }
+
+    #[test]
+    fn osc52_sequence_is_wrapped_for_multiplexers() {
+        // GIVEN
+        // WHEN
+        let tmux = osc52_sequence("src/main.rs", Some(Multiplexer::Tmux));
+        let screen = osc52_sequence("src/main.rs", Some(Multiplexer::Screen));
+
+        // THEN
+        assert_eq!(tmux, "\x1bPtmux;\x1b\x1b]52;c;c3JjL21haW4ucnM=\x07\x1b\\");
+        assert_eq!(screen, "\x1bP\x1b]52;c;c3JjL21haW4ucnM=\x07\x1b\\");
+    }
+}
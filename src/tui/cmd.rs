@@ -1,19 +1,37 @@
-use crate::domain::{FileCache, WatchUpdate};
+use super::editor::EditorCommand;
+use crate::domain::{FileCache, PathFilter, WatchMetrics, WatchUpdate};
+use crate::watcher::{WatchRoot, WatchSettings};
 use std::path::PathBuf;
 use std::sync::Arc;
-use tokio::sync::RwLock;
 use tokio::sync::mpsc::Sender;
+use tokio::sync::{RwLock, watch};
 use tokio_util::sync::CancellationToken;
 
 #[derive(Clone, Debug)]
 #[allow(dead_code)]
 pub(super) enum Cmd {
     WatchForChanges {
-        root: PathBuf,
+        root: WatchRoot,
+        // which of the model's roots is watched, for reporting failures
+        root_index: usize,
         cache: Arc<RwLock<FileCache>>,
         sender: Sender<WatchUpdate>,
         cancellation_token: CancellationToken,
         prepopulate_cache: bool,
+        settings: WatchSettings,
+        metrics: Arc<WatchMetrics>,
+        filter: watch::Receiver<PathFilter>,
+    },
+    ExportComments {
+        contents: String,
+    },
+    CopyToClipboard {
+        contents: String,
+    },
+    // handled by the app itself, since the TUI needs to be suspended while the editor runs
+    OpenInEditor {
+        editor: EditorCommand,
+        dir: PathBuf,
     },
 }
 
@@ -21,6 +39,9 @@ impl std::fmt::Display for Cmd {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Cmd::WatchForChanges { .. } => write!(f, "watch for changes"),
+            Cmd::ExportComments { .. } => write!(f, "export comments"),
+            Cmd::CopyToClipboard { .. } => write!(f, "copy to clipboard"),
+            Cmd::OpenInEditor { .. } => write!(f, "open in editor"),
         }
     }
 }
@@ -0,0 +1,207 @@
+use crate::domain::DiffLine;
+use crate::utils::get_state_dir;
+use anyhow::Context;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const COMMENT_MARKER: &str = "» ";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentTarget {
+    Line,
+    Hunk,
+}
+
+impl std::fmt::Display for CommentTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CommentTarget::Line => write!(f, "line"),
+            CommentTarget::Hunk => write!(f, "hunk"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Comment {
+    // index of the commented change in the list of all changes; None once the list is reset, as
+    // the change isn't listed anymore (the comment itself is still kept around, and exported)
+    pub change_index: Option<usize>,
+    pub path: String,
+    pub target: CommentTarget,
+    // the commented line, or all lines of the commented hunk
+    pub excerpt: Vec<DiffLine>,
+    pub text: String,
+}
+
+impl Comment {
+    // the line after which the comment is shown in the diff pane
+    pub(super) fn anchor(&self) -> Option<&DiffLine> {
+        self.excerpt.last()
+    }
+
+    pub(super) fn is_anchored_at(&self, line: &DiffLine) -> bool {
+        self.anchor().is_some_and(|anchor| {
+            anchor.old_line_num == line.old_line_num && anchor.new_line_num == line.new_line_num
+        })
+    }
+
+    // how the comment is shown in the diff pane
+    pub(super) fn display_text(&self) -> String {
+        format!("{COMMENT_MARKER}{}", self.text)
+    }
+
+    // eg. "src/main.rs:12", or "src/main.rs:12-18" for a hunk. Line numbers refer to the new
+    // version of the file, unless all commented lines were removed.
+    pub(super) fn location(&self) -> String {
+        let new_lines = self
+            .excerpt
+            .iter()
+            .filter_map(|l| l.new_line_num)
+            .collect::<Vec<_>>();
+        let (line_nums, suffix) = if new_lines.is_empty() {
+            (
+                self.excerpt
+                    .iter()
+                    .filter_map(|l| l.old_line_num)
+                    .collect::<Vec<_>>(),
+                " (removed)",
+            )
+        } else {
+            (new_lines, "")
+        };
+
+        match (line_nums.iter().min(), line_nums.iter().max()) {
+            (Some(first), Some(last)) if first == last => {
+                format!("{}:{}{suffix}", self.path, first + 1)
+            }
+            (Some(first), Some(last)) => {
+                format!("{}:{}-{}{suffix}", self.path, first + 1, last + 1)
+            }
+            _ => self.path.clone(),
+        }
+    }
+}
+
+// Renders comments as Markdown that can be handed back to an agent as feedback
+pub(super) fn comments_to_markdown(comments: &[Comment]) -> String {
+    let mut output = String::from("# Review comments\n");
+
+    for comment in comments {
+        output.push_str(&format!(
+            "\n## {}\n\n{}\n\n```diff\n",
+            comment.location(),
+            comment.text
+        ));
+        for line in &comment.excerpt {
+            output.push_str(&format!("{}{}\n", line.kind.sign(), line.content()));
+        }
+        output.push_str("```\n");
+    }
+
+    output
+}
+
+// Writes exported comments to a new file in dfft's state directory, rather than the watched
+// directory, so that exporting doesn't show up as a change
+pub(super) async fn write_comments_file(contents: String) -> anyhow::Result<PathBuf> {
+    let dir = get_state_dir()?.join("reviews");
+    tokio::fs::create_dir_all(&dir)
+        .await
+        .context("couldn't create directory for review comments")?;
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let path = dir.join(format!("review-{timestamp}.md"));
+
+    tokio::fs::write(&path, contents)
+        .await
+        .context("couldn't write review comments")?;
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Diff, DiffOperation};
+
+    fn comment(target: CommentTarget, excerpt: Vec<DiffLine>, text: &str) -> Comment {
+        Comment {
+            change_index: Some(0),
+            path: "src/main.rs".to_string(),
+            target,
+            excerpt,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn location_refers_to_new_line_numbers() {
+        // GIVEN
+        let diff = Diff::new("a\nb\nc\nd\n", "a\nB\nc\nd\n").expect("diff should've been created");
+        let lines = diff.hunks[0].lines.clone();
+        let removed = lines
+            .iter()
+            .find(|l| l.kind == DiffOperation::Delete)
+            .cloned()
+            .expect("a line should've been removed");
+
+        // WHEN
+        // THEN
+        assert_eq!(
+            comment(CommentTarget::Hunk, lines, "").location(),
+            "src/main.rs:1-4"
+        );
+        assert_eq!(
+            comment(CommentTarget::Line, vec![removed], "").location(),
+            "src/main.rs:2 (removed)"
+        );
+    }
+
+    #[test]
+    fn exporting_comments_as_markdown_works() {
+        // GIVEN
+        let diff = Diff::new("a\nb\nc\n", "a\nB\nc\n").expect("diff should've been created");
+        let comments = vec![
+            comment(
+                CommentTarget::Hunk,
+                diff.hunks[0].lines.clone(),
+                "keep this lowercase",
+            ),
+            comment(
+                CommentTarget::Line,
+                vec![diff.hunks[0].lines[0].clone()],
+                "why is this here?",
+            ),
+        ];
+
+        // WHEN
+        let markdown = comments_to_markdown(&comments);
+
+        // THEN
+        insta::assert_snapshot!(markdown, @"
+        # Review comments
+
+        ## src/main.rs:1-3
+
+        keep this lowercase
+
+        ```diff
+         a
+        -b
+        +B
+         c
+        ```
+
+        ## src/main.rs:1
+
+        why is this here?
+
+        ```diff
+         a
+        ```
+        ");
+    }
+}
@@ -1,28 +1,6 @@
-use ratatui::style::Color;
-
 pub const MIN_TERMINAL_WIDTH: u16 = 80;
 pub const MIN_TERMINAL_HEIGHT: u16 = 24;
-pub const ADDITION_COLOR: Color = Color::from_u32(0x9ece6a);
-pub const SUBTRACTION_COLOR: Color = Color::from_u32(0xf7768e);
-pub const MODIFICATION_COLOR: Color = Color::from_u32(0xdf8e1d);
-pub const FILE_ERROR_COLOR: Color = Color::from_u32(0xfb4934);
-
-const HELP_CONTENT_RAW: &str = include_str!("static/help.txt");
 
-pub fn get_help_content() -> String {
-    #[cfg(feature = "sound")]
-    {
-        HELP_CONTENT_RAW.to_string()
-    }
-    #[cfg(not(feature = "sound"))]
-    {
-        HELP_CONTENT_RAW
-            .lines()
-            .filter(|line| !line.contains("toggle sound notifications"))
-            .collect::<Vec<_>>()
-            .join("\n")
-    }
-}
 pub const UNEXPECTED_ERROR_MSG: &str = "an unexpected error occurred";
 
 #[derive(PartialEq, Debug, Clone, Copy)]
@@ -31,6 +9,8 @@ pub enum Pane {
     Changes,
     Diff,
     Help,
+    Summary,
+    Comments,
 }
 
 impl std::fmt::Display for Pane {
@@ -39,6 +19,8 @@ impl std::fmt::Display for Pane {
             Pane::Changes => write!(f, "changes"),
             Pane::Diff => write!(f, "diff"),
             Pane::Help => write!(f, "help"),
+            Pane::Summary => write!(f, "summary"),
+            Pane::Comments => write!(f, "comments"),
         }
     }
 }
@@ -0,0 +1,109 @@
+use std::path::Path;
+
+const FALLBACK_EDITOR: &str = "vi";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditorCommand {
+    pub program: String,
+    pub args: Vec<String>,
+}
+
+// $VISUAL takes precedence over $EDITOR, as is convention
+pub(super) fn get_editor() -> String {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| FALLBACK_EDITOR.to_string())
+}
+
+// Builds the command for opening a file at a (1-indexed) line, using the syntax the editor
+// expects for the line number. Editors that aren't known get just the file path.
+pub(super) fn get_editor_command(editor: &str, path: &Path, line: Option<usize>) -> EditorCommand {
+    let mut parts = editor.split_whitespace().map(|s| s.to_string());
+    let program = parts.next().unwrap_or_else(|| FALLBACK_EDITOR.to_string());
+    let mut args = parts.collect::<Vec<_>>();
+
+    let name = Path::new(&program)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let path = path.to_string_lossy().to_string();
+
+    match (name.as_str(), line) {
+        ("vi" | "vim" | "nvim" | "nano" | "emacs" | "emacsclient", Some(line)) => {
+            args.push(format!("+{line}"));
+            args.push(path);
+        }
+        ("hx" | "helix", Some(line)) => args.push(format!("{path}:{line}")),
+        ("code" | "code-insiders" | "codium", Some(line)) => {
+            args.push("--goto".to_string());
+            args.push(format!("{path}:{line}"));
+        }
+        _ => args.push(path),
+    }
+
+    EditorCommand { program, args }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(editor: &str, line: Option<usize>) -> (String, Vec<String>) {
+        let EditorCommand { program, args } =
+            get_editor_command(editor, Path::new("src/main.rs"), line);
+        (program, args)
+    }
+
+    #[test]
+    fn editor_commands_use_each_editors_line_syntax() {
+        // GIVEN
+        // WHEN
+        // THEN
+        assert_eq!(
+            command("nvim", Some(12)),
+            ("nvim".to_string(), vec!["+12".into(), "src/main.rs".into()])
+        );
+        assert_eq!(
+            command("/usr/bin/vim", Some(3)),
+            (
+                "/usr/bin/vim".to_string(),
+                vec!["+3".into(), "src/main.rs".into()]
+            )
+        );
+        assert_eq!(
+            command("emacsclient -nw", Some(3)),
+            (
+                "emacsclient".to_string(),
+                vec!["-nw".into(), "+3".into(), "src/main.rs".into()]
+            )
+        );
+        assert_eq!(
+            command("hx", Some(7)),
+            ("hx".to_string(), vec!["src/main.rs:7".into()])
+        );
+        assert_eq!(
+            command("code --wait", Some(7)),
+            (
+                "code".to_string(),
+                vec!["--wait".into(), "--goto".into(), "src/main.rs:7".into()]
+            )
+        );
+    }
+
+    #[test]
+    fn unknown_editors_and_missing_lines_only_get_the_path() {
+        // GIVEN
+        // WHEN
+        // THEN
+        assert_eq!(
+            command("ed", Some(7)),
+            ("ed".to_string(), vec!["src/main.rs".into()])
+        );
+        assert_eq!(
+            command("nvim", None),
+            ("nvim".to_string(), vec!["src/main.rs".into()])
+        );
+    }
+}
@@ -0,0 +1,144 @@
+use crate::domain::{Change, ChangeKind};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChangeKindFilter {
+    #[default]
+    All,
+    Created,
+    Modified,
+    Removed,
+    Error,
+}
+
+impl ChangeKindFilter {
+    pub(super) fn next(self) -> Self {
+        match self {
+            ChangeKindFilter::All => ChangeKindFilter::Created,
+            ChangeKindFilter::Created => ChangeKindFilter::Modified,
+            ChangeKindFilter::Modified => ChangeKindFilter::Removed,
+            ChangeKindFilter::Removed => ChangeKindFilter::Error,
+            ChangeKindFilter::Error => ChangeKindFilter::All,
+        }
+    }
+
+    fn includes(&self, kind: &ChangeKind) -> bool {
+        match self {
+            ChangeKindFilter::All => true,
+            ChangeKindFilter::Created => match kind {
+                ChangeKind::Created(Ok(_)) => true,
+                ChangeKind::Binary(change) | ChangeKind::TooLarge(change) => {
+                    change.before.is_none()
+                }
+                _ => false,
+            },
+            ChangeKindFilter::Modified => match kind {
+                ChangeKind::Modified(Ok(_)) | ChangeKind::Reencoded(_) => true,
+                ChangeKind::Binary(change) | ChangeKind::TooLarge(change) => {
+                    change.before.is_some()
+                }
+                _ => false,
+            },
+            ChangeKindFilter::Removed => {
+                matches!(kind, ChangeKind::RemovedFile | ChangeKind::RemovedDir)
+            }
+            ChangeKindFilter::Error => {
+                matches!(
+                    kind,
+                    ChangeKind::Created(Err(_)) | ChangeKind::Modified(Err(_))
+                )
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for ChangeKindFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChangeKindFilter::All => write!(f, "all kinds"),
+            ChangeKindFilter::Created => write!(f, "created"),
+            ChangeKindFilter::Modified => write!(f, "modified"),
+            ChangeKindFilter::Removed => write!(f, "removed"),
+            ChangeKindFilter::Error => write!(f, "errors"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChangesFilter {
+    pub query: String,
+    pub kind: ChangeKindFilter,
+}
+
+impl ChangesFilter {
+    pub(super) fn is_active(&self) -> bool {
+        !self.query.is_empty() || self.kind != ChangeKindFilter::All
+    }
+
+    pub(super) fn matches(&self, change: &Change) -> bool {
+        self.kind.includes(&change.kind) && fuzzy_matches(&self.query, &change.path)
+    }
+}
+
+// A path matches if it contains every character of the query in order, eg. "mdrs" matches
+// "src/tui/model.rs". Matching is case insensitive unless the query has an uppercase character.
+fn fuzzy_matches(query: &str, path: &str) -> bool {
+    let case_sensitive = query.chars().any(|c| c.is_uppercase());
+    let mut path_chars = path.chars();
+
+    query.chars().all(|q| {
+        path_chars.any(|p| {
+            if case_sensitive {
+                p == q
+            } else {
+                p.to_lowercase().eq(q.to_lowercase())
+            }
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzy_matching_works() {
+        // GIVEN
+        let path = "src/tui/Model.rs";
+
+        // WHEN
+        // THEN
+        assert!(fuzzy_matches("", path));
+        assert!(fuzzy_matches("model", path));
+        assert!(fuzzy_matches("stmrs", path));
+        assert!(fuzzy_matches("Model", path));
+        assert!(!fuzzy_matches("MODEL", path));
+        assert!(!fuzzy_matches("rsmodel", path));
+    }
+
+    #[test]
+    fn filter_matches_on_both_path_and_kind() {
+        // GIVEN
+        let filter = ChangesFilter {
+            query: "tui".to_string(),
+            kind: ChangeKindFilter::Removed,
+        };
+        let change = |path: &str, kind| Change {
+            path: path.to_string(),
+            kind,
+            encoding: None,
+        };
+
+        // WHEN
+        // THEN
+        assert!(filter.matches(&change("src/tui/view.rs", ChangeKind::RemovedFile)));
+        assert!(!filter.matches(&change("src/main.rs", ChangeKind::RemovedFile)));
+        assert!(!filter.matches(&change(
+            "src/tui/view.rs",
+            ChangeKind::Created(Ok(String::new()))
+        )));
+        assert!(!filter.matches(&change(
+            "src/tui/view.rs",
+            ChangeKind::Created(Err("error".to_string()))
+        )));
+    }
+}
@@ -1,6 +1,8 @@
 use crate::watcher::watch_for_changes;
 
+use super::clipboard::copy_to_clipboard;
 use super::cmd::Cmd;
+use super::comments::write_comments_file;
 use super::msg::Msg;
 use tokio::sync::mpsc::Sender;
 
@@ -8,10 +10,14 @@ pub(super) async fn handle_command(command: Cmd, event_tx: Sender<Msg>) {
     match command {
         Cmd::WatchForChanges {
             root,
+            root_index,
             cache,
             sender,
             cancellation_token,
             prepopulate_cache,
+            settings,
+            metrics,
+            filter,
         } => {
             tokio::spawn(async move {
                 if let Err(e) = watch_for_changes(
@@ -20,12 +26,31 @@ pub(super) async fn handle_command(command: Cmd, event_tx: Sender<Msg>) {
                     sender.clone(),
                     cancellation_token,
                     prepopulate_cache,
+                    settings,
+                    metrics,
+                    filter,
                 )
                 .await
                 {
-                    let _ = event_tx.try_send(Msg::WatchingFailed(e.to_string()));
+                    let _ = event_tx.try_send(Msg::WatchingFailed(root_index, e.to_string()));
                 }
             });
         }
+        Cmd::ExportComments { contents } => {
+            tokio::spawn(async move {
+                let msg = match write_comments_file(contents).await {
+                    Ok(path) => Msg::CommentsExported(path),
+                    Err(e) => Msg::CommentsExportFailed(format!("{e:#}")),
+                };
+                let _ = event_tx.try_send(msg);
+            });
+        }
+        Cmd::CopyToClipboard { contents } => {
+            if let Err(e) = copy_to_clipboard(&contents) {
+                let _ = event_tx.try_send(Msg::CopyingFailed(e.to_string()));
+            }
+        }
+        // AppTui runs the editor itself, as that requires access to the terminal
+        Cmd::OpenInEditor { .. } => {}
     }
 }
@@ -0,0 +1,928 @@
+use super::common::Pane;
+use super::model::Model;
+use super::msg::Msg;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+const UNBIND_ACTION: &str = "none";
+const HELP_KEYS_COLUMN_WIDTH: usize = 20;
+const MOUSE_HELP_LINES: [(&str, &str); 2] = [
+    ("<click>", "focus pane/select change"),
+    ("<scroll>", "scroll pane under cursor"),
+];
+
+// Keymap overrides as read from the config file, eg.
+//
+// [keymap.diff]
+// "<c-n>" = "next_hunk"
+// "]" = "none"
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct KeymapConfig {
+    general: BTreeMap<String, String>,
+    diff: BTreeMap<String, String>,
+    changes: BTreeMap<String, String>,
+    prompt: BTreeMap<String, String>,
+    summary: BTreeMap<String, String>,
+    comments: BTreeMap<String, String>,
+    help: BTreeMap<String, String>,
+}
+
+impl KeymapConfig {
+    fn section(&self, context: KeymapContext) -> &BTreeMap<String, String> {
+        match context {
+            KeymapContext::General => &self.general,
+            KeymapContext::Diff => &self.diff,
+            KeymapContext::Changes => &self.changes,
+            KeymapContext::Prompt => &self.prompt,
+            KeymapContext::Summary => &self.summary,
+            KeymapContext::Comments => &self.comments,
+            KeymapContext::Help => &self.help,
+        }
+    }
+
+    fn section_mut(&mut self, context: KeymapContext) -> &mut BTreeMap<String, String> {
+        match context {
+            KeymapContext::General => &mut self.general,
+            KeymapContext::Diff => &mut self.diff,
+            KeymapContext::Changes => &mut self.changes,
+            KeymapContext::Prompt => &mut self.prompt,
+            KeymapContext::Summary => &mut self.summary,
+            KeymapContext::Comments => &mut self.comments,
+            KeymapContext::Help => &mut self.help,
+        }
+    }
+
+    // Layers another set of overrides on top of this one; keys bound in both take the binding
+    // from the other one
+    pub fn merge(&mut self, mut other: KeymapConfig) {
+        for context in KeymapContext::ALL {
+            self.section_mut(context)
+                .extend(std::mem::take(other.section_mut(context)));
+        }
+    }
+
+    // every override, as it'd be written in the config file
+    pub fn overrides(&self) -> Vec<String> {
+        KeymapContext::ALL
+            .iter()
+            .flat_map(|context| {
+                self.section(*context).iter().map(|(key, action)| {
+                    format!("[keymap.{}] \"{key}\" = \"{action}\"", context.name())
+                })
+            })
+            .collect()
+    }
+}
+
+// Where a set of bindings applies. General bindings apply everywhere except in the prompt,
+// unless a pane binds the same key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum KeymapContext {
+    General,
+    Diff,
+    Changes,
+    Prompt,
+    Summary,
+    Comments,
+    Help,
+}
+
+impl KeymapContext {
+    const ALL: [KeymapContext; 7] = [
+        KeymapContext::General,
+        KeymapContext::Diff,
+        KeymapContext::Changes,
+        KeymapContext::Prompt,
+        KeymapContext::Summary,
+        KeymapContext::Comments,
+        KeymapContext::Help,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            KeymapContext::General => "general",
+            KeymapContext::Diff => "diff",
+            KeymapContext::Changes => "changes",
+            KeymapContext::Prompt => "prompt",
+            KeymapContext::Summary => "summary",
+            KeymapContext::Comments => "comments",
+            KeymapContext::Help => "help",
+        }
+    }
+
+    fn title(&self) -> &'static str {
+        match self {
+            KeymapContext::General => "General",
+            KeymapContext::Diff => "Diff Pane",
+            KeymapContext::Changes => "Changes Pane",
+            KeymapContext::Prompt => "Search/Filter/Comment Prompt",
+            KeymapContext::Summary => "Summary Pane",
+            KeymapContext::Comments => "Comments Pane",
+            KeymapContext::Help => "Help Pane",
+        }
+    }
+}
+
+impl From<Pane> for KeymapContext {
+    fn from(pane: Pane) -> Self {
+        match pane {
+            Pane::Changes => KeymapContext::Changes,
+            Pane::Diff => KeymapContext::Diff,
+            Pane::Help => KeymapContext::Help,
+            Pane::Summary => KeymapContext::Summary,
+            Pane::Comments => KeymapContext::Comments,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct KeyChord {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    const fn key(code: KeyCode) -> Self {
+        Self {
+            code,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    const fn char(c: char) -> Self {
+        Self::key(KeyCode::Char(c))
+    }
+
+    const fn ctrl(c: char) -> Self {
+        Self {
+            code: KeyCode::Char(c),
+            modifiers: KeyModifiers::CONTROL,
+        }
+    }
+
+    // the character typed by this chord, if any
+    pub(super) fn typed_char(&self) -> Option<char> {
+        match self.code {
+            KeyCode::Char(c) if self.modifiers.is_empty() => Some(c),
+            _ => None,
+        }
+    }
+
+    // eg. "j", "<space>", "<c-d>", "<a-x>", "<s-tab>", "<down>"
+    fn parse(value: &str) -> Result<Self, String> {
+        let invalid = || format!("\"{value}\" is not a valid key");
+
+        if let Some(code) = single_char_key(value) {
+            return Ok(Self::key(code));
+        }
+
+        let mut inner = value
+            .strip_prefix('<')
+            .and_then(|v| v.strip_suffix('>'))
+            .filter(|v| !v.is_empty())
+            .ok_or_else(invalid)?;
+
+        let mut modifiers = KeyModifiers::NONE;
+        loop {
+            let modifier = match inner.split_once('-') {
+                Some(("c", rest)) if !rest.is_empty() => KeyModifiers::CONTROL,
+                Some(("a", rest)) if !rest.is_empty() => KeyModifiers::ALT,
+                Some(("s", rest)) if !rest.is_empty() => KeyModifiers::SHIFT,
+                _ => break,
+            };
+            modifiers |= modifier;
+            inner = &inner[2..];
+        }
+
+        let code = match single_char_key(inner) {
+            Some(code) => code,
+            None => match inner {
+                "space" => KeyCode::Char(' '),
+                "enter" => KeyCode::Enter,
+                "esc" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "bs" => KeyCode::Backspace,
+                "del" => KeyCode::Delete,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pgup" => KeyCode::PageUp,
+                "pgdn" => KeyCode::PageDown,
+                _ => match inner.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n) if (1..=12).contains(&n) => KeyCode::F(n),
+                    _ => return Err(invalid()),
+                },
+            },
+        };
+
+        // terminals report shift+tab as a key of its own; for characters, shift is implied by
+        // the case of the character
+        if modifiers.contains(KeyModifiers::SHIFT) {
+            if code != KeyCode::Tab || modifiers != KeyModifiers::SHIFT {
+                return Err(format!(
+                    "\"{value}\" is not a valid key (shift can only be used with <tab>; use \
+                     uppercase characters instead)"
+                ));
+            }
+            return Ok(Self::key(KeyCode::BackTab));
+        }
+
+        Ok(Self { code, modifiers })
+    }
+}
+
+// keys written as a single character; arrow keys can be written as arrows
+fn single_char_key(value: &str) -> Option<KeyCode> {
+    let mut chars = value.chars();
+    let (Some(c), None) = (chars.next(), chars.next()) else {
+        return None;
+    };
+
+    let code = match c {
+        '↑' => KeyCode::Up,
+        '↓' => KeyCode::Down,
+        '←' => KeyCode::Left,
+        '→' => KeyCode::Right,
+        c => KeyCode::Char(c),
+    };
+
+    Some(code)
+}
+
+impl From<KeyEvent> for KeyChord {
+    fn from(event: KeyEvent) -> Self {
+        Self {
+            code: event.code,
+            modifiers: event.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT),
+        }
+    }
+}
+
+impl std::fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let key = match self.code {
+            KeyCode::Char(' ') => "space".to_string(),
+            KeyCode::Char(c) => c.to_string(),
+            KeyCode::Enter => "enter".to_string(),
+            KeyCode::Esc => "esc".to_string(),
+            KeyCode::Tab => "tab".to_string(),
+            KeyCode::BackTab => "s-tab".to_string(),
+            KeyCode::Backspace => "bs".to_string(),
+            KeyCode::Delete => "del".to_string(),
+            KeyCode::Up => "↑".to_string(),
+            KeyCode::Down => "↓".to_string(),
+            KeyCode::Left => "←".to_string(),
+            KeyCode::Right => "→".to_string(),
+            KeyCode::Home => "home".to_string(),
+            KeyCode::End => "end".to_string(),
+            KeyCode::PageUp => "pgup".to_string(),
+            KeyCode::PageDown => "pgdn".to_string(),
+            KeyCode::F(n) => format!("f{n}"),
+            _ => "?".to_string(),
+        };
+
+        let mut prefix = String::new();
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            prefix.push_str("c-");
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            prefix.push_str("a-");
+        }
+
+        let is_bare = prefix.is_empty()
+            && (matches!(
+                self.code,
+                KeyCode::Up | KeyCode::Down | KeyCode::Left | KeyCode::Right
+            ) || (matches!(self.code, KeyCode::Char(c) if c != ' ')));
+
+        if is_bare {
+            write!(f, "{key}")
+        } else {
+            write!(f, "<{prefix}{key}>")
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Action {
+    ToggleHelp,
+    ToggleSummary,
+    ToggleComments,
+    GoBack,
+    Quit,
+    SelectNext,
+    SelectPrevious,
+    SelectFirst,
+    SelectLast,
+    ScrollDown,
+    ScrollUp,
+    ScrollHalfPageDown,
+    ScrollHalfPageUp,
+    ScrollLeft,
+    ScrollRight,
+    ToggleWrap,
+    ToggleFullFileView,
+    Search,
+    NextSearchMatch,
+    PreviousSearchMatch,
+    NextHunk,
+    PreviousHunk,
+    NextChangedLine,
+    PreviousChangedLine,
+    Filter,
+    CycleChangeKindFilter,
+    ToggleGroupedView,
+    ToggleExpanded,
+    ToggleReviewed,
+    SelectNextUnreviewed,
+    Comment,
+    GoToComment,
+    DeleteComment,
+    ExportComments,
+    OpenInEditor,
+    CopyPath,
+    CopyDiff,
+    CopyHunk,
+    ToggleWatching,
+    EditPathFilter,
+    ResetList,
+    ToggleFollowChanges,
+    #[cfg(feature = "sound")]
+    ToggleSound,
+    SwitchPane,
+    CycleSortOrder,
+    CyclePromptOption,
+    SubmitPrompt,
+    CancelPrompt,
+    DeletePromptInput,
+}
+
+impl Action {
+    // the name used for the action in the config file
+    fn name(&self) -> &'static str {
+        match self {
+            Action::ToggleHelp => "toggle_help",
+            Action::ToggleSummary => "toggle_summary",
+            Action::ToggleComments => "toggle_comments",
+            Action::GoBack => "go_back",
+            Action::Quit => "quit",
+            Action::SelectNext => "select_next",
+            Action::SelectPrevious => "select_previous",
+            Action::SelectFirst => "select_first",
+            Action::SelectLast => "select_last",
+            Action::ScrollDown => "scroll_down",
+            Action::ScrollUp => "scroll_up",
+            Action::ScrollHalfPageDown => "scroll_half_page_down",
+            Action::ScrollHalfPageUp => "scroll_half_page_up",
+            Action::ScrollLeft => "scroll_left",
+            Action::ScrollRight => "scroll_right",
+            Action::ToggleWrap => "toggle_wrap",
+            Action::ToggleFullFileView => "toggle_full_file_view",
+            Action::Search => "search",
+            Action::NextSearchMatch => "next_search_match",
+            Action::PreviousSearchMatch => "previous_search_match",
+            Action::NextHunk => "next_hunk",
+            Action::PreviousHunk => "previous_hunk",
+            Action::NextChangedLine => "next_changed_line",
+            Action::PreviousChangedLine => "previous_changed_line",
+            Action::Filter => "filter",
+            Action::CycleChangeKindFilter => "cycle_change_kind_filter",
+            Action::ToggleGroupedView => "toggle_grouped_view",
+            Action::ToggleExpanded => "toggle_expanded",
+            Action::ToggleReviewed => "toggle_reviewed",
+            Action::SelectNextUnreviewed => "select_next_unreviewed",
+            Action::Comment => "comment",
+            Action::GoToComment => "go_to_comment",
+            Action::DeleteComment => "delete_comment",
+            Action::ExportComments => "export_comments",
+            Action::OpenInEditor => "open_in_editor",
+            Action::CopyPath => "copy_path",
+            Action::CopyDiff => "copy_diff",
+            Action::CopyHunk => "copy_hunk",
+            Action::ToggleWatching => "toggle_watching",
+            Action::EditPathFilter => "edit_path_filter",
+            Action::ResetList => "reset_list",
+            Action::ToggleFollowChanges => "toggle_follow_changes",
+            #[cfg(feature = "sound")]
+            Action::ToggleSound => "toggle_sound",
+            Action::SwitchPane => "switch_pane",
+            Action::CycleSortOrder => "cycle_sort_order",
+            Action::CyclePromptOption => "cycle_prompt_option",
+            Action::SubmitPrompt => "submit_prompt",
+            Action::CancelPrompt => "cancel_prompt",
+            Action::DeletePromptInput => "delete_prompt_input",
+        }
+    }
+
+    fn description(&self, context: KeymapContext) -> &'static str {
+        let in_diff_or_changes = matches!(context, KeymapContext::Diff | KeymapContext::Changes);
+        match self {
+            Action::ToggleHelp => "show/hide help view",
+            Action::ToggleSummary => "show/hide session summary",
+            Action::ToggleComments => "show/hide comments",
+            Action::GoBack => "go back/exit",
+            Action::Quit => "exit immediately",
+            Action::SelectNext if context == KeymapContext::Comments => "select next comment",
+            Action::SelectNext => "select next change",
+            Action::SelectPrevious if context == KeymapContext::Comments => {
+                "select previous comment"
+            }
+            Action::SelectPrevious => "select previous change",
+            Action::SelectFirst => "select first change",
+            Action::SelectLast => "select last change",
+            Action::ScrollDown if in_diff_or_changes => "scroll diff down by a line",
+            Action::ScrollDown => "scroll down",
+            Action::ScrollUp if in_diff_or_changes => "scroll diff up by a line",
+            Action::ScrollUp => "scroll up",
+            Action::ScrollHalfPageDown => "scroll diff down by half page",
+            Action::ScrollHalfPageUp => "scroll diff up by half page",
+            Action::ScrollLeft => "scroll diff left",
+            Action::ScrollRight => "scroll diff right",
+            Action::ToggleWrap => "toggle wrapping long lines",
+            Action::ToggleFullFileView => "toggle full file view",
+            Action::Search => "search diff (regex; smart case)",
+            Action::NextSearchMatch => "go to next search match",
+            Action::PreviousSearchMatch => "go to previous search match",
+            Action::NextHunk => "go to next hunk",
+            Action::PreviousHunk => "go to previous hunk",
+            Action::NextChangedLine => "go to next changed line",
+            Action::PreviousChangedLine => "go to previous changed line",
+            Action::Filter => "filter changes by path (fuzzy)",
+            Action::CycleChangeKindFilter => "cycle kind of changes to show",
+            Action::ToggleGroupedView => "toggle grouping changes by file",
+            Action::ToggleExpanded => "expand/collapse file (when grouped)",
+            Action::ToggleReviewed => "toggle marking change as reviewed",
+            Action::SelectNextUnreviewed => "select next unreviewed change",
+            Action::Comment => "comment on line in focus",
+            Action::GoToComment => "go to comment",
+            Action::DeleteComment => "delete comment",
+            Action::ExportComments => "export comments as markdown",
+            Action::OpenInEditor => "open change in editor",
+            Action::CopyPath => "copy path",
+            Action::CopyDiff => "copy diff (or contents of created file)",
+            Action::CopyHunk => "copy hunk in focus",
+            Action::ToggleWatching => "toggle watching",
+            Action::EditPathFilter => "change globs for files to watch",
+            Action::ResetList => "reset list",
+            Action::ToggleFollowChanges => "toggle following changes",
+            #[cfg(feature = "sound")]
+            Action::ToggleSound => "toggle sound notifications",
+            Action::SwitchPane if context == KeymapContext::Diff => "switch to changes pane",
+            Action::SwitchPane => "switch to diff pane",
+            Action::CycleSortOrder => "cycle sort order (churn/changes/path)",
+            Action::CyclePromptOption => "cycle search scope/kind of changes/comment target",
+            Action::SubmitPrompt => "confirm",
+            Action::CancelPrompt => "cancel",
+            Action::DeletePromptInput => "delete last character",
+        }
+    }
+
+    pub(super) fn msg(&self, model: &Model) -> Option<Msg> {
+        let toggle_pane = |pane: Pane| {
+            if model.active_pane == pane {
+                Msg::GoBackOrQuit
+            } else {
+                Msg::GoToPane(pane)
+            }
+        };
+
+        let msg = match self {
+            Action::ToggleHelp => toggle_pane(Pane::Help),
+            Action::ToggleSummary => toggle_pane(Pane::Summary),
+            Action::ToggleComments => toggle_pane(Pane::Comments),
+            Action::GoBack => Msg::GoBackOrQuit,
+            Action::Quit => Msg::QuitImmediately,
+            Action::SelectNext => Msg::SelectNext,
+            Action::SelectPrevious => Msg::SelectPrevious,
+            Action::SelectFirst => Msg::SelectFirst,
+            Action::SelectLast => Msg::SelectLast,
+            Action::ScrollDown => Msg::ScrollDown,
+            Action::ScrollUp => Msg::ScrollUp,
+            Action::ScrollHalfPageDown => Msg::ScrollHalfPageDown,
+            Action::ScrollHalfPageUp => Msg::ScrollHalfPageUp,
+            Action::ScrollLeft => Msg::ScrollLeft,
+            Action::ScrollRight => Msg::ScrollRight,
+            Action::ToggleWrap => Msg::ToggleWrap,
+            Action::ToggleFullFileView => Msg::ToggleFullFileView,
+            Action::Search => Msg::StartSearch,
+            Action::NextSearchMatch => Msg::GoToNextSearchMatch,
+            Action::PreviousSearchMatch => Msg::GoToPreviousSearchMatch,
+            Action::NextHunk => Msg::GoToNextHunk,
+            Action::PreviousHunk => Msg::GoToPreviousHunk,
+            Action::NextChangedLine => Msg::GoToNextChangedLine,
+            Action::PreviousChangedLine => Msg::GoToPreviousChangedLine,
+            Action::Filter => Msg::StartFilter,
+            Action::CycleChangeKindFilter => Msg::CycleChangeKindFilter,
+            Action::ToggleGroupedView => Msg::ToggleGroupedView,
+            Action::ToggleExpanded => Msg::ToggleExpanded,
+            Action::ToggleReviewed => Msg::ToggleReviewed,
+            Action::SelectNextUnreviewed => Msg::SelectNextUnreviewed,
+            Action::Comment => Msg::StartComment,
+            Action::GoToComment => Msg::GoToComment,
+            Action::DeleteComment => Msg::DeleteComment,
+            Action::ExportComments => Msg::ExportComments,
+            Action::OpenInEditor => Msg::OpenInEditor,
+            Action::CopyPath => Msg::CopyPath,
+            Action::CopyDiff => Msg::CopyDiff,
+            Action::CopyHunk => Msg::CopyHunk,
+            Action::ToggleWatching => Msg::ToggleWatching,
+            Action::EditPathFilter => Msg::StartPathFilterEdit,
+            Action::ResetList => Msg::ResetList,
+            Action::ToggleFollowChanges => Msg::ToggleFollowChanges,
+            #[cfg(feature = "sound")]
+            Action::ToggleSound => Msg::ToggleSound,
+            Action::SwitchPane => match model.active_pane {
+                Pane::Diff => Msg::GoToPane(Pane::Changes),
+                Pane::Changes => Msg::GoToPane(Pane::Diff),
+                Pane::Help | Pane::Summary | Pane::Comments => return None,
+            },
+            Action::CycleSortOrder => Msg::CycleSummarySortKey,
+            Action::CyclePromptOption => Msg::CyclePromptOption,
+            Action::SubmitPrompt => Msg::SubmitPrompt,
+            Action::CancelPrompt => Msg::CancelPrompt,
+            Action::DeletePromptInput => Msg::DeletePromptInput,
+        };
+
+        Some(msg)
+    }
+}
+
+// The actions available in a context, in the order they're listed in the help pane, each with the
+// keys bound to it
+type Bindings = Vec<(Action, Vec<KeyChord>)>;
+
+fn default_bindings(context: KeymapContext) -> Bindings {
+    use Action::*;
+    use KeyChord as K;
+
+    let select_next = (SelectNext, vec![K::char('j'), K::key(KeyCode::Down)]);
+    let select_previous = (SelectPrevious, vec![K::char('k'), K::key(KeyCode::Up)]);
+    let scroll_down = (ScrollDown, vec![K::char('j'), K::key(KeyCode::Down)]);
+    let scroll_up = (ScrollUp, vec![K::char('k'), K::key(KeyCode::Up)]);
+    let switch_pane = (
+        SwitchPane,
+        vec![K::key(KeyCode::Tab), K::key(KeyCode::BackTab)],
+    );
+
+    match context {
+        KeymapContext::General => vec![
+            (ToggleHelp, vec![K::char('?')]),
+            (GoBack, vec![K::key(KeyCode::Esc), K::char('q')]),
+            (Quit, vec![K::ctrl('c')]),
+            (ToggleSummary, vec![K::char('S')]),
+            (ToggleComments, vec![K::char('R')]),
+        ],
+        KeymapContext::Diff => vec![
+            select_next,
+            select_previous,
+            (ScrollDown, vec![K::char('J')]),
+            (ScrollUp, vec![K::char('K')]),
+            (ScrollHalfPageDown, vec![K::ctrl('d')]),
+            (ScrollHalfPageUp, vec![K::ctrl('u')]),
+            (ScrollLeft, vec![K::char('h'), K::key(KeyCode::Left)]),
+            (ScrollRight, vec![K::char('l'), K::key(KeyCode::Right)]),
+            (ToggleWrap, vec![K::char('w')]),
+            (ToggleFullFileView, vec![K::char('v')]),
+            (Search, vec![K::char('/')]),
+            (NextSearchMatch, vec![K::char('n')]),
+            (PreviousSearchMatch, vec![K::char('N')]),
+            (NextHunk, vec![K::char(']')]),
+            (PreviousHunk, vec![K::char('[')]),
+            (NextChangedLine, vec![K::char('}')]),
+            (PreviousChangedLine, vec![K::char('{')]),
+            (ToggleGroupedView, vec![K::char('T')]),
+            (ToggleReviewed, vec![K::char('r')]),
+            (SelectNextUnreviewed, vec![K::char('u')]),
+            (Comment, vec![K::char('c')]),
+            (OpenInEditor, vec![K::char('e')]),
+            (CopyPath, vec![K::char('y')]),
+            (CopyDiff, vec![K::char('Y')]),
+            (CopyHunk, vec![K::ctrl('y')]),
+            (SelectFirst, vec![K::char('g')]),
+            (SelectLast, vec![K::char('G')]),
+            (ToggleWatching, vec![K::char(' ')]),
+            (EditPathFilter, vec![K::char('F')]),
+            (ResetList, vec![K::ctrl('r')]),
+            (ToggleFollowChanges, vec![K::char('f')]),
+            #[cfg(feature = "sound")]
+            (ToggleSound, vec![K::char('s')]),
+            switch_pane,
+        ],
+        KeymapContext::Changes => vec![
+            select_next,
+            select_previous,
+            (SelectFirst, vec![K::char('g')]),
+            (SelectLast, vec![K::char('G')]),
+            (ScrollDown, vec![K::char('J')]),
+            (ScrollUp, vec![K::char('K')]),
+            (ScrollHalfPageDown, vec![K::ctrl('d')]),
+            (ScrollHalfPageUp, vec![K::ctrl('u')]),
+            (ScrollLeft, vec![K::char('h'), K::key(KeyCode::Left)]),
+            (ScrollRight, vec![K::char('l'), K::key(KeyCode::Right)]),
+            (ToggleWrap, vec![K::char('w')]),
+            (ToggleFollowChanges, vec![K::char('f')]),
+            #[cfg(feature = "sound")]
+            (ToggleSound, vec![K::char('s')]),
+            (ResetList, vec![K::ctrl('r')]),
+            (ToggleWatching, vec![K::char(' ')]),
+            (EditPathFilter, vec![K::char('F')]),
+            (Filter, vec![K::char('/')]),
+            (CycleChangeKindFilter, vec![K::char('t')]),
+            (ToggleGroupedView, vec![K::char('T')]),
+            (ToggleExpanded, vec![K::key(KeyCode::Enter)]),
+            (ToggleReviewed, vec![K::char('r')]),
+            (SelectNextUnreviewed, vec![K::char('u')]),
+            (Comment, vec![K::char('c')]),
+            (OpenInEditor, vec![K::char('e')]),
+            (CopyPath, vec![K::char('y')]),
+            (CopyDiff, vec![K::char('Y')]),
+            (CopyHunk, vec![K::ctrl('y')]),
+            switch_pane,
+        ],
+        KeymapContext::Prompt => vec![
+            (CyclePromptOption, vec![K::key(KeyCode::Tab)]),
+            (SubmitPrompt, vec![K::key(KeyCode::Enter)]),
+            (CancelPrompt, vec![K::key(KeyCode::Esc)]),
+            (DeletePromptInput, vec![K::key(KeyCode::Backspace)]),
+        ],
+        KeymapContext::Summary => {
+            vec![scroll_down, scroll_up, (CycleSortOrder, vec![K::char('o')])]
+        }
+        KeymapContext::Comments => vec![
+            (SelectNext, vec![K::char('j'), K::key(KeyCode::Down)]),
+            (SelectPrevious, vec![K::char('k'), K::key(KeyCode::Up)]),
+            (GoToComment, vec![K::key(KeyCode::Enter)]),
+            (DeleteComment, vec![K::char('d')]),
+            (ExportComments, vec![K::char('E')]),
+        ],
+        KeymapContext::Help => vec![scroll_down, scroll_up],
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: Vec<(KeymapContext, Bindings)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self {
+            bindings: KeymapContext::ALL
+                .iter()
+                .map(|&context| (context, default_bindings(context)))
+                .collect(),
+        }
+    }
+}
+
+impl Keymap {
+    // Applies the overrides from the config file on top of the default keymap. A key bound to
+    // an action replaces whatever it was bound to before; binding it to "none" unbinds it.
+    pub fn from_config(config: &KeymapConfig) -> anyhow::Result<Self> {
+        let mut keymap = Self::default();
+        let mut errors = vec![];
+
+        for (context, actions) in &mut keymap.bindings {
+            for (key, action_name) in config.section(*context) {
+                let chord = match KeyChord::parse(key) {
+                    Ok(chord) => chord,
+                    Err(e) => {
+                        errors.push(format!("[keymap.{}] {e}", context.name()));
+                        continue;
+                    }
+                };
+
+                if *context == KeymapContext::Prompt && chord.typed_char().is_some() {
+                    errors.push(format!(
+                        "[keymap.prompt] \"{key}\" can't be bound, since it's typed into the prompt"
+                    ));
+                    continue;
+                }
+
+                let index = if action_name == UNBIND_ACTION {
+                    None
+                } else {
+                    match actions.iter().position(|(a, _)| a.name() == action_name) {
+                        Some(index) => Some(index),
+                        None => {
+                            errors.push(format!(
+                                "[keymap.{}] \"{action_name}\" is not an action available here; \
+                                 available actions: {}",
+                                context.name(),
+                                actions
+                                    .iter()
+                                    .map(|(a, _)| a.name())
+                                    .collect::<Vec<_>>()
+                                    .join(", ")
+                            ));
+                            continue;
+                        }
+                    }
+                };
+
+                for (_, chords) in actions.iter_mut() {
+                    chords.retain(|c| c != &chord);
+                }
+                if let Some((_, chords)) = index.and_then(|i| actions.get_mut(i)) {
+                    chords.push(chord);
+                }
+            }
+        }
+
+        if !errors.is_empty() {
+            anyhow::bail!("{}", errors.join("\n"));
+        }
+
+        Ok(keymap)
+    }
+
+    pub(super) fn action_for(&self, context: KeymapContext, chord: KeyChord) -> Option<Action> {
+        self.bindings
+            .iter()
+            .filter(|(c, _)| *c == context)
+            .flat_map(|(_, actions)| actions.iter())
+            .find(|(_, chords)| chords.contains(&chord))
+            .map(|(action, _)| *action)
+    }
+
+    // The contents of the help pane, listing every binding in effect
+    pub(super) fn help_content(&self, mouse: bool) -> String {
+        let mut lines = vec!["Keymaps".to_string(), "---".to_string()];
+
+        if mouse {
+            lines.push(String::new());
+            lines.push("Mouse".to_string());
+            for (keys, description) in MOUSE_HELP_LINES {
+                lines.push(format!("    {keys:<HELP_KEYS_COLUMN_WIDTH$} {description}"));
+            }
+        }
+
+        for (context, actions) in &self.bindings {
+            lines.push(String::new());
+            lines.push(context.title().to_string());
+            for (action, chords) in actions.iter().filter(|(_, chords)| !chords.is_empty()) {
+                let keys = chords
+                    .iter()
+                    .map(|c| c.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" / ");
+                lines.push(format!(
+                    "    {keys:<HELP_KEYS_COLUMN_WIDTH$} {}",
+                    action.description(*context)
+                ));
+            }
+        }
+
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(toml: &str) -> KeymapConfig {
+        #[derive(Deserialize)]
+        struct Wrapper {
+            keymap: KeymapConfig,
+        }
+
+        toml::from_str::<Wrapper>(toml)
+            .expect("config should've been parsed")
+            .keymap
+    }
+
+    #[test]
+    fn parsing_key_chords_works() {
+        // GIVEN
+        // WHEN
+        // THEN
+        assert_eq!(KeyChord::parse("j"), Ok(KeyChord::char('j')));
+        assert_eq!(KeyChord::parse("<c-d>"), Ok(KeyChord::ctrl('d')));
+        assert_eq!(KeyChord::parse("<space>"), Ok(KeyChord::char(' ')));
+        assert_eq!(
+            KeyChord::parse("<s-tab>"),
+            Ok(KeyChord::key(KeyCode::BackTab))
+        );
+        assert_eq!(KeyChord::parse("<down>"), Ok(KeyChord::key(KeyCode::Down)));
+        assert_eq!(KeyChord::parse("↓"), Ok(KeyChord::key(KeyCode::Down)));
+        assert_eq!(KeyChord::parse("<c-->"), Ok(KeyChord::ctrl('-')));
+        assert_eq!(
+            KeyChord::parse("<a-enter>"),
+            Ok(KeyChord {
+                code: KeyCode::Enter,
+                modifiers: KeyModifiers::ALT,
+            })
+        );
+    }
+
+    #[test]
+    fn parsing_invalid_key_chords_fails() {
+        // GIVEN
+        // WHEN
+        // THEN
+        assert!(KeyChord::parse("").is_err());
+        assert!(KeyChord::parse("jj").is_err());
+        assert!(KeyChord::parse("<c-dd>").is_err());
+        assert!(KeyChord::parse("<>").is_err());
+        assert!(KeyChord::parse("<s-j>").is_err());
+    }
+
+    #[test]
+    fn key_chords_are_displayed_in_the_format_they_are_parsed_from() {
+        // GIVEN
+        let values = ["j", "<c-d>", "<space>", "<s-tab>", "↓", "<a-enter>", "<f5>"];
+
+        // WHEN
+        let displayed = values
+            .iter()
+            .map(|v| KeyChord::parse(v).map(|c| c.to_string()))
+            .collect::<Result<Vec<_>, _>>()
+            .expect("chords should've been parsed");
+
+        // THEN
+        assert_eq!(displayed, values);
+    }
+
+    #[test]
+    fn config_overrides_default_bindings() {
+        // GIVEN
+        let config = config(
+            r#"
+[keymap.diff]
+"<c-n>" = "next_hunk"
+"]" = "none"
+"n" = "select_next"
+"#,
+        );
+
+        // WHEN
+        let keymap = Keymap::from_config(&config).expect("keymap should've been built");
+
+        // THEN
+        let action = |c| keymap.action_for(KeymapContext::Diff, c);
+        assert_eq!(action(KeyChord::ctrl('n')), Some(Action::NextHunk));
+        assert_eq!(action(KeyChord::char(']')), None);
+        assert_eq!(action(KeyChord::char('n')), Some(Action::SelectNext));
+        assert_eq!(action(KeyChord::char('j')), Some(Action::SelectNext));
+        assert_eq!(
+            keymap.action_for(KeymapContext::Changes, KeyChord::char(']')),
+            None
+        );
+    }
+
+    #[test]
+    fn invalid_config_reports_every_error() {
+        // GIVEN
+        let config = config(
+            r#"
+[keymap.diff]
+"<c-" = "next_hunk"
+"x" = "export_comments"
+[keymap.prompt]
+"a" = "cancel_prompt"
+"#,
+        );
+
+        // WHEN
+        let result = Keymap::from_config(&config);
+
+        // THEN
+        let Err(error) = result else {
+            panic!("keymap shouldn't have been built");
+        };
+        // the list of available actions is cut off, as it depends on enabled features
+        let messages = error
+            .to_string()
+            .lines()
+            .map(|line| line.split(';').next().unwrap_or_default().to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        insta::assert_snapshot!(messages, @r#"
+        [keymap.diff] "<c-" is not a valid key
+        [keymap.diff] "export_comments" is not an action available here
+        [keymap.prompt] "a" can't be bound, since it's typed into the prompt
+        "#);
+    }
+
+    #[test]
+    fn unknown_config_sections_are_rejected() {
+        // GIVEN
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Wrapper {
+            keymap: KeymapConfig,
+        }
+
+        // WHEN
+        let result = toml::from_str::<Wrapper>("[keymap.dif]\nj = \"select_next\"\n");
+
+        // THEN
+        assert!(result.is_err());
+    }
+}
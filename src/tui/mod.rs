@@ -1,14 +1,25 @@
 mod app;
 mod behaviours;
+mod clipboard;
 mod cmd;
+mod comments;
 mod common;
+mod editor;
+mod filter;
 mod handle;
+mod keymap;
 mod model;
 mod msg;
+mod search;
+mod summary;
 #[cfg(test)]
 mod tests;
+mod theme;
+mod tree;
 mod update;
 mod view;
 
 pub use app::run;
 pub use behaviours::*;
+pub use keymap::{Keymap, KeymapConfig};
+pub use theme::{Theme, ThemeConfig};
@@ -1,8 +1,21 @@
 use super::TuiBehaviours;
+use super::cmd::Cmd;
+use super::comments::{Comment, CommentTarget, comments_to_markdown};
 use super::common::*;
-use crate::domain::{Change, ChangeKind, FileCache, Modification, WatchUpdate};
+use super::editor::{get_editor, get_editor_command};
+use super::filter::{ChangeKindFilter, ChangesFilter};
+use super::keymap::Keymap;
+use super::search::{DiffSearch, SearchScope};
+use super::summary::{SessionSummary, SummarySortKey};
+use super::theme::Theme;
+use super::tree::{ChangeTree, TreeRow};
+use crate::domain::{
+    CacheUsage, Change, ChangeKind, DiffLine, DiffOperation, FileCache, Modification, PathFilter,
+    WatchMetrics, WatchUpdate, gutter_width, line_num_padding, line_width, wrapped_line_count,
+};
 #[cfg(feature = "sound")]
 use crate::notifs::AudioPlayer;
+use crate::watcher::WatchRoot;
 use ratatui::{
     style::Style,
     text::{Line, Span},
@@ -10,19 +23,37 @@ use ratatui::{
 };
 use std::path::PathBuf;
 use std::sync::Arc;
-#[cfg(feature = "sound")]
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc::{Receiver, Sender};
-use tokio::sync::{RwLock, mpsc};
+use tokio::sync::{RwLock, mpsc, watch};
 use tokio_util::sync::CancellationToken;
 #[cfg(feature = "sound")]
 use tracing::warn;
 
 const USER_MESSAGE_DEFAULT_FRAMES: u16 = 4;
+const SCROLL_CONTEXT_LINES: usize = 3;
+const HORIZONTAL_SCROLL_STEP: usize = 4;
+const MOUSE_SCROLL_LINES: usize = 3;
+// the main view has the changes pane (of fixed height) and the status line at the bottom, with
+// the diff pane filling up the rest of the screen
+const CHANGES_PANE_HEIGHT: u16 = 12;
+const STATUS_LINE_HEIGHT: u16 = 1;
 const CREATED_LABEL: &str = " created  ";
 const MODIFIED_LABEL: &str = " modified ";
+const APPENDED_LABEL: &str = " appended ";
 const REMOVED_LABEL: &str = " removed  ";
 const ERROR_LABEL: &str = "  error   ";
+const BINARY_LABEL: &str = "  binary  ";
+const ENCODING_LABEL: &str = " encoding ";
+const TOO_LARGE_LABEL: &str = " too large";
+// Only this many lines of a change are shown in the diff pane, so that changes to huge files (eg.
+// generated SQL dumps) don't slow rendering down
+pub(super) const MAX_DISPLAYED_DIFF_LINES: usize = 5000;
+const USER_EDIT_MARKER: &str = "  (you)";
+const LAST_SESSION_MARKER: &str = "  (last session)";
+// how long after the editor is closed (on top of the time it can take the watcher to notice a
+// change) changes to the edited file are still attributed to the user
+const USER_EDIT_GRACE_PERIOD_MILLIS: u64 = 1000;
 
 #[cfg(feature = "sound")]
 const AUDIO_PLAYBACK_DEBOUNCE_DURATION_MILLIS: u128 = 1000;
@@ -79,11 +110,21 @@ impl UserMsg {
 #[derive(Debug)]
 pub struct ChangeItem {
     pub change: Change,
+    pub reviewed: bool,
+    // whether the change was made by the user from within dfft (via an editor), rather than by
+    // an agent
+    pub by_user: bool,
+    // whether the change was made while dfft wasn't running, and was found at startup by
+    // comparing files with the snapshots from the last session
+    pub since_last_session: bool,
 }
 
 #[derive(Debug)]
 pub struct Changes {
     pub items: Vec<ChangeItem>,
+    // indices of the items that match the filter; the list state's selection refers to these
+    pub visible: Vec<usize>,
+    pub filter: ChangesFilter,
     pub state: ListState,
 }
 
@@ -92,57 +133,267 @@ impl Changes {
         let state = ListState::default();
         let items = vec![];
 
-        Self { items, state }
+        Self {
+            items,
+            visible: vec![],
+            filter: ChangesFilter::default(),
+            state,
+        }
     }
 }
 
 impl Changes {
-    pub fn append(&mut self, change: Change, select_newly_added: bool) {
-        let item = ChangeItem { change };
+    // Returns whether the selected item changed. Items hidden by the filter are never selected,
+    // even when following changes.
+    pub fn append(&mut self, change: Change, select_newly_added: bool) -> bool {
+        let is_visible = self.filter.matches(&change);
+        let item = ChangeItem {
+            change,
+            reviewed: false,
+            by_user: false,
+            since_last_session: false,
+        };
         self.items.push(item);
 
+        if !is_visible {
+            return false;
+        }
+
+        self.visible.push(self.items.len() - 1);
+
         let selected = match self.state.selected() {
             Some(i) => {
                 if select_newly_added {
-                    Some(self.items.len() - 1)
+                    Some(self.visible.len() - 1)
                 } else {
                     Some(i)
                 }
             }
-            None => {
-                if self.items.is_empty() {
-                    None
-                } else {
-                    Some(0)
-                }
-            }
+            None => Some(0),
+        };
+
+        let selection_changed = selected != self.state.selected();
+        self.state = self.state.with_selected(selected);
+
+        selection_changed
+    }
+
+    // Merges an append to a file into the latest change to that file, if that was an append as well,
+    // so that a file that keeps growing (eg. a log) shows up as a single entry. Returns the index of
+    // the item the change was merged into, or the change itself if it couldn't be merged.
+    pub fn merge_append(&mut self, change: Change) -> Result<usize, Change> {
+        let ChangeKind::Modified(Ok(Modification::Diff(diff))) = &change.kind else {
+            return Err(change);
+        };
+        let Some(index) = self
+            .items
+            .iter()
+            .rposition(|item| item.change.path == change.path)
+        else {
+            return Err(change);
+        };
+        let merged = match &self.items[index].change.kind {
+            ChangeKind::Modified(Ok(Modification::Diff(latest))) => latest.merge_append(diff),
+            _ => None,
+        };
+        let Some(merged) = merged else {
+            return Err(change);
         };
 
+        let item = &mut self.items[index];
+        item.change.kind = ChangeKind::Modified(Ok(Modification::Diff(merged)));
+        item.change.encoding = change.encoding;
+        item.reviewed = false;
+        item.by_user = false;
+        item.since_last_session = false;
+
+        Ok(index)
+    }
+
+    pub fn selected_item(&self) -> Option<&ChangeItem> {
+        self.state
+            .selected()
+            .and_then(|i| self.visible.get(i))
+            .and_then(|&i| self.items.get(i))
+    }
+
+    // Keeps the selected item selected if it's still visible, otherwise selects the closest one
+    // that comes after it. Returns whether the selected item changed.
+    pub fn set_filter(&mut self, filter: ChangesFilter) -> bool {
+        let previously_selected = self
+            .state
+            .selected()
+            .and_then(|i| self.visible.get(i).copied());
+
+        self.filter = filter;
+        self.visible = self
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| self.filter.matches(&item.change))
+            .map(|(i, _)| i)
+            .collect();
+
+        let selected = match previously_selected {
+            _ if self.visible.is_empty() => None,
+            Some(prev) => Some(
+                self.visible
+                    .iter()
+                    .position(|&i| i >= prev)
+                    .unwrap_or(self.visible.len() - 1),
+            ),
+            None => Some(0),
+        };
         self.state = self.state.with_selected(selected);
+
+        self.selected_item_index() != previously_selected
+    }
+
+    fn selected_item_index(&self) -> Option<usize> {
+        self.state
+            .selected()
+            .and_then(|i| self.visible.get(i).copied())
     }
 }
 
-impl From<&ChangeItem> for ListItem<'_> {
-    fn from(value: &ChangeItem) -> Self {
-        let (label, color) = match value.change.kind {
-            ChangeKind::Created(Ok(_)) => (CREATED_LABEL, ADDITION_COLOR),
+impl ChangeItem {
+    pub(super) fn kind_span(&self, theme: &Theme) -> Span<'static> {
+        let (label, kind_color) = match self.change.kind {
+            ChangeKind::Created(Ok(_)) => (CREATED_LABEL, theme.addition),
+
+            ChangeKind::Created(Err(_)) => (ERROR_LABEL, theme.error),
+            ChangeKind::Modified(Ok(Modification::Diff(ref diff))) if diff.is_append() => {
+                (APPENDED_LABEL, theme.addition)
+            }
+            ChangeKind::Modified(Ok(_)) => (MODIFIED_LABEL, theme.modification),
+            ChangeKind::Modified(Err(_)) => (ERROR_LABEL, theme.error),
+            ChangeKind::RemovedFile | ChangeKind::RemovedDir => (REMOVED_LABEL, theme.subtraction),
+            ChangeKind::Binary(_) => (BINARY_LABEL, theme.modification),
+            ChangeKind::Reencoded(_) => (ENCODING_LABEL, theme.modification),
+            ChangeKind::TooLarge(_) => (TOO_LARGE_LABEL, theme.modification),
+        };
+        let color = if self.reviewed {
+            theme.reviewed
+        } else {
+            kind_color
+        };
+
+        Span::styled(label, theme.label(color))
+    }
+
+    pub(super) fn stats_spans(&self, theme: &Theme) -> Vec<Span<'static>> {
+        let mut spans = vec![];
+        if let Some(stats) = self.change.kind.stats() {
+            spans.push("  ".into());
+            let (insertion_style, deletion_style) = if self.reviewed {
+                (
+                    Style::default().fg(theme.reviewed),
+                    Style::default().fg(theme.reviewed),
+                )
+            } else {
+                (theme.insertion(), theme.deletion())
+            };
+            spans.push(Span::styled(
+                format!("+{}", stats.insertions),
+                insertion_style,
+            ));
+            if stats.deletions > 0 || matches!(self.change.kind, ChangeKind::Modified(_)) {
+                spans.push(" ".into());
+                spans.push(Span::styled(
+                    format!("-{}", stats.deletions),
+                    deletion_style,
+                ));
+            }
+        }
+
+        spans
+    }
+
+    pub(super) fn by_user_span(&self, theme: &Theme) -> Option<Span<'static>> {
+        self.by_user
+            .then(|| Span::styled(USER_EDIT_MARKER, Style::default().fg(theme.user_edit)))
+    }
+
+    pub(super) fn last_session_span(&self, theme: &Theme) -> Option<Span<'static>> {
+        self.since_last_session
+            .then(|| Span::styled(LAST_SESSION_MARKER, Style::default().fg(theme.info)))
+    }
 
-            ChangeKind::Created(Err(_)) => (ERROR_LABEL, FILE_ERROR_COLOR),
-            ChangeKind::Modified(Ok(_)) => (MODIFIED_LABEL, MODIFICATION_COLOR),
-            ChangeKind::Modified(Err(_)) => (ERROR_LABEL, FILE_ERROR_COLOR),
-            ChangeKind::RemovedFile | ChangeKind::RemovedDir => (REMOVED_LABEL, SUBTRACTION_COLOR),
+    // When several directories are watched, the name of the change's directory (which its path
+    // starts with) is shown as a label of its own
+    pub(super) fn list_item(&self, theme: &Theme, labelled_roots: bool) -> ListItem<'static> {
+        let mut spans = vec![self.kind_span(theme), " ".into()];
+        let path = match self.change.path.split_once('/') {
+            Some((root, path)) if labelled_roots => {
+                spans.push(Span::styled(
+                    format!("{root}:"),
+                    Style::default().fg(theme.info),
+                ));
+                spans.push(" ".into());
+                path.to_string()
+            }
+            _ => self.change.path.clone(),
         };
+        spans.push(if self.reviewed {
+            Span::styled(path, Style::default().fg(theme.reviewed))
+        } else {
+            Span::from(path)
+        });
+        spans.extend(self.stats_spans(theme));
+        spans.extend(self.by_user_span(theme));
+        spans.extend(self.last_session_span(theme));
+
+        ListItem::new(Line::from(spans))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptKind {
+    DiffSearch(SearchScope),
+    ChangesFilter(ChangeKindFilter),
+    Comment(CommentTarget),
+    PathFilter,
+}
+
+#[derive(Debug)]
+pub struct Prompt {
+    pub kind: PromptKind,
+    pub input: String,
+}
 
-        let line = Line::from(vec![
-            Span::styled(label, Style::default().bg(color).black().bold()),
-            " ".into(),
-            Span::from(value.change.path.clone()),
-        ]);
+// what a comment being typed out will refer to, captured when the comment prompt is opened
+#[derive(Debug)]
+struct CommentDraft {
+    change_index: usize,
+    path: String,
+    line: DiffLine,
+    hunk: Option<Vec<DiffLine>>,
+}
+
+impl CommentDraft {
+    fn to_comment(&self, target: CommentTarget, text: &str) -> Comment {
+        let excerpt = match (target, &self.hunk) {
+            (CommentTarget::Hunk, Some(hunk)) => hunk.clone(),
+            _ => vec![self.line.clone()],
+        };
 
-        ListItem::new(line)
+        Comment {
+            change_index: Some(self.change_index),
+            path: self.path.clone(),
+            target,
+            excerpt,
+            text: text.to_string(),
+        }
     }
 }
 
+// a file being (or recently) edited by the user in an external editor
+#[derive(Debug)]
+struct UserEdit {
+    path: String,
+    finished_at: Option<Instant>,
+}
+
 #[cfg(feature = "sound")]
 enum AudioHandler {
     NotInitialized,
@@ -150,12 +401,35 @@ enum AudioHandler {
     Unavailable,
 }
 
+// A directory being watched, along with the snapshots of its files
+#[derive(Debug)]
+pub struct WatchedRoot {
+    pub root: WatchRoot,
+    cache: Arc<RwLock<FileCache>>,
+    // whether watching this directory failed; other directories keep being watched
+    pub failed: bool,
+}
+
 pub struct Model {
     pub behaviours: TuiBehaviours,
-    pub root: PathBuf,
-    cache: Arc<RwLock<FileCache>>,
+    pub roots: Vec<WatchedRoot>,
+    // set for roots on file systems where changes might go unnoticed without polling
+    pub poll_suggestions: Vec<String>,
+    // shared with the watchers, which pick up changes to it right away
+    path_filter: watch::Sender<PathFilter>,
+    watch_metrics: Arc<WatchMetrics>,
     pub active_pane: Pane,
     pub changes: Changes,
+    pub grouped_view: bool,
+    pub change_tree: ChangeTree,
+    net_change: Option<ChangeItem>,
+    // every line of the selected change's file, for the full file view; working these out takes a
+    // while for large files, so it's only done when the selected change (or the view) changes
+    full_file_lines: Option<Vec<DiffLine>>,
+    diff_layout: DiffLayout,
+    // when the selected change started being viewed; used for marking changes as reviewed
+    // automatically
+    viewing_since: Option<Instant>,
     pub last_active_pane: Option<Pane>,
     pub running_state: RunningState,
     pub user_msg: Option<UserMsg>,
@@ -169,10 +443,32 @@ pub struct Model {
     cancellation_token: CancellationToken,
     pub debug: bool,
     pub help_scroll: usize,
+    pub keymap: Keymap,
+    // generated from the keymap
+    pub help_content: String,
+    pub theme: Theme,
     pub help_line_count: usize,
     pub max_help_scroll_available: usize,
+    pub session_summary: SessionSummary,
+    pub summary_sort_key: SummarySortKey,
+    pub summary_scroll: usize,
+    pub max_summary_scroll_available: usize,
     pub diff_scroll: usize,
+    // line (in the diff pane's lines) that was last jumped to; cleared on manual scrolling
+    diff_cursor: Option<usize>,
     pub max_diff_scroll_available: usize,
+    pub full_file_view: bool,
+    pub prompt: Option<Prompt>,
+    pub diff_search: Option<DiffSearch>,
+    search_before_prompt: Option<DiffSearch>,
+    filter_before_prompt: Option<ChangesFilter>,
+    pub comments: Vec<Comment>,
+    pub comments_state: ListState,
+    comment_draft: Option<CommentDraft>,
+    user_edit: Option<UserEdit>,
+    pub wrap_diff_lines: bool,
+    pub diff_horizontal_scroll: usize,
+    pub max_diff_horizontal_scroll_available: usize,
     #[cfg(feature = "sound")]
     audio_handler: AudioHandler,
     #[cfg(feature = "sound")]
@@ -182,7 +478,7 @@ pub struct Model {
 impl Model {
     pub fn new(
         behaviours: TuiBehaviours,
-        root: PathBuf,
+        roots: Vec<PathBuf>,
         terminal_dimensions: TerminalDimensions,
         debug: bool,
     ) -> Self {
@@ -190,13 +486,35 @@ impl Model {
             || terminal_dimensions.height < MIN_TERMINAL_HEIGHT;
 
         let (changes_tx, changes_rx) = mpsc::channel::<WatchUpdate>(100);
-
+        // the memory budget for snapshots is shared between the watched directories
+        let budget = behaviours.watch_settings.max_cache_size as usize / roots.len().max(1);
+        let roots = WatchRoot::from_paths(roots)
+            .into_iter()
+            .map(|root| WatchedRoot {
+                root,
+                cache: Arc::new(RwLock::new(
+                    FileCache::new()
+                        .with_budget(budget, behaviours.watch_settings.compress_snapshots),
+                )),
+                failed: false,
+            })
+            .collect();
+
+        let (path_filter, _) = watch::channel(behaviours.path_filter.clone());
         let mut model = Model {
             behaviours,
-            root,
-            cache: Arc::new(RwLock::new(FileCache::new())),
+            roots,
+            poll_suggestions: vec![],
+            path_filter,
+            watch_metrics: Arc::new(WatchMetrics::default()),
             active_pane: Pane::Diff,
             changes: Changes::new(),
+            grouped_view: false,
+            change_tree: ChangeTree::default(),
+            net_change: None,
+            full_file_lines: None,
+            diff_layout: DiffLayout::default(),
+            viewing_since: None,
             last_active_pane: None,
             running_state: RunningState::Running,
             user_msg: None,
@@ -210,27 +528,68 @@ impl Model {
             cancellation_token: CancellationToken::new(),
             debug,
             help_scroll: 0,
-            help_line_count: get_help_content().lines().count(),
+            keymap: Keymap::default(),
+            help_content: String::new(),
+            theme: Theme::default(),
+            help_line_count: 0,
             max_help_scroll_available: 0,
+            session_summary: SessionSummary::default(),
+            summary_sort_key: SummarySortKey::default(),
+            summary_scroll: 0,
+            max_summary_scroll_available: 0,
             diff_scroll: 0,
+            diff_cursor: None,
             max_diff_scroll_available: 0,
+            full_file_view: false,
+            prompt: None,
+            diff_search: None,
+            search_before_prompt: None,
+            filter_before_prompt: None,
+            comments: vec![],
+            comments_state: ListState::default(),
+            comment_draft: None,
+            user_edit: None,
+            wrap_diff_lines: false,
+            diff_horizontal_scroll: 0,
+            max_diff_horizontal_scroll_available: 0,
             #[cfg(feature = "sound")]
             audio_handler: AudioHandler::NotInitialized,
             #[cfg(feature = "sound")]
             audio_last_played_at: None,
         };
 
-        model.compute_max_help_scroll_available();
+        model.on_keymap_changed();
 
         model
     }
 
+    pub fn with_keymap(mut self, keymap: Keymap) -> Self {
+        self.keymap = keymap;
+        self.on_keymap_changed();
+        self
+    }
+
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    fn on_keymap_changed(&mut self) {
+        self.help_content = self.keymap.help_content(self.behaviours.mouse);
+        self.help_line_count = self.help_content.lines().count();
+        self.compute_max_help_scroll_available();
+    }
+
     pub(super) fn go_back_or_quit(&mut self) {
         let active_pane = Some(self.active_pane);
         match self.active_pane {
+            Pane::Changes if self.changes.filter.is_active() => {
+                self.apply_changes_filter(ChangesFilter::default());
+            }
             Pane::Changes => self.active_pane = Pane::Diff,
+            Pane::Diff if self.diff_search.is_some() => self.diff_search = None,
             Pane::Diff => self.running_state = RunningState::Done,
-            Pane::Help => match self.last_active_pane {
+            Pane::Help | Pane::Summary | Pane::Comments => match self.last_active_pane {
                 Some(p) => self.active_pane = p,
                 None => self.active_pane = Pane::Changes,
             },
@@ -242,61 +601,60 @@ impl Model {
     pub(super) fn select_next(&mut self) {
         match self.active_pane {
             Pane::Changes | Pane::Diff => {
-                if self.changes.items.is_empty() {
+                if self.change_list_len() == 0 {
                     return;
                 }
 
-                if let Some(i) = self.changes.state.selected()
-                    && i == self.changes.items.len() - 1
+                if let Some(i) = self.change_list_state().selected()
+                    && i == self.change_list_len() - 1
                 {
                     return;
                 }
 
-                self.changes.state.select_next();
-                self.compute_max_diff_scroll_available();
-                self.reset_diff_scroll();
+                self.change_list_state().select_next();
+                self.on_selected_change_updated();
             }
-            Pane::Help => {}
+            Pane::Comments => self.select_next_comment(),
+            Pane::Help | Pane::Summary => {}
         }
     }
 
     pub(super) fn select_previous(&mut self) {
         match self.active_pane {
             Pane::Changes | Pane::Diff => {
-                if self.changes.items.is_empty() {
+                if self.change_list_len() == 0 {
                     return;
                 }
 
-                if let Some(i) = self.changes.state.selected()
+                if let Some(i) = self.change_list_state().selected()
                     && i == 0
                 {
                     return;
                 }
 
-                self.changes.state.select_previous();
-                self.compute_max_diff_scroll_available();
-                self.reset_diff_scroll();
+                self.change_list_state().select_previous();
+                self.on_selected_change_updated();
             }
-            Pane::Help => {}
+            Pane::Comments => self.select_previous_comment(),
+            Pane::Help | Pane::Summary => {}
         }
     }
 
     pub(super) fn select_first(&mut self) {
         match self.active_pane {
             Pane::Changes | Pane::Diff => {
-                if self.changes.items.is_empty() {
+                if self.change_list_len() == 0 {
                     return;
                 }
 
-                if let Some(i) = self.changes.state.selected()
+                if let Some(i) = self.change_list_state().selected()
                     && i == 0
                 {
                     return;
                 }
 
-                self.changes.state.select_first();
-                self.compute_max_diff_scroll_available();
-                self.reset_diff_scroll();
+                self.change_list_state().select_first();
+                self.on_selected_change_updated();
             }
             _ => {}
         }
@@ -304,20 +662,19 @@ impl Model {
     pub(super) fn select_last(&mut self) {
         match self.active_pane {
             Pane::Changes | Pane::Diff => {
-                if self.changes.items.is_empty() {
+                if self.change_list_len() == 0 {
                     return;
                 }
 
-                let last_index = self.changes.items.len() - 1;
-                if let Some(i) = self.changes.state.selected()
+                let last_index = self.change_list_len() - 1;
+                if let Some(i) = self.change_list_state().selected()
                     && i == last_index
                 {
                     return;
                 }
 
-                self.changes.state.select(Some(last_index));
-                self.compute_max_diff_scroll_available();
-                self.reset_diff_scroll();
+                self.change_list_state().select(Some(last_index));
+                self.on_selected_change_updated();
             }
             _ => {}
         }
@@ -331,6 +688,10 @@ impl Model {
             Pane::Help => {
                 self.scroll_help_down();
             }
+            Pane::Summary => {
+                self.scroll_summary_down();
+            }
+            Pane::Comments => {}
         }
     }
 
@@ -342,185 +703,1721 @@ impl Model {
             Pane::Help => {
                 self.scroll_help_up();
             }
+            Pane::Summary => {
+                self.scroll_summary_up();
+            }
+            Pane::Comments => {}
         }
     }
 
-    pub(super) fn add_change(&mut self, change: Change) {
-        #[cfg(feature = "sound")]
-        if self.behaviours.play_sound {
-            self.play_change_sound(&change.kind);
+    // the pane shown at a row of the terminal
+    fn pane_at(&self, row: u16) -> Option<Pane> {
+        let panes_height = self
+            .terminal_dimensions
+            .height
+            .saturating_sub(STATUS_LINE_HEIGHT);
+        if row >= panes_height {
+            return None;
         }
 
-        self.changes.append(change, self.behaviours.follow_changes);
-
-        if self.behaviours.follow_changes || self.changes.items.len() == 1 {
-            self.reset_diff_scroll();
-            self.compute_max_diff_scroll_available();
+        match self.active_pane {
+            Pane::Changes | Pane::Diff => {
+                if row >= panes_height.saturating_sub(CHANGES_PANE_HEIGHT) {
+                    Some(Pane::Changes)
+                } else {
+                    Some(Pane::Diff)
+                }
+            }
+            pane => Some(pane),
         }
     }
 
-    pub(super) fn current_file_path(&self) -> Option<&str> {
-        self.changes
-            .state
-            .selected()
-            .and_then(|i| self.changes.items.get(i))
-            .map(|item| item.change.path.as_str())
-    }
-
-    pub(super) fn get_cancellation_token(&self) -> CancellationToken {
-        self.cancellation_token.clone()
-    }
+    // Scrolls the pane under the cursor; the changes and comments lists move their selection
+    pub(super) fn scroll_with_mouse(&mut self, row: u16, down: bool) {
+        let Some(pane) = self.pane_at(row) else {
+            return;
+        };
 
-    pub(super) fn reset_list(&mut self) {
-        self.changes = Changes::new();
-        self.reset_diff_scroll();
-        self.compute_max_diff_scroll_available();
+        match pane {
+            Pane::Changes | Pane::Comments if down => self.select_next(),
+            Pane::Changes | Pane::Comments => self.select_previous(),
+            Pane::Diff | Pane::Help | Pane::Summary => {
+                for _ in 0..MOUSE_SCROLL_LINES {
+                    if down {
+                        self.scroll_down(ScrollKind::Line);
+                    } else {
+                        self.scroll_up(ScrollKind::Line);
+                    }
+                }
+            }
+        }
     }
 
-    pub(super) fn pause_watching(&mut self) {
-        self.cancellation_token.cancel();
-        self.behaviours.watch = false;
-    }
+    // Focuses the pane that was clicked, and selects the clicked entry in the changes pane
+    pub(super) fn click_with_mouse(&mut self, row: u16) {
+        let Some(pane @ (Pane::Changes | Pane::Diff)) = self.pane_at(row) else {
+            return;
+        };
 
-    pub(super) fn regenerate_cancellation_token(&mut self) {
-        self.cancellation_token = CancellationToken::new();
-        self.behaviours.watch = true;
-    }
+        if pane != self.active_pane {
+            self.last_active_pane = Some(self.active_pane);
+            self.active_pane = pane;
+        }
 
-    pub(super) fn scroll_help_down(&mut self) {
-        if self.help_scroll < self.max_help_scroll_available {
-            self.help_scroll += 1;
+        if pane != Pane::Changes {
+            return;
         }
-    }
 
-    pub(super) fn scroll_help_up(&mut self) {
-        self.help_scroll = self.help_scroll.saturating_sub(1);
-    }
+        let pane_bottom = self
+            .terminal_dimensions
+            .height
+            .saturating_sub(STATUS_LINE_HEIGHT);
+        // top border + padding top
+        let first_item_row = pane_bottom.saturating_sub(CHANGES_PANE_HEIGHT) + 2;
+        // the last row is the bottom border
+        if row < first_item_row || row + 1 >= pane_bottom {
+            return;
+        }
 
-    pub(super) fn scroll_diff_down(&mut self, scroll_kind: ScrollKind) {
-        if self.changes.state.selected().is_none() {
+        let index = self.change_list_state_ref().offset() + (row - first_item_row) as usize;
+        if index >= self.change_list_len() || self.change_list_state_ref().selected() == Some(index)
+        {
             return;
         }
 
-        self.diff_scroll = (self.diff_scroll + self.diff_pane_scroll_line_count(scroll_kind))
-            .min(self.max_diff_scroll_available);
+        self.change_list_state().select(Some(index));
+        self.on_selected_change_updated();
     }
 
-    pub(super) fn scroll_diff_up(&mut self, scroll_kind: ScrollKind) {
-        if self.changes.state.selected().is_none() {
-            return;
+    pub(super) fn add_change(&mut self, change: Change) {
+        #[cfg(feature = "sound")]
+        if self.behaviours.play_sound {
+            self.play_change_sound(&change.kind);
         }
 
-        self.diff_scroll = self
-            .diff_scroll
-            .saturating_sub(self.diff_pane_scroll_line_count(scroll_kind));
+        self.session_summary.record(&change);
+        self.compute_max_summary_scroll_available();
+        let path = change.path.clone();
+        let by_user = self.is_being_edited_by_user(&path, Instant::now());
+        let change = if self.behaviours.collapse_appends {
+            match self.changes.merge_append(change) {
+                Ok(index) => {
+                    self.on_append_merged(index, by_user);
+                    return;
+                }
+                Err(change) => change,
+            }
+        } else {
+            change
+        };
+        let mut selection_changed = self.changes.append(change, self.behaviours.follow_changes);
+        // the user has seen their own edits, so there's nothing to review
+        if by_user && let Some(item) = self.changes.items.last_mut() {
+            item.by_user = true;
+            item.reviewed = true;
+        }
+
+        if self.grouped_view {
+            selection_changed = self.rebuild_change_tree();
+            if self.behaviours.follow_changes {
+                selection_changed |= self.change_tree.select_file(&path);
+            }
+        }
+
+        if selection_changed {
+            self.on_selected_change_updated();
+        }
     }
 
-    pub(super) fn reset_help_scroll(&mut self) {
-        self.help_scroll = 0;
+    // Changes found at startup are added like any other, but are marked so that it's clear they
+    // weren't made while dfft was watching
+    pub(super) fn add_change_from_last_session(&mut self, change: Change) {
+        let num_items = self.changes.items.len();
+        self.add_change(change);
+        if self.changes.items.len() > num_items
+            && let Some(item) = self.changes.items.last_mut()
+        {
+            item.since_last_session = true;
+        }
     }
 
-    pub(super) fn reset_diff_scroll(&mut self) {
-        self.diff_scroll = 0;
+    // The merged change stays where it is in the list; if it's being looked at, the diff pane keeps
+    // its scroll position so that the new lines can be followed
+    fn on_append_merged(&mut self, index: usize, by_user: bool) {
+        if by_user && let Some(item) = self.changes.items.get_mut(index) {
+            item.by_user = true;
+            item.reviewed = true;
+        }
+
+        let mut selection_changed = false;
+        if self.grouped_view {
+            selection_changed = self.rebuild_change_tree();
+        }
+
+        if self.behaviours.follow_changes {
+            if self.grouped_view {
+                let path = self.changes.items[index].change.path.clone();
+                selection_changed |= self.change_tree.select_file(&path);
+            } else if let Some(row) = self.changes.visible.iter().position(|&i| i == index)
+                && self.changes.state.selected() != Some(row)
+            {
+                self.changes.state.select(Some(row));
+                selection_changed = true;
+            }
+        }
+
+        if selection_changed {
+            self.on_selected_change_updated();
+        } else if self.selected_change_indices().contains(&index) {
+            if self.grouped_view {
+                self.net_change = self.compute_net_change();
+            }
+            self.refresh_full_file_lines();
+            self.compute_max_diff_scroll_available();
+            self.diff_scroll = self.diff_scroll.min(self.max_diff_scroll_available);
+            self.refresh_diff_search();
+        }
     }
 
-    // kinda weird that this model method relies on knowledge of the view, but oh well
-    pub(super) fn compute_max_help_scroll_available(&mut self) {
-        // -help------------------------------
-        // |                                 | <- padding top
-        // | 1    keymaps                    |
-        // | 2    aaaaa                      |
-        // | 3    aaaaa                      | <- available height = 10 - 4 = 6
-        // | 4    aaaaa                      |
-        // | 5    aaaaa                      |
-        // | 6    aaaaa                      |
-        // |---------------------------------| <- help border
-        // | dfft                            | __ lower edge of screen
-        // | 7    aaaaa | <- not visible
-        // | 8    aaaaa |
-        // | 9    aaaaa | <- max scroll available = 3
-        // |            |
-        // |            |
-        // |            |
-        // --------------
+    // Returns the command for opening the selected change's file in the user's editor, at the
+    // first line changed in it
+    pub(super) fn open_in_editor(&mut self) -> Option<Cmd> {
+        let change = &self.selected_change()?.change;
+        let line = match &change.kind {
+            ChangeKind::Created(Ok(_)) => Some(1),
+            ChangeKind::Modified(Ok(Modification::Diff(diff))) => diff.first_changed_line_num(),
+            ChangeKind::Modified(Ok(_)) | ChangeKind::Reencoded(_) | ChangeKind::TooLarge(_) => {
+                None
+            }
+            ChangeKind::Created(Err(_)) | ChangeKind::Modified(Err(_)) => {
+                self.user_msg = Some(UserMsg::info("this file couldn't be read"));
+                return None;
+            }
+            ChangeKind::RemovedFile | ChangeKind::RemovedDir => {
+                self.user_msg = Some(UserMsg::info("this file doesn't exist anymore"));
+                return None;
+            }
+            ChangeKind::Binary(_) => {
+                self.user_msg = Some(UserMsg::info("binary files can't be opened in an editor"));
+                return None;
+            }
+        };
 
-        // fully scrolled view
+        let path = change.path.clone();
+        let Some((root, relative_path)) = self.roots.iter().find_map(|r| {
+            r.root
+                .relative_path(&path)
+                .map(|relative_path| (&r.root.path, relative_path))
+        }) else {
+            self.user_msg = Some(UserMsg::error("couldn't find the directory of this file"));
+            return None;
+        };
+        let editor = get_editor_command(&get_editor(), &root.join(relative_path), line);
+        let dir = root.clone();
+        self.user_edit = Some(UserEdit {
+            path,
+            finished_at: None,
+        });
+
+        Some(Cmd::OpenInEditor { editor, dir })
+    }
 
-        // -help------------------------------
-        // |                                 |
-        // | 4    aaaaa                      |
-        // | 5    aaaaa                      |
-        // | 6    aaaaa                      |
-        // | 7    aaaaa                      |
-        // | 8    aaaaa                      |
-        // | 9    aaaaa                      |
-        // |---------------------------------|
-        // | dfft                            |
+    pub(super) fn on_editor_closed(&mut self, result: Result<(), String>, now: Instant) {
+        if let Some(user_edit) = &mut self.user_edit {
+            user_edit.finished_at = Some(now);
+        }
 
-        self.max_help_scroll_available = if self.terminal_too_small {
-            0
-        } else {
-            //      top border + padding top + lower border + status line
-            // 4 => 1          + 1           + 1            + 1
-            let available_height = self.terminal_dimensions.height as usize - 4;
-            self.help_line_count.saturating_sub(available_height)
+        if let Err(e) = result {
+            self.user_msg = Some(UserMsg::error(format!("couldn't open editor: {e}")));
+        }
+    }
+
+    fn is_being_edited_by_user(&self, path: &str, now: Instant) -> bool {
+        let Some(user_edit) = &self.user_edit else {
+            return false;
         };
+
+        user_edit.path == path
+            && user_edit.finished_at.is_none_or(|finished_at| {
+                now.saturating_duration_since(finished_at)
+                    < self.behaviours.watch_settings.max_event_delay()
+                        + Duration::from_millis(USER_EDIT_GRACE_PERIOD_MILLIS)
+            })
     }
 
-    // kinda weird that this model method relies on knowledge of the view, but oh well
-    pub(super) fn compute_max_diff_scroll_available(&mut self) {
-        let selected_index = self.changes.state.selected();
-        let change_item = selected_index.and_then(|i| self.changes.items.get(i));
+    pub(super) fn copy_path(&mut self) -> Option<Cmd> {
+        let path = self.current_file_path()?.to_string();
+        self.user_msg = Some(UserMsg::info(format!("copied path: {path}")));
 
-        let available_height = self.available_diff_pane_height();
+        Some(Cmd::CopyToClipboard { contents: path })
+    }
 
-        self.max_diff_scroll_available = match change_item {
-            Some(item) => match &item.change.kind {
-                ChangeKind::Modified(Ok(Modification::Diff(diff))) if !self.terminal_too_small => {
-                    diff.num_lines().saturating_sub(available_height)
-                }
-                ChangeKind::Created(Ok(contents)) if !self.terminal_too_small => {
-                    contents.lines().count().saturating_sub(available_height)
-                }
-                _ => 0,
-            },
-            None => {
-                if selected_index.is_some() {
-                    self.user_msg = Some(UserMsg::error(UNEXPECTED_ERROR_MSG));
-                }
-                0
+    // Copies the unified diff of the selected change, or the contents of a created file
+    pub(super) fn copy_diff(&mut self) -> Option<Cmd> {
+        let change = &self.selected_change()?.change;
+        let (contents, what) = match &change.kind {
+            ChangeKind::Created(Ok(contents)) => (contents.clone(), "file contents"),
+            ChangeKind::Modified(Ok(Modification::Diff(diff))) => {
+                (diff.to_unified(&change.path), "diff")
+            }
+            _ => {
+                self.user_msg = Some(UserMsg::info("this change has nothing to copy"));
+                return None;
             }
         };
+        self.user_msg = Some(UserMsg::info(format!("copied {what}")));
+
+        Some(Cmd::CopyToClipboard { contents })
     }
 
-    fn available_diff_pane_height(&self) -> usize {
-        //       top border + padding top + lower border + changes pane (fixed) + status bar height
-        // 16 => 1          + 1           + 1            + 12                   + 1
-        self.terminal_dimensions.height as usize - 16
+    // Copies the hunk in focus, or the first one if none of them is in focus yet
+    pub(super) fn copy_hunk(&mut self) -> Option<Cmd> {
+        let Some(ChangeKind::Modified(Ok(Modification::Diff(diff)))) =
+            self.selected_change().map(|c| &c.change.kind)
+        else {
+            self.selected_change()?;
+            self.user_msg = Some(UserMsg::info("this change has no hunks"));
+            return None;
+        };
+
+        let index = self
+            .hunk_position()
+            .and_then(|(current, _)| current)
+            .unwrap_or_default();
+        let (contents, num_hunks) = (diff.hunk_to_unified(index)?, diff.hunks.len());
+        self.user_msg = Some(UserMsg::info(format!(
+            "copied hunk {}/{num_hunks}",
+            index + 1
+        )));
+
+        Some(Cmd::CopyToClipboard { contents })
     }
 
-    fn diff_pane_scroll_line_count(&self, scroll_kind: ScrollKind) -> usize {
-        match scroll_kind {
-            ScrollKind::Line => 1,
-            ScrollKind::HalfPage => self.available_diff_pane_height() / 2,
-        }
+    pub(super) fn toggle_full_file_view(&mut self) {
+        self.full_file_view = !self.full_file_view;
+        self.on_displayed_diff_changed();
     }
 
-    pub(super) fn cache(&self) -> Arc<RwLock<FileCache>> {
-        self.cache.clone()
+    // to be called whenever the lines shown in the diff pane change, eg. when another change is
+    // selected, or the diff pane switches to another view
+    fn on_displayed_diff_changed(&mut self) {
+        self.refresh_full_file_lines();
+        self.compute_max_diff_scroll_available();
+        self.reset_diff_scroll();
+        self.refresh_diff_search();
     }
 
-    pub(super) fn snapshots_in_memory(&self) -> Option<usize> {
-        self.cache.try_read().ok().map(|c| c.len())
+    pub(super) fn toggle_diff_wrap(&mut self) {
+        self.wrap_diff_lines = !self.wrap_diff_lines;
+        self.diff_horizontal_scroll = 0;
+        self.compute_max_diff_scroll_available();
+        self.diff_scroll = self.diff_scroll.min(self.max_diff_scroll_available);
     }
 
-    #[cfg(feature = "sound")]
-    fn play_change_sound(&mut self, change_kind: &ChangeKind) {
-        if matches!(&self.audio_handler, AudioHandler::NotInitialized) {
-            self.initialize_audio_player();
+    pub(super) fn start_diff_search(&mut self) {
+        if self.selected_change().is_none() {
+            return;
         }
 
-        if should_audio_playback_be_debounced(&self.audio_last_played_at, Instant::now()) {
+        let scope = self
+            .diff_search
+            .as_ref()
+            .map(|s| s.scope)
+            .unwrap_or_default();
+
+        self.search_before_prompt = self.diff_search.take();
+        self.prompt = Some(Prompt {
+            kind: PromptKind::DiffSearch(scope),
+            input: String::new(),
+        });
+    }
+
+    pub(super) fn enter_prompt_input(&mut self, c: char) {
+        if let Some(prompt) = &mut self.prompt {
+            prompt.input.push(c);
+            self.on_prompt_input_changed();
+        }
+    }
+
+    pub(super) fn delete_prompt_input(&mut self) {
+        if let Some(prompt) = &mut self.prompt {
+            prompt.input.pop();
+            self.on_prompt_input_changed();
+        }
+    }
+
+    pub(super) fn start_changes_filter(&mut self) {
+        let filter = self.changes.filter.clone();
+        self.prompt = Some(Prompt {
+            kind: PromptKind::ChangesFilter(filter.kind),
+            input: filter.query.clone(),
+        });
+        self.filter_before_prompt = Some(filter);
+    }
+
+    pub(super) fn start_path_filter_edit(&mut self) {
+        self.prompt = Some(Prompt {
+            kind: PromptKind::PathFilter,
+            input: self.path_filter.borrow().to_string(),
+        });
+    }
+
+    pub(super) fn path_filter(&self) -> Option<String> {
+        let filter = self.path_filter.borrow();
+        filter.is_active().then(|| filter.to_string())
+    }
+
+    pub(super) fn cycle_change_kind_filter(&mut self) {
+        let filter = ChangesFilter {
+            kind: self.changes.filter.kind.next(),
+            ..self.changes.filter.clone()
+        };
+        self.apply_changes_filter(filter);
+    }
+
+    fn apply_changes_filter(&mut self, filter: ChangesFilter) {
+        let mut selection_changed = self.changes.set_filter(filter);
+        if self.grouped_view {
+            selection_changed = self.rebuild_change_tree();
+        }
+
+        if selection_changed {
+            self.on_selected_change_updated();
+        }
+    }
+
+    // In the grouped view, the currently selected change (or the latest change for a selected
+    // file) is kept selected, and vice versa
+    pub(super) fn toggle_grouped_view(&mut self) {
+        self.grouped_view = !self.grouped_view;
+
+        if self.grouped_view {
+            let selected_path = self.changes.selected_item().map(|i| i.change.path.clone());
+            self.rebuild_change_tree();
+            if let Some(path) = selected_path {
+                self.change_tree.select_file(&path);
+            }
+        } else {
+            let selected_index = match self.change_tree.selected_row() {
+                Some(TreeRow::File { changes, .. }) => changes.last().copied(),
+                Some(TreeRow::Change(i)) => Some(*i),
+                None => None,
+            };
+            if let Some(position) =
+                selected_index.and_then(|i| self.changes.visible.iter().position(|&v| v == i))
+            {
+                self.changes.state.select(Some(position));
+            }
+        }
+
+        self.on_selected_change_updated();
+    }
+
+    pub(super) fn toggle_file_expanded(&mut self) {
+        if !self.grouped_view {
+            return;
+        }
+
+        if self
+            .change_tree
+            .toggle_expanded(&self.changes.items, &self.changes.visible)
+        {
+            self.on_selected_change_updated();
+        }
+    }
+
+    fn rebuild_change_tree(&mut self) -> bool {
+        self.change_tree
+            .rebuild(&self.changes.items, &self.changes.visible)
+    }
+
+    fn change_list_len(&self) -> usize {
+        if self.grouped_view {
+            self.change_tree.rows.len()
+        } else {
+            self.changes.visible.len()
+        }
+    }
+
+    fn change_list_state_ref(&self) -> &ListState {
+        if self.grouped_view {
+            &self.change_tree.state
+        } else {
+            &self.changes.state
+        }
+    }
+
+    fn change_list_state(&mut self) -> &mut ListState {
+        if self.grouped_view {
+            &mut self.change_tree.state
+        } else {
+            &mut self.changes.state
+        }
+    }
+
+    fn on_selected_change_updated(&mut self) {
+        if self.grouped_view {
+            self.net_change = self.compute_net_change();
+        }
+        self.viewing_since = self.selected_change().map(|_| Instant::now());
+        self.on_displayed_diff_changed();
+    }
+
+    // indices (in the list of all changes) of the changes that a row in the changes list stands
+    // for; a file in the grouped view stands for all of its changes
+    fn change_indices_for_row(&self, row: usize) -> Vec<usize> {
+        if !self.grouped_view {
+            return self.changes.visible.get(row).copied().into_iter().collect();
+        }
+
+        match self.change_tree.rows.get(row) {
+            Some(TreeRow::File { changes, .. }) => changes.clone(),
+            Some(TreeRow::Change(i)) => vec![*i],
+            None => vec![],
+        }
+    }
+
+    // index (in the list of all changes) of the selected change; None when a file is selected in
+    // the grouped view
+    fn selected_item_index(&self) -> Option<usize> {
+        if !self.grouped_view {
+            return self.changes.selected_item_index();
+        }
+
+        match self.change_tree.selected_row() {
+            Some(TreeRow::Change(i)) => Some(*i),
+            _ => None,
+        }
+    }
+
+    fn selected_change_indices(&self) -> Vec<usize> {
+        self.change_list_state_ref()
+            .selected()
+            .map(|row| self.change_indices_for_row(row))
+            .unwrap_or_default()
+    }
+
+    pub(super) fn are_reviewed(&self, indices: &[usize]) -> bool {
+        indices
+            .iter()
+            .filter_map(|&i| self.changes.items.get(i))
+            .all(|item| item.reviewed)
+    }
+
+    fn set_reviewed(&mut self, indices: &[usize], reviewed: bool) {
+        for &i in indices {
+            if let Some(item) = self.changes.items.get_mut(i) {
+                item.reviewed = reviewed;
+            }
+        }
+
+        let file_reviewed = match self.change_tree.selected_row() {
+            Some(TreeRow::File { changes, .. }) => self.are_reviewed(changes),
+            _ => false,
+        };
+        if let Some(net_change) = &mut self.net_change {
+            net_change.reviewed = file_reviewed;
+        }
+    }
+
+    pub(super) fn toggle_reviewed(&mut self) {
+        let indices = self.selected_change_indices();
+        if indices.is_empty() {
+            return;
+        }
+
+        let reviewed = !self.are_reviewed(&indices);
+        self.set_reviewed(&indices, reviewed);
+        // so that a change marked as unreviewed doesn't get marked as reviewed again right away
+        self.viewing_since = None;
+    }
+
+    pub(super) fn num_unreviewed(&self) -> usize {
+        self.changes
+            .items
+            .iter()
+            .filter(|item| !item.reviewed)
+            .count()
+    }
+
+    // Selects the next row with unreviewed changes, wrapping around to the top of the list
+    pub(super) fn select_next_unreviewed(&mut self) {
+        let len = self.change_list_len();
+        if len == 0 {
+            return;
+        }
+
+        let selected = self.change_list_state_ref().selected();
+        let start = selected.map(|i| i + 1).unwrap_or_default();
+        let next_unreviewed = (0..len)
+            .map(|offset| (start + offset) % len)
+            .find(|&row| !self.are_reviewed(&self.change_indices_for_row(row)));
+
+        match next_unreviewed {
+            Some(row) if Some(row) == selected => {}
+            Some(row) => {
+                self.change_list_state().select(Some(row));
+                self.on_selected_change_updated();
+            }
+            None => self.user_msg = Some(UserMsg::info("no unreviewed changes")),
+        }
+    }
+
+    pub(super) fn is_awaiting_auto_review(&self) -> bool {
+        self.behaviours.auto_review_after.is_some() && self.viewing_since.is_some()
+    }
+
+    // Marks the selected change as reviewed once it has been viewed for long enough
+    pub(super) fn auto_review(&mut self, now: Instant) {
+        let (Some(review_after), Some(viewing_since)) =
+            (self.behaviours.auto_review_after, self.viewing_since)
+        else {
+            return;
+        };
+
+        if !matches!(self.active_pane, Pane::Changes | Pane::Diff)
+            || now.saturating_duration_since(viewing_since) < review_after
+        {
+            return;
+        }
+
+        let indices = self.selected_change_indices();
+        self.set_reviewed(&indices, true);
+        self.viewing_since = None;
+    }
+
+    // the net change across all changes to the file selected in the grouped view
+    fn compute_net_change(&self) -> Option<ChangeItem> {
+        let Some(TreeRow::File { path, changes }) = self.change_tree.selected_row() else {
+            return None;
+        };
+
+        let kinds = changes
+            .iter()
+            .filter_map(|&i| self.changes.items.get(i))
+            .map(|item| &item.change.kind);
+
+        let latest = changes.last().and_then(|&i| self.changes.items.get(i))?;
+        let kind = match ChangeKind::net(kinds) {
+            Some(kind) => kind,
+            None => latest.change.kind.clone(),
+        };
+
+        Some(ChangeItem {
+            change: Change {
+                path: path.clone(),
+                kind,
+                encoding: latest.change.encoding,
+            },
+            reviewed: self.are_reviewed(changes),
+            by_user: changes
+                .iter()
+                .filter_map(|&i| self.changes.items.get(i))
+                .all(|item| item.by_user),
+            since_last_session: changes
+                .iter()
+                .filter_map(|&i| self.changes.items.get(i))
+                .all(|item| item.since_last_session),
+        })
+    }
+
+    // the number of changes making up the net change shown for a file in the grouped view
+    pub(super) fn num_changes_in_net_change(&self) -> Option<usize> {
+        if !self.grouped_view {
+            return None;
+        }
+
+        match self.change_tree.selected_row() {
+            Some(TreeRow::File { changes, .. }) => Some(changes.len()),
+            _ => None,
+        }
+    }
+
+    // cycles through the search scope, or the change kind to filter by
+    pub(super) fn cycle_prompt_option(&mut self) {
+        match &mut self.prompt {
+            Some(Prompt {
+                kind: PromptKind::DiffSearch(scope),
+                ..
+            }) => *scope = scope.next(),
+            Some(Prompt {
+                kind: PromptKind::ChangesFilter(kind),
+                ..
+            }) => *kind = kind.next(),
+            // only a line can be commented on when it isn't part of a hunk
+            Some(Prompt {
+                kind: PromptKind::Comment(target),
+                ..
+            }) => {
+                if self
+                    .comment_draft
+                    .as_ref()
+                    .is_some_and(|d| d.hunk.is_some())
+                {
+                    *target = match target {
+                        CommentTarget::Line => CommentTarget::Hunk,
+                        CommentTarget::Hunk => CommentTarget::Line,
+                    };
+                }
+            }
+            Some(Prompt {
+                kind: PromptKind::PathFilter,
+                ..
+            })
+            | None => return,
+        }
+
+        self.on_prompt_input_changed();
+    }
+
+    pub(super) fn submit_prompt(&mut self) {
+        let Some(prompt) = self.prompt.take() else {
+            return;
+        };
+
+        match prompt.kind {
+            PromptKind::DiffSearch(scope) => {
+                self.search_before_prompt = None;
+                if prompt.input.is_empty() {
+                    self.diff_search = None;
+                    return;
+                }
+
+                if let Err(e) = DiffSearch::new(&prompt.input, scope) {
+                    // regex syntax errors span several lines, the last of which has the gist
+                    let error = e.to_string();
+                    let reason = error
+                        .lines()
+                        .last()
+                        .map(|l| l.trim_start_matches("error: "))
+                        .unwrap_or_default();
+                    self.diff_search = None;
+                    self.user_msg =
+                        Some(UserMsg::error(format!("invalid search pattern: {reason}")));
+                }
+            }
+            PromptKind::ChangesFilter(_) => self.filter_before_prompt = None,
+            PromptKind::Comment(target) => {
+                let Some(draft) = self.comment_draft.take() else {
+                    return;
+                };
+                let text = prompt.input.trim();
+                if text.is_empty() {
+                    return;
+                }
+
+                self.comments.push(draft.to_comment(target, text));
+                if self.comments_state.selected().is_none() {
+                    self.comments_state.select(Some(0));
+                }
+                self.compute_max_diff_scroll_available();
+            }
+            // files that start matching only get a snapshot once they change, as they weren't
+            // watched before
+            PromptKind::PathFilter => match PathFilter::parse(&prompt.input) {
+                Ok(filter) => {
+                    self.path_filter.send_replace(filter);
+                }
+                Err(e) => {
+                    self.user_msg = Some(UserMsg::error(format!("{e:#}")));
+                }
+            },
+        }
+    }
+
+    pub(super) fn cancel_prompt(&mut self) {
+        let Some(prompt) = self.prompt.take() else {
+            return;
+        };
+
+        match prompt.kind {
+            PromptKind::DiffSearch(_) => {
+                self.diff_search = self.search_before_prompt.take();
+                self.refresh_diff_search();
+            }
+            PromptKind::ChangesFilter(_) => {
+                if let Some(filter) = self.filter_before_prompt.take() {
+                    self.apply_changes_filter(filter);
+                }
+            }
+            PromptKind::Comment(_) => self.comment_draft = None,
+            PromptKind::PathFilter => {}
+        }
+    }
+
+    // searching happens incrementally, as the query is typed
+    fn on_prompt_input_changed(&mut self) {
+        let Some(prompt) = &self.prompt else {
+            return;
+        };
+
+        match prompt.kind {
+            PromptKind::DiffSearch(scope) => {
+                self.diff_search = if prompt.input.is_empty() {
+                    None
+                } else {
+                    DiffSearch::new(&prompt.input, scope).ok()
+                };
+                self.refresh_diff_search();
+                self.go_to_next_search_match();
+            }
+            PromptKind::ChangesFilter(kind) => {
+                let filter = ChangesFilter {
+                    query: prompt.input.clone(),
+                    kind,
+                };
+                self.apply_changes_filter(filter);
+            }
+            PromptKind::Comment(_) | PromptKind::PathFilter => {}
+        }
+    }
+
+    // Comments can be added to the line in focus (the one last jumped to, or the one near the top
+    // of the diff pane), or to the hunk it's a part of
+    pub(super) fn start_comment(&mut self) {
+        let Some(change_index) = self.selected_item_index() else {
+            if self.selected_change().is_some() {
+                self.user_msg = Some(UserMsg::info(
+                    "expand the file to comment on one of its changes",
+                ));
+            }
+            return;
+        };
+        let Some(path) = self.current_file_path().map(|p| p.to_string()) else {
+            return;
+        };
+        let Some(lines) = self.displayed_diff_lines() else {
+            self.user_msg = Some(UserMsg::info("this change has no lines to comment on"));
+            return;
+        };
+
+        let in_focus = self
+            .diff_cursor
+            .unwrap_or_else(|| self.line_in_focus_after_scroll())
+            .min(lines.len().saturating_sub(1));
+        // the line in focus might be a separator between hunks
+        let Some(line) = lines
+            .iter()
+            .skip(in_focus)
+            .chain(lines.iter().take(in_focus).rev())
+            .flatten()
+            .next()
+            .cloned()
+        else {
+            return;
+        };
+
+        let hunk = match self.selected_change().map(|c| &c.change.kind) {
+            Some(ChangeKind::Modified(Ok(Modification::Diff(diff)))) => diff
+                .hunks
+                .iter()
+                .find(|h| {
+                    h.lines.iter().any(|l| {
+                        l.old_line_num == line.old_line_num && l.new_line_num == line.new_line_num
+                    })
+                })
+                .map(|h| h.lines.clone()),
+            _ => None,
+        };
+
+        self.comment_draft = Some(CommentDraft {
+            change_index,
+            path,
+            line,
+            hunk,
+        });
+        self.prompt = Some(Prompt {
+            kind: PromptKind::Comment(CommentTarget::Line),
+            input: String::new(),
+        });
+    }
+
+    // where the comment being typed out will be added, eg. "src/main.rs:12"
+    pub(super) fn comment_draft_location(&self, target: CommentTarget) -> Option<String> {
+        self.comment_draft
+            .as_ref()
+            .map(|draft| draft.to_comment(target, "").location())
+    }
+
+    // Comments on the selected change, along with the index of the line (among the lines shown
+    // in the diff pane) after which each of them is shown
+    pub(super) fn displayed_comments(&self, lines: &[Option<DiffLine>]) -> Vec<(usize, &Comment)> {
+        if self.comments.is_empty() {
+            return vec![];
+        }
+        let Some(change_index) = self.selected_item_index() else {
+            return vec![];
+        };
+
+        let mut displayed = self
+            .comments
+            .iter()
+            .filter(|c| c.change_index == Some(change_index))
+            .filter_map(|c| {
+                lines
+                    .iter()
+                    .position(|l| l.as_ref().is_some_and(|l| c.is_anchored_at(l)))
+                    .map(|i| (i, c))
+            })
+            .collect::<Vec<_>>();
+        displayed.sort_by_key(|(i, _)| *i);
+
+        displayed
+    }
+
+    fn select_next_comment(&mut self) {
+        if let Some(i) = self.comments_state.selected()
+            && i + 1 < self.comments.len()
+        {
+            self.comments_state.select(Some(i + 1));
+        }
+    }
+
+    fn select_previous_comment(&mut self) {
+        if let Some(i) = self.comments_state.selected()
+            && i > 0
+        {
+            self.comments_state.select(Some(i - 1));
+        }
+    }
+
+    pub(super) fn delete_selected_comment(&mut self) {
+        let Some(i) = self.comments_state.selected() else {
+            return;
+        };
+        if i >= self.comments.len() {
+            return;
+        }
+
+        self.comments.remove(i);
+        self.comments_state.select(if self.comments.is_empty() {
+            None
+        } else {
+            Some(i.min(self.comments.len() - 1))
+        });
+        self.compute_max_diff_scroll_available();
+    }
+
+    pub(super) fn export_comments(&mut self) -> Option<Cmd> {
+        if self.comments.is_empty() {
+            self.user_msg = Some(UserMsg::info("there are no comments to export"));
+            return None;
+        }
+
+        Some(Cmd::ExportComments {
+            contents: comments_to_markdown(&self.comments),
+        })
+    }
+
+    // Selects the commented change, and scrolls to the commented line in the diff pane
+    pub(super) fn go_to_selected_comment(&mut self) {
+        let Some(comment) = self
+            .comments_state
+            .selected()
+            .and_then(|i| self.comments.get(i))
+            .cloned()
+        else {
+            return;
+        };
+        let Some(change_index) = comment.change_index else {
+            self.user_msg = Some(UserMsg::info(
+                "the commented change was cleared from the list",
+            ));
+            return;
+        };
+
+        let Some(position) = self.changes.visible.iter().position(|&i| i == change_index) else {
+            self.user_msg = Some(UserMsg::info(
+                "the commented change is hidden by the filter",
+            ));
+            return;
+        };
+
+        if self.grouped_view {
+            self.change_tree.reveal_change(
+                &self.changes.items,
+                &self.changes.visible,
+                change_index,
+            );
+        } else {
+            self.changes.state.select(Some(position));
+        }
+        self.on_selected_change_updated();
+        self.last_active_pane = Some(self.active_pane);
+        self.active_pane = Pane::Diff;
+
+        let Some(lines) = self.displayed_diff_lines() else {
+            return;
+        };
+        if let Some(line) = lines
+            .iter()
+            .position(|l| l.as_ref().is_some_and(|l| comment.is_anchored_at(l)))
+        {
+            self.scroll_to_changed_line(line);
+        }
+    }
+
+    pub(super) fn go_to_next_search_match(&mut self) {
+        let top_line = self.logical_line_at_diff_scroll();
+        if let Some(search) = &mut self.diff_search {
+            search.select_next(top_line);
+        }
+        self.scroll_to_current_search_match();
+    }
+
+    pub(super) fn go_to_previous_search_match(&mut self) {
+        let top_line = self.logical_line_at_diff_scroll();
+        if let Some(search) = &mut self.diff_search {
+            search.select_previous(top_line);
+        }
+        self.scroll_to_current_search_match();
+    }
+
+    fn refresh_diff_search(&mut self) {
+        let Some(lines) = self.displayed_diff_lines() else {
+            if let Some(search) = &mut self.diff_search {
+                search.matches.clear();
+                search.current = None;
+            }
+            return;
+        };
+
+        if let Some(search) = &mut self.diff_search {
+            search.find_matches(&lines);
+        }
+    }
+
+    fn scroll_to_current_search_match(&mut self) {
+        let Some(current_match) = self
+            .diff_search
+            .as_ref()
+            .and_then(|s| s.current_match())
+            .cloned()
+        else {
+            return;
+        };
+        let Some(lines) = self.displayed_diff_lines() else {
+            return;
+        };
+
+        self.diff_cursor = Some(current_match.line_index);
+        let row = self.visual_row_of_line(current_match.line_index);
+        let available_height = self.available_diff_pane_height();
+        if row < self.diff_scroll || row >= self.diff_scroll + available_height {
+            self.diff_scroll = row
+                .saturating_sub(SCROLL_CONTEXT_LINES)
+                .min(self.max_diff_scroll_available);
+        }
+
+        if self.wrap_diff_lines {
+            return;
+        }
+
+        let Some(Some(line)) = lines.get(current_match.line_index) else {
+            return;
+        };
+        let content = line.content();
+        let start = line_width(&content[..current_match.range.start]);
+        let end = line_width(&content[..current_match.range.end]);
+        let available_width = self
+            .available_diff_pane_width()
+            .saturating_sub(self.displayed_gutter_width(&lines));
+
+        if start < self.diff_horizontal_scroll
+            || end > self.diff_horizontal_scroll + available_width
+        {
+            self.diff_horizontal_scroll = start
+                .saturating_sub(available_width / 4)
+                .min(self.max_diff_horizontal_scroll_available);
+        }
+    }
+
+    pub(super) fn go_to_next_hunk(&mut self) {
+        let target = match self.current_hunk_index() {
+            Some(i) => i + 1,
+            None => 0,
+        };
+
+        if let Some(&line) = self.diff_layout.hunk_starts.get(target) {
+            self.diff_scroll = self
+                .visual_row_of_line(line)
+                .min(self.max_diff_scroll_available);
+            self.diff_cursor = Some(line);
+        }
+    }
+
+    // Goes to the start of the current hunk if it's been scrolled past, or to the previous one
+    pub(super) fn go_to_previous_hunk(&mut self) {
+        let hunk_starts = &self.diff_layout.hunk_starts;
+        let target = match self.current_hunk_index() {
+            Some(i)
+                if self.diff_cursor.is_none()
+                    && self.diff_scroll > self.visual_row_of_line(hunk_starts[i]) =>
+            {
+                i
+            }
+            Some(i) if i > 0 => i - 1,
+            _ => return,
+        };
+
+        let line = hunk_starts[target];
+        self.diff_scroll = self
+            .visual_row_of_line(line)
+            .min(self.max_diff_scroll_available);
+        self.diff_cursor = Some(line);
+    }
+
+    pub(super) fn go_to_next_changed_line(&mut self) {
+        let Some(lines) = self.displayed_diff_lines() else {
+            return;
+        };
+        let from = match self.diff_cursor {
+            Some(cursor) => cursor + 1,
+            None => self.line_in_focus_after_scroll(),
+        };
+
+        if let Some(line) = (from..lines.len()).find(|&i| is_changed_line(&lines[i])) {
+            self.scroll_to_changed_line(line);
+        }
+    }
+
+    pub(super) fn go_to_previous_changed_line(&mut self) {
+        let Some(lines) = self.displayed_diff_lines() else {
+            return;
+        };
+        let before = self
+            .diff_cursor
+            .unwrap_or_else(|| self.line_in_focus_after_scroll());
+
+        if let Some(line) = (0..before.min(lines.len()))
+            .rev()
+            .find(|&i| is_changed_line(&lines[i]))
+        {
+            self.scroll_to_changed_line(line);
+        }
+    }
+
+    fn scroll_to_changed_line(&mut self, line: usize) {
+        self.diff_scroll = self
+            .visual_row_of_line(line)
+            .saturating_sub(SCROLL_CONTEXT_LINES)
+            .min(self.max_diff_scroll_available);
+        self.diff_cursor = Some(line);
+    }
+
+    // changed lines are jumped to with a few lines of context above them, so when scrolled
+    // manually, the line in focus is the one just below that context
+    fn line_in_focus_after_scroll(&self) -> usize {
+        if self.diff_scroll == 0 {
+            0
+        } else {
+            self.logical_line_at_diff_scroll() + SCROLL_CONTEXT_LINES
+        }
+    }
+
+    // Returns the index of the hunk, along with the total number of hunks, for the selected
+    // change's diff
+    pub(super) fn hunk_position(&self) -> Option<(Option<usize>, usize)> {
+        let num_hunks = self.diff_layout.hunk_starts.len();
+        if num_hunks == 0 {
+            return None;
+        }
+
+        Some((self.current_hunk_index(), num_hunks))
+    }
+
+    fn current_hunk_index(&self) -> Option<usize> {
+        let hunk_starts = &self.diff_layout.hunk_starts;
+        match self.diff_cursor {
+            Some(cursor) => hunk_starts.iter().rposition(|&start| start <= cursor),
+            None => hunk_starts
+                .iter()
+                .rposition(|&start| self.visual_row_of_line(start) <= self.diff_scroll),
+        }
+    }
+
+    // indices of the first line of each hunk among the lines shown in the diff pane; hunks are in
+    // the same order as the lines, so they're all found in one go
+    fn hunk_start_lines(&self, lines: &[Option<DiffLine>]) -> Vec<usize> {
+        let Some(ChangeKind::Modified(Ok(Modification::Diff(diff)))) =
+            self.selected_change().map(|c| &c.change.kind)
+        else {
+            return vec![];
+        };
+
+        let mut firsts = diff
+            .hunks
+            .iter()
+            .filter_map(|hunk| hunk.lines.first())
+            .peekable();
+        let mut starts = vec![];
+        for (i, line) in lines.iter().enumerate() {
+            let Some(first) = firsts.peek() else {
+                break;
+            };
+            if line.as_ref().is_some_and(|l| {
+                l.old_line_num == first.old_line_num && l.new_line_num == first.new_line_num
+            }) {
+                starts.push(i);
+                firsts.next();
+            }
+        }
+
+        starts
+    }
+
+    fn refresh_full_file_lines(&mut self) {
+        self.full_file_lines = match self.selected_change().map(|c| &c.change.kind) {
+            Some(ChangeKind::Modified(Ok(Modification::Diff(diff)))) if self.full_file_view => {
+                Some(diff.full_file_lines())
+            }
+            _ => None,
+        };
+    }
+
+    // Lines shown in the diff pane for the selected change, if it has any; None stands for a
+    // separator between hunks
+    pub(super) fn displayed_diff_lines(&self) -> Option<Vec<Option<DiffLine>>> {
+        let lines = match &self.selected_change()?.change.kind {
+            ChangeKind::Modified(Ok(Modification::Diff(_))) if self.full_file_view => self
+                .full_file_lines
+                .as_ref()?
+                .iter()
+                .take(MAX_DISPLAYED_DIFF_LINES)
+                .cloned()
+                .map(Some)
+                .collect(),
+            ChangeKind::Modified(Ok(Modification::Diff(diff))) => {
+                let mut lines = vec![];
+                for (idx, hunk) in diff.hunks.iter().enumerate() {
+                    if idx > 0 {
+                        lines.push(None);
+                    }
+                    lines.extend(hunk.lines.iter().cloned().map(Some));
+                }
+                lines
+            }
+            ChangeKind::Created(Ok(contents)) => DiffLine::all_inserted(contents)
+                .into_iter()
+                .map(Some)
+                .collect(),
+            _ => return None,
+        };
+
+        Some(lines.into_iter().take(MAX_DISPLAYED_DIFF_LINES).collect())
+    }
+
+    // The number of lines of the selected change that don't fit within the limit of lines shown
+    pub(super) fn num_hidden_diff_lines(&self) -> usize {
+        let num_lines = match self.selected_change().map(|c| &c.change.kind) {
+            Some(ChangeKind::Modified(Ok(Modification::Diff(_)))) if self.full_file_view => {
+                self.full_file_lines.as_ref().map_or(0, |lines| lines.len())
+            }
+            Some(ChangeKind::Modified(Ok(Modification::Diff(diff)))) => {
+                diff.hunks
+                    .iter()
+                    .map(|hunk| hunk.lines.len())
+                    .sum::<usize>()
+                    + diff.hunks.len().saturating_sub(1)
+            }
+            Some(ChangeKind::Created(Ok(contents))) => contents.split_inclusive('\n').count(),
+            _ => 0,
+        };
+
+        num_lines.saturating_sub(MAX_DISPLAYED_DIFF_LINES)
+    }
+
+    // contents of newly created files are shown as is, unless in the full file view
+    pub(super) fn diff_pane_shows_line_numbers(&self) -> bool {
+        self.full_file_view
+            || !matches!(
+                self.selected_change().map(|c| &c.change.kind),
+                Some(ChangeKind::Created(_))
+            )
+    }
+
+    fn displayed_gutter_width(&self, lines: &[Option<DiffLine>]) -> usize {
+        if self.diff_pane_shows_line_numbers() {
+            gutter_width(line_num_padding(lines.iter().flatten()))
+        } else {
+            0
+        }
+    }
+
+    fn refresh_diff_layout(&mut self) {
+        let Some(lines) = self.displayed_diff_lines() else {
+            self.diff_layout = DiffLayout::default();
+            return;
+        };
+        let content_wrap_width = self
+            .diff_wrap_width()
+            .map(|w| w.saturating_sub(self.displayed_gutter_width(&lines)));
+        let rows_of = |text: &str| match content_wrap_width {
+            Some(w) => wrapped_line_count(text, w),
+            None => 1,
+        };
+
+        let mut comment_rows = vec![0; lines.len()];
+        for (i, comment) in self.displayed_comments(&lines) {
+            comment_rows[i] += rows_of(&comment.display_text());
+        }
+
+        let mut row_offsets = Vec::with_capacity(lines.len() + 1);
+        let mut row = 0;
+        row_offsets.push(row);
+        for (line, comment_rows) in lines.iter().zip(&comment_rows) {
+            row += match line {
+                Some(l) => rows_of(&l.content()),
+                None => 1,
+            } + comment_rows;
+            row_offsets.push(row);
+        }
+
+        self.diff_layout = DiffLayout {
+            row_offsets,
+            comment_rows: comment_rows.iter().sum(),
+            hunk_starts: self.hunk_start_lines(&lines),
+        };
+    }
+
+    fn visual_row_of_line(&self, line_index: usize) -> usize {
+        let offsets = &self.diff_layout.row_offsets;
+        offsets
+            .get(line_index)
+            .or(offsets.last())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn logical_line_at_diff_scroll(&self) -> usize {
+        let offsets = &self.diff_layout.row_offsets;
+        let num_lines = offsets.len().saturating_sub(1);
+        let line = offsets.get(1..).map_or(0, |ends| {
+            ends.partition_point(|&end| end <= self.diff_scroll)
+        });
+
+        if line < num_lines { line } else { 0 }
+    }
+
+    pub(super) fn selected_change(&self) -> Option<&ChangeItem> {
+        if !self.grouped_view {
+            return self.changes.selected_item();
+        }
+
+        match self.change_tree.selected_row()? {
+            TreeRow::File { .. } => self.net_change.as_ref(),
+            TreeRow::Change(i) => self.changes.items.get(*i),
+        }
+    }
+
+    pub(super) fn current_file_path(&self) -> Option<&str> {
+        self.selected_change().map(|item| item.change.path.as_str())
+    }
+
+    pub(super) fn get_cancellation_token(&self) -> CancellationToken {
+        self.cancellation_token.clone()
+    }
+
+    pub(super) fn reset_list(&mut self) {
+        let filter = self.changes.filter.clone();
+        self.changes = Changes::new();
+        self.changes.filter = filter;
+        self.change_tree.rebuild(&[], &[]);
+        // comments refer to changes by their position in the list, so they're detached from the
+        // changes rather than dropped
+        for comment in &mut self.comments {
+            comment.change_index = None;
+        }
+        self.session_summary = SessionSummary::default();
+        self.reset_summary_scroll();
+        self.on_selected_change_updated();
+    }
+
+    pub(super) fn pause_watching(&mut self) {
+        self.cancellation_token.cancel();
+        self.behaviours.watch = false;
+    }
+
+    pub(super) fn regenerate_cancellation_token(&mut self) {
+        self.cancellation_token = CancellationToken::new();
+        self.behaviours.watch = true;
+        for r in &mut self.roots {
+            r.failed = false;
+        }
+    }
+
+    pub(super) fn scroll_help_down(&mut self) {
+        if self.help_scroll < self.max_help_scroll_available {
+            self.help_scroll += 1;
+        }
+    }
+
+    pub(super) fn scroll_help_up(&mut self) {
+        self.help_scroll = self.help_scroll.saturating_sub(1);
+    }
+
+    pub(super) fn scroll_summary_down(&mut self) {
+        if self.summary_scroll < self.max_summary_scroll_available {
+            self.summary_scroll += 1;
+        }
+    }
+
+    pub(super) fn scroll_summary_up(&mut self) {
+        self.summary_scroll = self.summary_scroll.saturating_sub(1);
+    }
+
+    pub(super) fn cycle_summary_sort_key(&mut self) {
+        self.summary_sort_key = self.summary_sort_key.next();
+        self.summary_scroll = 0;
+    }
+
+    pub(super) fn reset_summary_scroll(&mut self) {
+        self.summary_scroll = 0;
+        self.compute_max_summary_scroll_available();
+    }
+
+    pub(super) fn compute_max_summary_scroll_available(&mut self) {
+        self.max_summary_scroll_available = if self.terminal_too_small {
+            0
+        } else {
+            //      top border + padding top + header + lower border + status line
+            // 5 => 1          + 1           + 1      + 1            + 1
+            let available_height = (self.terminal_dimensions.height as usize).saturating_sub(5);
+            self.session_summary
+                .num_files()
+                .saturating_sub(available_height)
+        };
+    }
+
+    pub(super) fn scroll_diff_down(&mut self, scroll_kind: ScrollKind) {
+        if self.selected_change().is_none() {
+            return;
+        }
+
+        self.diff_cursor = None;
+        self.diff_scroll = (self.diff_scroll + self.diff_pane_scroll_line_count(scroll_kind))
+            .min(self.max_diff_scroll_available);
+    }
+
+    pub(super) fn scroll_diff_up(&mut self, scroll_kind: ScrollKind) {
+        if self.selected_change().is_none() {
+            return;
+        }
+
+        self.diff_cursor = None;
+        self.diff_scroll = self
+            .diff_scroll
+            .saturating_sub(self.diff_pane_scroll_line_count(scroll_kind));
+    }
+
+    pub(super) fn scroll_diff_right(&mut self) {
+        if self.selected_change().is_none() {
+            return;
+        }
+
+        self.diff_horizontal_scroll = (self.diff_horizontal_scroll + HORIZONTAL_SCROLL_STEP)
+            .min(self.max_diff_horizontal_scroll_available);
+    }
+
+    pub(super) fn scroll_diff_left(&mut self) {
+        if self.selected_change().is_none() {
+            return;
+        }
+
+        self.diff_horizontal_scroll = self
+            .diff_horizontal_scroll
+            .saturating_sub(HORIZONTAL_SCROLL_STEP);
+    }
+
+    pub(super) fn reset_help_scroll(&mut self) {
+        self.help_scroll = 0;
+    }
+
+    // In the full file view, "resetting" the scroll means jumping to the first change
+    pub(super) fn reset_diff_scroll(&mut self) {
+        self.diff_cursor = None;
+        self.diff_horizontal_scroll = 0;
+        self.diff_scroll = if self.full_file_view {
+            self.first_change_line_in_full_file_view()
+                .map(|i| i.saturating_sub(SCROLL_CONTEXT_LINES))
+                .unwrap_or_default()
+                .min(self.max_diff_scroll_available)
+        } else {
+            0
+        };
+    }
+
+    fn first_change_line_in_full_file_view(&self) -> Option<usize> {
+        match &self.selected_change()?.change.kind {
+            ChangeKind::Modified(Ok(Modification::Diff(_))) => {
+                let lines = self.full_file_lines.as_ref()?;
+                let position = lines
+                    .iter()
+                    .position(|line| line.kind != DiffOperation::Equal)?;
+                Some(visual_line_count(
+                    &lines[..position],
+                    self.diff_content_wrap_width(lines),
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    // kinda weird that this model method relies on knowledge of the view, but oh well
+    pub(super) fn compute_max_help_scroll_available(&mut self) {
+        // -help------------------------------
+        // |                                 | <- padding top
+        // | 1    keymaps                    |
+        // | 2    aaaaa                      |
+        // | 3    aaaaa                      | <- available height = 10 - 4 = 6
+        // | 4    aaaaa                      |
+        // | 5    aaaaa                      |
+        // | 6    aaaaa                      |
+        // |---------------------------------| <- help border
+        // | dfft                            | __ lower edge of screen
+        // | 7    aaaaa | <- not visible
+        // | 8    aaaaa |
+        // | 9    aaaaa | <- max scroll available = 3
+        // |            |
+        // |            |
+        // |            |
+        // --------------
+
+        // fully scrolled view
+
+        // -help------------------------------
+        // |                                 |
+        // | 4    aaaaa                      |
+        // | 5    aaaaa                      |
+        // | 6    aaaaa                      |
+        // | 7    aaaaa                      |
+        // | 8    aaaaa                      |
+        // | 9    aaaaa                      |
+        // |---------------------------------|
+        // | dfft                            |
+
+        self.max_help_scroll_available = if self.terminal_too_small {
+            0
+        } else {
+            //      top border + padding top + lower border + status line
+            // 4 => 1          + 1           + 1            + 1
+            let available_height = self.terminal_dimensions.height as usize - 4;
+            self.help_line_count.saturating_sub(available_height)
+        };
+    }
+
+    // kinda weird that this model method relies on knowledge of the view, but oh well; also works
+    // out the layout of the diff pane, since that changes along with the limits
+    pub(super) fn compute_max_diff_scroll_available(&mut self) {
+        self.refresh_diff_layout();
+
+        let selected_index = self.change_list_state_ref().selected();
+        let change_item = self.selected_change();
+
+        if change_item.is_none() && selected_index.is_some() {
+            self.user_msg = Some(UserMsg::error(UNEXPECTED_ERROR_MSG));
+        }
+
+        let (max_scroll, max_horizontal_scroll) = match self.diff_pane_content_size() {
+            Some(size) if !self.terminal_too_small => (
+                size.num_lines
+                    .saturating_sub(self.available_diff_pane_height()),
+                if self.wrap_diff_lines {
+                    0
+                } else {
+                    size.max_line_width.saturating_sub(
+                        self.available_diff_pane_width()
+                            .saturating_sub(size.gutter_width),
+                    )
+                },
+            ),
+            _ => (0, 0),
+        };
+
+        self.max_diff_scroll_available = max_scroll;
+        self.max_diff_horizontal_scroll_available = max_horizontal_scroll;
+        self.diff_horizontal_scroll = self
+            .diff_horizontal_scroll
+            .min(self.max_diff_horizontal_scroll_available);
+    }
+
+    fn diff_pane_content_size(&self) -> Option<DiffPaneContentSize> {
+        if self.num_hidden_diff_lines() > 0 {
+            return self.truncated_diff_pane_content_size();
+        }
+
+        let size = match &self.selected_change()?.change.kind {
+            ChangeKind::Modified(Ok(Modification::Diff(_))) if self.full_file_view => {
+                self.diff_lines_content_size(self.full_file_lines.as_ref()?)
+            }
+            ChangeKind::Modified(Ok(Modification::Diff(diff))) => DiffPaneContentSize {
+                num_lines: diff.num_lines(self.diff_wrap_width()),
+                max_line_width: diff.max_line_width(),
+                gutter_width: gutter_width(diff.line_num_padding()),
+            },
+            ChangeKind::Created(Ok(contents)) if self.full_file_view => {
+                self.diff_lines_content_size(&DiffLine::all_inserted(contents))
+            }
+            ChangeKind::Created(Ok(contents)) => DiffPaneContentSize {
+                num_lines: match self.diff_wrap_width() {
+                    Some(w) => contents.lines().map(|l| wrapped_line_count(l, w)).sum(),
+                    None => contents.lines().count(),
+                },
+                max_line_width: contents.lines().map(line_width).max().unwrap_or_default(),
+                gutter_width: 0,
+            },
+            _ => return None,
+        };
+
+        Some(DiffPaneContentSize {
+            num_lines: size.num_lines + self.diff_layout.comment_rows,
+            ..size
+        })
+    }
+
+    // measured by the lines actually shown, followed by a line saying how many weren't
+    fn truncated_diff_pane_content_size(&self) -> Option<DiffPaneContentSize> {
+        let lines = self.displayed_diff_lines()?;
+        let shown = lines.iter().flatten().cloned().collect::<Vec<_>>();
+        let size = self.diff_lines_content_size(&shown);
+        let num_separators = lines.iter().filter(|line| line.is_none()).count();
+
+        Some(DiffPaneContentSize {
+            num_lines: size.num_lines + num_separators + self.diff_layout.comment_rows + 1,
+            gutter_width: if self.diff_pane_shows_line_numbers() {
+                size.gutter_width
+            } else {
+                0
+            },
+            ..size
+        })
+    }
+
+    fn diff_lines_content_size(&self, lines: &[DiffLine]) -> DiffPaneContentSize {
+        DiffPaneContentSize {
+            num_lines: visual_line_count(lines, self.diff_content_wrap_width(lines)),
+            max_line_width: lines
+                .iter()
+                .map(|line| line_width(&line.content()))
+                .max()
+                .unwrap_or_default(),
+            gutter_width: gutter_width(line_num_padding(lines.iter())),
+        }
+    }
+
+    fn diff_wrap_width(&self) -> Option<usize> {
+        self.wrap_diff_lines
+            .then(|| self.available_diff_pane_width())
+    }
+
+    // width available to the contents of diff lines (ie, without line numbers), if wrapping
+    fn diff_content_wrap_width(&self, lines: &[DiffLine]) -> Option<usize> {
+        self.diff_wrap_width()
+            .map(|w| w.saturating_sub(gutter_width(line_num_padding(lines.iter()))))
+    }
+
+    fn available_diff_pane_height(&self) -> usize {
+        //       top border + padding top + lower border + changes pane (fixed) + status bar height
+        // 16 => 1          + 1           + 1            + 12                   + 1
+        self.terminal_dimensions.height as usize - 16
+    }
+
+    fn available_diff_pane_width(&self) -> usize {
+        //      left border + padding left + right border
+        // 3 => 1           + 1            + 1
+        (self.terminal_dimensions.width as usize).saturating_sub(3)
+    }
+
+    fn diff_pane_scroll_line_count(&self, scroll_kind: ScrollKind) -> usize {
+        match scroll_kind {
+            ScrollKind::Line => 1,
+            ScrollKind::HalfPage => self.available_diff_pane_height() / 2,
+        }
+    }
+
+    // One command per watched directory, each watching it with its own cache
+    pub(super) fn watch_cmds(&self) -> Vec<Cmd> {
+        self.roots
+            .iter()
+            .enumerate()
+            .map(|(index, r)| Cmd::WatchForChanges {
+                root: r.root.clone(),
+                root_index: index,
+                cache: Arc::clone(&r.cache),
+                sender: self.watch_updates_tx.clone(),
+                cancellation_token: self.get_cancellation_token(),
+                prepopulate_cache: self.behaviours.prepopulate_cache,
+                settings: self.behaviours.watch_settings.clone(),
+                metrics: self.watch_metrics(),
+                filter: self.path_filter.subscribe(),
+            })
+            .collect()
+    }
+
+    pub(super) fn is_watching_root(&self, index: usize) -> bool {
+        self.behaviours.watch && self.roots.get(index).is_some_and(|r| !r.failed)
+    }
+
+    // Watching stops altogether only once watching every directory has failed
+    pub(super) fn on_watching_failed(&mut self, root_index: usize, error: String) {
+        let name = match self.roots.get_mut(root_index) {
+            Some(r) => {
+                r.failed = true;
+                r.root.name.clone()
+            }
+            None => None,
+        };
+        if self.roots.iter().all(|r| r.failed) {
+            self.behaviours.watch = false;
+        }
+
+        let msg = match name {
+            Some(name) => format!("watching {name} for changes failed: {error}"),
+            None => format!("watching for changes failed: {error}"),
+        };
+        self.user_msg = Some(UserMsg::error(msg));
+    }
+
+    // Stays in the status bar, since changes made to files on such a file system from elsewhere (eg.
+    // from outside a container) might never show up
+    pub(super) fn on_polling_suggested(&mut self, description: String) {
+        if !self.poll_suggestions.contains(&description) {
+            self.poll_suggestions.push(description);
+        }
+    }
+
+    pub(super) fn is_polling(&self) -> bool {
+        self.behaviours.watch_settings.poll_interval.is_some()
+    }
+
+    pub(super) fn watch_metrics(&self) -> Arc<WatchMetrics> {
+        self.watch_metrics.clone()
+    }
+
+    pub(super) fn cache_usage(&self) -> Option<CacheUsage> {
+        let mut total: Option<CacheUsage> = None;
+        for r in &self.roots {
+            let usage = r.cache.try_read().ok()?.usage();
+            total = Some(match total {
+                Some(total) => CacheUsage {
+                    num_files: total.num_files + usage.num_files,
+                    bytes_used: total.bytes_used + usage.bytes_used,
+                    budget: total.budget.zip(usage.budget).map(|(a, b)| a + b),
+                },
+                None => usage,
+            });
+        }
+
+        total
+    }
+
+    #[cfg(feature = "sound")]
+    fn play_change_sound(&mut self, change_kind: &ChangeKind) {
+        if matches!(&self.audio_handler, AudioHandler::NotInitialized) {
+            self.initialize_audio_player();
+        }
+
+        if should_audio_playback_be_debounced(&self.audio_last_played_at, Instant::now()) {
             return;
         }
 
@@ -574,6 +2471,39 @@ impl Model {
     }
 }
 
+// Where each line shown in the diff pane starts, in rows (ie, counting wrapped lines and the
+// comments shown after lines), followed by the total number of rows, along with the lines that start
+// hunks. Worked out whenever the lines shown, the wrap width, or the comments change, so that going
+// from lines to rows (and back) doesn't need to go through every line each time.
+#[derive(Debug, Default)]
+struct DiffLayout {
+    row_offsets: Vec<usize>,
+    comment_rows: usize,
+    hunk_starts: Vec<usize>,
+}
+
+struct DiffPaneContentSize {
+    // number of lines on screen, accounting for wrapping
+    num_lines: usize,
+    max_line_width: usize,
+    gutter_width: usize,
+}
+
+fn is_changed_line(line: &Option<DiffLine>) -> bool {
+    line.as_ref()
+        .is_some_and(|l| l.kind != DiffOperation::Equal)
+}
+
+fn visual_line_count(lines: &[DiffLine], content_wrap_width: Option<usize>) -> usize {
+    match content_wrap_width {
+        Some(w) => lines
+            .iter()
+            .map(|line| wrapped_line_count(&line.content(), w))
+            .sum(),
+        None => lines.len(),
+    }
+}
+
 #[cfg(feature = "sound")]
 fn should_audio_playback_be_debounced(last_played_at: &Option<Instant>, now: Instant) -> bool {
     last_played_at.is_some_and(|lpa| {
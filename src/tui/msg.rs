@@ -1,121 +1,119 @@
 use super::common::Pane;
+use super::keymap::{KeyChord, KeymapContext};
 use super::model::Model;
 use crate::domain::Change;
-use ratatui::crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::crossterm::event::{Event, KeyCode, KeyEventKind, MouseButton, MouseEventKind};
+use std::path::PathBuf;
+use std::time::Instant;
 
 pub enum Msg {
     // user actions
+    CopyDiff,
+    CopyHunk,
+    CopyPath,
+    CycleChangeKindFilter,
+    DeleteComment,
+    ExportComments,
+    CycleSummarySortKey,
     GoBackOrQuit,
     GoToPane(Pane),
+    GoToComment,
+    GoToNextChangedLine,
+    GoToNextHunk,
+    GoToNextSearchMatch,
+    GoToPreviousChangedLine,
+    GoToPreviousHunk,
+    GoToPreviousSearchMatch,
+    MouseClick(u16),
+    MouseScroll {
+        row: u16,
+        down: bool,
+    },
+    OpenInEditor,
     QuitImmediately,
     ResetList,
     ScrollDown,
     ScrollHalfPageDown,
     ScrollHalfPageUp,
+    ScrollLeft,
+    ScrollRight,
     ScrollUp,
     SelectFirst,
     SelectLast,
     SelectNext,
+    SelectNextUnreviewed,
     SelectPrevious,
+    StartComment,
+    StartFilter,
+    StartPathFilterEdit,
+    StartSearch,
     TerminalResize(u16, u16),
+    ToggleExpanded,
     ToggleFollowChanges,
+    ToggleFullFileView,
+    ToggleGroupedView,
+    ToggleReviewed,
+    ToggleWrap,
     #[cfg(feature = "sound")]
     ToggleSound,
     ToggleWatching,
+    // prompt
+    CancelPrompt,
+    CyclePromptOption,
+    DeletePromptInput,
+    EnterPromptInput(char),
+    SubmitPrompt,
     // internal
     ChangeReceived(Change),
+    ChangedSinceLastSession(Change),
+    CommentsExported(PathBuf),
+    CommentsExportFailed(String),
+    CopyingFailed(String),
+    EditorClosed(Result<(), String>),
+    Tick(Instant),
     PrepopulationFailed(String),
     PrepopulationFinished,
-    WatchingFailed(String),
+    WatchingFailed(usize, String),
+    PollingSuggested(String),
 }
 
 pub fn get_event_handling_msg(model: &Model, event: Event) -> Option<Msg> {
     match event {
-        Event::Key(key_event) => match model.terminal_too_small {
-            true => match key_event.kind {
-                KeyEventKind::Press => match key_event.code {
-                    KeyCode::Esc | KeyCode::Char('q') => Some(Msg::GoBackOrQuit),
-                    _ => None,
-                },
-                _ => None,
-            },
-            false => match key_event.kind {
-                KeyEventKind::Press => match model.active_pane {
-                    Pane::Changes => match key_event.code {
-                        KeyCode::Char('j') | KeyCode::Down => Some(Msg::SelectNext),
-                        KeyCode::Char('k') | KeyCode::Up => Some(Msg::SelectPrevious),
-                        KeyCode::Char('J') => Some(Msg::ScrollDown),
-                        KeyCode::Char('K') => Some(Msg::ScrollUp),
-                        KeyCode::Char('d') if key_event.modifiers == KeyModifiers::CONTROL => {
-                            Some(Msg::ScrollHalfPageDown)
-                        }
-                        KeyCode::Char('u') if key_event.modifiers == KeyModifiers::CONTROL => {
-                            Some(Msg::ScrollHalfPageUp)
-                        }
-                        KeyCode::Char('g') => Some(Msg::SelectFirst),
-                        KeyCode::Char('G') => Some(Msg::SelectLast),
-                        KeyCode::Char('f') => Some(Msg::ToggleFollowChanges),
-                        KeyCode::Char('r') if key_event.modifiers == KeyModifiers::CONTROL => {
-                            Some(Msg::ResetList)
-                        }
-                        #[cfg(feature = "sound")]
-                        KeyCode::Char('s') => Some(Msg::ToggleSound),
-                        KeyCode::Esc | KeyCode::Char('q') => Some(Msg::GoBackOrQuit),
-                        KeyCode::Tab | KeyCode::BackTab => Some(Msg::GoToPane(Pane::Diff)),
-                        KeyCode::Char(' ') => Some(Msg::ToggleWatching),
-                        KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => {
-                            Some(Msg::QuitImmediately)
-                        }
-                        KeyCode::Char('?') => Some(Msg::GoToPane(Pane::Help)),
-                        _ => None,
-                    },
-                    Pane::Diff => match key_event.code {
-                        KeyCode::Char('j') | KeyCode::Down => Some(Msg::SelectNext),
-                        KeyCode::Char('k') | KeyCode::Up => Some(Msg::SelectPrevious),
-                        KeyCode::Char('g') => Some(Msg::SelectFirst),
-                        KeyCode::Char('G') => Some(Msg::SelectLast),
-                        KeyCode::Char('J') => Some(Msg::ScrollDown),
-                        KeyCode::Char('K') => Some(Msg::ScrollUp),
-                        KeyCode::Char('d') if key_event.modifiers == KeyModifiers::CONTROL => {
-                            Some(Msg::ScrollHalfPageDown)
-                        }
-                        KeyCode::Char('u') if key_event.modifiers == KeyModifiers::CONTROL => {
-                            Some(Msg::ScrollHalfPageUp)
-                        }
-                        KeyCode::Tab | KeyCode::BackTab => Some(Msg::GoToPane(Pane::Changes)),
-                        KeyCode::Char('f') => Some(Msg::ToggleFollowChanges),
-                        KeyCode::Char(' ') => Some(Msg::ToggleWatching),
-                        KeyCode::Char('r') if key_event.modifiers == KeyModifiers::CONTROL => {
-                            Some(Msg::ResetList)
-                        }
-                        #[cfg(feature = "sound")]
-                        KeyCode::Char('s') => Some(Msg::ToggleSound),
-                        KeyCode::Char('?') => Some(Msg::GoToPane(Pane::Help)),
-                        KeyCode::Esc | KeyCode::Char('q') => Some(Msg::GoBackOrQuit),
-                        KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => {
-                            Some(Msg::QuitImmediately)
-                        }
-                        _ => None,
-                    },
-                    Pane::Help => match key_event.code {
-                        KeyCode::Char('j') | KeyCode::Down => Some(Msg::ScrollDown),
-                        KeyCode::Char('k') | KeyCode::Up => Some(Msg::ScrollUp),
-                        KeyCode::Char('?') | KeyCode::Char('q') | KeyCode::Esc => {
-                            Some(Msg::GoBackOrQuit)
-                        }
-                        KeyCode::Char('c') => {
-                            if key_event.modifiers == KeyModifiers::CONTROL {
-                                Some(Msg::QuitImmediately)
-                            } else {
-                                None
-                            }
-                        }
-                        _ => None,
+        Event::Key(key_event) if key_event.kind != KeyEventKind::Press => None,
+        Event::Key(key_event) if model.terminal_too_small => match key_event.code {
+            KeyCode::Esc | KeyCode::Char('q') => Some(Msg::GoBackOrQuit),
+            _ => None,
+        },
+        Event::Key(key_event) => {
+            let chord = KeyChord::from(key_event);
+            let keymap = &model.keymap;
+
+            // characters not bound to any prompt action are typed into the prompt
+            let action = if model.prompt.is_some() {
+                match keymap.action_for(KeymapContext::Prompt, chord) {
+                    Some(action) => Some(action),
+                    None => match chord.typed_char() {
+                        Some(c) => return Some(Msg::EnterPromptInput(c)),
+                        None => keymap.action_for(KeymapContext::General, chord),
                     },
-                },
+                }
+            } else {
+                keymap
+                    .action_for(KeymapContext::from(model.active_pane), chord)
+                    .or_else(|| keymap.action_for(KeymapContext::General, chord))
+            };
+
+            action.and_then(|action| action.msg(model))
+        }
+        Event::Mouse(mouse_event) if !model.terminal_too_small && model.prompt.is_none() => {
+            let row = mouse_event.row;
+            match mouse_event.kind {
+                MouseEventKind::Down(MouseButton::Left) => Some(Msg::MouseClick(row)),
+                MouseEventKind::ScrollDown => Some(Msg::MouseScroll { row, down: true }),
+                MouseEventKind::ScrollUp => Some(Msg::MouseScroll { row, down: false }),
                 _ => None,
-            },
-        },
+            }
+        }
         Event::Resize(w, h) => Some(Msg::TerminalResize(w, h)),
         _ => None,
     }
@@ -0,0 +1,260 @@
+use crate::domain::{DiffLine, DiffOperation};
+use regex::{Regex, RegexBuilder};
+use std::ops::Range;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SearchScope {
+    #[default]
+    All,
+    Added,
+    Removed,
+}
+
+impl SearchScope {
+    pub(super) fn next(self) -> Self {
+        match self {
+            SearchScope::All => SearchScope::Added,
+            SearchScope::Added => SearchScope::Removed,
+            SearchScope::Removed => SearchScope::All,
+        }
+    }
+
+    fn includes(&self, line: &DiffLine) -> bool {
+        match self {
+            SearchScope::All => true,
+            SearchScope::Added => line.kind == DiffOperation::Insert,
+            SearchScope::Removed => line.kind == DiffOperation::Delete,
+        }
+    }
+}
+
+impl std::fmt::Display for SearchScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SearchScope::All => write!(f, "all lines"),
+            SearchScope::Added => write!(f, "added lines"),
+            SearchScope::Removed => write!(f, "removed lines"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchMatch {
+    // index of the line in the diff pane (before wrapping)
+    pub line_index: usize,
+    // byte range within the line's content
+    pub range: Range<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DiffSearch {
+    pub query: String,
+    pub scope: SearchScope,
+    pub matches: Vec<SearchMatch>,
+    pub current: Option<usize>,
+    regex: Regex,
+}
+
+impl DiffSearch {
+    // Queries are treated as regular expressions, and are case insensitive unless they contain an
+    // uppercase character
+    pub(super) fn new(query: &str, scope: SearchScope) -> Result<Self, regex::Error> {
+        let regex = RegexBuilder::new(query)
+            .case_insensitive(!query.chars().any(|c| c.is_uppercase()))
+            .build()?;
+
+        Ok(Self {
+            query: query.to_string(),
+            scope,
+            matches: vec![],
+            current: None,
+            regex,
+        })
+    }
+
+    // lines that are None represent separators between hunks, and are never matched against
+    pub(super) fn find_matches(&mut self, lines: &[Option<DiffLine>]) {
+        self.current = None;
+        self.matches = lines
+            .iter()
+            .enumerate()
+            .filter_map(|(i, line)| line.as_ref().map(|l| (i, l)))
+            .filter(|(_, line)| self.scope.includes(line))
+            .flat_map(|(line_index, line)| {
+                self.regex
+                    .find_iter(&line.content())
+                    .filter(|m| !m.is_empty())
+                    .map(|m| SearchMatch {
+                        line_index,
+                        range: m.range(),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+    }
+
+    pub(super) fn current_match(&self) -> Option<&SearchMatch> {
+        self.current.and_then(|i| self.matches.get(i))
+    }
+
+    // Moves to the first match at or after the given line, wrapping around to the start
+    pub(super) fn select_next(&mut self, from_line: usize) {
+        if self.matches.is_empty() {
+            return;
+        }
+
+        self.current = Some(match self.current {
+            Some(i) => (i + 1) % self.matches.len(),
+            None => self
+                .matches
+                .iter()
+                .position(|m| m.line_index >= from_line)
+                .unwrap_or_default(),
+        });
+    }
+
+    // Moves to the last match before the given line, wrapping around to the end
+    pub(super) fn select_previous(&mut self, from_line: usize) {
+        if self.matches.is_empty() {
+            return;
+        }
+
+        let last = self.matches.len() - 1;
+        self.current = Some(match self.current {
+            Some(0) => last,
+            Some(i) => i - 1,
+            None => self
+                .matches
+                .iter()
+                .rposition(|m| m.line_index < from_line)
+                .unwrap_or(last),
+        });
+    }
+
+    pub(super) fn matches_for_line(
+        &self,
+        line_index: usize,
+    ) -> impl Iterator<Item = (usize, &SearchMatch)> {
+        self.matches
+            .iter()
+            .enumerate()
+            .filter(move |(_, m)| m.line_index == line_index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Diff;
+
+    fn lines(old: &str, new: &str) -> Vec<Option<DiffLine>> {
+        Diff::new(old, new)
+            .expect("diff should've been created")
+            .full_file_lines()
+            .into_iter()
+            .map(Some)
+            .collect()
+    }
+
+    #[test]
+    fn finding_matches_works() {
+        // GIVEN
+        let lines = lines(
+            "let a = 1;\nlet b = 2;\nlet c = 3;\n",
+            "let a = 1;\nlet bb = 22;\nlet c = 3;\n",
+        );
+        let mut search = DiffSearch::new("b+", SearchScope::All).expect("regex is valid");
+
+        // WHEN
+        search.find_matches(&lines);
+
+        // THEN
+        assert_eq!(
+            search.matches,
+            vec![
+                SearchMatch {
+                    line_index: 1,
+                    range: 4..5,
+                },
+                SearchMatch {
+                    line_index: 2,
+                    range: 4..6,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn search_can_be_restricted_to_added_or_removed_lines() {
+        // GIVEN
+        let lines = lines(
+            "let a = 1;\nlet b = 2;\nlet c = 3;\n",
+            "let a = 1;\nlet bb = 22;\nlet c = 3;\n",
+        );
+
+        // WHEN
+        let mut added = DiffSearch::new("let", SearchScope::Added).expect("regex is valid");
+        added.find_matches(&lines);
+        let mut removed = DiffSearch::new("let", SearchScope::Removed).expect("regex is valid");
+        removed.find_matches(&lines);
+
+        // THEN
+        let added_lines = added
+            .matches
+            .iter()
+            .map(|m| m.line_index)
+            .collect::<Vec<_>>();
+        let removed_lines = removed
+            .matches
+            .iter()
+            .map(|m| m.line_index)
+            .collect::<Vec<_>>();
+        assert_eq!(added_lines, vec![2]);
+        assert_eq!(removed_lines, vec![1]);
+    }
+
+    #[test]
+    fn search_is_case_insensitive_unless_query_has_uppercase_characters() {
+        // GIVEN
+        let lines = lines("", "Hello\nhello\n");
+
+        // WHEN
+        let mut insensitive = DiffSearch::new("hello", SearchScope::All).expect("regex is valid");
+        insensitive.find_matches(&lines);
+        let mut sensitive = DiffSearch::new("Hello", SearchScope::All).expect("regex is valid");
+        sensitive.find_matches(&lines);
+
+        // THEN
+        assert_eq!(insensitive.matches.len(), 2);
+        assert_eq!(sensitive.matches.len(), 1);
+    }
+
+    #[test]
+    fn navigating_between_matches_wraps_around() {
+        // GIVEN
+        let lines = lines("", "match\nno\nmatch\nno\nmatch\n");
+        let mut search = DiffSearch::new("match", SearchScope::All).expect("regex is valid");
+        search.find_matches(&lines);
+
+        // WHEN
+        // THEN
+        search.select_next(1);
+        assert_eq!(search.current, Some(1));
+        search.select_next(1);
+        assert_eq!(search.current, Some(2));
+        search.select_next(1);
+        assert_eq!(search.current, Some(0));
+        search.select_previous(1);
+        assert_eq!(search.current, Some(2));
+    }
+
+    #[test]
+    fn invalid_regex_is_reported() {
+        // GIVEN
+        // WHEN
+        let result = DiffSearch::new("(unclosed", SearchScope::All);
+
+        // THEN
+        assert!(result.is_err());
+    }
+}
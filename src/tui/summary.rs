@@ -0,0 +1,195 @@
+use crate::domain::{Change, ChangeKind};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SummarySortKey {
+    #[default]
+    Churn,
+    Changes,
+    Path,
+}
+
+impl SummarySortKey {
+    pub(super) fn next(self) -> Self {
+        match self {
+            SummarySortKey::Churn => SummarySortKey::Changes,
+            SummarySortKey::Changes => SummarySortKey::Path,
+            SummarySortKey::Path => SummarySortKey::Churn,
+        }
+    }
+}
+
+impl std::fmt::Display for SummarySortKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SummarySortKey::Churn => write!(f, "churn"),
+            SummarySortKey::Changes => write!(f, "changes"),
+            SummarySortKey::Path => write!(f, "path"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileSummary {
+    pub path: String,
+    pub insertions: usize,
+    pub deletions: usize,
+    pub num_changes: usize,
+}
+
+impl FileSummary {
+    fn churn(&self) -> usize {
+        self.insertions + self.deletions
+    }
+}
+
+// Cumulative stats for every file touched during the session
+#[derive(Debug, Default)]
+pub struct SessionSummary {
+    files: Vec<FileSummary>,
+    index: HashMap<String, usize>,
+}
+
+impl SessionSummary {
+    pub(super) fn record(&mut self, change: &Change) {
+        if matches!(change.kind, ChangeKind::RemovedDir) {
+            return;
+        }
+
+        let idx = match self.index.get(&change.path) {
+            Some(&idx) => idx,
+            None => {
+                self.files.push(FileSummary {
+                    path: change.path.clone(),
+                    insertions: 0,
+                    deletions: 0,
+                    num_changes: 0,
+                });
+                self.index.insert(change.path.clone(), self.files.len() - 1);
+                self.files.len() - 1
+            }
+        };
+
+        let Some(file) = self.files.get_mut(idx) else {
+            return;
+        };
+
+        file.num_changes += 1;
+        if let Some(stats) = change.kind.stats() {
+            file.insertions += stats.insertions;
+            file.deletions += stats.deletions;
+        }
+    }
+
+    pub(super) fn sorted(&self, sort_key: SummarySortKey) -> Vec<&FileSummary> {
+        let mut files = self.files.iter().collect::<Vec<_>>();
+        match sort_key {
+            SummarySortKey::Churn => {
+                files.sort_by(|a, b| b.churn().cmp(&a.churn()).then_with(|| a.path.cmp(&b.path)))
+            }
+            SummarySortKey::Changes => files.sort_by(|a, b| {
+                b.num_changes
+                    .cmp(&a.num_changes)
+                    .then_with(|| a.path.cmp(&b.path))
+            }),
+            SummarySortKey::Path => files.sort_by(|a, b| a.path.cmp(&b.path)),
+        }
+
+        files
+    }
+
+    pub(super) fn num_files(&self) -> usize {
+        self.files.len()
+    }
+
+    pub(super) fn totals(&self) -> (usize, usize) {
+        self.files
+            .iter()
+            .fold((0, 0), |(insertions, deletions), f| {
+                (insertions + f.insertions, deletions + f.deletions)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Diff, Modification};
+
+    fn modification(path: &str, old: &str, new: &str) -> Change {
+        Change {
+            path: path.to_string(),
+            kind: ChangeKind::Modified(Ok(Modification::Diff(
+                Diff::new(old, new).expect("diff should've been created"),
+            ))),
+            encoding: None,
+        }
+    }
+
+    #[test]
+    fn recording_changes_accumulates_stats_per_file() {
+        // GIVEN
+        let mut summary = SessionSummary::default();
+
+        // WHEN
+        summary.record(&Change {
+            path: "a.txt".to_string(),
+            kind: ChangeKind::Created(Ok("line 1\nline 2\n".to_string())),
+            encoding: None,
+        });
+        summary.record(&modification("a.txt", "line 1\nline 2\n", "line 1\n"));
+        summary.record(&Change {
+            path: "a.txt".to_string(),
+            kind: ChangeKind::RemovedFile,
+            encoding: None,
+        });
+        summary.record(&Change {
+            path: "dir".to_string(),
+            kind: ChangeKind::RemovedDir,
+            encoding: None,
+        });
+
+        // THEN
+        assert_eq!(
+            summary.sorted(SummarySortKey::Path),
+            vec![&FileSummary {
+                path: "a.txt".to_string(),
+                insertions: 2,
+                deletions: 1,
+                num_changes: 3,
+            }]
+        );
+        assert_eq!(summary.totals(), (2, 1));
+    }
+
+    #[test]
+    fn sorting_summary_works() {
+        // GIVEN
+        let mut summary = SessionSummary::default();
+        summary.record(&modification("a.txt", "1\n", "2\n"));
+        summary.record(&modification("b.txt", "1\n2\n3\n4\n", "5\n6\n7\n8\n"));
+        summary.record(&modification("c.txt", "1\n", "2\n"));
+        summary.record(&modification("c.txt", "2\n", "3\n"));
+        summary.record(&modification("c.txt", "3\n", "4\n"));
+
+        // WHEN
+        let paths = |key| {
+            summary
+                .sorted(key)
+                .into_iter()
+                .map(|f| f.path.as_str())
+                .collect::<Vec<_>>()
+        };
+
+        // THEN
+        assert_eq!(
+            paths(SummarySortKey::Churn),
+            vec!["b.txt", "c.txt", "a.txt"]
+        );
+        assert_eq!(
+            paths(SummarySortKey::Changes),
+            vec!["c.txt", "a.txt", "b.txt"]
+        );
+        assert_eq!(paths(SummarySortKey::Path), vec!["a.txt", "b.txt", "c.txt"]);
+    }
+}
@@ -3,7 +3,10 @@ use super::super::common::Pane;
 use super::super::model::Model;
 use super::super::{msg::Msg, update::update, view::view};
 use super::helpers::get_test_terminal;
-use crate::domain::{Change, ChangeKind};
+use crate::domain::{
+    Change, ChangeKind, Diff, EncodingChange, Fingerprint, FingerprintChange, Modification,
+    TextEncoding,
+};
 use insta::assert_snapshot;
 use std::path::PathBuf;
 
@@ -13,7 +16,7 @@ fn selecting_first_change_from_an_empty_changes_list_doesnt_crash() {
     let (mut terminal, terminal_dimensions) = get_test_terminal();
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -59,7 +62,7 @@ fn selecting_last_change_from_an_empty_changes_list_doesnt_crash() {
     let (mut terminal, terminal_dimensions) = get_test_terminal();
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -105,7 +108,7 @@ fn selecting_first_change_from_changes_pane_works() {
     let (mut terminal, terminal_dimensions) = get_test_terminal();
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -116,6 +119,7 @@ fn selecting_first_change_from_changes_pane_works() {
         let change = Change {
             path: format!("file-{i}.txt"),
             kind: ChangeKind::Created(Ok(format!("file {i} contents"))),
+            encoding: None,
         };
         update(&mut model, Msg::ChangeReceived(change));
     }
@@ -140,17 +144,17 @@ fn selecting_first_change_from_changes_pane_works() {
     "└──────────────────────────────────────────────────────────────────────────────┘"
     "┌ changes  (5/20) ─────────────────────────────────────────────────────────────┐"
     "│                                                                              │"
-    "│   created   file-1.txt                                                       │"
-    "│   created   file-2.txt                                                       │"
-    "│   created   file-3.txt                                                       │"
-    "│   created   file-4.txt                                                       │"
-    "│>  created   file-5.txt                                                       │"
-    "│   created   file-6.txt                                                       │"
-    "│   created   file-7.txt                                                       │"
-    "│   created   file-8.txt                                                       │"
-    "│   created   file-9.txt                                                       │"
+    "│   created   file-1.txt  +1                                                   │"
+    "│   created   file-2.txt  +1                                                   │"
+    "│   created   file-3.txt  +1                                                   │"
+    "│   created   file-4.txt  +1                                                   │"
+    "│>  created   file-5.txt  +1                                                   │"
+    "│   created   file-6.txt  +1                                                   │"
+    "│   created   file-7.txt  +1                                                   │"
+    "│   created   file-8.txt  +1                                                   │"
+    "│   created   file-9.txt  +1                                                   │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching]                                                               "
+    " dfft  [watching] [20 unreviewed]                                               "
     "#);
 
     // WHEN
@@ -175,17 +179,17 @@ fn selecting_first_change_from_changes_pane_works() {
     "└──────────────────────────────────────────────────────────────────────────────┘"
     "┌ changes  (1/20) ─────────────────────────────────────────────────────────────┐"
     "│                                                                              │"
-    "│>  created   file-1.txt                                                       │"
-    "│   created   file-2.txt                                                       │"
-    "│   created   file-3.txt                                                       │"
-    "│   created   file-4.txt                                                       │"
-    "│   created   file-5.txt                                                       │"
-    "│   created   file-6.txt                                                       │"
-    "│   created   file-7.txt                                                       │"
-    "│   created   file-8.txt                                                       │"
-    "│   created   file-9.txt                                                       │"
+    "│>  created   file-1.txt  +1                                                   │"
+    "│   created   file-2.txt  +1                                                   │"
+    "│   created   file-3.txt  +1                                                   │"
+    "│   created   file-4.txt  +1                                                   │"
+    "│   created   file-5.txt  +1                                                   │"
+    "│   created   file-6.txt  +1                                                   │"
+    "│   created   file-7.txt  +1                                                   │"
+    "│   created   file-8.txt  +1                                                   │"
+    "│   created   file-9.txt  +1                                                   │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching]                                                               "
+    " dfft  [watching] [20 unreviewed]                                               "
     "#);
 }
 
@@ -195,7 +199,7 @@ fn selecting_last_change_from_changes_pane_works() {
     let (mut terminal, terminal_dimensions) = get_test_terminal();
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -206,6 +210,7 @@ fn selecting_last_change_from_changes_pane_works() {
         let change = Change {
             path: format!("file-{i}.txt"),
             kind: ChangeKind::Created(Ok(format!("file {i} contents"))),
+            encoding: None,
         };
         update(&mut model, Msg::ChangeReceived(change));
     }
@@ -232,16 +237,520 @@ fn selecting_last_change_from_changes_pane_works() {
     "└──────────────────────────────────────────────────────────────────────────────┘"
     "┌ changes  (20/20) ────────────────────────────────────────────────────────────┐"
     "│                                                                              │"
-    "│   created   file-12.txt                                                      │"
-    "│   created   file-13.txt                                                      │"
-    "│   created   file-14.txt                                                      │"
-    "│   created   file-15.txt                                                      │"
-    "│   created   file-16.txt                                                      │"
-    "│   created   file-17.txt                                                      │"
-    "│   created   file-18.txt                                                      │"
-    "│   created   file-19.txt                                                      │"
-    "│>  created   file-20.txt                                                      │"
+    "│   created   file-12.txt  +1                                                  │"
+    "│   created   file-13.txt  +1                                                  │"
+    "│   created   file-14.txt  +1                                                  │"
+    "│   created   file-15.txt  +1                                                  │"
+    "│   created   file-16.txt  +1                                                  │"
+    "│   created   file-17.txt  +1                                                  │"
+    "│   created   file-18.txt  +1                                                  │"
+    "│   created   file-19.txt  +1                                                  │"
+    "│>  created   file-20.txt  +1                                                  │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching]                                                               "
+    " dfft  [watching] [20 unreviewed]                                               "
+    "#);
+}
+
+#[test]
+fn binary_changes_show_size_and_hash() {
+    // GIVEN
+    let (mut terminal, terminal_dimensions) = get_test_terminal();
+    let mut model = Model::new(
+        TuiBehaviours::default_for_test(),
+        vec![PathBuf::new()],
+        terminal_dimensions,
+        false,
+    );
+    let change = Change {
+        path: "assets/logo.png".to_string(),
+        kind: ChangeKind::Binary(FingerprintChange {
+            before: Some(Fingerprint {
+                size: 2048,
+                hash: 0x1a2b3c4d5e6f7a8b,
+            }),
+            after: Fingerprint {
+                size: 3072,
+                hash: 0x9c8d7e6f5a4b3c2d,
+            },
+        }),
+        encoding: None,
+    };
+    let created = Change {
+        path: "dist/app.wasm".to_string(),
+        kind: ChangeKind::Binary(FingerprintChange {
+            before: None,
+            after: Fingerprint {
+                size: 512,
+                hash: 0x0123456789abcdef,
+            },
+        }),
+        encoding: None,
+    };
+
+    // WHEN
+    update(&mut model, Msg::ChangeReceived(change));
+    update(&mut model, Msg::ChangeReceived(created));
+    update(&mut model, Msg::SelectFirst);
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff  assets/logo.png ───────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│ binary file; contents aren't shown                                           │"
+    "│                                                                              │"
+    "│ before: 2048 bytes (hash 1a2b3c4d)                                           │"
+    "│ after:  3072 bytes (hash 9c8d7e6f)                                           │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes  (1/2) ──────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│>   binary   assets/logo.png                                                  │"
+    "│    binary   dist/app.wasm                                                    │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [2 unreviewed]                                                "
+    "#);
+}
+
+#[test]
+fn changes_to_files_above_the_hard_ceiling_only_show_sizes() {
+    // GIVEN
+    let (mut terminal, terminal_dimensions) = get_test_terminal();
+    let mut model = Model::new(
+        TuiBehaviours::default_for_test(),
+        vec![PathBuf::new()],
+        terminal_dimensions,
+        false,
+    );
+    let change = Change {
+        path: "dumps/prod.sql".to_string(),
+        kind: ChangeKind::TooLarge(FingerprintChange {
+            before: Some(Fingerprint {
+                size: 20_000_000,
+                hash: 0x1a2b3c4d5e6f7a8b,
+            }),
+            after: Fingerprint {
+                size: 20_000_512,
+                hash: 0x9c8d7e6f5a4b3c2d,
+            },
+        }),
+        encoding: None,
+    };
+
+    // WHEN
+    update(&mut model, Msg::ChangeReceived(change));
+    update(&mut model, Msg::SelectFirst);
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff  dumps/prod.sql ────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│ file is too large to be diffed                                               │"
+    "│                                                                              │"
+    "│ changed, 20000000 bytes → 20000512 bytes                                     │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes  (1/1) ──────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│>  too large dumps/prod.sql                                                   │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [1 unreviewed]                                                "
+    "#);
+}
+
+#[test]
+fn large_file_diffs_are_labelled_and_truncated() {
+    // GIVEN
+    let (mut terminal, terminal_dimensions) = get_test_terminal();
+    let mut model = Model::new(
+        TuiBehaviours::default_for_test(),
+        vec![PathBuf::new()],
+        terminal_dimensions,
+        false,
+    );
+    let old = "-- dump\n".to_string();
+    let new = format!(
+        "{old}{}",
+        (1..=6000)
+            .map(|i| format!("INSERT INTO t VALUES ({i});\n"))
+            .collect::<String>()
+    );
+    let diff = Diff::new_for_large_file(&old, new.into()).expect("diff should've been computed");
+    let change = Change {
+        path: "dumps/prod.sql".to_string(),
+        kind: ChangeKind::Modified(Ok(Modification::Diff(diff))),
+        encoding: None,
+    };
+
+    // WHEN
+    update(&mut model, Msg::ChangeReceived(change));
+    update(&mut model, Msg::SelectFirst);
+    update(&mut model, Msg::GoToPane(Pane::Diff));
+    for _ in 0..2000 {
+        update(&mut model, Msg::ScrollHalfPageDown);
+    }
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff  dumps/prod.sql [hunk 1/1] [large file] ────────────────────────────────┐"
+    "│                                                                              │"
+    "│       4994  |+INSERT INTO t VALUES (4993);                                   │"
+    "│       4995  |+INSERT INTO t VALUES (4994);                                   │"
+    "│       4996  |+INSERT INTO t VALUES (4995);                                   │"
+    "│       4997  |+INSERT INTO t VALUES (4996);                                   │"
+    "│       4998  |+INSERT INTO t VALUES (4997);                                   │"
+    "│       4999  |+INSERT INTO t VALUES (4998);                                   │"
+    "│       5000  |+INSERT INTO t VALUES (4999);                                   │"
+    "│ ... 1001 more lines not shown (only the first 5000 are)                      │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes  (1/1) ──────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│>  modified  dumps/prod.sql  +6000 -0                                         │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [1 unreviewed]                                                "
+    "#);
+}
+
+fn log_append(old: &str, new: &str) -> Change {
+    let diff = Diff::new_for(old, new.into(), false).expect("diff should've been computed");
+    Change {
+        path: "logs/agent.log".to_string(),
+        kind: ChangeKind::Modified(Ok(Modification::Diff(diff))),
+        encoding: None,
+    }
+}
+
+#[test]
+fn appends_are_listed_separately_by_default() {
+    // GIVEN
+    let (mut terminal, terminal_dimensions) = get_test_terminal();
+    let mut model = Model::new(
+        TuiBehaviours::default_for_test(),
+        vec![PathBuf::new()],
+        terminal_dimensions,
+        false,
+    );
+
+    // WHEN
+    update(
+        &mut model,
+        Msg::ChangeReceived(log_append("started\n", "started\nstep 1\n")),
+    );
+    update(
+        &mut model,
+        Msg::ChangeReceived(log_append(
+            "started\nstep 1\n",
+            "started\nstep 1\nstep 2\nstep 3\n",
+        )),
+    );
+    update(&mut model, Msg::SelectLast);
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff  logs/agent.log [hunk 1/1] ─────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│ 1   1   | started                                                            │"
+    "│ 2   2   | step 1                                                             │"
+    "│     3   |+step 2                                                             │"
+    "│     4   |+step 3                                                             │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes  (2/2) ──────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│   appended  logs/agent.log  +1 -0                                            │"
+    "│>  appended  logs/agent.log  +2 -0                                            │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [2 unreviewed]                                                "
+    "#);
+}
+
+#[test]
+fn repeated_appends_are_collapsed_into_one_change_when_enabled() {
+    // GIVEN
+    let (mut terminal, terminal_dimensions) = get_test_terminal();
+    let mut behaviours = TuiBehaviours::default_for_test();
+    behaviours.collapse_appends = true;
+    let mut model = Model::new(behaviours, vec![PathBuf::new()], terminal_dimensions, false);
+    let other = Change {
+        path: "src/main.rs".to_string(),
+        kind: ChangeKind::Created(Ok("fn main() {}\n".to_string())),
+        encoding: None,
+    };
+
+    // WHEN
+    update(
+        &mut model,
+        Msg::ChangeReceived(log_append("started\n", "started\nstep 1\n")),
+    );
+    update(&mut model, Msg::ChangeReceived(other));
+    update(
+        &mut model,
+        Msg::ChangeReceived(log_append(
+            "started\nstep 1\n",
+            "started\nstep 1\nstep 2\nstep 3\n",
+        )),
+    );
+    update(&mut model, Msg::SelectFirst);
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff  logs/agent.log [hunk 1/1] ─────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│ 1   1   | started                                                            │"
+    "│     2   |+step 1                                                             │"
+    "│     3   |+step 2                                                             │"
+    "│     4   |+step 3                                                             │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes  (1/2) ──────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│>  appended  logs/agent.log  +3 -0                                            │"
+    "│   created   src/main.rs  +1                                                  │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [2 unreviewed]                                                "
+    "#);
+}
+
+#[test]
+fn changes_other_than_appends_arent_collapsed() {
+    // GIVEN
+    let (mut terminal, terminal_dimensions) = get_test_terminal();
+    let mut behaviours = TuiBehaviours::default_for_test();
+    behaviours.collapse_appends = true;
+    let mut model = Model::new(behaviours, vec![PathBuf::new()], terminal_dimensions, false);
+
+    // WHEN
+    update(
+        &mut model,
+        Msg::ChangeReceived(log_append("started\n", "started\nstep 1\n")),
+    );
+    update(
+        &mut model,
+        Msg::ChangeReceived(log_append("started\nstep 1\n", "restarted\n")),
+    );
+    update(
+        &mut model,
+        Msg::ChangeReceived(log_append("restarted\n", "restarted\nstep 1\n")),
+    );
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff  logs/agent.log [hunk 1/1] ─────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│ 1   1   | started                                                            │"
+    "│     2   |+step 1                                                             │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes  (1/3) ──────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│>  appended  logs/agent.log  +1 -0                                            │"
+    "│   modified  logs/agent.log  +1 -2                                            │"
+    "│   appended  logs/agent.log  +1 -0                                            │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [3 unreviewed]                                                "
+    "#);
+}
+
+#[test]
+fn encoding_changes_are_listed_separately_and_shown_in_the_title() {
+    // GIVEN
+    let (mut terminal, terminal_dimensions) = get_test_terminal();
+    let mut model = Model::new(
+        TuiBehaviours::default_for_test(),
+        vec![PathBuf::new()],
+        terminal_dimensions,
+        false,
+    );
+    let utf16 = TextEncoding {
+        encoding: encoding_rs::UTF_16LE,
+        bom: true,
+    };
+    let snapshot = Change {
+        path: "notes.txt".to_string(),
+        kind: ChangeKind::Modified(Ok(Modification::InitialSnapshot)),
+        encoding: Some(TextEncoding::UTF8),
+    };
+    let reencoded = Change {
+        path: "notes.txt".to_string(),
+        kind: ChangeKind::Reencoded(EncodingChange {
+            before: TextEncoding::UTF8,
+            after: utf16,
+        }),
+        encoding: Some(utf16),
+    };
+
+    // WHEN
+    update(&mut model, Msg::ChangeReceived(snapshot));
+    update(&mut model, Msg::ChangeReceived(reencoded));
+    update(&mut model, Msg::SelectLast);
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff  notes.txt [UTF-16LE with BOM] ─────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│ encoding changed from UTF-8 to UTF-16LE with BOM                             │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes  (2/2) ──────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│   modified  notes.txt                                                        │"
+    "│>  encoding  notes.txt                                                        │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [2 unreviewed]                                                "
+    "#);
+}
+
+#[test]
+fn changes_since_the_last_session_are_marked() {
+    // GIVEN
+    let (mut terminal, terminal_dimensions) = get_test_terminal();
+    let mut model = Model::new(
+        TuiBehaviours::default_for_test(),
+        vec![PathBuf::new()],
+        terminal_dimensions,
+        false,
+    );
+    let offline = Change {
+        path: "src/main.rs".to_string(),
+        kind: ChangeKind::Modified(Ok(Modification::Diff(
+            Diff::new("fn main() {}\n", "fn main() {\n    run();\n}\n")
+                .expect("diff should've been computed"),
+        ))),
+        encoding: Some(TextEncoding::UTF8),
+    };
+    let live = Change {
+        path: "src/lib.rs".to_string(),
+        kind: ChangeKind::Created(Ok("pub fn run() {}\n".to_string())),
+        encoding: Some(TextEncoding::UTF8),
+    };
+
+    // WHEN
+    update(&mut model, Msg::ChangedSinceLastSession(offline));
+    update(&mut model, Msg::ChangeReceived(live));
+    update(&mut model, Msg::SelectFirst);
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff  src/main.rs [hunk 1/1] [changed since last session] ───────────────────┐"
+    "│                                                                              │"
+    "│ 1       |-fn main() {}                                                       │"
+    "│     1   |+fn main() {                                                        │"
+    "│     2   |+    run();                                                         │"
+    "│     3   |+}                                                                  │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes  (1/2) ──────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│>  modified  src/main.rs  +3 -1  (last session)                               │"
+    "│   created   src/lib.rs  +1                                                   │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [2 unreviewed]                                                "
     "#);
 }
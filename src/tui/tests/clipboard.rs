@@ -0,0 +1,135 @@
+use super::super::TuiBehaviours;
+use super::super::cmd::Cmd;
+use super::super::common::Pane;
+use super::super::model::Model;
+use super::super::{msg::Msg, update::update};
+use super::helpers::{get_model_with_changes, modification};
+use crate::domain::{Change, ChangeKind};
+use insta::assert_snapshot;
+
+fn get_model_with_diff_in_focus(change: Change) -> Model {
+    let mut model = get_model_with_changes(TuiBehaviours::default_for_test(), vec![change]);
+    model.active_pane = Pane::Diff;
+
+    model
+}
+
+fn get_model_with_change(kind: ChangeKind) -> Model {
+    get_model_with_diff_in_focus(Change {
+        path: "src/main.rs".to_string(),
+        kind,
+        encoding: None,
+    })
+}
+
+fn get_model_with_modification() -> Model {
+    let old = (1..=12).map(|i| format!("line {i}\n")).collect::<String>();
+    let new = old
+        .replace("line 2\n", "line 2 (changed)\n")
+        .replace("line 11\n", "");
+
+    get_model_with_diff_in_focus(modification("src/main.rs", &old, &new))
+}
+
+fn copied(cmds: &[Cmd]) -> &str {
+    let [Cmd::CopyToClipboard { contents }] = cmds else {
+        panic!("contents should've been copied");
+    };
+
+    contents
+}
+
+fn user_msg(model: &Model) -> Option<&str> {
+    model.user_msg.as_ref().map(|m| m.value.as_str())
+}
+
+#[test]
+fn copying_path_works() {
+    // GIVEN
+    let mut model = get_model_with_modification();
+
+    // WHEN
+    let cmds = update(&mut model, Msg::CopyPath);
+
+    // THEN
+    assert_eq!(copied(&cmds), "src/main.rs");
+    assert_eq!(user_msg(&model), Some("copied path: src/main.rs"));
+}
+
+#[test]
+fn copying_diff_copies_it_in_unified_format() {
+    // GIVEN
+    let mut model = get_model_with_modification();
+
+    // WHEN
+    let cmds = update(&mut model, Msg::CopyDiff);
+
+    // THEN
+    assert_snapshot!(copied(&cmds), @"
+    --- a/src/main.rs
+    +++ b/src/main.rs
+    @@ -1,5 +1,5 @@
+     line 1
+    -line 2
+    +line 2 (changed)
+     line 3
+     line 4
+     line 5
+    @@ -8,5 +8,4 @@
+     line 8
+     line 9
+     line 10
+    -line 11
+     line 12
+    ");
+    assert_eq!(user_msg(&model), Some("copied diff"));
+}
+
+#[test]
+fn copying_diff_of_created_file_copies_its_contents() {
+    // GIVEN
+    let mut model = get_model_with_change(ChangeKind::Created(Ok("fn main() {}\n".to_string())));
+
+    // WHEN
+    let cmds = update(&mut model, Msg::CopyDiff);
+
+    // THEN
+    assert_eq!(copied(&cmds), "fn main() {}\n");
+    assert_eq!(user_msg(&model), Some("copied file contents"));
+}
+
+#[test]
+fn copying_hunk_copies_the_one_in_focus() {
+    // GIVEN
+    let mut model = get_model_with_modification();
+    update(&mut model, Msg::GoToNextHunk);
+
+    // WHEN
+    let cmds = update(&mut model, Msg::CopyHunk);
+
+    // THEN
+    assert_snapshot!(copied(&cmds), @"
+    @@ -8,5 +8,4 @@
+     line 8
+     line 9
+     line 10
+    -line 11
+     line 12
+    ");
+    assert_eq!(user_msg(&model), Some("copied hunk 2/2"));
+}
+
+#[test]
+fn removed_files_have_nothing_to_copy() {
+    // GIVEN
+    let mut model = get_model_with_change(ChangeKind::RemovedFile);
+
+    // WHEN
+    let diff_cmds = update(&mut model, Msg::CopyDiff);
+    let hunk_cmds = update(&mut model, Msg::CopyHunk);
+
+    // THEN
+    assert!(diff_cmds.is_empty());
+    assert!(hunk_cmds.is_empty());
+    assert_eq!(user_msg(&model), Some("this change has no hunks"));
+}
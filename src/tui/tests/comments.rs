@@ -0,0 +1,289 @@
+use super::super::TuiBehaviours;
+use super::super::cmd::Cmd;
+use super::super::common::Pane;
+use super::super::model::Model;
+use super::super::{msg::Msg, update::update, view::view};
+use super::helpers::{get_model_with_changes, get_test_terminal, modification};
+use insta::assert_snapshot;
+
+fn get_model_with_file_changes() -> Model {
+    let changes = vec![
+        modification("src/main.rs", "a\nb\nc\n", "a\nB\nc\n"),
+        modification("src/lib.rs", "x\n", "y\n"),
+    ];
+
+    get_model_with_changes(TuiBehaviours::default_for_test(), changes)
+}
+
+fn add_comment(model: &mut Model, text: &str, on_hunk: bool) {
+    update(model, Msg::StartComment);
+    if on_hunk {
+        update(model, Msg::CyclePromptOption);
+    }
+    for c in text.chars() {
+        update(model, Msg::EnterPromptInput(c));
+    }
+    update(model, Msg::SubmitPrompt);
+}
+
+#[test]
+fn comments_are_shown_after_the_commented_line() {
+    // GIVEN
+    let (mut terminal, _) = get_test_terminal();
+    let mut model = get_model_with_file_changes();
+    update(&mut model, Msg::GoToNextChangedLine);
+
+    // WHEN
+    add_comment(&mut model, "keep this lowercase", false);
+    add_comment(&mut model, "revert this hunk", true);
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff  src/main.rs [hunk 1/1] ────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│ 1   1   | a                                                                  │"
+    "│ 2       |-b                                                                  │"
+    "│           » keep this lowercase                                              │"
+    "│     2   |+B                                                                  │"
+    "│ 3   3   | c                                                                  │"
+    "│           » revert this hunk                                                 │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes  (1/2) ──────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│>  modified  src/main.rs  +1 -1                                               │"
+    "│   modified  src/lib.rs  +1 -1                                                │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [2 unreviewed]                                                "
+    "#);
+}
+
+#[test]
+fn comment_prompt_shows_what_is_being_commented_on() {
+    // GIVEN
+    let (mut terminal, _) = get_test_terminal();
+    let mut model = get_model_with_file_changes();
+    update(&mut model, Msg::GoToNextChangedLine);
+    update(&mut model, Msg::GoToNextChangedLine);
+
+    // WHEN
+    update(&mut model, Msg::StartComment);
+    update(&mut model, Msg::EnterPromptInput('o'));
+    update(&mut model, Msg::EnterPromptInput('k'));
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+    let line_prompt = terminal.backend().to_string();
+    update(&mut model, Msg::CyclePromptOption);
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert!(line_prompt.contains(" comment  [line: src/main.rs:2] ok"));
+    assert!(
+        terminal
+            .backend()
+            .to_string()
+            .contains(" comment  [hunk: src/main.rs:1-3] ok")
+    );
+}
+
+#[test]
+fn empty_comments_are_discarded() {
+    // GIVEN
+    let mut model = get_model_with_file_changes();
+
+    // WHEN
+    add_comment(&mut model, "  ", false);
+
+    // THEN
+    assert!(model.comments.is_empty());
+    assert!(model.prompt.is_none());
+}
+
+#[test]
+fn comments_pane_lists_all_comments() {
+    // GIVEN
+    let (mut terminal, _) = get_test_terminal();
+    let mut model = get_model_with_file_changes();
+    update(&mut model, Msg::GoToNextChangedLine);
+    add_comment(&mut model, "keep this lowercase", false);
+    update(&mut model, Msg::SelectNext);
+    add_comment(&mut model, "why y?", true);
+
+    // WHEN
+    update(&mut model, Msg::GoToPane(Pane::Comments));
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ comments  (1/2) ─────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│> src/main.rs:2 (removed) [line]  keep this lowercase                         │"
+    "│  src/lib.rs:1 [hunk]  why y?                                                 │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [2 unreviewed]                                                "
+    "#);
+}
+
+#[test]
+fn deleting_comments_works() {
+    // GIVEN
+    let mut model = get_model_with_file_changes();
+    add_comment(&mut model, "first", false);
+    add_comment(&mut model, "second", false);
+    update(&mut model, Msg::GoToPane(Pane::Comments));
+    update(&mut model, Msg::SelectNext);
+
+    // WHEN
+    update(&mut model, Msg::DeleteComment);
+
+    // THEN
+    let texts = model
+        .comments
+        .iter()
+        .map(|c| c.text.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(texts, vec!["first"]);
+    assert_eq!(model.comments_state.selected(), Some(0));
+}
+
+#[test]
+fn going_to_a_comment_selects_the_commented_change() {
+    // GIVEN
+    let mut model = get_model_with_file_changes();
+    add_comment(&mut model, "looks off", false);
+    update(&mut model, Msg::SelectNext);
+    update(&mut model, Msg::GoToPane(Pane::Comments));
+
+    // WHEN
+    update(&mut model, Msg::GoToComment);
+
+    // THEN
+    assert_eq!(model.active_pane, Pane::Diff);
+    assert_eq!(model.current_file_path(), Some("src/main.rs"));
+}
+
+#[test]
+fn comments_are_kept_when_the_list_is_reset() {
+    // GIVEN
+    let mut model = get_model_with_file_changes();
+    update(&mut model, Msg::GoToNextChangedLine);
+    add_comment(&mut model, "keep this lowercase", false);
+
+    // WHEN
+    update(&mut model, Msg::ResetList);
+    update(&mut model, Msg::GoToPane(Pane::Comments));
+    update(&mut model, Msg::GoToComment);
+    let cmds = update(&mut model, Msg::ExportComments);
+
+    // THEN
+    assert!(model.changes.items.is_empty());
+    assert_eq!(model.comments.len(), 1);
+    assert_eq!(
+        model.user_msg.as_ref().map(|m| m.value.as_str()),
+        Some("the commented change was cleared from the list")
+    );
+    let [Cmd::ExportComments { contents }] = cmds.as_slice() else {
+        panic!("comments should've been exported");
+    };
+    assert!(contents.contains("keep this lowercase"));
+}
+
+#[test]
+fn exporting_comments_writes_them_as_markdown() {
+    // GIVEN
+    let mut model = get_model_with_file_changes();
+    update(&mut model, Msg::GoToNextChangedLine);
+    add_comment(&mut model, "keep this lowercase", false);
+    update(&mut model, Msg::GoToPane(Pane::Comments));
+
+    // WHEN
+    let cmds = update(&mut model, Msg::ExportComments);
+
+    // THEN
+    let [Cmd::ExportComments { contents }] = cmds.as_slice() else {
+        panic!("comments should've been exported");
+    };
+    assert_snapshot!(contents, @"
+    # Review comments
+
+    ## src/main.rs:2 (removed)
+
+    keep this lowercase
+
+    ```diff
+    -b
+    ```
+    ");
+}
+
+#[test]
+fn comments_on_a_file_in_grouped_view_are_not_allowed() {
+    // GIVEN
+    let mut model = get_model_with_file_changes();
+    update(&mut model, Msg::ToggleGroupedView);
+
+    // WHEN
+    update(&mut model, Msg::StartComment);
+
+    // THEN
+    assert!(model.prompt.is_none());
+    assert_eq!(
+        model.user_msg.as_ref().map(|m| m.value.as_str()),
+        Some("expand the file to comment on one of its changes")
+    );
+}
+
+#[test]
+fn scroll_limits_account_for_comments() {
+    // GIVEN
+    let mut model = get_model_with_file_changes();
+    let old = (1..=30).map(|i| format!("line {i}\n")).collect::<String>();
+    let new = (1..=30).map(|i| format!("LINE {i}\n")).collect::<String>();
+    update(
+        &mut model,
+        Msg::ChangeReceived(modification("src/big.rs", &old, &new)),
+    );
+    update(&mut model, Msg::SelectLast);
+    let max_scroll_before = model.max_diff_scroll_available;
+
+    // WHEN
+    add_comment(&mut model, "first", false);
+    add_comment(&mut model, "second", true);
+
+    // THEN
+    assert_eq!(model.max_diff_scroll_available, max_scroll_before + 2);
+}
@@ -13,7 +13,7 @@ fn diff_pane_doesnt_scroll_beyond_limits() {
     let (mut terminal, terminal_dimensions) = get_test_terminal();
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -33,6 +33,7 @@ fn diff_pane_doesnt_scroll_beyond_limits() {
     let change = Change {
         path: "modified_file.txt".to_string(),
         kind: ChangeKind::Modified(Ok(Modification::Diff(diff))),
+        encoding: None,
     };
     update(&mut model, Msg::ChangeReceived(change));
 
@@ -45,7 +46,7 @@ fn diff_pane_doesnt_scroll_beyond_limits() {
         .draw(|f| view(&mut model, f))
         .expect("frame should've been drawn");
     assert_snapshot!(terminal.backend(), @r#"
-    "┌ diff  modified_file.txt ─────────────────────────────────────────────────────┐"
+    "┌ diff  modified_file.txt [hunk 1/2] ──────────────────────────────────────────┐"
     "│                                                                              │"
     "│ 1   1   | line 1                                                             │"
     "│ 2   2   | line 2                                                             │"
@@ -58,7 +59,7 @@ fn diff_pane_doesnt_scroll_beyond_limits() {
     "└──────────────────────────────────────────────────────────────────────────────┘"
     "┌ changes  (1/1) ──────────────────────────────────────────────────────────────┐"
     "│                                                                              │"
-    "│>  modified  modified_file.txt                                                │"
+    "│>  modified  modified_file.txt  +4 -4                                         │"
     "│                                                                              │"
     "│                                                                              │"
     "│                                                                              │"
@@ -68,7 +69,7 @@ fn diff_pane_doesnt_scroll_beyond_limits() {
     "│                                                                              │"
     "│                                                                              │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching]                                                               "
+    " dfft  [watching] [1 unreviewed]                                                "
     "#);
 
     for _ in 1..=20 {
@@ -78,7 +79,7 @@ fn diff_pane_doesnt_scroll_beyond_limits() {
         .draw(|f| view(&mut model, f))
         .expect("frame should've been drawn");
     assert_snapshot!(terminal.backend(), @r#"
-    "┌ diff  modified_file.txt ─────────────────────────────────────────────────────┐"
+    "┌ diff  modified_file.txt [hunk 2/2] ──────────────────────────────────────────┐"
     "│                                                                              │"
     "│ 13  13  | line 13                                                            │"
     "│ 14      |-line 14                                                            │"
@@ -91,7 +92,7 @@ fn diff_pane_doesnt_scroll_beyond_limits() {
     "└──────────────────────────────────────────────────────────────────────────────┘"
     "┌ changes  (1/1) ──────────────────────────────────────────────────────────────┐"
     "│                                                                              │"
-    "│>  modified  modified_file.txt                                                │"
+    "│>  modified  modified_file.txt  +4 -4                                         │"
     "│                                                                              │"
     "│                                                                              │"
     "│                                                                              │"
@@ -101,7 +102,7 @@ fn diff_pane_doesnt_scroll_beyond_limits() {
     "│                                                                              │"
     "│                                                                              │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching]                                                               "
+    " dfft  [watching] [1 unreviewed]                                                "
     "#);
 }
 
@@ -111,7 +112,7 @@ fn main_view_renders_modified_file_with_diff() {
     let (mut terminal, terminal_dimensions) = get_test_terminal();
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -133,6 +134,7 @@ line 2
     let change = Change {
         path: "modified_file.txt".to_string(),
         kind: ChangeKind::Modified(Ok(Modification::Diff(diff))),
+        encoding: None,
     };
     update(&mut model, Msg::ChangeReceived(change));
 
@@ -143,7 +145,7 @@ line 2
 
     // THEN
     assert_snapshot!(terminal.backend(), @r#"
-    "┌ diff  modified_file.txt ─────────────────────────────────────────────────────┐"
+    "┌ diff  modified_file.txt [hunk 1/1] ──────────────────────────────────────────┐"
     "│                                                                              │"
     "│ 1   1   |                                                                    │"
     "│ 2       |-line 1                                                             │"
@@ -156,7 +158,7 @@ line 2
     "└──────────────────────────────────────────────────────────────────────────────┘"
     "┌ changes  (1/1) ──────────────────────────────────────────────────────────────┐"
     "│                                                                              │"
-    "│>  modified  modified_file.txt                                                │"
+    "│>  modified  modified_file.txt  +3 -2                                         │"
     "│                                                                              │"
     "│                                                                              │"
     "│                                                                              │"
@@ -166,7 +168,7 @@ line 2
     "│                                                                              │"
     "│                                                                              │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching]                                                               "
+    " dfft  [watching] [1 unreviewed]                                                "
     "#);
 }
 
@@ -176,7 +178,7 @@ fn selecting_first_change_from_diff_pane_works() {
     let (mut terminal, terminal_dimensions) = get_test_terminal();
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -186,6 +188,7 @@ fn selecting_first_change_from_diff_pane_works() {
         let change = Change {
             path: format!("file-{i}.txt"),
             kind: ChangeKind::Created(Ok(format!("file {i} contents"))),
+            encoding: None,
         };
         update(&mut model, Msg::ChangeReceived(change));
     }
@@ -210,17 +213,17 @@ fn selecting_first_change_from_diff_pane_works() {
     "└──────────────────────────────────────────────────────────────────────────────┘"
     "┌ changes  (5/20) ─────────────────────────────────────────────────────────────┐"
     "│                                                                              │"
-    "│   created   file-1.txt                                                       │"
-    "│   created   file-2.txt                                                       │"
-    "│   created   file-3.txt                                                       │"
-    "│   created   file-4.txt                                                       │"
-    "│>  created   file-5.txt                                                       │"
-    "│   created   file-6.txt                                                       │"
-    "│   created   file-7.txt                                                       │"
-    "│   created   file-8.txt                                                       │"
-    "│   created   file-9.txt                                                       │"
-    "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching]                                                               "
+    "│   created   file-1.txt  +1                                                   │"
+    "│   created   file-2.txt  +1                                                   │"
+    "│   created   file-3.txt  +1                                                   │"
+    "│   created   file-4.txt  +1                                                   │"
+    "│>  created   file-5.txt  +1                                                   │"
+    "│   created   file-6.txt  +1                                                   │"
+    "│   created   file-7.txt  +1                                                   │"
+    "│   created   file-8.txt  +1                                                   │"
+    "│   created   file-9.txt  +1                                                   │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [20 unreviewed]                                               "
     "#);
 
     // WHEN
@@ -245,17 +248,17 @@ fn selecting_first_change_from_diff_pane_works() {
     "└──────────────────────────────────────────────────────────────────────────────┘"
     "┌ changes  (1/20) ─────────────────────────────────────────────────────────────┐"
     "│                                                                              │"
-    "│>  created   file-1.txt                                                       │"
-    "│   created   file-2.txt                                                       │"
-    "│   created   file-3.txt                                                       │"
-    "│   created   file-4.txt                                                       │"
-    "│   created   file-5.txt                                                       │"
-    "│   created   file-6.txt                                                       │"
-    "│   created   file-7.txt                                                       │"
-    "│   created   file-8.txt                                                       │"
-    "│   created   file-9.txt                                                       │"
-    "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching]                                                               "
+    "│>  created   file-1.txt  +1                                                   │"
+    "│   created   file-2.txt  +1                                                   │"
+    "│   created   file-3.txt  +1                                                   │"
+    "│   created   file-4.txt  +1                                                   │"
+    "│   created   file-5.txt  +1                                                   │"
+    "│   created   file-6.txt  +1                                                   │"
+    "│   created   file-7.txt  +1                                                   │"
+    "│   created   file-8.txt  +1                                                   │"
+    "│   created   file-9.txt  +1                                                   │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [20 unreviewed]                                               "
     "#);
 }
 
@@ -265,7 +268,7 @@ fn selecting_last_change_from_diff_pane_works() {
     let (mut terminal, terminal_dimensions) = get_test_terminal();
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -275,6 +278,7 @@ fn selecting_last_change_from_diff_pane_works() {
         let change = Change {
             path: format!("file-{i}.txt"),
             kind: ChangeKind::Created(Ok(format!("file {i} contents"))),
+            encoding: None,
         };
         update(&mut model, Msg::ChangeReceived(change));
     }
@@ -301,17 +305,17 @@ fn selecting_last_change_from_diff_pane_works() {
     "└──────────────────────────────────────────────────────────────────────────────┘"
     "┌ changes  (20/20) ────────────────────────────────────────────────────────────┐"
     "│                                                                              │"
-    "│   created   file-12.txt                                                      │"
-    "│   created   file-13.txt                                                      │"
-    "│   created   file-14.txt                                                      │"
-    "│   created   file-15.txt                                                      │"
-    "│   created   file-16.txt                                                      │"
-    "│   created   file-17.txt                                                      │"
-    "│   created   file-18.txt                                                      │"
-    "│   created   file-19.txt                                                      │"
-    "│>  created   file-20.txt                                                      │"
-    "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching]                                                               "
+    "│   created   file-12.txt  +1                                                  │"
+    "│   created   file-13.txt  +1                                                  │"
+    "│   created   file-14.txt  +1                                                  │"
+    "│   created   file-15.txt  +1                                                  │"
+    "│   created   file-16.txt  +1                                                  │"
+    "│   created   file-17.txt  +1                                                  │"
+    "│   created   file-18.txt  +1                                                  │"
+    "│   created   file-19.txt  +1                                                  │"
+    "│>  created   file-20.txt  +1                                                  │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [20 unreviewed]                                               "
     "#);
 }
 
@@ -321,7 +325,7 @@ fn diff_pane_renders_diff_with_several_hunks_correctly() {
     let (mut terminal, terminal_dimensions) = get_test_terminal_with_dims(80, 34);
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -342,6 +346,7 @@ fn diff_pane_renders_diff_with_several_hunks_correctly() {
     let change = Change {
         path: "modified_file.txt".to_string(),
         kind: ChangeKind::Modified(Ok(Modification::Diff(diff))),
+        encoding: None,
     };
     update(&mut model, Msg::ChangeReceived(change));
 
@@ -352,7 +357,7 @@ fn diff_pane_renders_diff_with_several_hunks_correctly() {
 
     // THEN
     assert_snapshot!(terminal.backend(), @r#"
-    "┌ diff  modified_file.txt ─────────────────────────────────────────────────────┐"
+    "┌ diff  modified_file.txt [hunk 1/2] ──────────────────────────────────────────┐"
     "│                                                                              │"
     "│ 6      6      | line 6                                                       │"
     "│ 7      7      | line 7                                                       │"
@@ -375,7 +380,7 @@ fn diff_pane_renders_diff_with_several_hunks_correctly() {
     "└──────────────────────────────────────────────────────────────────────────────┘"
     "┌ changes  (1/1) ──────────────────────────────────────────────────────────────┐"
     "│                                                                              │"
-    "│>  modified  modified_file.txt                                                │"
+    "│>  modified  modified_file.txt  +4 -4                                         │"
     "│                                                                              │"
     "│                                                                              │"
     "│                                                                              │"
@@ -385,7 +390,7 @@ fn diff_pane_renders_diff_with_several_hunks_correctly() {
     "│                                                                              │"
     "│                                                                              │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching]                                                               "
+    " dfft  [watching] [1 unreviewed]                                                "
     "#);
 }
 
@@ -395,7 +400,7 @@ fn scrolling_diff_by_a_line_works() {
     let (mut terminal, terminal_dimensions) = get_test_terminal();
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -416,6 +421,7 @@ fn scrolling_diff_by_a_line_works() {
     let change = Change {
         path: "modified_file.txt".to_string(),
         kind: ChangeKind::Modified(Ok(Modification::Diff(diff))),
+        encoding: None,
     };
     update(&mut model, Msg::ChangeReceived(change));
     terminal
@@ -423,7 +429,7 @@ fn scrolling_diff_by_a_line_works() {
         .expect("frame should've been drawn");
 
     assert_snapshot!(terminal.backend(), @r#"
-    "┌ diff  modified_file.txt ─────────────────────────────────────────────────────┐"
+    "┌ diff  modified_file.txt [hunk 1/2] ──────────────────────────────────────────┐"
     "│                                                                              │"
     "│ 1   1   | line 1                                                             │"
     "│ 2   2   | line 2                                                             │"
@@ -436,7 +442,7 @@ fn scrolling_diff_by_a_line_works() {
     "└──────────────────────────────────────────────────────────────────────────────┘"
     "┌ changes  (1/1) ──────────────────────────────────────────────────────────────┐"
     "│                                                                              │"
-    "│>  modified  modified_file.txt                                                │"
+    "│>  modified  modified_file.txt  +4 -4                                         │"
     "│                                                                              │"
     "│                                                                              │"
     "│                                                                              │"
@@ -446,7 +452,7 @@ fn scrolling_diff_by_a_line_works() {
     "│                                                                              │"
     "│                                                                              │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching]                                                               "
+    " dfft  [watching] [1 unreviewed]                                                "
     "#);
 
     // WHEN
@@ -459,7 +465,7 @@ fn scrolling_diff_by_a_line_works() {
         .expect("frame should've been drawn");
 
     assert_snapshot!(terminal.backend(), @r#"
-    "┌ diff  modified_file.txt ─────────────────────────────────────────────────────┐"
+    "┌ diff  modified_file.txt [hunk 1/2] ──────────────────────────────────────────┐"
     "│                                                                              │"
     "│ 4       |-line 4                                                             │"
     "│ 5       |-line 5                                                             │"
@@ -472,7 +478,7 @@ fn scrolling_diff_by_a_line_works() {
     "└──────────────────────────────────────────────────────────────────────────────┘"
     "┌ changes  (1/1) ──────────────────────────────────────────────────────────────┐"
     "│                                                                              │"
-    "│>  modified  modified_file.txt                                                │"
+    "│>  modified  modified_file.txt  +4 -4                                         │"
     "│                                                                              │"
     "│                                                                              │"
     "│                                                                              │"
@@ -482,7 +488,7 @@ fn scrolling_diff_by_a_line_works() {
     "│                                                                              │"
     "│                                                                              │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching]                                                               "
+    " dfft  [watching] [1 unreviewed]                                                "
     "#);
 
     for _ in 1..=3 {
@@ -493,7 +499,7 @@ fn scrolling_diff_by_a_line_works() {
         .expect("frame should've been drawn");
 
     assert_snapshot!(terminal.backend(), @r#"
-    "┌ diff  modified_file.txt ─────────────────────────────────────────────────────┐"
+    "┌ diff  modified_file.txt [hunk 1/2] ──────────────────────────────────────────┐"
     "│                                                                              │"
     "│ 1   1   | line 1                                                             │"
     "│ 2   2   | line 2                                                             │"
@@ -506,7 +512,7 @@ fn scrolling_diff_by_a_line_works() {
     "└──────────────────────────────────────────────────────────────────────────────┘"
     "┌ changes  (1/1) ──────────────────────────────────────────────────────────────┐"
     "│                                                                              │"
-    "│>  modified  modified_file.txt                                                │"
+    "│>  modified  modified_file.txt  +4 -4                                         │"
     "│                                                                              │"
     "│                                                                              │"
     "│                                                                              │"
@@ -516,7 +522,7 @@ fn scrolling_diff_by_a_line_works() {
     "│                                                                              │"
     "│                                                                              │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching]                                                               "
+    " dfft  [watching] [1 unreviewed]                                                "
     "#);
 }
 
@@ -526,7 +532,7 @@ fn scrolling_diff_by_half_a_page_works() {
     let (mut terminal, terminal_dimensions) = get_test_terminal();
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -547,6 +553,7 @@ fn scrolling_diff_by_half_a_page_works() {
     let change = Change {
         path: "modified_file.txt".to_string(),
         kind: ChangeKind::Modified(Ok(Modification::Diff(diff))),
+        encoding: None,
     };
     update(&mut model, Msg::ChangeReceived(change));
     terminal
@@ -554,7 +561,7 @@ fn scrolling_diff_by_half_a_page_works() {
         .expect("frame should've been drawn");
 
     assert_snapshot!(terminal.backend(), @r#"
-    "┌ diff  modified_file.txt ─────────────────────────────────────────────────────┐"
+    "┌ diff  modified_file.txt [hunk 1/2] ──────────────────────────────────────────┐"
     "│                                                                              │"
     "│ 1   1   | line 1                                                             │"
     "│ 2   2   | line 2                                                             │"
@@ -567,7 +574,7 @@ fn scrolling_diff_by_half_a_page_works() {
     "└──────────────────────────────────────────────────────────────────────────────┘"
     "┌ changes  (1/1) ──────────────────────────────────────────────────────────────┐"
     "│                                                                              │"
-    "│>  modified  modified_file.txt                                                │"
+    "│>  modified  modified_file.txt  +4 -4                                         │"
     "│                                                                              │"
     "│                                                                              │"
     "│                                                                              │"
@@ -577,7 +584,7 @@ fn scrolling_diff_by_half_a_page_works() {
     "│                                                                              │"
     "│                                                                              │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching]                                                               "
+    " dfft  [watching] [1 unreviewed]                                                "
     "#);
 
     // WHEN
@@ -588,7 +595,7 @@ fn scrolling_diff_by_half_a_page_works() {
         .expect("frame should've been drawn");
 
     assert_snapshot!(terminal.backend(), @r#"
-    "┌ diff  modified_file.txt ─────────────────────────────────────────────────────┐"
+    "┌ diff  modified_file.txt [hunk 1/2] ──────────────────────────────────────────┐"
     "│                                                                              │"
     "│ 5       |-line 5                                                             │"
     "│     4   |+line 4 (modified)                                                  │"
@@ -601,7 +608,7 @@ fn scrolling_diff_by_half_a_page_works() {
     "└──────────────────────────────────────────────────────────────────────────────┘"
     "┌ changes  (1/1) ──────────────────────────────────────────────────────────────┐"
     "│                                                                              │"
-    "│>  modified  modified_file.txt                                                │"
+    "│>  modified  modified_file.txt  +4 -4                                         │"
     "│                                                                              │"
     "│                                                                              │"
     "│                                                                              │"
@@ -611,7 +618,7 @@ fn scrolling_diff_by_half_a_page_works() {
     "│                                                                              │"
     "│                                                                              │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching]                                                               "
+    " dfft  [watching] [1 unreviewed]                                                "
     "#);
 
     update(&mut model, Msg::ScrollHalfPageUp);
@@ -620,7 +627,7 @@ fn scrolling_diff_by_half_a_page_works() {
         .expect("frame should've been drawn");
 
     assert_snapshot!(terminal.backend(), @r#"
-    "┌ diff  modified_file.txt ─────────────────────────────────────────────────────┐"
+    "┌ diff  modified_file.txt [hunk 1/2] ──────────────────────────────────────────┐"
     "│                                                                              │"
     "│ 1   1   | line 1                                                             │"
     "│ 2   2   | line 2                                                             │"
@@ -633,7 +640,7 @@ fn scrolling_diff_by_half_a_page_works() {
     "└──────────────────────────────────────────────────────────────────────────────┘"
     "┌ changes  (1/1) ──────────────────────────────────────────────────────────────┐"
     "│                                                                              │"
-    "│>  modified  modified_file.txt                                                │"
+    "│>  modified  modified_file.txt  +4 -4                                         │"
     "│                                                                              │"
     "│                                                                              │"
     "│                                                                              │"
@@ -643,7 +650,7 @@ fn scrolling_diff_by_half_a_page_works() {
     "│                                                                              │"
     "│                                                                              │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching]                                                               "
+    " dfft  [watching] [1 unreviewed]                                                "
     "#);
 }
 
@@ -653,7 +660,7 @@ fn diff_scroll_is_reset_when_follow_mode_is_on() {
     let (mut terminal, terminal_dimensions) = get_test_terminal();
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -673,6 +680,7 @@ fn diff_scroll_is_reset_when_follow_mode_is_on() {
     let change = Change {
         path: "modified_file.txt".to_string(),
         kind: ChangeKind::Modified(Ok(Modification::Diff(diff))),
+        encoding: None,
     };
     update(&mut model, Msg::ChangeReceived(change));
     for _ in 1..=4 {
@@ -683,7 +691,7 @@ fn diff_scroll_is_reset_when_follow_mode_is_on() {
         .expect("frame should've been drawn");
 
     assert_snapshot!(terminal.backend(), @r#"
-    "┌ diff  modified_file.txt ─────────────────────────────────────────────────────┐"
+    "┌ diff  modified_file.txt [hunk 1/2] ──────────────────────────────────────────┐"
     "│                                                                              │"
     "│     10  |+line 10 (modified)                                                 │"
     "│ 11  11  | line 11                                                            │"
@@ -696,7 +704,7 @@ fn diff_scroll_is_reset_when_follow_mode_is_on() {
     "└──────────────────────────────────────────────────────────────────────────────┘"
     "┌ changes  (1/1) ──────────────────────────────────────────────────────────────┐"
     "│                                                                              │"
-    "│>  modified  modified_file.txt                                                │"
+    "│>  modified  modified_file.txt  +2 -2                                         │"
     "│                                                                              │"
     "│                                                                              │"
     "│                                                                              │"
@@ -706,7 +714,7 @@ fn diff_scroll_is_reset_when_follow_mode_is_on() {
     "│                                                                              │"
     "│                                                                              │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching] [following changes]                                           "
+    " dfft  [watching] [following changes] [1 unreviewed]                            "
     "#);
 
     // WHEN
@@ -721,6 +729,7 @@ fn diff_scroll_is_reset_when_follow_mode_is_on() {
     let change = Change {
         path: "another_modified_file.txt".to_string(),
         kind: ChangeKind::Modified(Ok(Modification::Diff(diff))),
+        encoding: None,
     };
     update(&mut model, Msg::ChangeReceived(change));
     terminal
@@ -729,7 +738,7 @@ fn diff_scroll_is_reset_when_follow_mode_is_on() {
 
     // THEN
     assert_snapshot!(terminal.backend(), @r#"
-    "┌ diff  another_modified_file.txt ─────────────────────────────────────────────┐"
+    "┌ diff  another_modified_file.txt [hunk 1/1] ──────────────────────────────────┐"
     "│                                                                              │"
     "│ 1       |-line 1                                                             │"
     "│     1   |+line 1 (modified)                                                  │"
@@ -742,8 +751,8 @@ fn diff_scroll_is_reset_when_follow_mode_is_on() {
     "└──────────────────────────────────────────────────────────────────────────────┘"
     "┌ changes  (2/2) ──────────────────────────────────────────────────────────────┐"
     "│                                                                              │"
-    "│   modified  modified_file.txt                                                │"
-    "│>  modified  another_modified_file.txt                                        │"
+    "│   modified  modified_file.txt  +2 -2                                         │"
+    "│>  modified  another_modified_file.txt  +1 -1                                 │"
     "│                                                                              │"
     "│                                                                              │"
     "│                                                                              │"
@@ -752,7 +761,7 @@ fn diff_scroll_is_reset_when_follow_mode_is_on() {
     "│                                                                              │"
     "│                                                                              │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching] [following changes]                                           "
+    " dfft  [watching] [following changes] [2 unreviewed]                            "
     "#);
 }
 
@@ -762,7 +771,7 @@ fn diff_scroll_is_reset_when_another_change_is_selected() {
     let (mut terminal, terminal_dimensions) = get_test_terminal();
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -781,6 +790,7 @@ fn diff_scroll_is_reset_when_another_change_is_selected() {
     let change = Change {
         path: "modified_file.txt".to_string(),
         kind: ChangeKind::Modified(Ok(Modification::Diff(diff))),
+        encoding: None,
     };
     update(&mut model, Msg::ChangeReceived(change));
 
@@ -798,6 +808,7 @@ fn diff_scroll_is_reset_when_another_change_is_selected() {
     let change = Change {
         path: "another_modified_file.txt".to_string(),
         kind: ChangeKind::Modified(Ok(Modification::Diff(diff))),
+        encoding: None,
     };
     update(&mut model, Msg::ChangeReceived(change));
     terminal
@@ -805,7 +816,7 @@ fn diff_scroll_is_reset_when_another_change_is_selected() {
         .expect("frame should've been drawn");
 
     assert_snapshot!(terminal.backend(), @r#"
-    "┌ diff  modified_file.txt ─────────────────────────────────────────────────────┐"
+    "┌ diff  modified_file.txt [hunk 1/2] ──────────────────────────────────────────┐"
     "│                                                                              │"
     "│     10  |+line 10 (modified)                                                 │"
     "│ 11  11  | line 11                                                            │"
@@ -818,8 +829,8 @@ fn diff_scroll_is_reset_when_another_change_is_selected() {
     "└──────────────────────────────────────────────────────────────────────────────┘"
     "┌ changes  (1/2) ──────────────────────────────────────────────────────────────┐"
     "│                                                                              │"
-    "│>  modified  modified_file.txt                                                │"
-    "│   modified  another_modified_file.txt                                        │"
+    "│>  modified  modified_file.txt  +2 -2                                         │"
+    "│   modified  another_modified_file.txt  +1 -1                                 │"
     "│                                                                              │"
     "│                                                                              │"
     "│                                                                              │"
@@ -828,7 +839,7 @@ fn diff_scroll_is_reset_when_another_change_is_selected() {
     "│                                                                              │"
     "│                                                                              │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching]                                                               "
+    " dfft  [watching] [2 unreviewed]                                                "
     "#);
 
     // WHEN
@@ -839,7 +850,7 @@ fn diff_scroll_is_reset_when_another_change_is_selected() {
 
     // THEN
     assert_snapshot!(terminal.backend(), @r#"
-    "┌ diff  another_modified_file.txt ─────────────────────────────────────────────┐"
+    "┌ diff  another_modified_file.txt [hunk 1/1] ──────────────────────────────────┐"
     "│                                                                              │"
     "│ 1       |-line 1                                                             │"
     "│     1   |+line 1 (modified)                                                  │"
@@ -852,8 +863,8 @@ fn diff_scroll_is_reset_when_another_change_is_selected() {
     "└──────────────────────────────────────────────────────────────────────────────┘"
     "┌ changes  (2/2) ──────────────────────────────────────────────────────────────┐"
     "│                                                                              │"
-    "│   modified  modified_file.txt                                                │"
-    "│>  modified  another_modified_file.txt                                        │"
+    "│   modified  modified_file.txt  +2 -2                                         │"
+    "│>  modified  another_modified_file.txt  +1 -1                                 │"
     "│                                                                              │"
     "│                                                                              │"
     "│                                                                              │"
@@ -862,7 +873,7 @@ fn diff_scroll_is_reset_when_another_change_is_selected() {
     "│                                                                              │"
     "│                                                                              │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching]                                                               "
+    " dfft  [watching] [2 unreviewed]                                                "
     "#);
 }
 
@@ -872,7 +883,7 @@ fn max_scroll_for_diff_is_reset_when_change_list_is_reset() {
     let (mut terminal, terminal_dimensions) = get_test_terminal();
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -890,6 +901,7 @@ fn max_scroll_for_diff_is_reset_when_change_list_is_reset() {
     let change = Change {
         path: "modified_file.txt".to_string(),
         kind: ChangeKind::Modified(Ok(Modification::Diff(diff))),
+        encoding: None,
     };
     update(&mut model, Msg::ChangeReceived(change));
 
@@ -901,7 +913,7 @@ fn max_scroll_for_diff_is_reset_when_change_list_is_reset() {
         .expect("frame should've been drawn");
 
     assert_snapshot!(terminal.backend(), @r#"
-    "┌ diff  modified_file.txt ─────────────────────────────────────────────────────┐"
+    "┌ diff  modified_file.txt [hunk 1/2] ──────────────────────────────────────────┐"
     "│                                                                              │"
     "│     10  |+line 10 (modified)                                                 │"
     "│ 11  11  | line 11                                                            │"
@@ -914,7 +926,7 @@ fn max_scroll_for_diff_is_reset_when_change_list_is_reset() {
     "└──────────────────────────────────────────────────────────────────────────────┘"
     "┌ changes  (1/1) ──────────────────────────────────────────────────────────────┐"
     "│                                                                              │"
-    "│>  modified  modified_file.txt                                                │"
+    "│>  modified  modified_file.txt  +2 -2                                         │"
     "│                                                                              │"
     "│                                                                              │"
     "│                                                                              │"
@@ -924,7 +936,7 @@ fn max_scroll_for_diff_is_reset_when_change_list_is_reset() {
     "│                                                                              │"
     "│                                                                              │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching]                                                               "
+    " dfft  [watching] [1 unreviewed]                                                "
     "#);
 
     // WHEN
@@ -934,6 +946,7 @@ fn max_scroll_for_diff_is_reset_when_change_list_is_reset() {
     let change = Change {
         path: "newly_modified_file.txt".to_string(),
         kind: ChangeKind::Modified(Ok(Modification::Diff(diff))),
+        encoding: None,
     };
     update(&mut model, Msg::ChangeReceived(change));
     for _ in 1..=4 {
@@ -945,7 +958,7 @@ fn max_scroll_for_diff_is_reset_when_change_list_is_reset() {
 
     // THEN
     assert_snapshot!(terminal.backend(), @r#"
-    "┌ diff  newly_modified_file.txt ───────────────────────────────────────────────┐"
+    "┌ diff  newly_modified_file.txt [hunk 1/1] ────────────────────────────────────┐"
     "│                                                                              │"
     "│ 1       |-old                                                                │"
     "│     1   |+new                                                                │"
@@ -958,7 +971,7 @@ fn max_scroll_for_diff_is_reset_when_change_list_is_reset() {
     "└──────────────────────────────────────────────────────────────────────────────┘"
     "┌ changes  (1/1) ──────────────────────────────────────────────────────────────┐"
     "│                                                                              │"
-    "│>  modified  newly_modified_file.txt                                          │"
+    "│>  modified  newly_modified_file.txt  +1 -1                                   │"
     "│                                                                              │"
     "│                                                                              │"
     "│                                                                              │"
@@ -968,7 +981,7 @@ fn max_scroll_for_diff_is_reset_when_change_list_is_reset() {
     "│                                                                              │"
     "│                                                                              │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching]                                                               "
+    " dfft  [watching] [1 unreviewed]                                                "
     "#);
 }
 
@@ -978,7 +991,7 @@ fn max_scroll_for_diff_is_recomputed_when_terminal_height_changes() {
     let (mut terminal, terminal_dimensions) = get_test_terminal_with_dims(80, 30);
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -997,6 +1010,7 @@ fn max_scroll_for_diff_is_recomputed_when_terminal_height_changes() {
     let change = Change {
         path: "modified_file.txt".to_string(),
         kind: ChangeKind::Modified(Ok(Modification::Diff(diff))),
+        encoding: None,
     };
     update(&mut model, Msg::ChangeReceived(change));
 
@@ -1009,7 +1023,7 @@ fn max_scroll_for_diff_is_recomputed_when_terminal_height_changes() {
         .draw(|f| view(&mut model, f))
         .expect("frame should've been drawn");
     assert_snapshot!(terminal.backend(), @r#"
-    "┌ diff  modified_file.txt ─────────────────────────────────────────────────────┐"
+    "┌ diff  modified_file.txt [hunk 1/2] ──────────────────────────────────────────┐"
     "│                                                                              │"
     "│ 3   3   | line 3                                                             │"
     "│ 4   4   | line 4                                                             │"
@@ -1028,7 +1042,7 @@ fn max_scroll_for_diff_is_recomputed_when_terminal_height_changes() {
     "└──────────────────────────────────────────────────────────────────────────────┘"
     "┌ changes  (1/1) ──────────────────────────────────────────────────────────────┐"
     "│                                                                              │"
-    "│>  modified  modified_file.txt                                                │"
+    "│>  modified  modified_file.txt  +4 -4                                         │"
     "│                                                                              │"
     "│                                                                              │"
     "│                                                                              │"
@@ -1038,7 +1052,7 @@ fn max_scroll_for_diff_is_recomputed_when_terminal_height_changes() {
     "│                                                                              │"
     "│                                                                              │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching]                                                               "
+    " dfft  [watching] [1 unreviewed]                                                "
     "#);
 
     let (mut new_terminal, new_terminal_dimensions) = get_test_terminal();
@@ -1053,7 +1067,7 @@ fn max_scroll_for_diff_is_recomputed_when_terminal_height_changes() {
         .draw(|f| view(&mut model, f))
         .expect("frame should've been drawn");
     assert_snapshot!(new_terminal.backend(), @r#"
-    "┌ diff  modified_file.txt ─────────────────────────────────────────────────────┐"
+    "┌ diff  modified_file.txt [hunk 1/2] ──────────────────────────────────────────┐"
     "│                                                                              │"
     "│ 3   3   | line 3                                                             │"
     "│ 4   4   | line 4                                                             │"
@@ -1066,7 +1080,7 @@ fn max_scroll_for_diff_is_recomputed_when_terminal_height_changes() {
     "└──────────────────────────────────────────────────────────────────────────────┘"
     "┌ changes  (1/1) ──────────────────────────────────────────────────────────────┐"
     "│                                                                              │"
-    "│>  modified  modified_file.txt                                                │"
+    "│>  modified  modified_file.txt  +4 -4                                         │"
     "│                                                                              │"
     "│                                                                              │"
     "│                                                                              │"
@@ -1076,7 +1090,7 @@ fn max_scroll_for_diff_is_recomputed_when_terminal_height_changes() {
     "│                                                                              │"
     "│                                                                              │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching]                                                               "
+    " dfft  [watching] [1 unreviewed]                                                "
     "#);
 
     for _ in 1..=20 {
@@ -1087,7 +1101,7 @@ fn max_scroll_for_diff_is_recomputed_when_terminal_height_changes() {
         .draw(|f| view(&mut model, f))
         .expect("frame should've been drawn");
     assert_snapshot!(new_terminal.backend(), @r#"
-    "┌ diff  modified_file.txt ─────────────────────────────────────────────────────┐"
+    "┌ diff  modified_file.txt [hunk 2/2] ──────────────────────────────────────────┐"
     "│                                                                              │"
     "│ 14  14  | line 14                                                            │"
     "│ 15      |-line 15                                                            │"
@@ -1100,7 +1114,7 @@ fn max_scroll_for_diff_is_recomputed_when_terminal_height_changes() {
     "└──────────────────────────────────────────────────────────────────────────────┘"
     "┌ changes  (1/1) ──────────────────────────────────────────────────────────────┐"
     "│                                                                              │"
-    "│>  modified  modified_file.txt                                                │"
+    "│>  modified  modified_file.txt  +4 -4                                         │"
     "│                                                                              │"
     "│                                                                              │"
     "│                                                                              │"
@@ -1110,7 +1124,7 @@ fn max_scroll_for_diff_is_recomputed_when_terminal_height_changes() {
     "│                                                                              │"
     "│                                                                              │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching]                                                               "
+    " dfft  [watching] [1 unreviewed]                                                "
     "#);
 }
 
@@ -1121,7 +1135,7 @@ fn max_scroll_for_diff_doesnt_change_when_only_terminal_width_changes() {
     let (width, height) = terminal_dimensions.values();
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -1140,6 +1154,7 @@ fn max_scroll_for_diff_doesnt_change_when_only_terminal_width_changes() {
     let change = Change {
         path: "modified_file.txt".to_string(),
         kind: ChangeKind::Modified(Ok(Modification::Diff(diff))),
+        encoding: None,
     };
     update(&mut model, Msg::ChangeReceived(change));
     for _ in 1..=5 {
@@ -1160,7 +1175,7 @@ fn max_scroll_for_diff_is_recomputed_when_terminal_size_crosses_minimum_threshol
     let (width, height) = terminal_dimensions.values();
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -1179,6 +1194,7 @@ fn max_scroll_for_diff_is_recomputed_when_terminal_size_crosses_minimum_threshol
     let change = Change {
         path: "modified_file.txt".to_string(),
         kind: ChangeKind::Modified(Ok(Modification::Diff(diff))),
+        encoding: None,
     };
     update(&mut model, Msg::ChangeReceived(change));
     for _ in 1..=5 {
@@ -1194,3 +1210,616 @@ fn max_scroll_for_diff_is_recomputed_when_terminal_size_crosses_minimum_threshol
     update(&mut model, Msg::TerminalResize(width + 20, height));
     assert_eq!(model.max_diff_scroll_available, max_diff_scroll);
 }
+
+#[test]
+fn full_file_view_shows_complete_file_with_changes_in_place() {
+    // GIVEN
+    let (mut terminal, terminal_dimensions) = get_test_terminal();
+    let mut model = Model::new(
+        TuiBehaviours::default_for_test(),
+        vec![PathBuf::new()],
+        terminal_dimensions,
+        false,
+    );
+
+    let mut lines = (1..=8).map(|n| format!("line {n}")).collect::<Vec<_>>();
+    let old = lines.join("\n");
+
+    lines[4] = "line 5 (modified)".to_string();
+    lines.remove(6);
+
+    let new = lines.join("\n");
+
+    let diff = Diff::new(&old, &new).expect("diff should've been created");
+
+    let change = Change {
+        path: "modified_file.txt".to_string(),
+        kind: ChangeKind::Modified(Ok(Modification::Diff(diff))),
+        encoding: None,
+    };
+    update(&mut model, Msg::ChangeReceived(change));
+
+    // WHEN
+    update(&mut model, Msg::ToggleFullFileView);
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff  modified_file.txt [hunk 1/1] [full file] ──────────────────────────────┐"
+    "│                                                                              │"
+    "│ 2   2   | line 2                                                             │"
+    "│ 3   3   | line 3                                                             │"
+    "│ 4   4   | line 4                                                             │"
+    "│ 5       |-line 5                                                             │"
+    "│     5   |+line 5 (modified)                                                  │"
+    "│ 6   6   | line 6                                                             │"
+    "│ 7       |-line 7                                                             │"
+    "│ 8   7   | line 8                                                             │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes  (1/1) ──────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│>  modified  modified_file.txt  +1 -2                                         │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [1 unreviewed]                                                "
+    "#);
+}
+
+#[test]
+fn full_file_view_jumps_to_the_first_change() {
+    // GIVEN
+    let (mut terminal, terminal_dimensions) = get_test_terminal();
+    let mut model = Model::new(
+        TuiBehaviours::default_for_test(),
+        vec![PathBuf::new()],
+        terminal_dimensions,
+        false,
+    );
+
+    let mut lines = (1..=30).map(|n| format!("line {n}")).collect::<Vec<_>>();
+    let old = lines.join("\n");
+
+    lines[19] = "line 20 (modified)".to_string();
+
+    let new = lines.join("\n");
+
+    let diff = Diff::new(&old, &new).expect("diff should've been created");
+
+    let change = Change {
+        path: "modified_file.txt".to_string(),
+        kind: ChangeKind::Modified(Ok(Modification::Diff(diff))),
+        encoding: None,
+    };
+    update(&mut model, Msg::ChangeReceived(change));
+
+    // WHEN
+    update(&mut model, Msg::ToggleFullFileView);
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff  modified_file.txt [hunk 1/1] [full file] ──────────────────────────────┐"
+    "│                                                                              │"
+    "│ 17  17  | line 17                                                            │"
+    "│ 18  18  | line 18                                                            │"
+    "│ 19  19  | line 19                                                            │"
+    "│ 20      |-line 20                                                            │"
+    "│     20  |+line 20 (modified)                                                 │"
+    "│ 21  21  | line 21                                                            │"
+    "│ 22  22  | line 22                                                            │"
+    "│ 23  23  | line 23                                                            │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes  (1/1) ──────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│>  modified  modified_file.txt  +1 -1                                         │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [1 unreviewed]                                                "
+    "#);
+}
+
+#[test]
+fn full_file_view_shows_line_numbers_for_created_files() {
+    // GIVEN
+    let (mut terminal, terminal_dimensions) = get_test_terminal();
+    let mut model = Model::new(
+        TuiBehaviours::default_for_test(),
+        vec![PathBuf::new()],
+        terminal_dimensions,
+        false,
+    );
+
+    let contents = (1..=5)
+        .map(|n| format!("line {n}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let change = Change {
+        path: "created.txt".to_string(),
+        kind: ChangeKind::Created(Ok(contents)),
+        encoding: None,
+    };
+    update(&mut model, Msg::ChangeReceived(change));
+
+    // WHEN
+    update(&mut model, Msg::ToggleFullFileView);
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff  created.txt [full file] ───────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│     1   |+line 1                                                             │"
+    "│     2   |+line 2                                                             │"
+    "│     3   |+line 3                                                             │"
+    "│     4   |+line 4                                                             │"
+    "│     5   |+line 5                                                             │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes  (1/1) ──────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│>  created   created.txt  +5                                                  │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [1 unreviewed]                                                "
+    "#);
+}
+
+#[test]
+fn scrolling_diff_horizontally_works() {
+    // GIVEN
+    let (mut terminal, terminal_dimensions) = get_test_terminal();
+    let mut model = Model::new(
+        TuiBehaviours::default_for_test(),
+        vec![PathBuf::new()],
+        terminal_dimensions,
+        false,
+    );
+
+    let old = "short line\nanother line\n";
+    let new = format!("short line\n{}\n", "0123456789".repeat(10));
+    let diff = Diff::new(old, &new).expect("diff should've been created");
+
+    let change = Change {
+        path: "modified_file.txt".to_string(),
+        kind: ChangeKind::Modified(Ok(Modification::Diff(diff))),
+        encoding: None,
+    };
+    update(&mut model, Msg::ChangeReceived(change));
+
+    // WHEN
+    for _ in 1..=2 {
+        update(&mut model, Msg::ScrollRight);
+    }
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff  modified_file.txt [hunk 1/1] ──────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│ 1   1   | ne                                                                 │"
+    "│ 2       |-line                                                               │"
+    "│     2   |+8901234567890123456789012345678901234567890123456789012345678901234│"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes  (1/1) ──────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│>  modified  modified_file.txt  +1 -1                                         │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [1 unreviewed]                                                "
+    "#);
+
+    for _ in 1..=20 {
+        update(&mut model, Msg::ScrollRight);
+    }
+    assert_eq!(model.diff_horizontal_scroll, 33);
+    assert_eq!(model.max_diff_horizontal_scroll_available, 33);
+
+    for _ in 1..=20 {
+        update(&mut model, Msg::ScrollLeft);
+    }
+    assert_eq!(model.diff_horizontal_scroll, 0);
+}
+
+#[test]
+fn wrapping_diff_lines_works() {
+    // GIVEN
+    let (mut terminal, terminal_dimensions) = get_test_terminal();
+    let mut model = Model::new(
+        TuiBehaviours::default_for_test(),
+        vec![PathBuf::new()],
+        terminal_dimensions,
+        false,
+    );
+
+    let old = "short line\nanother line\n";
+    let new = format!("short line\n{}\n", "0123456789".repeat(50));
+    let diff = Diff::new(old, &new).expect("diff should've been created");
+
+    let change = Change {
+        path: "modified_file.txt".to_string(),
+        kind: ChangeKind::Modified(Ok(Modification::Diff(diff))),
+        encoding: None,
+    };
+    update(&mut model, Msg::ChangeReceived(change));
+    assert_eq!(model.max_diff_scroll_available, 0);
+
+    // WHEN
+    update(&mut model, Msg::ToggleWrap);
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff  modified_file.txt [hunk 1/1] [wrap] ───────────────────────────────────┐"
+    "│                                                                              │"
+    "│ 1   1   | short line                                                         │"
+    "│ 2       |-another line                                                       │"
+    "│     2   |+0123456789012345678901234567890123456789012345678901234567890123456│"
+    "│           7890123456789012345678901234567890123456789012345678901234567890123│"
+    "│           4567890123456789012345678901234567890123456789012345678901234567890│"
+    "│           1234567890123456789012345678901234567890123456789012345678901234567│"
+    "│           8901234567890123456789012345678901234567890123456789012345678901234│"
+    "│           5678901234567890123456789012345678901234567890123456789012345678901│"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes  (1/1) ──────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│>  modified  modified_file.txt  +1 -1                                         │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [1 unreviewed]                                                "
+    "#);
+    assert_eq!(model.max_diff_horizontal_scroll_available, 0);
+
+    assert_eq!(model.max_diff_scroll_available, 2);
+
+    update(&mut model, Msg::TerminalResize(120, 24));
+    assert_eq!(model.max_diff_scroll_available, 0);
+
+    update(&mut model, Msg::TerminalResize(80, 24));
+    update(&mut model, Msg::ToggleWrap);
+    assert_eq!(model.max_diff_scroll_available, 0);
+}
+
+#[test]
+fn wrapping_created_file_contents_works() {
+    // GIVEN
+    let (mut terminal, terminal_dimensions) = get_test_terminal();
+    let mut model = Model::new(
+        TuiBehaviours::default_for_test(),
+        vec![PathBuf::new()],
+        terminal_dimensions,
+        false,
+    );
+
+    let contents = (1..=5)
+        .map(|n| format!("line {n}: {}", "abcdefghij".repeat(n * 2)))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let change = Change {
+        path: "created.txt".to_string(),
+        kind: ChangeKind::Created(Ok(contents)),
+        encoding: None,
+    };
+    update(&mut model, Msg::ChangeReceived(change));
+
+    // WHEN
+    update(&mut model, Msg::ToggleWrap);
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff  created.txt [wrap] ────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│ line 1: abcdefghijabcdefghij                                                 │"
+    "│ line 2: abcdefghijabcdefghijabcdefghijabcdefghij                             │"
+    "│ line 3: abcdefghijabcdefghijabcdefghijabcdefghijabcdefghijabcdefghij         │"
+    "│ line 4: abcdefghijabcdefghijabcdefghijabcdefghijabcdefghijabcdefghijabcdefghi│"
+    "│ jabcdefghij                                                                  │"
+    "│ line 5: abcdefghijabcdefghijabcdefghijabcdefghijabcdefghijabcdefghijabcdefghi│"
+    "│ jabcdefghijabcdefghijabcdefghij                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes  (1/1) ──────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│>  created   created.txt  +5                                                  │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [1 unreviewed]                                                "
+    "#);
+    assert_eq!(model.max_diff_scroll_available, 0);
+}
+
+fn get_model_with_several_hunks() -> Model {
+    let (_, terminal_dimensions) = get_test_terminal();
+    let mut model = Model::new(
+        TuiBehaviours::default_for_test(),
+        vec![PathBuf::new()],
+        terminal_dimensions,
+        false,
+    );
+
+    let mut lines = (1..=60).map(|n| format!("line {n}")).collect::<Vec<_>>();
+    let old = lines.join("\n");
+
+    lines[4] = "line 5 (modified)".to_string();
+    lines[24] = "line 25 (modified)".to_string();
+    lines[44] = "line 45 (modified)".to_string();
+    lines[45] = "line 46 (modified)".to_string();
+
+    let new = lines.join("\n");
+
+    let diff = Diff::new(&old, &new).expect("diff should've been created");
+
+    let change = Change {
+        path: "modified_file.txt".to_string(),
+        kind: ChangeKind::Modified(Ok(Modification::Diff(diff))),
+        encoding: None,
+    };
+    update(&mut model, Msg::ChangeReceived(change));
+
+    model
+}
+
+#[test]
+fn jumping_to_next_hunk_works() {
+    // GIVEN
+    let (mut terminal, _) = get_test_terminal();
+    let mut model = get_model_with_several_hunks();
+
+    // WHEN
+    update(&mut model, Msg::GoToNextHunk);
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff  modified_file.txt [hunk 2/3] ──────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│ 22  22  | line 22                                                            │"
+    "│ 23  23  | line 23                                                            │"
+    "│ 24  24  | line 24                                                            │"
+    "│ 25      |-line 25                                                            │"
+    "│     25  |+line 25 (modified)                                                 │"
+    "│ 26  26  | line 26                                                            │"
+    "│ 27  27  | line 27                                                            │"
+    "│ 28  28  | line 28                                                            │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes  (1/1) ──────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│>  modified  modified_file.txt  +4 -4                                         │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [1 unreviewed]                                                "
+    "#);
+}
+
+#[test]
+fn jumping_to_next_hunk_in_full_file_view_works() {
+    // GIVEN
+    let (mut terminal, _) = get_test_terminal();
+    let mut model = get_model_with_several_hunks();
+    update(&mut model, Msg::ToggleFullFileView);
+
+    // WHEN
+    update(&mut model, Msg::GoToNextHunk);
+    update(&mut model, Msg::GoToNextHunk);
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff  modified_file.txt [hunk 3/3] [full file] ──────────────────────────────┐"
+    "│                                                                              │"
+    "│ 42  42  | line 42                                                            │"
+    "│ 43  43  | line 43                                                            │"
+    "│ 44  44  | line 44                                                            │"
+    "│ 45      |-line 45                                                            │"
+    "│ 46      |-line 46                                                            │"
+    "│     45  |+line 45 (modified)                                                 │"
+    "│     46  |+line 46 (modified)                                                 │"
+    "│ 47  47  | line 47                                                            │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes  (1/1) ──────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│>  modified  modified_file.txt  +4 -4                                         │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [1 unreviewed]                                                "
+    "#);
+}
+
+#[test]
+fn jumping_to_next_hunk_doesnt_go_beyond_the_last_one() {
+    // GIVEN
+    let mut model = get_model_with_several_hunks();
+
+    // WHEN
+    for _ in 0..5 {
+        update(&mut model, Msg::GoToNextHunk);
+    }
+
+    // THEN
+    assert_eq!(model.hunk_position(), Some((Some(2), 3)));
+}
+
+#[test]
+fn jumping_to_previous_hunk_goes_to_start_of_current_hunk_first() {
+    // GIVEN
+    let mut model = get_model_with_several_hunks();
+    update(&mut model, Msg::GoToNextHunk);
+    update(&mut model, Msg::ScrollDown);
+    update(&mut model, Msg::ScrollDown);
+    let scroll_at_hunk_start = model.diff_scroll - 2;
+
+    // WHEN
+    update(&mut model, Msg::GoToPreviousHunk);
+
+    // THEN
+    assert_eq!(model.diff_scroll, scroll_at_hunk_start);
+    assert_eq!(model.hunk_position(), Some((Some(1), 3)));
+
+    // WHEN
+    update(&mut model, Msg::GoToPreviousHunk);
+
+    // THEN
+    assert_eq!(model.diff_scroll, 0);
+    assert_eq!(model.hunk_position(), Some((Some(0), 3)));
+}
+
+#[test]
+fn jumping_between_changed_lines_works() {
+    // GIVEN
+    let (mut terminal, _) = get_test_terminal();
+    let mut model = get_model_with_several_hunks();
+    update(&mut model, Msg::ToggleFullFileView);
+
+    // WHEN
+    // -line 5 -> +line 5 (modified) -> -line 25 -> +line 25 (modified)
+    for _ in 0..4 {
+        update(&mut model, Msg::GoToNextChangedLine);
+    }
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff  modified_file.txt [hunk 2/3] [full file] ──────────────────────────────┐"
+    "│                                                                              │"
+    "│ 23  23  | line 23                                                            │"
+    "│ 24  24  | line 24                                                            │"
+    "│ 25      |-line 25                                                            │"
+    "│     25  |+line 25 (modified)                                                 │"
+    "│ 26  26  | line 26                                                            │"
+    "│ 27  27  | line 27                                                            │"
+    "│ 28  28  | line 28                                                            │"
+    "│ 29  29  | line 29                                                            │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes  (1/1) ──────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│>  modified  modified_file.txt  +4 -4                                         │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [1 unreviewed]                                                "
+    "#);
+
+    // WHEN
+    update(&mut model, Msg::GoToPreviousChangedLine);
+    update(&mut model, Msg::GoToPreviousChangedLine);
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff  modified_file.txt [hunk 1/3] [full file] ──────────────────────────────┐"
+    "│                                                                              │"
+    "│ 3   3   | line 3                                                             │"
+    "│ 4   4   | line 4                                                             │"
+    "│ 5       |-line 5                                                             │"
+    "│     5   |+line 5 (modified)                                                  │"
+    "│ 6   6   | line 6                                                             │"
+    "│ 7   7   | line 7                                                             │"
+    "│ 8   8   | line 8                                                             │"
+    "│ 9   9   | line 9                                                             │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes  (1/1) ──────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│>  modified  modified_file.txt  +4 -4                                         │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [1 unreviewed]                                                "
+    "#);
+}
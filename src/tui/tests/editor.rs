@@ -0,0 +1,234 @@
+use super::super::TuiBehaviours;
+use super::super::cmd::Cmd;
+use super::super::common::Pane;
+use super::super::model::Model;
+use super::super::{msg::Msg, update::update, view::view};
+use super::helpers::{get_model, get_test_terminal, modification};
+use crate::domain::{Change, ChangeKind};
+use insta::assert_snapshot;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+fn get_model_with_roots(roots: Vec<PathBuf>) -> Model {
+    let mut model = get_model(TuiBehaviours::default_for_test(), roots);
+    model.active_pane = Pane::Changes;
+
+    model
+}
+
+#[test]
+fn opening_a_change_in_editor_targets_its_file() {
+    // GIVEN
+    let mut model = get_model_with_roots(vec![PathBuf::from("/repo")]);
+    update(
+        &mut model,
+        Msg::ChangeReceived(modification("src/main.rs", "a\nb\nc\n", "a\nb\nC\n")),
+    );
+
+    // WHEN
+    let cmds = update(&mut model, Msg::OpenInEditor);
+
+    // THEN
+    let [Cmd::OpenInEditor { editor, dir }] = cmds.as_slice() else {
+        panic!("editor should've been opened");
+    };
+    assert_eq!(dir, &PathBuf::from("/repo"));
+    assert!(
+        editor
+            .args
+            .iter()
+            .any(|arg| arg.contains("/repo/src/main.rs"))
+    );
+}
+
+#[test]
+fn opening_a_change_in_editor_targets_the_directory_it_was_made_in() {
+    // GIVEN
+    let mut model = get_model_with_roots(vec![
+        PathBuf::from("/work/frontend"),
+        PathBuf::from("/work/backend"),
+    ]);
+    update(
+        &mut model,
+        Msg::ChangeReceived(modification("backend/src/main.rs", "a\n", "b\n")),
+    );
+
+    // WHEN
+    let cmds = update(&mut model, Msg::OpenInEditor);
+
+    // THEN
+    let [Cmd::OpenInEditor { editor, dir }] = cmds.as_slice() else {
+        panic!("editor should've been opened");
+    };
+    assert_eq!(dir, &PathBuf::from("/work/backend"));
+    assert!(
+        editor
+            .args
+            .iter()
+            .any(|arg| arg.contains("/work/backend/src/main.rs"))
+    );
+}
+
+#[test]
+fn removed_files_cannot_be_opened_in_editor() {
+    // GIVEN
+    let mut model = get_model_with_roots(vec![PathBuf::from("/repo")]);
+    update(
+        &mut model,
+        Msg::ChangeReceived(Change {
+            path: "src/main.rs".to_string(),
+            kind: ChangeKind::RemovedFile,
+            encoding: None,
+        }),
+    );
+
+    // WHEN
+    let cmds = update(&mut model, Msg::OpenInEditor);
+
+    // THEN
+    assert!(cmds.is_empty());
+    assert_eq!(
+        model.user_msg.as_ref().map(|m| m.value.as_str()),
+        Some("this file doesn't exist anymore")
+    );
+}
+
+#[test]
+fn changes_made_from_the_editor_are_marked_as_made_by_user() {
+    // GIVEN
+    let (mut terminal, _) = get_test_terminal();
+    let mut model = get_model_with_roots(vec![PathBuf::from("/repo")]);
+    update(
+        &mut model,
+        Msg::ChangeReceived(modification("src/main.rs", "a\n", "b\n")),
+    );
+    update(&mut model, Msg::OpenInEditor);
+
+    // WHEN
+    update(
+        &mut model,
+        Msg::ChangeReceived(modification("src/lib.rs", "a\n", "b\n")),
+    );
+    update(
+        &mut model,
+        Msg::ChangeReceived(modification("src/main.rs", "b\n", "c\n")),
+    );
+    update(&mut model, Msg::EditorClosed(Ok(())));
+    update(&mut model, Msg::SelectLast);
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_eq!(
+        model
+            .changes
+            .items
+            .iter()
+            .map(|i| (i.by_user, i.reviewed))
+            .collect::<Vec<_>>(),
+        vec![(false, false), (false, false), (true, true)]
+    );
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff  src/main.rs [hunk 1/1] [edited by you] ────────────────────────────────┐"
+    "│                                                                              │"
+    "│ 1       |-b                                                                  │"
+    "│     1   |+c                                                                  │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes  (3/3) ──────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│   modified  src/main.rs  +1 -1                                               │"
+    "│   modified  src/lib.rs  +1 -1                                                │"
+    "│>  modified  src/main.rs  +1 -1  (you)                                        │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [2 unreviewed]                                                "
+    "#);
+}
+
+#[test]
+fn failing_to_open_editor_shows_an_error() {
+    // GIVEN
+    let mut model = get_model_with_roots(vec![PathBuf::from("/repo")]);
+    update(
+        &mut model,
+        Msg::ChangeReceived(modification("src/main.rs", "a\n", "b\n")),
+    );
+    update(&mut model, Msg::OpenInEditor);
+
+    // WHEN
+    update(
+        &mut model,
+        Msg::EditorClosed(Err("couldn't run nvim: not found".to_string())),
+    );
+
+    // THEN
+    assert_eq!(
+        model.user_msg.as_ref().map(|m| m.value.as_str()),
+        Some("couldn't open editor: couldn't run nvim: not found")
+    );
+}
+
+#[test]
+fn changes_noticed_late_due_to_a_long_debounce_are_still_marked_as_made_by_user() {
+    // GIVEN
+    let mut behaviours = TuiBehaviours::default_for_test();
+    behaviours.watch_settings.debounce = Duration::from_secs(5);
+    let mut model = get_model(behaviours, vec![PathBuf::from("/repo")]);
+    model.active_pane = Pane::Changes;
+    update(
+        &mut model,
+        Msg::ChangeReceived(modification("src/main.rs", "a\n", "b\n")),
+    );
+    update(&mut model, Msg::OpenInEditor);
+    model.on_editor_closed(Ok(()), Instant::now() - Duration::from_secs(4));
+
+    // WHEN
+    update(
+        &mut model,
+        Msg::ChangeReceived(modification("src/main.rs", "b\n", "c\n")),
+    );
+
+    // THEN
+    assert_eq!(
+        model
+            .changes
+            .items
+            .iter()
+            .map(|i| i.by_user)
+            .collect::<Vec<_>>(),
+        vec![false, true]
+    );
+}
+
+#[test]
+fn changes_noticed_after_the_grace_period_are_not_marked_as_made_by_user() {
+    // GIVEN
+    let mut model = get_model_with_roots(vec![PathBuf::from("/repo")]);
+    update(
+        &mut model,
+        Msg::ChangeReceived(modification("src/main.rs", "a\n", "b\n")),
+    );
+    update(&mut model, Msg::OpenInEditor);
+    model.on_editor_closed(Ok(()), Instant::now() - Duration::from_secs(4));
+
+    // WHEN
+    update(
+        &mut model,
+        Msg::ChangeReceived(modification("src/main.rs", "b\n", "c\n")),
+    );
+
+    // THEN
+    assert!(model.changes.items.iter().all(|i| !i.by_user));
+}
@@ -0,0 +1,325 @@
+use super::super::TuiBehaviours;
+use super::super::common::Pane;
+use super::super::model::Model;
+use super::super::{msg::Msg, update::update, view::view};
+use super::helpers::{get_model_with_changes, get_test_terminal, modification};
+use crate::domain::{Change, ChangeKind};
+use insta::assert_snapshot;
+
+fn get_model_with_file_changes(follow_changes: bool) -> Model {
+    let mut behaviours = TuiBehaviours::default_for_test();
+    behaviours.follow_changes = follow_changes;
+
+    let changes = vec![
+        Change {
+            path: "src/tui/model.rs".to_string(),
+            kind: ChangeKind::Created(Ok("model\n".to_string())),
+            encoding: None,
+        },
+        modification("src/tui/view.rs", "a\n", "b\n"),
+        modification("src/main.rs", "a\n", "b\n"),
+        Change {
+            path: "README.md".to_string(),
+            kind: ChangeKind::RemovedFile,
+            encoding: None,
+        },
+    ];
+    let mut model = get_model_with_changes(behaviours, changes);
+    model.active_pane = Pane::Changes;
+
+    model
+}
+
+// the filter prompt starts out with the current query
+fn filter_by(model: &mut Model, query: &str) {
+    update(model, Msg::StartFilter);
+    for _ in 0..model.changes.filter.query.chars().count() {
+        update(model, Msg::DeletePromptInput);
+    }
+    for c in query.chars() {
+        update(model, Msg::EnterPromptInput(c));
+    }
+    update(model, Msg::SubmitPrompt);
+}
+
+#[test]
+fn filtering_changes_by_path_works() {
+    // GIVEN
+    let (mut terminal, _) = get_test_terminal();
+    let mut model = get_model_with_file_changes(false);
+
+    // WHEN
+    filter_by(&mut model, "tuirs");
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff  src/tui/model.rs ──────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│ model                                                                        │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes  (1/2) [/tuirs] [2 hidden] ──────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│>  created   src/tui/model.rs  +1                                             │"
+    "│   modified  src/tui/view.rs  +1 -1                                           │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [4 unreviewed]                                                "
+    "#);
+}
+
+#[test]
+fn filter_prompt_shows_number_of_matching_changes() {
+    // GIVEN
+    let (mut terminal, _) = get_test_terminal();
+    let mut model = get_model_with_file_changes(false);
+
+    // WHEN
+    update(&mut model, Msg::StartFilter);
+    update(&mut model, Msg::EnterPromptInput('v'));
+    update(&mut model, Msg::CyclePromptOption);
+    update(&mut model, Msg::CyclePromptOption);
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff  src/tui/view.rs [hunk 1/1] ────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│ 1       |-a                                                                  │"
+    "│     1   |+b                                                                  │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes  (1/1) [/v] [modified] [3 hidden] ───────────────────────────────────┐"
+    "│                                                                              │"
+    "│>  modified  src/tui/view.rs  +1 -1                                           │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " filter  [modified] /v  (1/4 changes)                                           "
+    "#);
+}
+
+#[test]
+fn filtering_changes_by_kind_works() {
+    // GIVEN
+    let (mut terminal, _) = get_test_terminal();
+    let mut model = get_model_with_file_changes(false);
+
+    // WHEN
+    update(&mut model, Msg::CycleChangeKindFilter);
+    update(&mut model, Msg::CycleChangeKindFilter);
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff  src/tui/view.rs [hunk 1/1] ────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│ 1       |-a                                                                  │"
+    "│     1   |+b                                                                  │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes  (1/2) [modified] [2 hidden] ────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│>  modified  src/tui/view.rs  +1 -1                                           │"
+    "│   modified  src/main.rs  +1 -1                                               │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [4 unreviewed]                                                "
+    "#);
+}
+
+#[test]
+fn filter_that_matches_nothing_shows_a_message() {
+    // GIVEN
+    let (mut terminal, _) = get_test_terminal();
+    let mut model = get_model_with_file_changes(false);
+
+    // WHEN
+    filter_by(&mut model, "nothing");
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert!(model.selected_change().is_none());
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff ────────────────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                     dfft                                     │"
+    "│                                     ‾‾‾‾                                     │"
+    "│                                                                              │"
+    "│            will show you changes in this directory as they happen            │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes  [/nothing] [4 hidden] ──────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│ no changes match the filter                                                  │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [4 unreviewed]                                                "
+    "#);
+}
+
+#[test]
+fn filtering_keeps_selection_on_visible_change() {
+    // GIVEN
+    let mut model = get_model_with_file_changes(false);
+    update(&mut model, Msg::SelectNext);
+    update(&mut model, Msg::SelectNext);
+
+    // WHEN
+    filter_by(&mut model, "main");
+
+    // THEN
+    assert_eq!(model.current_file_path(), Some("src/main.rs"));
+
+    // WHEN
+    filter_by(&mut model, "");
+
+    // THEN
+    assert!(!model.changes.filter.is_active());
+    assert_eq!(model.current_file_path(), Some("src/main.rs"));
+}
+
+#[test]
+fn filtering_selects_next_visible_change_when_selected_one_is_hidden() {
+    // GIVEN
+    let mut model = get_model_with_file_changes(false);
+    update(&mut model, Msg::SelectNext);
+
+    // WHEN
+    filter_by(&mut model, "rs");
+    update(&mut model, Msg::CycleChangeKindFilter);
+    update(&mut model, Msg::CycleChangeKindFilter);
+
+    // THEN
+    // src/tui/view.rs is still visible
+    assert_eq!(model.current_file_path(), Some("src/tui/view.rs"));
+
+    // WHEN
+    update(&mut model, Msg::StartFilter);
+    update(&mut model, Msg::DeletePromptInput);
+    update(&mut model, Msg::DeletePromptInput);
+    update(&mut model, Msg::EnterPromptInput('m'));
+    update(&mut model, Msg::EnterPromptInput('a'));
+    update(&mut model, Msg::SubmitPrompt);
+
+    // THEN
+    assert_eq!(model.current_file_path(), Some("src/main.rs"));
+}
+
+#[test]
+fn follow_mode_doesnt_select_changes_hidden_by_filter() {
+    // GIVEN
+    let mut model = get_model_with_file_changes(true);
+    filter_by(&mut model, "tui");
+    let selected_before = model.current_file_path().map(String::from);
+
+    // WHEN
+    update(
+        &mut model,
+        Msg::ChangeReceived(Change {
+            path: "src/main.rs".to_string(),
+            kind: ChangeKind::RemovedFile,
+            encoding: None,
+        }),
+    );
+
+    // THEN
+    assert_eq!(model.current_file_path().map(String::from), selected_before);
+
+    // WHEN
+    update(
+        &mut model,
+        Msg::ChangeReceived(Change {
+            path: "src/tui/mod.rs".to_string(),
+            kind: ChangeKind::RemovedFile,
+            encoding: None,
+        }),
+    );
+
+    // THEN
+    assert_eq!(model.current_file_path(), Some("src/tui/mod.rs"));
+}
+
+#[test]
+fn cancelling_filter_prompt_restores_previous_filter() {
+    // GIVEN
+    let mut model = get_model_with_file_changes(false);
+    filter_by(&mut model, "tui");
+
+    // WHEN
+    update(&mut model, Msg::StartFilter);
+    update(&mut model, Msg::EnterPromptInput('x'));
+    update(&mut model, Msg::CancelPrompt);
+
+    // THEN
+    assert_eq!(model.changes.filter.query, "tui");
+    assert_eq!(model.changes.visible.len(), 2);
+}
+
+#[test]
+fn going_back_from_changes_pane_clears_filter_first() {
+    // GIVEN
+    let mut model = get_model_with_file_changes(false);
+    filter_by(&mut model, "tui");
+
+    // WHEN
+    update(&mut model, Msg::GoBackOrQuit);
+
+    // THEN
+    assert!(!model.changes.filter.is_active());
+    assert_eq!(model.changes.visible.len(), 4);
+    assert_eq!(model.active_pane, Pane::Changes);
+}
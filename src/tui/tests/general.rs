@@ -12,7 +12,7 @@ fn terminal_too_small_view_is_shown_when_width_too_small() {
     let (mut terminal, terminal_dimensions) = get_test_terminal_with_dims(60, 24);
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -57,7 +57,7 @@ fn terminal_too_small_view_is_shown_when_height_too_small() {
     let (mut terminal, terminal_dimensions) = get_test_terminal_with_dims(80, 20);
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -98,7 +98,7 @@ fn terminal_too_small_view_is_shown_when_both_dimensions_small() {
     let (mut terminal, terminal_dimensions) = get_test_terminal_with_dims(40, 20);
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -139,7 +139,7 @@ fn main_view_renders_banner_when_no_changes_present() {
     let (mut terminal, terminal_dimensions) = get_test_terminal_with_dims(100, 42);
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -202,7 +202,7 @@ fn main_view_renders_created_file_change() {
     let (mut terminal, terminal_dimensions) = get_test_terminal();
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -210,6 +210,7 @@ fn main_view_renders_created_file_change() {
     let change = Change {
         path: "new_file.txt".to_string(),
         kind: ChangeKind::Created(Ok("test file contents".to_string())),
+        encoding: None,
     };
     update(&mut model, Msg::ChangeReceived(change));
 
@@ -233,7 +234,7 @@ fn main_view_renders_created_file_change() {
     "└──────────────────────────────────────────────────────────────────────────────┘"
     "┌ changes  (1/1) ──────────────────────────────────────────────────────────────┐"
     "│                                                                              │"
-    "│>  created   new_file.txt                                                     │"
+    "│>  created   new_file.txt  +1                                                 │"
     "│                                                                              │"
     "│                                                                              │"
     "│                                                                              │"
@@ -243,7 +244,7 @@ fn main_view_renders_created_file_change() {
     "│                                                                              │"
     "│                                                                              │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching]                                                               "
+    " dfft  [watching] [1 unreviewed]                                                "
     "#);
 }
 
@@ -253,7 +254,7 @@ fn main_view_renders_removed_file_change() {
     let (mut terminal, terminal_dimensions) = get_test_terminal();
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -261,6 +262,7 @@ fn main_view_renders_removed_file_change() {
     let change = Change {
         path: "deleted_file.txt".to_string(),
         kind: ChangeKind::RemovedFile,
+        encoding: None,
     };
     update(&mut model, Msg::ChangeReceived(change));
 
@@ -294,7 +296,7 @@ fn main_view_renders_removed_file_change() {
     "│                                                                              │"
     "│                                                                              │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching]                                                               "
+    " dfft  [watching] [1 unreviewed]                                                "
     "#);
 }
 
@@ -304,7 +306,7 @@ fn main_view_renders_created_file_with_error() {
     let (mut terminal, terminal_dimensions) = get_test_terminal();
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -312,6 +314,7 @@ fn main_view_renders_created_file_with_error() {
     let change = Change {
         path: "error_file.txt".to_string(),
         kind: ChangeKind::Created(Err("Permission denied".to_string())),
+        encoding: None,
     };
     update(&mut model, Msg::ChangeReceived(change));
 
@@ -345,7 +348,7 @@ fn main_view_renders_created_file_with_error() {
     "│                                                                              │"
     "│                                                                              │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching]                                                               "
+    " dfft  [watching] [1 unreviewed]                                                "
     "#);
 }
 
@@ -355,7 +358,7 @@ fn main_view_renders_modified_file_with_error() {
     let (mut terminal, terminal_dimensions) = get_test_terminal();
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -363,6 +366,7 @@ fn main_view_renders_modified_file_with_error() {
     let change = Change {
         path: "error_modified.txt".to_string(),
         kind: ChangeKind::Modified(Err("File not found".to_string())),
+        encoding: None,
     };
     update(&mut model, Msg::ChangeReceived(change));
 
@@ -396,7 +400,7 @@ fn main_view_renders_modified_file_with_error() {
     "│                                                                              │"
     "│                                                                              │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching]                                                               "
+    " dfft  [watching] [1 unreviewed]                                                "
     "#);
 }
 
@@ -406,7 +410,7 @@ fn main_view_renders_initial_snapshot_change() {
     let (mut terminal, terminal_dimensions) = get_test_terminal();
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -414,6 +418,7 @@ fn main_view_renders_initial_snapshot_change() {
     let change = Change {
         path: "snapshot_file.txt".to_string(),
         kind: ChangeKind::Modified(Ok(Modification::InitialSnapshot)),
+        encoding: None,
     };
     update(&mut model, Msg::ChangeReceived(change));
 
@@ -447,7 +452,7 @@ fn main_view_renders_initial_snapshot_change() {
     "│                                                                              │"
     "│                                                                              │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching]                                                               "
+    " dfft  [watching] [1 unreviewed]                                                "
     "#);
 }
 
@@ -457,7 +462,7 @@ fn changes_list_shows_item_count_in_title() {
     let (mut terminal, terminal_dimensions) = get_test_terminal();
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -466,6 +471,7 @@ fn changes_list_shows_item_count_in_title() {
         let change = Change {
             path: format!("file{i}.txt"),
             kind: ChangeKind::Created(Ok("test file contents".to_string())),
+            encoding: None,
         };
         update(&mut model, Msg::ChangeReceived(change));
     }
@@ -490,9 +496,9 @@ fn changes_list_shows_item_count_in_title() {
     "└──────────────────────────────────────────────────────────────────────────────┘"
     "┌ changes  (1/3) ──────────────────────────────────────────────────────────────┐"
     "│                                                                              │"
-    "│>  created   file0.txt                                                        │"
-    "│   created   file1.txt                                                        │"
-    "│   created   file2.txt                                                        │"
+    "│>  created   file0.txt  +1                                                    │"
+    "│   created   file1.txt  +1                                                    │"
+    "│   created   file2.txt  +1                                                    │"
     "│                                                                              │"
     "│                                                                              │"
     "│                                                                              │"
@@ -500,7 +506,7 @@ fn changes_list_shows_item_count_in_title() {
     "│                                                                              │"
     "│                                                                              │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching]                                                               "
+    " dfft  [watching] [3 unreviewed]                                                "
     "#);
 }
 
@@ -510,7 +516,7 @@ fn changes_list_handles_long_file_paths() {
     let (mut terminal, terminal_dimensions) = get_test_terminal();
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -519,6 +525,7 @@ fn changes_list_handles_long_file_paths() {
         path: "a/very/long/path/to/a/file/that/goes/beyond/the/terminal/width/dimension.txt"
             .to_string(),
         kind: ChangeKind::Created(Ok("test file contents".to_string())),
+        encoding: None,
     };
     update(&mut model, Msg::ChangeReceived(change));
 
@@ -552,7 +559,7 @@ fn changes_list_handles_long_file_paths() {
     "│                                                                              │"
     "│                                                                              │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching]                                                               "
+    " dfft  [watching] [1 unreviewed]                                                "
     "#);
 }
 
@@ -562,7 +569,7 @@ fn status_line_shows_paused_status() {
     let (mut terminal, terminal_dimensions) = get_test_terminal();
     let mut model = Model::new(
         TuiBehaviours::default_for_test().with_watch_off(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -607,7 +614,7 @@ fn status_line_shows_following_changes_indicator() {
     let (mut terminal, terminal_dimensions) = get_test_terminal();
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -653,7 +660,7 @@ fn status_line_shows_info_message() {
     let (mut terminal, terminal_dimensions) = get_test_terminal();
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -699,7 +706,7 @@ fn status_line_shows_error_message() {
     let (mut terminal, terminal_dimensions) = get_test_terminal();
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -745,7 +752,7 @@ fn info_message_disappears_after_its_frame_budget_expires() {
     let (mut terminal, terminal_dimensions) = get_test_terminal();
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -822,7 +829,7 @@ fn cursor_moves_automatically_when_following_enabled() {
     let (mut terminal, terminal_dimensions) = get_test_terminal();
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -831,12 +838,14 @@ fn cursor_moves_automatically_when_following_enabled() {
     let change = Change {
         path: "first.txt".to_string(),
         kind: ChangeKind::Created(Ok("test file contents".to_string())),
+        encoding: None,
     };
     update(&mut model, Msg::ChangeReceived(change));
 
     let change = Change {
         path: "this-should-be-selected.txt".to_string(),
         kind: ChangeKind::Created(Ok("test file contents".to_string())),
+        encoding: None,
     };
     update(&mut model, Msg::ChangeReceived(change));
 
@@ -860,8 +869,8 @@ fn cursor_moves_automatically_when_following_enabled() {
     "└──────────────────────────────────────────────────────────────────────────────┘"
     "┌ changes  (2/2) ──────────────────────────────────────────────────────────────┐"
     "│                                                                              │"
-    "│   created   first.txt                                                        │"
-    "│>  created   this-should-be-selected.txt                                      │"
+    "│   created   first.txt  +1                                                    │"
+    "│>  created   this-should-be-selected.txt  +1                                  │"
     "│                                                                              │"
     "│                                                                              │"
     "│                                                                              │"
@@ -870,7 +879,7 @@ fn cursor_moves_automatically_when_following_enabled() {
     "│                                                                              │"
     "│                                                                              │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching] [following changes]                                           "
+    " dfft  [watching] [following changes] [2 unreviewed]                            "
     "#);
 }
 
@@ -880,7 +889,7 @@ fn cursor_moves_to_the_end_when_following_is_turned_on_after_a_while() {
     let (mut terminal, terminal_dimensions) = get_test_terminal();
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -891,6 +900,7 @@ fn cursor_moves_to_the_end_when_following_is_turned_on_after_a_while() {
         let change = Change {
             path: format!("file-{i}.txt"),
             kind: ChangeKind::Created(Ok("test file contents".to_string())),
+            encoding: None,
         };
         update(&mut model, Msg::ChangeReceived(change));
     }
@@ -911,9 +921,9 @@ fn cursor_moves_to_the_end_when_following_is_turned_on_after_a_while() {
     "└──────────────────────────────────────────────────────────────────────────────┘"
     "┌ changes  (1/3) ──────────────────────────────────────────────────────────────┐"
     "│                                                                              │"
-    "│>  created   file-1.txt                                                       │"
-    "│   created   file-2.txt                                                       │"
-    "│   created   file-3.txt                                                       │"
+    "│>  created   file-1.txt  +1                                                   │"
+    "│   created   file-2.txt  +1                                                   │"
+    "│   created   file-3.txt  +1                                                   │"
     "│                                                                              │"
     "│                                                                              │"
     "│                                                                              │"
@@ -921,13 +931,14 @@ fn cursor_moves_to_the_end_when_following_is_turned_on_after_a_while() {
     "│                                                                              │"
     "│                                                                              │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching]                                                               "
+    " dfft  [watching] [3 unreviewed]                                                "
     "#);
 
     update(&mut model, Msg::ToggleFollowChanges);
     let change = Change {
         path: "this-should-be-selected.txt".to_string(),
         kind: ChangeKind::Created(Ok("test file contents".to_string())),
+        encoding: None,
     };
     update(&mut model, Msg::ChangeReceived(change));
     terminal
@@ -947,17 +958,17 @@ fn cursor_moves_to_the_end_when_following_is_turned_on_after_a_while() {
     "└──────────────────────────────────────────────────────────────────────────────┘"
     "┌ changes  (4/4) ──────────────────────────────────────────────────────────────┐"
     "│                                                                              │"
-    "│   created   file-1.txt                                                       │"
-    "│   created   file-2.txt                                                       │"
-    "│   created   file-3.txt                                                       │"
-    "│>  created   this-should-be-selected.txt                                      │"
+    "│   created   file-1.txt  +1                                                   │"
+    "│   created   file-2.txt  +1                                                   │"
+    "│   created   file-3.txt  +1                                                   │"
+    "│>  created   this-should-be-selected.txt  +1                                  │"
     "│                                                                              │"
     "│                                                                              │"
     "│                                                                              │"
     "│                                                                              │"
     "│                                                                              │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching] [following changes]                                           "
+    " dfft  [watching] [following changes] [4 unreviewed]                            "
     "#);
 }
 
@@ -967,7 +978,7 @@ fn cursor_doesnt_move_by_itself_when_following_disabled() {
     let (mut terminal, terminal_dimensions) = get_test_terminal();
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -975,12 +986,14 @@ fn cursor_doesnt_move_by_itself_when_following_disabled() {
     let change = Change {
         path: "this-will-still-be-selected.txt".to_string(),
         kind: ChangeKind::Created(Ok("test file contents".to_string())),
+        encoding: None,
     };
     update(&mut model, Msg::ChangeReceived(change));
 
     let change = Change {
         path: "second.txt".to_string(),
         kind: ChangeKind::Created(Ok("test file contents".to_string())),
+        encoding: None,
     };
     update(&mut model, Msg::ChangeReceived(change));
 
@@ -1004,8 +1017,8 @@ fn cursor_doesnt_move_by_itself_when_following_disabled() {
     "└──────────────────────────────────────────────────────────────────────────────┘"
     "┌ changes  (1/2) ──────────────────────────────────────────────────────────────┐"
     "│                                                                              │"
-    "│>  created   this-will-still-be-selected.txt                                  │"
-    "│   created   second.txt                                                       │"
+    "│>  created   this-will-still-be-selected.txt  +1                              │"
+    "│   created   second.txt  +1                                                   │"
     "│                                                                              │"
     "│                                                                              │"
     "│                                                                              │"
@@ -1014,7 +1027,7 @@ fn cursor_doesnt_move_by_itself_when_following_disabled() {
     "│                                                                              │"
     "│                                                                              │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching]                                                               "
+    " dfft  [watching] [2 unreviewed]                                                "
     "#);
 }
 
@@ -1024,7 +1037,7 @@ fn scrolling_for_created_file_contents_doesnt_go_beyond_limits() {
     let (mut terminal, terminal_dimensions) = get_test_terminal();
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -1036,6 +1049,7 @@ fn scrolling_for_created_file_contents_doesnt_go_beyond_limits() {
     let change = Change {
         path: "created.txt".to_string(),
         kind: ChangeKind::Created(Ok(contents)),
+        encoding: None,
     };
     update(&mut model, Msg::ChangeReceived(change));
 
@@ -1061,7 +1075,7 @@ fn scrolling_for_created_file_contents_doesnt_go_beyond_limits() {
     "└──────────────────────────────────────────────────────────────────────────────┘"
     "┌ changes  (1/1) ──────────────────────────────────────────────────────────────┐"
     "│                                                                              │"
-    "│>  created   created.txt                                                      │"
+    "│>  created   created.txt  +10                                                 │"
     "│                                                                              │"
     "│                                                                              │"
     "│                                                                              │"
@@ -1071,7 +1085,7 @@ fn scrolling_for_created_file_contents_doesnt_go_beyond_limits() {
     "│                                                                              │"
     "│                                                                              │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching]                                                               "
+    " dfft  [watching] [1 unreviewed]                                                "
     "#);
 
     for _ in 1..=20 {
@@ -1094,7 +1108,7 @@ fn scrolling_for_created_file_contents_doesnt_go_beyond_limits() {
     "└──────────────────────────────────────────────────────────────────────────────┘"
     "┌ changes  (1/1) ──────────────────────────────────────────────────────────────┐"
     "│                                                                              │"
-    "│>  created   created.txt                                                      │"
+    "│>  created   created.txt  +10                                                 │"
     "│                                                                              │"
     "│                                                                              │"
     "│                                                                              │"
@@ -1104,7 +1118,7 @@ fn scrolling_for_created_file_contents_doesnt_go_beyond_limits() {
     "│                                                                              │"
     "│                                                                              │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching]                                                               "
+    " dfft  [watching] [1 unreviewed]                                                "
     "#);
 }
 
@@ -1114,7 +1128,7 @@ fn max_scroll_for_created_file_contents_is_recomputed_when_terminal_height_chang
     let (mut terminal, terminal_dimensions) = get_test_terminal_with_dims(80, 30);
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -1126,6 +1140,7 @@ fn max_scroll_for_created_file_contents_is_recomputed_when_terminal_height_chang
     let change = Change {
         path: "created.txt".to_string(),
         kind: ChangeKind::Created(Ok(contents)),
+        encoding: None,
     };
     update(&mut model, Msg::ChangeReceived(change));
 
@@ -1157,7 +1172,7 @@ fn max_scroll_for_created_file_contents_is_recomputed_when_terminal_height_chang
     "└──────────────────────────────────────────────────────────────────────────────┘"
     "┌ changes  (1/1) ──────────────────────────────────────────────────────────────┐"
     "│                                                                              │"
-    "│>  created   created.txt                                                      │"
+    "│>  created   created.txt  +20                                                 │"
     "│                                                                              │"
     "│                                                                              │"
     "│                                                                              │"
@@ -1167,7 +1182,7 @@ fn max_scroll_for_created_file_contents_is_recomputed_when_terminal_height_chang
     "│                                                                              │"
     "│                                                                              │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching]                                                               "
+    " dfft  [watching] [1 unreviewed]                                                "
     "#);
 
     let (mut new_terminal, new_terminal_dimensions) = get_test_terminal();
@@ -1195,7 +1210,7 @@ fn max_scroll_for_created_file_contents_is_recomputed_when_terminal_height_chang
     "└──────────────────────────────────────────────────────────────────────────────┘"
     "┌ changes  (1/1) ──────────────────────────────────────────────────────────────┐"
     "│                                                                              │"
-    "│>  created   created.txt                                                      │"
+    "│>  created   created.txt  +20                                                 │"
     "│                                                                              │"
     "│                                                                              │"
     "│                                                                              │"
@@ -1205,7 +1220,7 @@ fn max_scroll_for_created_file_contents_is_recomputed_when_terminal_height_chang
     "│                                                                              │"
     "│                                                                              │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching]                                                               "
+    " dfft  [watching] [1 unreviewed]                                                "
     "#);
 
     for _ in 1..=20 {
@@ -1229,7 +1244,7 @@ fn max_scroll_for_created_file_contents_is_recomputed_when_terminal_height_chang
     "└──────────────────────────────────────────────────────────────────────────────┘"
     "┌ changes  (1/1) ──────────────────────────────────────────────────────────────┐"
     "│                                                                              │"
-    "│>  created   created.txt                                                      │"
+    "│>  created   created.txt  +20                                                 │"
     "│                                                                              │"
     "│                                                                              │"
     "│                                                                              │"
@@ -1239,7 +1254,7 @@ fn max_scroll_for_created_file_contents_is_recomputed_when_terminal_height_chang
     "│                                                                              │"
     "│                                                                              │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching]                                                               "
+    " dfft  [watching] [1 unreviewed]                                                "
     "#);
 }
 
@@ -1250,7 +1265,7 @@ fn sound_unavailable_indicator_is_shown_when_applicable() {
     let (mut terminal, terminal_dimensions) = get_test_terminal_with_dims(90, 24);
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -1297,7 +1312,7 @@ fn showing_debug_info_works() {
     let (mut terminal, terminal_dimensions) = get_test_terminal_with_dims(90, 24);
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         true,
     );
@@ -1309,6 +1324,7 @@ fn showing_debug_info_works() {
     let change = Change {
         path: "created.txt".to_string(),
         kind: ChangeKind::Created(Ok(contents)),
+        encoding: None,
     };
     update(&mut model, Msg::ChangeReceived(change));
 
@@ -1332,7 +1348,7 @@ fn showing_debug_info_works() {
     "└────────────────────────────────────────────────────────────────────────────────────────┘"
     "┌ changes  (1/1) ────────────────────────────────────────────────────────────────────────┐"
     "│                                                                                        │"
-    "│>  created   created.txt                                                                │"
+    "│>  created   created.txt  +5                                                            │"
     "│                                                                                        │"
     "│                                                                                        │"
     "│                                                                                        │"
@@ -1342,6 +1358,6 @@ fn showing_debug_info_works() {
     "│                                                                                        │"
     "│                                                                                        │"
     "└────────────────────────────────────────────────────────────────────────────────────────┘"
-    " dfft  [watching] [render: 0] [event: 0] [watch: 0] [dimensions: 90x24]                   "
+    " dfft  [watching] [1 unreviewed] [render: 0] [event: 0] [watch: 0] [dimensions: 90x24] [qu"
     "#);
 }
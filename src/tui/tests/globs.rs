@@ -0,0 +1,138 @@
+use super::super::TuiBehaviours;
+use super::super::cmd::Cmd;
+use super::super::model::{MessageKind, Model};
+use super::super::{msg::Msg, update::update, view::view};
+use super::helpers::{get_model, get_test_terminal};
+use crate::domain::PathFilter;
+use insta::assert_snapshot;
+use std::path::PathBuf;
+
+fn get_model_with_path_filter(path_filter: PathFilter) -> Model {
+    let mut behaviours = TuiBehaviours::default_for_test();
+    behaviours.path_filter = path_filter;
+
+    get_model(behaviours, vec![PathBuf::new()])
+}
+
+fn type_input(model: &mut Model, input: &str) {
+    for c in input.chars() {
+        update(model, Msg::EnterPromptInput(c));
+    }
+}
+
+#[test]
+fn globs_prompt_starts_with_the_globs_in_effect() {
+    // GIVEN
+    let (mut terminal, _) = get_test_terminal();
+    let mut model = get_model_with_path_filter(
+        PathFilter::new(vec!["src/**/*.rs".to_string()], vec![])
+            .expect("filter should've been built"),
+    );
+
+    // WHEN
+    update(&mut model, Msg::StartPathFilterEdit);
+    type_input(&mut model, " !docs");
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff ────────────────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                     dfft                                     │"
+    "│                                     ‾‾‾‾                                     │"
+    "│                                                                              │"
+    "│            will show you changes in this directory as they happen            │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes ─────────────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│ changes will appear here                                                     │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " globs  src/**/*.rs !docs  (separated by spaces, ! to exclude)                  "
+    "#);
+}
+
+#[test]
+fn watchers_pick_up_globs_changed_from_the_prompt() {
+    // GIVEN
+    let (mut terminal, _) = get_test_terminal();
+    let mut model = get_model_with_path_filter(PathFilter::default());
+    let cmds = model.watch_cmds();
+    let [Cmd::WatchForChanges { filter, .. }] = cmds.as_slice() else {
+        panic!("a single directory should've been watched");
+    };
+
+    // WHEN
+    update(&mut model, Msg::StartPathFilterEdit);
+    type_input(&mut model, "src !src/generated");
+    update(&mut model, Msg::SubmitPrompt);
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert!(filter.borrow().matches("src/main.rs"));
+    assert!(!filter.borrow().matches("src/generated/schema.rs"));
+    assert!(!filter.borrow().matches("README.md"));
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff ────────────────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                     dfft                                     │"
+    "│                                     ‾‾‾‾                                     │"
+    "│                                                                              │"
+    "│            will show you changes in this directory as they happen            │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes ─────────────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│ changes will appear here                                                     │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [globs: src !src/generated]                                   "
+    "#);
+}
+
+#[test]
+fn invalid_globs_leave_the_globs_in_effect_unchanged() {
+    // GIVEN
+    let mut model = get_model_with_path_filter(
+        PathFilter::new(vec![], vec!["docs".to_string()]).expect("filter should've been built"),
+    );
+
+    // WHEN
+    update(&mut model, Msg::StartPathFilterEdit);
+    type_input(&mut model, " src/[a-");
+    update(&mut model, Msg::SubmitPrompt);
+
+    // THEN
+    assert_eq!(model.path_filter().as_deref(), Some("!docs"));
+    let user_msg = model.user_msg.as_ref().expect("error should've been shown");
+    assert!(matches!(user_msg.kind, MessageKind::Error));
+    assert_eq!(
+        user_msg.value,
+        r#"invalid glob "src/[a-": error parsing glob 'src/[a-': unclosed character class; missing ']'"#
+    );
+}
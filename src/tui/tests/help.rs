@@ -16,7 +16,7 @@ fn rendering_help_pane_works() {
 
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -34,10 +34,16 @@ fn rendering_help_pane_works() {
     "│ Keymaps                                                                      │"
     "│ ---                                                                          │"
     "│                                                                              │"
+    "│ Mouse                                                                        │"
+    "│     <click>              focus pane/select change                            │"
+    "│     <scroll>             scroll pane under cursor                            │"
+    "│                                                                              │"
     "│ General                                                                      │"
     "│     ?                    show/hide help view                                 │"
-    "│     Esc / q              go back/exit                                        │"
-    "│     <ctrl+c>             exit immediately                                    │"
+    "│     <esc> / q            go back/exit                                        │"
+    "│     <c-c>                exit immediately                                    │"
+    "│     S                    show/hide session summary                           │"
+    "│     R                    show/hide comments                                  │"
     "│                                                                              │"
     "│ Diff Pane                                                                    │"
     "│     j / ↓                select next change                                  │"
@@ -46,37 +52,31 @@ fn rendering_help_pane_works() {
     "│     K                    scroll diff up by a line                            │"
     "│     <c-d>                scroll diff down by half page                       │"
     "│     <c-u>                scroll diff up by half page                         │"
+    "│     h / ←                scroll diff left                                    │"
+    "│     l / →                scroll diff right                                   │"
+    "│     w                    toggle wrapping long lines                          │"
+    "│     v                    toggle full file view                               │"
+    "│     /                    search diff (regex; smart case)                     │"
+    "│     n                    go to next search match                             │"
+    "│     N                    go to previous search match                         │"
+    "│     ]                    go to next hunk                                     │"
+    "│     [                    go to previous hunk                                 │"
+    "│     }                    go to next changed line                             │"
+    "│     {                    go to previous changed line                         │"
+    "│     T                    toggle grouping changes by file                     │"
+    "│     r                    toggle marking change as reviewed                   │"
+    "│     u                    select next unreviewed change                       │"
+    "│     c                    comment on line in focus                            │"
+    "│     e                    open change in editor                               │"
+    "│     y                    copy path                                           │"
+    "│     Y                    copy diff (or contents of created file)             │"
+    "│     <c-y>                copy hunk in focus                                  │"
     "│     g                    select first change                                 │"
     "│     G                    select last change                                  │"
     "│     <space>              toggle watching                                     │"
+    "│     F                    change globs for files to watch                     │"
     "│     <c-r>                reset list                                          │"
     "│     f                    toggle following changes                            │"
-    "│     s                    toggle sound notifications                          │"
-    "│     <tab>/<s-tab>        switch to changes pane                              │"
-    "│                                                                              │"
-    "│ Changes Pane                                                                 │"
-    "│     j / ↓                select next change                                  │"
-    "│     k / ↑                select previous change                              │"
-    "│     g                    select first change                                 │"
-    "│     G                    select last change                                  │"
-    "│     J                    scroll diff down by a line                          │"
-    "│     K                    scroll diff up by a line                            │"
-    "│     <c-d>                scroll diff down by half page                       │"
-    "│     <c-u>                scroll diff up by half page                         │"
-    "│     f                    toggle following changes                            │"
-    "│     s                    toggle sound notifications                          │"
-    "│     <c-r>                reset list                                          │"
-    "│     <space>              toggle watching                                     │"
-    "│     <tab>/<s-tab>        switch to diff pane                                 │"
-    "│                                                                              │"
-    "│ Help Pane                                                                    │"
-    "│     j / ↓                scroll down                                         │"
-    "│     k / ↑                scroll up                                           │"
-    "│                                                                              │"
-    "│                                                                              │"
-    "│                                                                              │"
-    "│                                                                              │"
-    "│                                                                              │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
     " dfft  [watching]                                                               "
     "#);
@@ -90,7 +90,7 @@ fn help_doesnt_show_keymaps_for_sound_if_feature_is_off() {
 
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -108,10 +108,16 @@ fn help_doesnt_show_keymaps_for_sound_if_feature_is_off() {
     "│ Keymaps                                                                      │"
     "│ ---                                                                          │"
     "│                                                                              │"
+    "│ Mouse                                                                        │"
+    "│     <click>              focus pane/select change                            │"
+    "│     <scroll>             scroll pane under cursor                            │"
+    "│                                                                              │"
     "│ General                                                                      │"
     "│     ?                    show/hide help view                                 │"
-    "│     Esc / q              go back/exit                                        │"
-    "│     <ctrl+c>             exit immediately                                    │"
+    "│     <esc> / q            go back/exit                                        │"
+    "│     <c-c>                exit immediately                                    │"
+    "│     S                    show/hide session summary                           │"
+    "│     R                    show/hide comments                                  │"
     "│                                                                              │"
     "│ Diff Pane                                                                    │"
     "│     j / ↓                select next change                                  │"
@@ -120,35 +126,29 @@ fn help_doesnt_show_keymaps_for_sound_if_feature_is_off() {
     "│     K                    scroll diff up by a line                            │"
     "│     <c-d>                scroll diff down by half page                       │"
     "│     <c-u>                scroll diff up by half page                         │"
+    "│     h / ←                scroll diff left                                    │"
+    "│     l / →                scroll diff right                                   │"
+    "│     w                    toggle wrapping long lines                          │"
+    "│     v                    toggle full file view                               │"
+    "│     /                    search diff (regex; smart case)                     │"
+    "│     n                    go to next search match                             │"
+    "│     N                    go to previous search match                         │"
+    "│     ]                    go to next hunk                                     │"
+    "│     [                    go to previous hunk                                 │"
+    "│     }                    go to next changed line                             │"
+    "│     {                    go to previous changed line                         │"
+    "│     T                    toggle grouping changes by file                     │"
+    "│     r                    toggle marking change as reviewed                   │"
+    "│     u                    select next unreviewed change                       │"
+    "│     c                    comment on line in focus                            │"
+    "│     e                    open change in editor                               │"
+    "│     y                    copy path                                           │"
+    "│     Y                    copy diff (or contents of created file)             │"
+    "│     <c-y>                copy hunk in focus                                  │"
     "│     g                    select first change                                 │"
     "│     G                    select last change                                  │"
     "│     <space>              toggle watching                                     │"
-    "│     <c-r>                reset list                                          │"
-    "│     f                    toggle following changes                            │"
-    "│     <tab>/<s-tab>        switch to changes pane                              │"
-    "│                                                                              │"
-    "│ Changes Pane                                                                 │"
-    "│     j / ↓                select next change                                  │"
-    "│     k / ↑                select previous change                              │"
-    "│     g                    select first change                                 │"
-    "│     G                    select last change                                  │"
-    "│     J                    scroll diff down by a line                          │"
-    "│     K                    scroll diff up by a line                            │"
-    "│     <c-d>                scroll diff down by half page                       │"
-    "│     <c-u>                scroll diff up by half page                         │"
-    "│     f                    toggle following changes                            │"
-    "│     <c-r>                reset list                                          │"
-    "│     <space>              toggle watching                                     │"
-    "│     <tab>/<s-tab>        switch to diff pane                                 │"
-    "│                                                                              │"
-    "│ Help Pane                                                                    │"
-    "│     j / ↓                scroll down                                         │"
-    "│     k / ↑                scroll up                                           │"
-    "│                                                                              │"
-    "│                                                                              │"
-    "│                                                                              │"
-    "│                                                                              │"
-    "│                                                                              │"
+    "│     F                    change globs for files to watch                     │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
     " dfft  [watching]                                                               "
     "#);
@@ -162,7 +162,7 @@ fn scrolling_help_pane_works() {
 
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -181,9 +181,15 @@ fn scrolling_help_pane_works() {
     assert_snapshot!(terminal.backend(), @r#"
     "┌ help ────────────────────────────────────────────────────────────────────────┐"
     "│                                                                              │"
+    "│     <click>              focus pane/select change                            │"
+    "│     <scroll>             scroll pane under cursor                            │"
+    "│                                                                              │"
+    "│ General                                                                      │"
     "│     ?                    show/hide help view                                 │"
-    "│     Esc / q              go back/exit                                        │"
-    "│     <ctrl+c>             exit immediately                                    │"
+    "│     <esc> / q            go back/exit                                        │"
+    "│     <c-c>                exit immediately                                    │"
+    "│     S                    show/hide session summary                           │"
+    "│     R                    show/hide comments                                  │"
     "│                                                                              │"
     "│ Diff Pane                                                                    │"
     "│     j / ↓                select next change                                  │"
@@ -192,15 +198,9 @@ fn scrolling_help_pane_works() {
     "│     K                    scroll diff up by a line                            │"
     "│     <c-d>                scroll diff down by half page                       │"
     "│     <c-u>                scroll diff up by half page                         │"
-    "│     g                    select first change                                 │"
-    "│     G                    select last change                                  │"
-    "│     <space>              toggle watching                                     │"
-    "│     <c-r>                reset list                                          │"
-    "│     f                    toggle following changes                            │"
-    "│     s                    toggle sound notifications                          │"
-    "│     <tab>/<s-tab>        switch to changes pane                              │"
-    "│                                                                              │"
-    "│ Changes Pane                                                                 │"
+    "│     h / ←                scroll diff left                                    │"
+    "│     l / →                scroll diff right                                   │"
+    "│     w                    toggle wrapping long lines                          │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
     " dfft  [watching]                                                               "
     "#);
@@ -214,7 +214,7 @@ fn help_pane_doesnt_scroll_beyond_limits() {
 
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -235,10 +235,16 @@ fn help_pane_doesnt_scroll_beyond_limits() {
     "│ Keymaps                                                                      │"
     "│ ---                                                                          │"
     "│                                                                              │"
+    "│ Mouse                                                                        │"
+    "│     <click>              focus pane/select change                            │"
+    "│     <scroll>             scroll pane under cursor                            │"
+    "│                                                                              │"
     "│ General                                                                      │"
     "│     ?                    show/hide help view                                 │"
-    "│     Esc / q              go back/exit                                        │"
-    "│     <ctrl+c>             exit immediately                                    │"
+    "│     <esc> / q            go back/exit                                        │"
+    "│     <c-c>                exit immediately                                    │"
+    "│     S                    show/hide session summary                           │"
+    "│     R                    show/hide comments                                  │"
     "│                                                                              │"
     "│ Diff Pane                                                                    │"
     "│     j / ↓                select next change                                  │"
@@ -246,17 +252,11 @@ fn help_pane_doesnt_scroll_beyond_limits() {
     "│     J                    scroll diff down by a line                          │"
     "│     K                    scroll diff up by a line                            │"
     "│     <c-d>                scroll diff down by half page                       │"
-    "│     <c-u>                scroll diff up by half page                         │"
-    "│     g                    select first change                                 │"
-    "│     G                    select last change                                  │"
-    "│     <space>              toggle watching                                     │"
-    "│     <c-r>                reset list                                          │"
-    "│     f                    toggle following changes                            │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
     " dfft  [watching]                                                               "
     "#);
 
-    for _ in 1..=20 {
+    for _ in 1..=100 {
         update(&mut model, Msg::ScrollDown);
     }
     terminal
@@ -265,26 +265,26 @@ fn help_pane_doesnt_scroll_beyond_limits() {
     assert_snapshot!(terminal.backend(), @r#"
     "┌ help ────────────────────────────────────────────────────────────────────────┐"
     "│                                                                              │"
-    "│     s                    toggle sound notifications                          │"
-    "│     <tab>/<s-tab>        switch to changes pane                              │"
+    "│     <tab>                cycle search scope/kind of changes/comment target   │"
+    "│     <enter>              confirm                                             │"
+    "│     <esc>                cancel                                              │"
+    "│     <bs>                 delete last character                               │"
     "│                                                                              │"
-    "│ Changes Pane                                                                 │"
-    "│     j / ↓                select next change                                  │"
-    "│     k / ↑                select previous change                              │"
-    "│     g                    select first change                                 │"
-    "│     G                    select last change                                  │"
-    "│     J                    scroll diff down by a line                          │"
-    "│     K                    scroll diff up by a line                            │"
-    "│     <c-d>                scroll diff down by half page                       │"
-    "│     <c-u>                scroll diff up by half page                         │"
-    "│     f                    toggle following changes                            │"
-    "│     s                    toggle sound notifications                          │"
-    "│     <c-r>                reset list                                          │"
-    "│     <space>              toggle watching                                     │"
-    "│     <tab>/<s-tab>        switch to diff pane                                 │"
+    "│ Summary Pane                                                                 │"
+    "│     j / ↓                scroll down                                         │"
+    "│     k / ↑                scroll up                                           │"
+    "│     o                    cycle sort order (churn/changes/path)               │"
+    "│                                                                              │"
+    "│ Comments Pane                                                                │"
+    "│     j / ↓                select next comment                                 │"
+    "│     k / ↑                select previous comment                             │"
+    "│     <enter>              go to comment                                       │"
+    "│     d                    delete comment                                      │"
+    "│     E                    export comments as markdown                         │"
     "│                                                                              │"
     "│ Help Pane                                                                    │"
     "│     j / ↓                scroll down                                         │"
+    "│     k / ↑                scroll up                                           │"
     "└──────────────────────────────────────────────────────────────────────────────┘"
     " dfft  [watching]                                                               "
     "#);
@@ -297,7 +297,7 @@ fn max_scroll_for_help_doesnt_change_when_only_terminal_width_changes() {
     let (width, height) = terminal_dimensions.values();
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -317,7 +317,7 @@ fn max_scroll_for_help_is_recomputed_when_terminal_size_crosses_minimum_threshol
     let (width, height) = terminal_dimensions.values();
     let mut model = Model::new(
         TuiBehaviours::default_for_test(),
-        PathBuf::new(),
+        vec![PathBuf::new()],
         terminal_dimensions,
         false,
     );
@@ -1,5 +1,10 @@
+use super::super::TuiBehaviours;
 use super::super::common::{MIN_TERMINAL_HEIGHT, MIN_TERMINAL_WIDTH, TerminalDimensions};
+use super::super::model::Model;
+use super::super::{msg::Msg, update::update};
+use crate::domain::{Change, ChangeKind, Diff, Modification};
 use ratatui::{Terminal, backend::TestBackend};
+use std::path::PathBuf;
 
 pub(super) fn get_test_terminal() -> (Terminal<TestBackend>, TerminalDimensions) {
     let terminal = Terminal::new(TestBackend::new(MIN_TERMINAL_WIDTH, MIN_TERMINAL_HEIGHT))
@@ -19,3 +24,30 @@ pub(super) fn get_test_terminal_with_dims(
 
     (terminal, terminal_dimensions)
 }
+
+pub(super) fn modification(path: &str, old: &str, new: &str) -> Change {
+    Change {
+        path: path.to_string(),
+        kind: ChangeKind::Modified(Ok(Modification::Diff(
+            Diff::new(old, new).expect("diff should've been created"),
+        ))),
+        encoding: None,
+    }
+}
+
+pub(super) fn get_model(behaviours: TuiBehaviours, roots: Vec<PathBuf>) -> Model {
+    let (_, terminal_dimensions) = get_test_terminal();
+
+    Model::new(behaviours, roots, terminal_dimensions, false)
+}
+
+// A model for a single root, which has received the changes
+pub(super) fn get_model_with_changes(behaviours: TuiBehaviours, changes: Vec<Change>) -> Model {
+    let mut model = get_model(behaviours, vec![PathBuf::new()]);
+
+    for change in changes {
+        update(&mut model, Msg::ChangeReceived(change));
+    }
+
+    model
+}
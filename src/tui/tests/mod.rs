@@ -1,5 +1,18 @@
 mod change;
+mod clipboard;
+mod comments;
 mod diff;
+mod editor;
+mod filter;
 mod general;
+mod globs;
 mod help;
 mod helpers;
+mod mouse;
+mod polling;
+mod review;
+mod roots;
+mod search;
+mod summary;
+mod theme;
+mod tree;
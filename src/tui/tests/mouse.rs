@@ -0,0 +1,152 @@
+use super::super::TuiBehaviours;
+use super::super::common::Pane;
+use super::super::model::Model;
+use super::super::msg::{Msg, get_event_handling_msg};
+use super::super::update::update;
+use super::helpers::{get_model_with_changes, modification};
+use ratatui::crossterm::event::{Event, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+
+// with the test terminal's height of 24, the diff pane spans rows 0-10, the changes pane's
+// entries start at row 13, and the status line is at row 23
+
+// a file of numbered lines, before and after its first line is changed
+fn numbered_lines(num_lines: usize) -> (String, String) {
+    let old = (1..=num_lines)
+        .map(|i| format!("line {i}\n"))
+        .collect::<String>();
+    let new = old.replace("line 1\n", "line 1 (changed)\n");
+
+    (old, new)
+}
+
+fn get_model_with_file_changes() -> Model {
+    let (old, new) = numbered_lines(3);
+    let changes = ["src/main.rs", "src/lib.rs", "src/app.rs"]
+        .into_iter()
+        .map(|path| modification(path, &old, &new))
+        .collect();
+    let mut model = get_model_with_changes(TuiBehaviours::default_for_test(), changes);
+    model.active_pane = Pane::Diff;
+
+    model
+}
+
+fn mouse(model: &mut Model, kind: MouseEventKind, row: u16) {
+    let event = Event::Mouse(MouseEvent {
+        kind,
+        column: 10,
+        row,
+        modifiers: KeyModifiers::NONE,
+    });
+    if let Some(msg) = get_event_handling_msg(model, event) {
+        update(model, msg);
+    }
+}
+
+#[test]
+fn clicking_a_change_selects_it() {
+    // GIVEN
+    let mut model = get_model_with_file_changes();
+
+    // WHEN
+    mouse(&mut model, MouseEventKind::Down(MouseButton::Left), 15);
+
+    // THEN
+    assert_eq!(model.active_pane, Pane::Changes);
+    assert_eq!(model.changes.state.selected(), Some(2));
+    assert_eq!(model.current_file_path(), Some("src/app.rs"));
+}
+
+#[test]
+fn clicking_outside_of_changes_only_focuses_the_pane() {
+    // GIVEN
+    let mut model = get_model_with_file_changes();
+
+    // WHEN
+    mouse(&mut model, MouseEventKind::Down(MouseButton::Left), 12);
+    let pane_after_padding_click = model.active_pane;
+    mouse(&mut model, MouseEventKind::Down(MouseButton::Left), 17);
+
+    // THEN
+    assert_eq!(pane_after_padding_click, Pane::Changes);
+    assert_eq!(model.changes.state.selected(), Some(0));
+}
+
+#[test]
+fn clicking_the_diff_pane_focuses_it() {
+    // GIVEN
+    let mut model = get_model_with_file_changes();
+    model.active_pane = Pane::Changes;
+
+    // WHEN
+    mouse(&mut model, MouseEventKind::Down(MouseButton::Left), 5);
+
+    // THEN
+    assert_eq!(model.active_pane, Pane::Diff);
+}
+
+#[test]
+fn scrolling_over_the_diff_pane_scrolls_the_diff() {
+    // GIVEN
+    let mut model = get_model_with_file_changes();
+    let (old, new) = numbered_lines(30);
+    update(
+        &mut model,
+        Msg::ChangeReceived(modification("src/long.rs", &old, &new)),
+    );
+    update(&mut model, Msg::SelectLast);
+    update(&mut model, Msg::ToggleFullFileView);
+
+    // WHEN
+    mouse(&mut model, MouseEventKind::ScrollDown, 5);
+    mouse(&mut model, MouseEventKind::ScrollDown, 5);
+    let scroll_after_scrolling_down = model.diff_scroll;
+    mouse(&mut model, MouseEventKind::ScrollUp, 5);
+
+    // THEN
+    assert_eq!(scroll_after_scrolling_down, 6);
+    assert_eq!(model.diff_scroll, 3);
+    assert_eq!(model.changes.state.selected(), Some(3));
+}
+
+#[test]
+fn scrolling_over_the_changes_pane_changes_the_selection() {
+    // GIVEN
+    let mut model = get_model_with_file_changes();
+
+    // WHEN
+    mouse(&mut model, MouseEventKind::ScrollDown, 14);
+    mouse(&mut model, MouseEventKind::ScrollDown, 14);
+    mouse(&mut model, MouseEventKind::ScrollUp, 14);
+
+    // THEN
+    assert_eq!(model.active_pane, Pane::Diff);
+    assert_eq!(model.changes.state.selected(), Some(1));
+}
+
+#[test]
+fn scrolling_over_the_help_pane_scrolls_it() {
+    // GIVEN
+    let mut model = get_model_with_file_changes();
+    update(&mut model, Msg::GoToPane(Pane::Help));
+
+    // WHEN
+    mouse(&mut model, MouseEventKind::ScrollDown, 10);
+
+    // THEN
+    assert_eq!(model.help_scroll, 3);
+}
+
+#[test]
+fn mouse_events_on_the_status_line_are_ignored() {
+    // GIVEN
+    let mut model = get_model_with_file_changes();
+
+    // WHEN
+    mouse(&mut model, MouseEventKind::Down(MouseButton::Left), 23);
+    mouse(&mut model, MouseEventKind::ScrollDown, 23);
+
+    // THEN
+    assert_eq!(model.active_pane, Pane::Diff);
+    assert_eq!(model.changes.state.selected(), Some(0));
+}
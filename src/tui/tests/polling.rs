@@ -0,0 +1,90 @@
+use super::super::TuiBehaviours;
+use super::super::{msg::Msg, update::update, view::view};
+use super::helpers::{get_model, get_test_terminal};
+use insta::assert_snapshot;
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[test]
+fn polling_is_suggested_for_roots_on_network_file_systems() {
+    // GIVEN
+    let (mut terminal, _) = get_test_terminal();
+    let mut model = get_model(TuiBehaviours::default_for_test(), vec![PathBuf::new()]);
+
+    // WHEN
+    update(&mut model, Msg::PollingSuggested("on nfs4".to_string()));
+    update(&mut model, Msg::PollingSuggested("on nfs4".to_string()));
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff ────────────────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                     dfft                                     │"
+    "│                                     ‾‾‾‾                                     │"
+    "│                                                                              │"
+    "│            will show you changes in this directory as they happen            │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes ─────────────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│ changes will appear here                                                     │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [on nfs4, try --poll]                                         "
+    "#);
+}
+
+#[test]
+fn status_bar_shows_when_polling() {
+    // GIVEN
+    let (mut terminal, _) = get_test_terminal();
+    let mut behaviours = TuiBehaviours::default_for_test();
+    behaviours.watch_settings.poll_interval = Some(Duration::from_millis(1000));
+    let mut model = get_model(behaviours, vec![PathBuf::new()]);
+
+    // WHEN
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff ────────────────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                     dfft                                     │"
+    "│                                     ‾‾‾‾                                     │"
+    "│                                                                              │"
+    "│            will show you changes in this directory as they happen            │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes ─────────────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│ changes will appear here                                                     │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [polling]                                                     "
+    "#);
+}
@@ -0,0 +1,218 @@
+use super::super::TuiBehaviours;
+use super::super::common::Pane;
+use super::super::model::Model;
+use super::super::{msg::Msg, update::update, view::view};
+use super::helpers::{get_model_with_changes, get_test_terminal, modification};
+use insta::assert_snapshot;
+use std::time::{Duration, Instant};
+
+fn get_model_with_file_changes(behaviours: TuiBehaviours) -> Model {
+    let changes = vec![
+        modification("src/main.rs", "a\n", "b\n"),
+        modification("src/lib.rs", "a\n", "b\n"),
+        modification("src/main.rs", "b\n", "c\n"),
+    ];
+    let mut model = get_model_with_changes(behaviours, changes);
+    model.active_pane = Pane::Changes;
+
+    model
+}
+
+fn reviewed(model: &Model) -> Vec<bool> {
+    model.changes.items.iter().map(|i| i.reviewed).collect()
+}
+
+#[test]
+fn marking_changes_as_reviewed_updates_unreviewed_count() {
+    // GIVEN
+    let (mut terminal, _) = get_test_terminal();
+    let mut model = get_model_with_file_changes(TuiBehaviours::default_for_test());
+
+    // WHEN
+    update(&mut model, Msg::ToggleReviewed);
+    update(&mut model, Msg::SelectNext);
+    update(&mut model, Msg::ToggleReviewed);
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_eq!(reviewed(&model), vec![true, true, false]);
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff  src/lib.rs [hunk 1/1] ─────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│ 1       |-a                                                                  │"
+    "│     1   |+b                                                                  │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes  (2/3) ──────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│   modified  src/main.rs  +1 -1                                               │"
+    "│>  modified  src/lib.rs  +1 -1                                                │"
+    "│   modified  src/main.rs  +1 -1                                               │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [1 unreviewed]                                                "
+    "#);
+}
+
+#[test]
+fn toggling_reviewed_twice_marks_change_as_unreviewed() {
+    // GIVEN
+    let mut model = get_model_with_file_changes(TuiBehaviours::default_for_test());
+
+    // WHEN
+    update(&mut model, Msg::ToggleReviewed);
+    update(&mut model, Msg::ToggleReviewed);
+
+    // THEN
+    assert_eq!(reviewed(&model), vec![false, false, false]);
+    assert_eq!(model.num_unreviewed(), 3);
+}
+
+#[test]
+fn jumping_to_next_unreviewed_change_wraps_around() {
+    // GIVEN
+    let mut model = get_model_with_file_changes(TuiBehaviours::default_for_test());
+    update(&mut model, Msg::SelectNext);
+    update(&mut model, Msg::ToggleReviewed);
+    update(&mut model, Msg::SelectNext);
+    update(&mut model, Msg::ToggleReviewed);
+
+    // WHEN
+    update(&mut model, Msg::SelectNextUnreviewed);
+
+    // THEN
+    assert_eq!(model.changes.state.selected(), Some(0));
+
+    // WHEN
+    update(&mut model, Msg::ToggleReviewed);
+    update(&mut model, Msg::SelectNextUnreviewed);
+
+    // THEN
+    assert_eq!(model.changes.state.selected(), Some(0));
+    assert_eq!(
+        model.user_msg.as_ref().map(|m| m.value.as_str()),
+        Some("no unreviewed changes")
+    );
+}
+
+#[test]
+fn changes_are_marked_as_reviewed_after_being_viewed_for_long_enough() {
+    // GIVEN
+    let mut behaviours = TuiBehaviours::default_for_test();
+    behaviours.auto_review_after = Some(Duration::from_secs(3));
+    let mut model = get_model_with_file_changes(behaviours);
+
+    // WHEN
+    update(
+        &mut model,
+        Msg::Tick(Instant::now() + Duration::from_secs(1)),
+    );
+
+    // THEN
+    assert_eq!(reviewed(&model), vec![false, false, false]);
+    assert!(model.is_awaiting_auto_review());
+
+    // WHEN
+    update(
+        &mut model,
+        Msg::Tick(Instant::now() + Duration::from_secs(5)),
+    );
+
+    // THEN
+    assert_eq!(reviewed(&model), vec![true, false, false]);
+    assert!(!model.is_awaiting_auto_review());
+}
+
+#[test]
+fn changes_marked_as_unreviewed_are_not_reviewed_automatically() {
+    // GIVEN
+    let mut behaviours = TuiBehaviours::default_for_test();
+    behaviours.auto_review_after = Some(Duration::from_secs(3));
+    let mut model = get_model_with_file_changes(behaviours);
+    update(&mut model, Msg::ToggleReviewed);
+    update(&mut model, Msg::ToggleReviewed);
+
+    // WHEN
+    update(
+        &mut model,
+        Msg::Tick(Instant::now() + Duration::from_secs(5)),
+    );
+
+    // THEN
+    assert_eq!(reviewed(&model), vec![false, false, false]);
+}
+
+#[test]
+fn reviewing_a_file_in_grouped_view_reviews_all_its_changes() {
+    // GIVEN
+    let (mut terminal, _) = get_test_terminal();
+    let mut model = get_model_with_file_changes(TuiBehaviours::default_for_test());
+    update(&mut model, Msg::ToggleGroupedView);
+    update(&mut model, Msg::SelectLast);
+
+    // WHEN
+    update(&mut model, Msg::ToggleReviewed);
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_eq!(reviewed(&model), vec![true, false, true]);
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff  src/main.rs [hunk 1/1] [net: 2 changes] ───────────────────────────────┐"
+    "│                                                                              │"
+    "│ 1       |-a                                                                  │"
+    "│     1   |+c                                                                  │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes  (2/2) [grouped: 2 files] ───────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│  ▸ src/lib.rs  1 change                                                      │"
+    "│> ▸ src/main.rs  2 changes                                                    │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [1 unreviewed]                                                "
+    "#);
+}
+
+#[test]
+fn modifying_a_reviewed_file_again_marks_it_as_unreviewed() {
+    // GIVEN
+    let mut model = get_model_with_file_changes(TuiBehaviours::default_for_test());
+    update(&mut model, Msg::ToggleGroupedView);
+    update(&mut model, Msg::SelectLast);
+    update(&mut model, Msg::ToggleReviewed);
+
+    // WHEN
+    update(
+        &mut model,
+        Msg::ChangeReceived(modification("src/main.rs", "c\n", "d\n")),
+    );
+
+    // THEN
+    assert_eq!(reviewed(&model), vec![true, false, true, false]);
+    assert_eq!(model.selected_change().map(|c| c.reviewed), Some(false));
+}
@@ -0,0 +1,147 @@
+use super::super::TuiBehaviours;
+use super::super::model::Model;
+use super::super::{msg::Msg, update::update, view::view};
+use super::helpers::{get_model, get_test_terminal, modification};
+use insta::assert_snapshot;
+use std::path::PathBuf;
+
+fn get_model_with_two_roots() -> Model {
+    get_model(
+        TuiBehaviours::default_for_test(),
+        vec![
+            PathBuf::from("/work/frontend"),
+            PathBuf::from("/work/backend"),
+        ],
+    )
+}
+
+#[test]
+fn changes_are_labelled_with_the_directory_they_were_made_in() {
+    // GIVEN
+    let (mut terminal, _) = get_test_terminal();
+    let mut model = get_model_with_two_roots();
+
+    // WHEN
+    update(
+        &mut model,
+        Msg::ChangeReceived(modification("frontend/package.json", "a\n", "b\n")),
+    );
+    update(
+        &mut model,
+        Msg::ChangeReceived(modification("backend/package.json", "a\n", "b\n")),
+    );
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff  frontend/package.json [hunk 1/1] ──────────────────────────────────────┐"
+    "│                                                                              │"
+    "│ 1       |-a                                                                  │"
+    "│     1   |+b                                                                  │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes  (1/2) ──────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│>  modified  frontend: package.json  +1 -1                                    │"
+    "│   modified  backend: package.json  +1 -1                                     │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [frontend: watching] [backend: watching] [2 unreviewed]                  "
+    "#);
+}
+
+#[test]
+fn status_line_shows_which_directories_are_watched() {
+    // GIVEN
+    let (mut terminal, _) = get_test_terminal();
+    let mut model = get_model_with_two_roots();
+
+    // WHEN
+    update(
+        &mut model,
+        Msg::WatchingFailed(1, "too many open files".to_string()),
+    );
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert!(model.behaviours.watch);
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff ────────────────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                     dfft                                     │"
+    "│                                     ‾‾‾‾                                     │"
+    "│                                                                              │"
+    "│            will show you changes in this directory as they happen            │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes ─────────────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│ changes will appear here                                                     │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [frontend: watching] [backend: paused] watching backend for changes faile"
+    "#);
+}
+
+#[test]
+fn watching_stops_once_every_directory_has_failed() {
+    // GIVEN
+    let mut model = get_model_with_two_roots();
+
+    // WHEN
+    update(
+        &mut model,
+        Msg::WatchingFailed(0, "too many open files".to_string()),
+    );
+    update(
+        &mut model,
+        Msg::WatchingFailed(1, "too many open files".to_string()),
+    );
+
+    // THEN
+    assert!(!model.behaviours.watch);
+}
+
+#[test]
+fn resuming_watching_watches_every_directory_again() {
+    // GIVEN
+    let mut model = get_model_with_two_roots();
+    update(
+        &mut model,
+        Msg::WatchingFailed(1, "too many open files".to_string()),
+    );
+    update(&mut model, Msg::ToggleWatching);
+
+    // WHEN
+    let cmds = update(&mut model, Msg::ToggleWatching);
+
+    // THEN
+    assert_eq!(cmds.len(), 2);
+    assert!(model.is_watching_root(0));
+    assert!(model.is_watching_root(1));
+}
@@ -0,0 +1,312 @@
+use super::super::TuiBehaviours;
+use super::super::common::Pane;
+use super::super::model::{Model, RunningState};
+use super::super::{msg::Msg, update::update, view::view};
+use super::helpers::{get_model_with_changes, get_test_terminal, modification};
+use crate::domain::{Change, ChangeKind};
+use insta::assert_snapshot;
+
+fn get_model_with_modified_file() -> Model {
+    let mut lines = (1..=30).map(|n| format!("line {n}")).collect::<Vec<_>>();
+    let old = lines.join("\n");
+
+    lines[4] = "line 5 (modified)".to_string();
+    lines[24] = "line 25 (modified)".to_string();
+
+    let new = lines.join("\n");
+
+    let mut model = get_model_with_changes(
+        TuiBehaviours::default_for_test(),
+        vec![modification("modified_file.txt", &old, &new)],
+    );
+    update(&mut model, Msg::ToggleFullFileView);
+
+    model
+}
+
+fn search_for(model: &mut Model, query: &str) {
+    update(model, Msg::StartSearch);
+    for c in query.chars() {
+        update(model, Msg::EnterPromptInput(c));
+    }
+}
+
+#[test]
+fn search_prompt_is_shown_in_the_status_line() {
+    // GIVEN
+    let (mut terminal, _) = get_test_terminal();
+    let mut model = get_model_with_modified_file();
+
+    // WHEN
+    search_for(&mut model, "modified");
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff  modified_file.txt [hunk 1/2] [full file] ──────────────────────────────┐"
+    "│                                                                              │"
+    "│ 2   2   | line 2                                                             │"
+    "│ 3   3   | line 3                                                             │"
+    "│ 4   4   | line 4                                                             │"
+    "│ 5       |-line 5                                                             │"
+    "│     5   |+line 5 (modified)                                                  │"
+    "│ 6   6   | line 6                                                             │"
+    "│ 7   7   | line 7                                                             │"
+    "│ 8   8   | line 8                                                             │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes  (1/1) ──────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│>  modified  modified_file.txt  +2 -2                                         │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " search  [all lines] /modified  (match 1/2)                                     "
+    "#);
+}
+
+#[test]
+fn submitting_search_scrolls_to_the_first_match() {
+    // GIVEN
+    let (mut terminal, _) = get_test_terminal();
+    let mut model = get_model_with_modified_file();
+    update(&mut model, Msg::ScrollDown);
+    update(&mut model, Msg::ScrollDown);
+
+    // WHEN
+    search_for(&mut model, "line 2[0-9]");
+    update(&mut model, Msg::SubmitPrompt);
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff  modified_file.txt [hunk 1/2] [full file] ──────────────────────────────┐"
+    "│                                                                              │"
+    "│ 17  17  | line 17                                                            │"
+    "│ 18  18  | line 18                                                            │"
+    "│ 19  19  | line 19                                                            │"
+    "│ 20  20  | line 20                                                            │"
+    "│ 21  21  | line 21                                                            │"
+    "│ 22  22  | line 22                                                            │"
+    "│ 23  23  | line 23                                                            │"
+    "│ 24  24  | line 24                                                            │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes  (1/1) ──────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│>  modified  modified_file.txt  +2 -2                                         │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [1 unreviewed] [/line 2[0-9]: match 1/11]                     "
+    "#);
+}
+
+#[test]
+fn navigating_between_search_matches_works() {
+    // GIVEN
+    let (mut terminal, _) = get_test_terminal();
+    let mut model = get_model_with_modified_file();
+    search_for(&mut model, "modified");
+    update(&mut model, Msg::SubmitPrompt);
+
+    // WHEN
+    update(&mut model, Msg::GoToNextSearchMatch);
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff  modified_file.txt [hunk 2/2] [full file] ──────────────────────────────┐"
+    "│                                                                              │"
+    "│ 23  23  | line 23                                                            │"
+    "│ 24  24  | line 24                                                            │"
+    "│ 25      |-line 25                                                            │"
+    "│     25  |+line 25 (modified)                                                 │"
+    "│ 26  26  | line 26                                                            │"
+    "│ 27  27  | line 27                                                            │"
+    "│ 28  28  | line 28                                                            │"
+    "│ 29  29  | line 29                                                            │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes  (1/1) ──────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│>  modified  modified_file.txt  +2 -2                                         │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [1 unreviewed] [/modified: match 2/2]                         "
+    "#);
+
+    // WHEN
+    update(&mut model, Msg::GoToNextSearchMatch);
+    update(&mut model, Msg::GoToPreviousSearchMatch);
+    update(&mut model, Msg::GoToPreviousSearchMatch);
+
+    // THEN
+    let current = model.diff_search.as_ref().and_then(|s| s.current);
+    assert_eq!(current, Some(0));
+}
+
+#[test]
+fn search_matches_are_highlighted() {
+    // GIVEN
+    let (mut terminal, _) = get_test_terminal();
+    let mut model = get_model_with_modified_file();
+
+    // WHEN
+    search_for(&mut model, "modified");
+    update(&mut model, Msg::SubmitPrompt);
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    let buffer = terminal.backend().buffer();
+    let highlighted_row = (0..buffer.area.height)
+        .find(|&y| {
+            (0..buffer.area.width)
+                .map(|x| buffer[(x, y)].symbol())
+                .collect::<String>()
+                .contains("line 5 (modified)")
+        })
+        .expect("row with match should've been rendered");
+    let highlighted = (0..buffer.area.width)
+        .filter(|&x| buffer[(x, highlighted_row)].bg != ratatui::style::Color::Reset)
+        .map(|x| buffer[(x, highlighted_row)].symbol())
+        .collect::<String>();
+    assert_eq!(highlighted, "modified");
+}
+
+#[test]
+fn search_can_be_restricted_to_removed_lines() {
+    // GIVEN
+    let mut model = get_model_with_modified_file();
+
+    // WHEN
+    search_for(&mut model, "line");
+    update(&mut model, Msg::CyclePromptOption);
+    update(&mut model, Msg::CyclePromptOption);
+    update(&mut model, Msg::SubmitPrompt);
+
+    // THEN
+    let num_matches = model.diff_search.as_ref().map(|s| s.matches.len());
+    assert_eq!(num_matches, Some(2));
+}
+
+#[test]
+fn cancelling_search_prompt_restores_previous_search() {
+    // GIVEN
+    let mut model = get_model_with_modified_file();
+    search_for(&mut model, "modified");
+    update(&mut model, Msg::SubmitPrompt);
+
+    // WHEN
+    search_for(&mut model, "line 1");
+    update(&mut model, Msg::CancelPrompt);
+
+    // THEN
+    assert!(model.prompt.is_none());
+    let query = model.diff_search.as_ref().map(|s| s.query.as_str());
+    assert_eq!(query, Some("modified"));
+}
+
+#[test]
+fn submitting_invalid_search_pattern_shows_error() {
+    // GIVEN
+    let (mut terminal, _) = get_test_terminal();
+    let mut model = get_model_with_modified_file();
+
+    // WHEN
+    search_for(&mut model, "line (");
+    update(&mut model, Msg::SubmitPrompt);
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert!(model.diff_search.is_none());
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff  modified_file.txt [hunk 1/2] [full file] ──────────────────────────────┐"
+    "│                                                                              │"
+    "│ 2   2   | line 2                                                             │"
+    "│ 3   3   | line 3                                                             │"
+    "│ 4   4   | line 4                                                             │"
+    "│ 5       |-line 5                                                             │"
+    "│     5   |+line 5 (modified)                                                  │"
+    "│ 6   6   | line 6                                                             │"
+    "│ 7   7   | line 7                                                             │"
+    "│ 8   8   | line 8                                                             │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes  (1/1) ──────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│>  modified  modified_file.txt  +2 -2                                         │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [1 unreviewed] invalid search pattern: unclosed group         "
+    "#);
+}
+
+#[test]
+fn going_back_clears_active_search_before_quitting() {
+    // GIVEN
+    let mut model = get_model_with_modified_file();
+    model.active_pane = Pane::Diff;
+    search_for(&mut model, "modified");
+    update(&mut model, Msg::SubmitPrompt);
+
+    // WHEN
+    update(&mut model, Msg::GoBackOrQuit);
+
+    // THEN
+    assert!(model.diff_search.is_none());
+    assert_eq!(model.running_state, RunningState::Running);
+}
+
+#[test]
+fn search_is_rerun_when_another_change_is_selected() {
+    // GIVEN
+    let mut model = get_model_with_modified_file();
+    let change = Change {
+        path: "created_file.txt".to_string(),
+        kind: ChangeKind::Created(Ok("modified\nnot\nmodified\nmodified\n".to_string())),
+        encoding: None,
+    };
+    update(&mut model, Msg::ChangeReceived(change));
+    search_for(&mut model, "modified");
+    update(&mut model, Msg::SubmitPrompt);
+
+    // WHEN
+    update(&mut model, Msg::SelectNext);
+
+    // THEN
+    let num_matches = model.diff_search.as_ref().map(|s| s.matches.len());
+    assert_eq!(num_matches, Some(3));
+}
@@ -0,0 +1,254 @@
+use super::super::TuiBehaviours;
+use super::super::common::Pane;
+use super::super::model::Model;
+use super::super::{msg::Msg, update::update, view::view};
+use super::helpers::{
+    get_model_with_changes, get_test_terminal, get_test_terminal_with_dims, modification,
+};
+use crate::domain::{Change, ChangeKind};
+use insta::assert_snapshot;
+use std::path::PathBuf;
+
+#[test]
+fn changes_pane_shows_stats_for_each_change() {
+    // GIVEN
+    let (mut terminal, terminal_dimensions) = get_test_terminal();
+    let mut model = Model::new(
+        TuiBehaviours::default_for_test(),
+        vec![PathBuf::new()],
+        terminal_dimensions,
+        false,
+    );
+
+    // WHEN
+    update(
+        &mut model,
+        Msg::ChangeReceived(Change {
+            path: "created.txt".to_string(),
+            kind: ChangeKind::Created(Ok("line 1\nline 2\nline 3\n".to_string())),
+            encoding: None,
+        }),
+    );
+    update(
+        &mut model,
+        Msg::ChangeReceived(modification(
+            "modified.txt",
+            "line 1\nline 2\n",
+            "line 1 (changed)\nline 2\nline 3\n",
+        )),
+    );
+    update(
+        &mut model,
+        Msg::ChangeReceived(Change {
+            path: "removed.txt".to_string(),
+            kind: ChangeKind::RemovedFile,
+            encoding: None,
+        }),
+    );
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff  created.txt ───────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│ line 1                                                                       │"
+    "│ line 2                                                                       │"
+    "│ line 3                                                                       │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes  (1/3) ──────────────────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│>  created   created.txt  +3                                                  │"
+    "│   modified  modified.txt  +2 -1                                              │"
+    "│   removed   removed.txt                                                      │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [3 unreviewed]                                                "
+    "#);
+}
+
+fn get_model_with_session_history() -> Model {
+    let changes = vec![
+        modification("src/main.rs", "a\n", "b\n"),
+        modification("src/lib.rs", "a\nb\nc\nd\n", "e\nf\ng\nh\n"),
+        modification("src/main.rs", "b\n", "c\n"),
+        modification("src/main.rs", "c\n", "d\n"),
+        Change {
+            path: "README.md".to_string(),
+            kind: ChangeKind::Created(Ok("# title\n".to_string())),
+            encoding: None,
+        },
+    ];
+
+    get_model_with_changes(TuiBehaviours::default_for_test(), changes)
+}
+
+#[test]
+fn summary_pane_lists_files_sorted_by_churn() {
+    // GIVEN
+    let (mut terminal, _) = get_test_terminal();
+    let mut model = get_model_with_session_history();
+
+    // WHEN
+    update(&mut model, Msg::GoToPane(Pane::Summary));
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ summary  (3 files, +8 -7) [sorted by churn] ─────────────────────────────────┐"
+    "│                                                                              │"
+    "│ file                                               added    removed  changes │"
+    "│ src/lib.rs                                         +4       -4       1       │"
+    "│ src/main.rs                                        +3       -3       3       │"
+    "│ README.md                                          +1       -0       1       │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [5 unreviewed]                                                "
+    "#);
+}
+
+#[test]
+fn summary_pane_sort_key_can_be_changed() {
+    // GIVEN
+    let (mut terminal, _) = get_test_terminal();
+    let mut model = get_model_with_session_history();
+    update(&mut model, Msg::GoToPane(Pane::Summary));
+
+    // WHEN
+    update(&mut model, Msg::CycleSummarySortKey);
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ summary  (3 files, +8 -7) [sorted by changes] ───────────────────────────────┐"
+    "│                                                                              │"
+    "│ file                                               added    removed  changes │"
+    "│ src/main.rs                                        +3       -3       3       │"
+    "│ README.md                                          +1       -0       1       │"
+    "│ src/lib.rs                                         +4       -4       1       │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [5 unreviewed]                                                "
+    "#);
+
+    // WHEN
+    update(&mut model, Msg::CycleSummarySortKey);
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ summary  (3 files, +8 -7) [sorted by path] ──────────────────────────────────┐"
+    "│                                                                              │"
+    "│ file                                               added    removed  changes │"
+    "│ README.md                                          +1       -0       1       │"
+    "│ src/lib.rs                                         +4       -4       1       │"
+    "│ src/main.rs                                        +3       -3       3       │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [5 unreviewed]                                                "
+    "#);
+}
+
+#[test]
+fn summary_pane_doesnt_scroll_beyond_limits() {
+    // GIVEN
+    let (_, terminal_dimensions) = get_test_terminal_with_dims(80, 24);
+    let mut model = Model::new(
+        TuiBehaviours::default_for_test(),
+        vec![PathBuf::new()],
+        terminal_dimensions,
+        false,
+    );
+    for i in 0..30 {
+        update(
+            &mut model,
+            Msg::ChangeReceived(modification(&format!("file-{i:02}.txt"), "a\n", "b\n")),
+        );
+    }
+    update(&mut model, Msg::GoToPane(Pane::Summary));
+
+    // WHEN
+    for _ in 0..50 {
+        update(&mut model, Msg::ScrollDown);
+    }
+
+    // THEN
+    // 30 files - (24 - 5) visible rows
+    assert_eq!(model.summary_scroll, 11);
+}
+
+#[test]
+fn going_back_from_summary_pane_returns_to_previous_pane() {
+    // GIVEN
+    let mut model = get_model_with_session_history();
+    update(&mut model, Msg::GoToPane(Pane::Changes));
+    update(&mut model, Msg::GoToPane(Pane::Summary));
+
+    // WHEN
+    update(&mut model, Msg::GoBackOrQuit);
+
+    // THEN
+    assert_eq!(model.active_pane, Pane::Changes);
+}
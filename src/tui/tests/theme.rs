@@ -0,0 +1,90 @@
+use super::super::TuiBehaviours;
+use super::super::common::Pane;
+use super::super::model::Model;
+use super::super::theme::Theme;
+use super::super::{msg::Msg, update::update, view::view};
+use super::helpers::get_test_terminal;
+use crate::domain::{Change, ChangeKind, Diff, Modification};
+use ratatui::buffer::Buffer;
+use ratatui::style::{Color, Modifier};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+fn render_with_theme(name: &str) -> Buffer {
+    let (mut terminal, terminal_dimensions) = get_test_terminal();
+    let theme =
+        Theme::from_config(Some(name), &BTreeMap::new()).expect("theme should've been resolved");
+    let mut model = Model::new(
+        TuiBehaviours::default_for_test(),
+        vec![PathBuf::new()],
+        terminal_dimensions,
+        false,
+    )
+    .with_theme(theme);
+    model.active_pane = Pane::Diff;
+    update(
+        &mut model,
+        Msg::ChangeReceived(Change {
+            path: "src/main.rs".to_string(),
+            kind: ChangeKind::Modified(Ok(Modification::Diff(
+                Diff::new("a\nb\n", "a\nc\n").expect("diff should've been created"),
+            ))),
+            encoding: None,
+        }),
+    );
+
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    terminal.backend().buffer().clone()
+}
+
+// the style of the first cell of the row that starts with the given text, after the border
+fn row_style(buffer: &Buffer, prefix: &str) -> (Color, Modifier) {
+    let area = buffer.area;
+    for y in 0..area.height {
+        let row = (1..area.width)
+            .map(|x| buffer[(x, y)].symbol())
+            .collect::<String>();
+        if let Some(offset) = row.find(prefix) {
+            let cell = &buffer[(1 + offset as u16, y)];
+            return (cell.fg, cell.modifier);
+        }
+    }
+
+    panic!("row starting with {prefix:?} should've been rendered");
+}
+
+#[test]
+fn diff_markers_are_colored_by_default() {
+    // GIVEN
+    // WHEN
+    let buffer = render_with_theme("dark");
+
+    // THEN
+    let (insertion_color, _) = row_style(&buffer, "|+");
+    let (deletion_color, _) = row_style(&buffer, "|-");
+    assert_ne!(insertion_color, Color::Reset);
+    assert_ne!(deletion_color, Color::Reset);
+    assert_ne!(insertion_color, deletion_color);
+}
+
+#[test]
+fn no_color_theme_uses_text_modifiers_instead_of_colors() {
+    // GIVEN
+    // WHEN
+    let buffer = render_with_theme("no-color");
+
+    // THEN
+    assert!(
+        buffer
+            .content()
+            .iter()
+            .all(|cell| cell.fg == Color::Reset && cell.bg == Color::Reset)
+    );
+    let (_, insertion_modifier) = row_style(&buffer, "|+");
+    let (_, deletion_modifier) = row_style(&buffer, "|-");
+    assert!(insertion_modifier.contains(Modifier::BOLD));
+    assert!(deletion_modifier.contains(Modifier::UNDERLINED));
+}
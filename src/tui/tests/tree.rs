@@ -0,0 +1,235 @@
+use super::super::TuiBehaviours;
+use super::super::common::Pane;
+use super::super::model::Model;
+use super::super::{msg::Msg, update::update, view::view};
+use super::helpers::{get_model_with_changes, get_test_terminal, modification};
+use crate::domain::{Change, ChangeKind};
+use insta::assert_snapshot;
+
+fn get_model_with_file_changes() -> Model {
+    let changes = vec![
+        modification("src/main.rs", "a\nb\nc\n", "a\nB\nc\n"),
+        Change {
+            path: "notes.txt".to_string(),
+            kind: ChangeKind::Created(Ok("note\n".to_string())),
+            encoding: None,
+        },
+        modification("src/main.rs", "a\nB\nc\n", "a\nB\nC\n"),
+    ];
+    let mut model = get_model_with_changes(TuiBehaviours::default_for_test(), changes);
+    model.active_pane = Pane::Changes;
+
+    model
+}
+
+#[test]
+fn grouped_view_shows_net_diff_for_selected_file() {
+    // GIVEN
+    let (mut terminal, _) = get_test_terminal();
+    let mut model = get_model_with_file_changes();
+
+    // WHEN
+    update(&mut model, Msg::ToggleGroupedView);
+    update(&mut model, Msg::SelectNext);
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff  src/main.rs [hunk 1/1] [net: 2 changes] ───────────────────────────────┐"
+    "│                                                                              │"
+    "│ 1   1   | a                                                                  │"
+    "│ 2       |-b                                                                  │"
+    "│ 3       |-c                                                                  │"
+    "│     2   |+B                                                                  │"
+    "│     3   |+C                                                                  │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes  (2/2) [grouped: 2 files] ───────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│  ▸ notes.txt  1 change                                                       │"
+    "│> ▸ src/main.rs  2 changes                                                    │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [3 unreviewed]                                                "
+    "#);
+}
+
+#[test]
+fn expanding_a_file_in_grouped_view_shows_its_changes() {
+    // GIVEN
+    let (mut terminal, _) = get_test_terminal();
+    let mut model = get_model_with_file_changes();
+    update(&mut model, Msg::ToggleGroupedView);
+    update(&mut model, Msg::SelectNext);
+
+    // WHEN
+    update(&mut model, Msg::ToggleExpanded);
+    update(&mut model, Msg::SelectNext);
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff  src/main.rs [hunk 1/1] ────────────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│ 1   1   | a                                                                  │"
+    "│ 2       |-b                                                                  │"
+    "│     2   |+B                                                                  │"
+    "│ 3   3   | c                                                                  │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes  (3/4) [grouped: 2 files] ───────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│  ▸ notes.txt  1 change                                                       │"
+    "│  ▾ src/main.rs  2 changes                                                    │"
+    "│>      modified   +1 -1                                                       │"
+    "│       modified   +1 -1                                                       │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [3 unreviewed]                                                "
+    "#);
+}
+
+#[test]
+fn net_change_is_empty_when_file_is_restored() {
+    // GIVEN
+    let (mut terminal, _) = get_test_terminal();
+    let mut model = get_model_with_file_changes();
+    update(
+        &mut model,
+        Msg::ChangeReceived(modification("src/main.rs", "a\nB\nC\n", "a\nb\nc\n")),
+    );
+
+    // WHEN
+    update(&mut model, Msg::ToggleGroupedView);
+    update(&mut model, Msg::SelectLast);
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff  src/main.rs [net: 3 changes] ──────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│ no net changes; contents are the same as they were at the start              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes  (2/2) [grouped: 2 files] ───────────────────────────────────────────┐"
+    "│                                                                              │"
+    "│  ▸ notes.txt  1 change                                                       │"
+    "│> ▸ src/main.rs  3 changes                                                    │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [4 unreviewed]                                                "
+    "#);
+}
+
+#[test]
+fn toggling_grouped_view_off_keeps_latest_change_of_file_selected() {
+    // GIVEN
+    let mut model = get_model_with_file_changes();
+    update(&mut model, Msg::ToggleGroupedView);
+    update(&mut model, Msg::SelectLast);
+
+    // WHEN
+    update(&mut model, Msg::ToggleGroupedView);
+
+    // THEN
+    assert!(!model.grouped_view);
+    assert_eq!(model.changes.state.selected(), Some(2));
+}
+
+#[test]
+fn following_changes_in_grouped_view_selects_the_changed_file() {
+    // GIVEN
+    let mut model = get_model_with_file_changes();
+    model.behaviours.follow_changes = true;
+    update(&mut model, Msg::ToggleGroupedView);
+
+    // WHEN
+    update(
+        &mut model,
+        Msg::ChangeReceived(modification("notes.txt", "note\n", "notes\n")),
+    );
+
+    // THEN
+    assert_eq!(model.change_tree.state.selected(), Some(0));
+    assert_eq!(model.current_file_path(), Some("notes.txt"));
+}
+
+#[test]
+fn filtering_grouped_view_with_an_expanded_file_counts_hidden_changes() {
+    // GIVEN
+    let (mut terminal, _) = get_test_terminal();
+    let changes = vec![modification("src/main.rs", "a\n", "b\n")];
+    let mut model = get_model_with_changes(TuiBehaviours::default_for_test(), changes);
+    model.active_pane = Pane::Changes;
+    update(&mut model, Msg::ToggleGroupedView);
+    update(&mut model, Msg::SelectFirst);
+    update(&mut model, Msg::ToggleExpanded);
+
+    // WHEN
+    update(&mut model, Msg::CycleChangeKindFilter);
+    update(&mut model, Msg::CycleChangeKindFilter);
+    terminal
+        .draw(|f| view(&mut model, f))
+        .expect("frame should've been drawn");
+
+    // THEN
+    assert_snapshot!(terminal.backend(), @r#"
+    "┌ diff  src/main.rs [hunk 1/1] [net: 1 change] ────────────────────────────────┐"
+    "│                                                                              │"
+    "│ 1       |-a                                                                  │"
+    "│     1   |+b                                                                  │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    "┌ changes  (1/2) [grouped: 1 file] [modified] [0 hidden] ──────────────────────┐"
+    "│                                                                              │"
+    "│> ▾ src/main.rs  1 change                                                     │"
+    "│       modified   +1 -1                                                       │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "│                                                                              │"
+    "└──────────────────────────────────────────────────────────────────────────────┘"
+    " dfft  [watching] [1 unreviewed]                                                "
+    "#);
+}
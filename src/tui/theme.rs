@@ -0,0 +1,578 @@
+use ratatui::style::{Color, Modifier, Style};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+const AUTO_THEME: &str = "auto";
+
+// A user-defined theme as read from the config file, eg.
+//
+// [themes.mine]
+// base = "light"
+//
+// [themes.mine.colors]
+// primary = "#40a02b"
+// addition = "green"
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeConfig {
+    base: Option<String>,
+    colors: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BuiltinTheme {
+    Dark,
+    Light,
+    HighContrast,
+    NoColor,
+}
+
+impl BuiltinTheme {
+    const ALL: [BuiltinTheme; 4] = [
+        BuiltinTheme::Dark,
+        BuiltinTheme::Light,
+        BuiltinTheme::HighContrast,
+        BuiltinTheme::NoColor,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            BuiltinTheme::Dark => "dark",
+            BuiltinTheme::Light => "light",
+            BuiltinTheme::HighContrast => "high-contrast",
+            BuiltinTheme::NoColor => "no-color",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|t| t.name() == name)
+    }
+
+    // Picks a theme based on the environment. NO_COLOR (when set to a non-empty value) turns off
+    // colors; otherwise COLORFGBG, which some terminals set to "<fg>;<bg>" using ANSI color
+    // indices, tells whether the background is light.
+    fn detect(no_color: Option<&str>, colorfgbg: Option<&str>) -> Self {
+        if no_color.is_some_and(|v| !v.is_empty()) {
+            return BuiltinTheme::NoColor;
+        }
+
+        let background = colorfgbg
+            .and_then(|v| v.rsplit(';').next())
+            .and_then(|bg| bg.parse::<u8>().ok());
+
+        match background {
+            Some(7) | Some(9..=15) => BuiltinTheme::Light,
+            _ => BuiltinTheme::Dark,
+        }
+    }
+
+    fn theme(&self) -> Theme {
+        match self {
+            BuiltinTheme::Dark => Theme::dark(),
+            BuiltinTheme::Light => Theme::light(),
+            BuiltinTheme::HighContrast => Theme::high_contrast(),
+            BuiltinTheme::NoColor => Theme::no_color(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Theme {
+    pub(super) primary: Color,
+    pub(super) title_fg: Color,
+    pub(super) inactive_title: Color,
+    pub(super) inactive_border: Color,
+    pub(super) text: Color,
+    pub(super) background: Color,
+    pub(super) dim: Color,
+    pub(super) info: Color,
+    pub(super) error: Color,
+    pub(super) addition: Color,
+    pub(super) subtraction: Color,
+    pub(super) modification: Color,
+    pub(super) reviewed: Color,
+    pub(super) user_edit: Color,
+    pub(super) watching: Color,
+    pub(super) paused: Color,
+    pub(super) snapshots: Color,
+    pub(super) following: Color,
+    pub(super) unreviewed: Color,
+    pub(super) help: Color,
+    pub(super) summary: Color,
+    pub(super) filter: Color,
+    pub(super) comment: Color,
+    pub(super) search: Color,
+    pub(super) search_match: Color,
+    #[cfg_attr(not(feature = "sound"), allow(dead_code))]
+    pub(super) sound_on: Color,
+    #[cfg_attr(not(feature = "sound"), allow(dead_code))]
+    pub(super) sound_unavailable: Color,
+    // without colors, elements that rely on them are told apart via text modifiers instead
+    monochrome: bool,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Theme {
+    const COLOR_NAMES: [&str; 27] = [
+        "primary",
+        "title_fg",
+        "inactive_title",
+        "inactive_border",
+        "text",
+        "background",
+        "dim",
+        "info",
+        "error",
+        "addition",
+        "subtraction",
+        "modification",
+        "reviewed",
+        "user_edit",
+        "watching",
+        "paused",
+        "snapshots",
+        "following",
+        "unreviewed",
+        "help",
+        "summary",
+        "filter",
+        "comment",
+        "search",
+        "search_match",
+        "sound_on",
+        "sound_unavailable",
+    ];
+
+    fn dark() -> Self {
+        Self {
+            primary: Color::from_u32(0xa6d189),
+            title_fg: Color::from_u32(0x151515),
+            inactive_title: Color::from_u32(0x838ba7),
+            inactive_border: Color::from_u32(0x737994),
+            text: Color::White,
+            background: Color::Black,
+            dim: Color::Gray,
+            info: Color::from_u32(0x83a598),
+            error: Color::from_u32(0xfb4934),
+            addition: Color::from_u32(0x9ece6a),
+            subtraction: Color::from_u32(0xf7768e),
+            modification: Color::from_u32(0xdf8e1d),
+            reviewed: Color::from_u32(0x737994),
+            user_edit: Color::from_u32(0xca9ee6),
+            watching: Color::from_u32(0xbabbf1),
+            paused: Color::from_u32(0xe5c890),
+            snapshots: Color::from_u32(0xca9ee6),
+            following: Color::from_u32(0x99d1db),
+            unreviewed: Color::from_u32(0xf4b8e4),
+            help: Color::from_u32(0xbabbf1),
+            summary: Color::from_u32(0x99d1db),
+            filter: Color::from_u32(0xe5c890),
+            comment: Color::from_u32(0x8caaee),
+            search: Color::from_u32(0xe5c890),
+            search_match: Color::from_u32(0x737994),
+            sound_on: Color::from_u32(0xf5a97f),
+            sound_unavailable: Color::from_u32(0xe78284),
+            monochrome: false,
+        }
+    }
+
+    fn light() -> Self {
+        Self {
+            primary: Color::from_u32(0x40a02b),
+            title_fg: Color::from_u32(0xeff1f5),
+            inactive_title: Color::from_u32(0x8c8fa1),
+            inactive_border: Color::from_u32(0x9ca0b0),
+            text: Color::from_u32(0x4c4f69),
+            background: Color::from_u32(0xeff1f5),
+            dim: Color::from_u32(0x6c6f85),
+            info: Color::from_u32(0x179299),
+            error: Color::from_u32(0xd20f39),
+            addition: Color::from_u32(0x40a02b),
+            subtraction: Color::from_u32(0xd20f39),
+            modification: Color::from_u32(0xdf8e1d),
+            reviewed: Color::from_u32(0x9ca0b0),
+            user_edit: Color::from_u32(0x8839ef),
+            watching: Color::from_u32(0x7287fd),
+            paused: Color::from_u32(0xdf8e1d),
+            snapshots: Color::from_u32(0x8839ef),
+            following: Color::from_u32(0x04a5e5),
+            unreviewed: Color::from_u32(0xea76cb),
+            help: Color::from_u32(0x7287fd),
+            summary: Color::from_u32(0x04a5e5),
+            filter: Color::from_u32(0xdf8e1d),
+            comment: Color::from_u32(0x1e66f5),
+            search: Color::from_u32(0xdf8e1d),
+            search_match: Color::from_u32(0x7c7f93),
+            sound_on: Color::from_u32(0xfe640b),
+            sound_unavailable: Color::from_u32(0xe64553),
+            monochrome: false,
+        }
+    }
+
+    // sticks to the 16 ANSI colors, so that the terminal's own palette is used
+    fn high_contrast() -> Self {
+        Self {
+            primary: Color::LightGreen,
+            title_fg: Color::Black,
+            inactive_title: Color::White,
+            inactive_border: Color::White,
+            text: Color::White,
+            background: Color::Black,
+            dim: Color::Gray,
+            info: Color::LightCyan,
+            error: Color::LightRed,
+            addition: Color::LightGreen,
+            subtraction: Color::LightRed,
+            modification: Color::LightYellow,
+            reviewed: Color::Gray,
+            user_edit: Color::LightMagenta,
+            watching: Color::LightBlue,
+            paused: Color::LightYellow,
+            snapshots: Color::LightMagenta,
+            following: Color::LightCyan,
+            unreviewed: Color::LightMagenta,
+            help: Color::LightBlue,
+            summary: Color::LightCyan,
+            filter: Color::LightYellow,
+            comment: Color::LightBlue,
+            search: Color::LightYellow,
+            search_match: Color::White,
+            sound_on: Color::LightYellow,
+            sound_unavailable: Color::LightRed,
+            monochrome: false,
+        }
+    }
+
+    fn no_color() -> Self {
+        Self {
+            primary: Color::Reset,
+            title_fg: Color::Reset,
+            inactive_title: Color::Reset,
+            inactive_border: Color::Reset,
+            text: Color::Reset,
+            background: Color::Reset,
+            dim: Color::Reset,
+            info: Color::Reset,
+            error: Color::Reset,
+            addition: Color::Reset,
+            subtraction: Color::Reset,
+            modification: Color::Reset,
+            reviewed: Color::Reset,
+            user_edit: Color::Reset,
+            watching: Color::Reset,
+            paused: Color::Reset,
+            snapshots: Color::Reset,
+            following: Color::Reset,
+            unreviewed: Color::Reset,
+            help: Color::Reset,
+            summary: Color::Reset,
+            filter: Color::Reset,
+            comment: Color::Reset,
+            search: Color::Reset,
+            search_match: Color::Reset,
+            sound_on: Color::Reset,
+            sound_unavailable: Color::Reset,
+            monochrome: true,
+        }
+    }
+
+    // Resolves the theme to use, given its name ("auto" if not provided) and the user-defined
+    // themes. Automatic detection relies on the environment.
+    pub fn from_config(
+        name: Option<&str>,
+        themes: &BTreeMap<String, ThemeConfig>,
+    ) -> anyhow::Result<Self> {
+        let detected = BuiltinTheme::detect(
+            std::env::var("NO_COLOR").ok().as_deref(),
+            std::env::var("COLORFGBG").ok().as_deref(),
+        );
+
+        Self::resolve(name.unwrap_or(AUTO_THEME), themes, detected)
+    }
+
+    fn resolve(
+        name: &str,
+        themes: &BTreeMap<String, ThemeConfig>,
+        detected: BuiltinTheme,
+    ) -> anyhow::Result<Self> {
+        let builtin = |name: &str| match name {
+            AUTO_THEME => Some(detected),
+            _ => BuiltinTheme::from_name(name),
+        };
+
+        if let Some(theme) = builtin(name) {
+            return Ok(theme.theme());
+        }
+
+        let Some(config) = themes.get(name) else {
+            anyhow::bail!(
+                "unknown theme \"{name}\"; available themes: {}",
+                Self::available_names(themes).join(", ")
+            );
+        };
+
+        let mut errors = vec![];
+
+        let base_name = config.base.as_deref().unwrap_or(AUTO_THEME);
+        let mut theme = match builtin(base_name) {
+            Some(base) => base.theme(),
+            None => {
+                errors.push(format!(
+                    "[themes.{name}] \"{base_name}\" is not a built-in theme; available themes: \
+                     {AUTO_THEME}, {}",
+                    BuiltinTheme::ALL.map(|t| t.name()).join(", ")
+                ));
+                Theme::default()
+            }
+        };
+
+        for (color_name, value) in &config.colors {
+            let Some(color) = theme.color_mut(color_name) else {
+                errors.push(format!(
+                    "[themes.{name}.colors] \"{color_name}\" is not a themeable color; available \
+                     colors: {}",
+                    Self::COLOR_NAMES.join(", ")
+                ));
+                continue;
+            };
+
+            match Color::from_str(value) {
+                Ok(c) => *color = c,
+                Err(_) => errors.push(format!(
+                    "[themes.{name}.colors] \"{value}\" is not a valid color (use a hex code like \
+                     \"#a6d189\", an ANSI color name like \"lightgreen\", or an index like \"114\")"
+                )),
+            }
+        }
+
+        if !errors.is_empty() {
+            anyhow::bail!("{}", errors.join("\n"));
+        }
+
+        Ok(theme)
+    }
+
+    fn available_names(themes: &BTreeMap<String, ThemeConfig>) -> Vec<&str> {
+        std::iter::once(AUTO_THEME)
+            .chain(BuiltinTheme::ALL.iter().map(|t| t.name()))
+            .chain(themes.keys().map(String::as_str))
+            .collect()
+    }
+
+    fn color_mut(&mut self, name: &str) -> Option<&mut Color> {
+        let color = match name {
+            "primary" => &mut self.primary,
+            "title_fg" => &mut self.title_fg,
+            "inactive_title" => &mut self.inactive_title,
+            "inactive_border" => &mut self.inactive_border,
+            "text" => &mut self.text,
+            "background" => &mut self.background,
+            "dim" => &mut self.dim,
+            "info" => &mut self.info,
+            "error" => &mut self.error,
+            "addition" => &mut self.addition,
+            "subtraction" => &mut self.subtraction,
+            "modification" => &mut self.modification,
+            "reviewed" => &mut self.reviewed,
+            "user_edit" => &mut self.user_edit,
+            "watching" => &mut self.watching,
+            "paused" => &mut self.paused,
+            "snapshots" => &mut self.snapshots,
+            "following" => &mut self.following,
+            "unreviewed" => &mut self.unreviewed,
+            "help" => &mut self.help,
+            "summary" => &mut self.summary,
+            "filter" => &mut self.filter,
+            "comment" => &mut self.comment,
+            "search" => &mut self.search,
+            "search_match" => &mut self.search_match,
+            "sound_on" => &mut self.sound_on,
+            "sound_unavailable" => &mut self.sound_unavailable,
+            _ => return None,
+        };
+
+        Some(color)
+    }
+
+    // Labels drawn on a colored background, like pane titles
+    pub(super) fn label(&self, bg: Color) -> Style {
+        if self.monochrome {
+            Style::new().bold().reversed()
+        } else {
+            Style::new().bold().bg(bg).fg(self.title_fg)
+        }
+    }
+
+    pub(super) fn insertion(&self) -> Style {
+        if self.monochrome {
+            Style::new().bold()
+        } else {
+            Style::new().fg(self.addition)
+        }
+    }
+
+    pub(super) fn deletion(&self) -> Style {
+        if self.monochrome {
+            Style::new().underlined()
+        } else {
+            Style::new().fg(self.subtraction)
+        }
+    }
+
+    // how the changed parts of a modified line stand out from the rest of it
+    pub(super) fn inline_emphasis(&self) -> Modifier {
+        if self.monochrome {
+            Modifier::REVERSED
+        } else {
+            Modifier::UNDERLINED
+        }
+    }
+
+    pub(super) fn search_highlight(&self, current: bool) -> Style {
+        match (self.monochrome, current) {
+            (true, true) => Style::new().reversed().bold(),
+            (true, false) => Style::new().reversed(),
+            (false, true) => Style::new().bg(self.search).fg(self.title_fg),
+            (false, false) => Style::new().bg(self.search_match).fg(self.title_fg),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn themes(contents: &str) -> BTreeMap<String, ThemeConfig> {
+        toml::from_str(contents).expect("themes should've been parsed")
+    }
+
+    #[test]
+    fn detecting_theme_works() {
+        // GIVEN
+        // WHEN
+        // THEN
+        assert_eq!(BuiltinTheme::detect(None, None), BuiltinTheme::Dark);
+        assert_eq!(BuiltinTheme::detect(Some("1"), None), BuiltinTheme::NoColor);
+        assert_eq!(BuiltinTheme::detect(Some(""), None), BuiltinTheme::Dark);
+        assert_eq!(
+            BuiltinTheme::detect(None, Some("0;15")),
+            BuiltinTheme::Light
+        );
+        assert_eq!(
+            BuiltinTheme::detect(None, Some("0;default;7")),
+            BuiltinTheme::Light
+        );
+        assert_eq!(BuiltinTheme::detect(None, Some("15;0")), BuiltinTheme::Dark);
+        assert_eq!(BuiltinTheme::detect(None, Some("15;8")), BuiltinTheme::Dark);
+        assert_eq!(
+            BuiltinTheme::detect(None, Some("default")),
+            BuiltinTheme::Dark
+        );
+        assert_eq!(
+            BuiltinTheme::detect(Some("1"), Some("0;15")),
+            BuiltinTheme::NoColor
+        );
+    }
+
+    #[test]
+    fn resolving_builtin_themes_works() {
+        // GIVEN
+        let themes = BTreeMap::new();
+
+        // WHEN
+        let light = Theme::resolve("light", &themes, BuiltinTheme::Dark);
+        let auto = Theme::resolve("auto", &themes, BuiltinTheme::NoColor);
+
+        // THEN
+        assert_eq!(light.map(|t| t.primary).ok(), Some(Theme::light().primary));
+        assert_eq!(auto.map(|t| t.monochrome).ok(), Some(true));
+    }
+
+    #[test]
+    fn user_defined_themes_override_colors_of_their_base() {
+        // GIVEN
+        let themes = themes(
+            r##"
+[mine]
+base = "light"
+
+[mine.colors]
+primary = "#112233"
+addition = "green"
+"##,
+        );
+
+        // WHEN
+        let theme = Theme::resolve("mine", &themes, BuiltinTheme::Dark)
+            .expect("theme should've been resolved");
+
+        // THEN
+        assert_eq!(theme.primary, Color::from_u32(0x112233));
+        assert_eq!(theme.addition, Color::Green);
+        assert_eq!(theme.subtraction, Theme::light().subtraction);
+    }
+
+    #[test]
+    fn user_defined_themes_use_detected_theme_as_base_by_default() {
+        // GIVEN
+        let themes = themes(
+            r#"
+[mine.colors]
+primary = "red"
+"#,
+        );
+
+        // WHEN
+        let theme = Theme::resolve("mine", &themes, BuiltinTheme::Light)
+            .expect("theme should've been resolved");
+
+        // THEN
+        assert_eq!(theme.primary, Color::Red);
+        assert_eq!(theme.addition, Theme::light().addition);
+    }
+
+    #[test]
+    fn unknown_theme_is_reported() {
+        // GIVEN
+        let themes = themes("[mine]");
+
+        // WHEN
+        let error = Theme::resolve("solarized", &themes, BuiltinTheme::Dark)
+            .expect_err("theme should've been rejected");
+
+        // THEN
+        insta::assert_snapshot!(error, @r#"unknown theme "solarized"; available themes: auto, dark, light, high-contrast, no-color, mine"#);
+    }
+
+    #[test]
+    fn invalid_theme_reports_every_error() {
+        // GIVEN
+        let themes = themes(
+            r#"
+[mine]
+base = "solarized"
+
+[mine.colors]
+primary = "not-a-color"
+accent = "red"
+"#,
+        );
+
+        // WHEN
+        let error = Theme::resolve("mine", &themes, BuiltinTheme::Dark)
+            .expect_err("theme should've been rejected");
+
+        // THEN
+        insta::assert_snapshot!(error, @r##"
+        [themes.mine] "solarized" is not a built-in theme; available themes: auto, dark, light, high-contrast, no-color
+        [themes.mine.colors] "accent" is not a themeable color; available colors: primary, title_fg, inactive_title, inactive_border, text, background, dim, info, error, addition, subtraction, modification, reviewed, user_edit, watching, paused, snapshots, following, unreviewed, help, summary, filter, comment, search, search_match, sound_on, sound_unavailable
+        [themes.mine.colors] "not-a-color" is not a valid color (use a hex code like "#a6d189", an ANSI color name like "lightgreen", or an index like "114")
+        "##);
+    }
+}
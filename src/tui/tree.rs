@@ -0,0 +1,262 @@
+use super::model::ChangeItem;
+use ratatui::widgets::ListState;
+use std::collections::{BTreeMap, HashSet};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TreeRow {
+    // indices of the file's changes in the list of all changes, oldest first
+    File { path: String, changes: Vec<usize> },
+    Change(usize),
+}
+
+// Changes grouped by file path, where each file can be expanded to show its individual changes
+#[derive(Debug, Default)]
+pub struct ChangeTree {
+    pub rows: Vec<TreeRow>,
+    pub state: ListState,
+    expanded: HashSet<String>,
+}
+
+impl ChangeTree {
+    pub(super) fn selected_row(&self) -> Option<&TreeRow> {
+        self.state.selected().and_then(|i| self.rows.get(i))
+    }
+
+    pub(super) fn num_files(&self) -> usize {
+        self.rows
+            .iter()
+            .filter(|row| matches!(row, TreeRow::File { .. }))
+            .count()
+    }
+
+    pub(super) fn is_expanded(&self, path: &str) -> bool {
+        self.expanded.contains(path)
+    }
+
+    // Rebuilds rows from the changes that are visible, keeping the selected node selected if
+    // possible. Returns whether the selected row changed.
+    pub(super) fn rebuild(&mut self, items: &[ChangeItem], visible: &[usize]) -> bool {
+        let previously_selected = self.selected_row().cloned();
+
+        let mut files: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+        for &i in visible {
+            if let Some(item) = items.get(i) {
+                files.entry(&item.change.path).or_default().push(i);
+            }
+        }
+
+        self.rows = files
+            .into_iter()
+            .flat_map(|(path, changes)| {
+                let change_rows = if self.expanded.contains(path) {
+                    changes.iter().map(|&i| TreeRow::Change(i)).collect()
+                } else {
+                    vec![]
+                };
+
+                std::iter::once(TreeRow::File {
+                    path: path.to_string(),
+                    changes,
+                })
+                .chain(change_rows)
+            })
+            .collect();
+
+        let selected = match &previously_selected {
+            _ if self.rows.is_empty() => None,
+            Some(TreeRow::Change(i)) => self
+                .position_of_change(*i)
+                .or_else(|| {
+                    items
+                        .get(*i)
+                        .and_then(|item| self.position_of_file(&item.change.path))
+                })
+                .or(self.state.selected()),
+            Some(TreeRow::File { path, .. }) => {
+                self.position_of_file(path).or(self.state.selected())
+            }
+            None => Some(0),
+        }
+        .map(|i| i.min(self.rows.len().saturating_sub(1)));
+
+        self.state.select(selected);
+
+        self.selected_row() != previously_selected.as_ref()
+    }
+
+    // Expands or collapses the file that the selected row belongs to
+    pub(super) fn toggle_expanded(&mut self, items: &[ChangeItem], visible: &[usize]) -> bool {
+        let previously_selected = self.selected_row().cloned();
+        let path = match self.selected_row() {
+            Some(TreeRow::File { path, .. }) => path.clone(),
+            Some(TreeRow::Change(i)) => match items.get(*i) {
+                Some(item) => item.change.path.clone(),
+                None => return false,
+            },
+            None => return false,
+        };
+
+        if !self.expanded.remove(&path) {
+            self.expanded.insert(path.clone());
+        }
+
+        // collapsing a file while one of its changes is selected moves the selection to the file
+        if let Some(TreeRow::Change(_)) = self.selected_row()
+            && let Some(position) = self.position_of_file(&path)
+        {
+            self.state.select(Some(position));
+        }
+
+        self.rebuild(items, visible);
+
+        self.selected_row() != previously_selected.as_ref()
+    }
+
+    // Expands the file that a change belongs to, and selects the change
+    pub(super) fn reveal_change(&mut self, items: &[ChangeItem], visible: &[usize], index: usize) {
+        let Some(item) = items.get(index) else {
+            return;
+        };
+
+        self.expanded.insert(item.change.path.clone());
+        self.rebuild(items, visible);
+        if let Some(position) = self.position_of_change(index) {
+            self.state.select(Some(position));
+        }
+    }
+
+    pub(super) fn select_file(&mut self, path: &str) -> bool {
+        let previously_selected = self.state.selected();
+        if let Some(position) = self.position_of_file(path) {
+            self.state.select(Some(position));
+        }
+
+        self.state.selected() != previously_selected
+    }
+
+    fn position_of_file(&self, path: &str) -> Option<usize> {
+        self.rows
+            .iter()
+            .position(|row| matches!(row, TreeRow::File { path: p, .. } if p == path))
+    }
+
+    fn position_of_change(&self, index: usize) -> Option<usize> {
+        self.rows
+            .iter()
+            .position(|row| matches!(row, TreeRow::Change(i) if *i == index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Change, ChangeKind};
+
+    fn items(paths: &[&str]) -> Vec<ChangeItem> {
+        paths
+            .iter()
+            .map(|path| ChangeItem {
+                change: Change {
+                    path: path.to_string(),
+                    kind: ChangeKind::RemovedFile,
+                    encoding: None,
+                },
+                reviewed: false,
+                by_user: false,
+                since_last_session: false,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn changes_are_grouped_by_file() {
+        // GIVEN
+        let items = items(&["b.txt", "a.txt", "b.txt", "c.txt"]);
+        let mut tree = ChangeTree::default();
+
+        // WHEN
+        tree.rebuild(&items, &[0, 1, 2]);
+
+        // THEN
+        assert_eq!(
+            tree.rows,
+            vec![
+                TreeRow::File {
+                    path: "a.txt".to_string(),
+                    changes: vec![1],
+                },
+                TreeRow::File {
+                    path: "b.txt".to_string(),
+                    changes: vec![0, 2],
+                },
+            ]
+        );
+        assert_eq!(tree.state.selected(), Some(0));
+    }
+
+    #[test]
+    fn expanding_a_file_shows_its_changes() {
+        // GIVEN
+        let items = items(&["b.txt", "a.txt", "b.txt"]);
+        let visible = [0, 1, 2];
+        let mut tree = ChangeTree::default();
+        tree.rebuild(&items, &visible);
+        tree.state.select(Some(1));
+
+        // WHEN
+        tree.toggle_expanded(&items, &visible);
+
+        // THEN
+        assert_eq!(
+            tree.rows[1..],
+            [
+                TreeRow::File {
+                    path: "b.txt".to_string(),
+                    changes: vec![0, 2],
+                },
+                TreeRow::Change(0),
+                TreeRow::Change(2),
+            ]
+        );
+
+        // WHEN
+        tree.state.select(Some(3));
+        tree.toggle_expanded(&items, &visible);
+
+        // THEN
+        assert_eq!(tree.rows.len(), 2);
+        assert_eq!(tree.state.selected(), Some(1));
+    }
+
+    #[test]
+    fn rebuilding_keeps_the_selected_file_selected() {
+        // GIVEN
+        let mut items = items(&["b.txt", "c.txt"]);
+        let mut tree = ChangeTree::default();
+        tree.rebuild(&items, &[0, 1]);
+        tree.state.select(Some(1));
+
+        // WHEN
+        items.push(ChangeItem {
+            change: Change {
+                path: "a.txt".to_string(),
+                kind: ChangeKind::RemovedFile,
+                encoding: None,
+            },
+            reviewed: false,
+            by_user: false,
+            since_last_session: false,
+        });
+        let selection_changed = tree.rebuild(&items, &[0, 1, 2]);
+
+        // THEN
+        assert!(!selection_changed);
+        assert_eq!(
+            tree.selected_row(),
+            Some(&TreeRow::File {
+                path: "c.txt".to_string(),
+                changes: vec![1],
+            })
+        );
+    }
+}
@@ -2,6 +2,7 @@ use super::cmd::Cmd;
 use super::common::*;
 use super::model::*;
 use super::msg::Msg;
+use std::time::Instant;
 
 pub fn update(model: &mut Model, msg: Msg) -> Vec<Cmd> {
     let mut cmds = vec![];
@@ -11,22 +12,49 @@ pub fn update(model: &mut Model, msg: Msg) -> Vec<Cmd> {
         Msg::GoToPane(pane) => {
             model.last_active_pane = Some(model.active_pane);
             model.active_pane = pane;
-            if pane == Pane::Help {
-                model.reset_help_scroll();
+            match pane {
+                Pane::Help => model.reset_help_scroll(),
+                Pane::Summary => model.reset_summary_scroll(),
+                Pane::Changes | Pane::Diff | Pane::Comments => {}
             }
         }
+        Msg::CopyDiff => cmds.extend(model.copy_diff()),
+        Msg::CopyHunk => cmds.extend(model.copy_hunk()),
+        Msg::CopyPath => cmds.extend(model.copy_path()),
+        Msg::CycleChangeKindFilter => model.cycle_change_kind_filter(),
+        Msg::CycleSummarySortKey => model.cycle_summary_sort_key(),
+        Msg::DeleteComment => model.delete_selected_comment(),
+        Msg::ExportComments => cmds.extend(model.export_comments()),
+        Msg::GoToComment => model.go_to_selected_comment(),
+        Msg::GoToNextChangedLine => model.go_to_next_changed_line(),
+        Msg::GoToNextHunk => model.go_to_next_hunk(),
+        Msg::GoToNextSearchMatch => model.go_to_next_search_match(),
+        Msg::GoToPreviousChangedLine => model.go_to_previous_changed_line(),
+        Msg::GoToPreviousHunk => model.go_to_previous_hunk(),
+        Msg::GoToPreviousSearchMatch => model.go_to_previous_search_match(),
+        Msg::MouseClick(row) => model.click_with_mouse(row),
+        Msg::MouseScroll { row, down } => model.scroll_with_mouse(row, down),
+        Msg::OpenInEditor => cmds.extend(model.open_in_editor()),
         Msg::QuitImmediately => model.running_state = RunningState::Done,
         Msg::ResetList => model.reset_list(),
         Msg::ScrollDown => model.scroll_down(ScrollKind::Line),
         Msg::ScrollHalfPageDown => model.scroll_down(ScrollKind::HalfPage),
         Msg::ScrollHalfPageUp => model.scroll_up(ScrollKind::HalfPage),
+        Msg::ScrollLeft => model.scroll_diff_left(),
+        Msg::ScrollRight => model.scroll_diff_right(),
         Msg::ScrollUp => model.scroll_up(ScrollKind::Line),
         Msg::SelectFirst => model.select_first(),
         Msg::SelectLast => model.select_last(),
         Msg::SelectNext => model.select_next(),
+        Msg::SelectNextUnreviewed => model.select_next_unreviewed(),
         Msg::SelectPrevious => model.select_previous(),
+        Msg::StartComment => model.start_comment(),
+        Msg::StartFilter => model.start_changes_filter(),
+        Msg::StartPathFilterEdit => model.start_path_filter_edit(),
+        Msg::StartSearch => model.start_diff_search(),
         Msg::TerminalResize(new_width, new_height) => {
             let height_changed = model.terminal_dimensions.height != new_height;
+            let width_changed = model.terminal_dimensions.width != new_width;
             let was_too_small = model.terminal_too_small;
             model.terminal_dimensions.update(new_width, new_height);
             model.terminal_too_small =
@@ -34,12 +62,23 @@ pub fn update(model: &mut Model, msg: Msg) -> Vec<Cmd> {
 
             if height_changed || was_too_small != model.terminal_too_small {
                 model.compute_max_help_scroll_available();
+                model.compute_max_summary_scroll_available();
+                model.compute_max_diff_scroll_available();
+            } else if width_changed {
+                // wrapping and horizontal scroll limits depend on the width of the diff pane
                 model.compute_max_diff_scroll_available();
             }
+
+            model.diff_scroll = model.diff_scroll.min(model.max_diff_scroll_available);
         }
         Msg::ToggleFollowChanges => {
             model.behaviours.follow_changes = !model.behaviours.follow_changes;
         }
+        Msg::ToggleFullFileView => model.toggle_full_file_view(),
+        Msg::ToggleExpanded => model.toggle_file_expanded(),
+        Msg::ToggleGroupedView => model.toggle_grouped_view(),
+        Msg::ToggleReviewed => model.toggle_reviewed(),
+        Msg::ToggleWrap => model.toggle_diff_wrap(),
         #[cfg(feature = "sound")]
         Msg::ToggleSound => {
             model.toggle_sound();
@@ -49,17 +88,32 @@ pub fn update(model: &mut Model, msg: Msg) -> Vec<Cmd> {
                 model.pause_watching();
             } else {
                 model.regenerate_cancellation_token();
-                cmds.push(Cmd::WatchForChanges {
-                    root: model.root.clone(),
-                    cache: model.cache(),
-                    sender: model.watch_updates_tx.clone(),
-                    cancellation_token: model.get_cancellation_token(),
-                    prepopulate_cache: model.behaviours.prepopulate_cache,
-                });
+                cmds.extend(model.watch_cmds());
             }
         }
+        // prompt
+        Msg::CancelPrompt => model.cancel_prompt(),
+        Msg::CyclePromptOption => model.cycle_prompt_option(),
+        Msg::DeletePromptInput => model.delete_prompt_input(),
+        Msg::EnterPromptInput(c) => model.enter_prompt_input(c),
+        Msg::SubmitPrompt => model.submit_prompt(),
         // internal
         Msg::ChangeReceived(change) => model.add_change(change),
+        Msg::ChangedSinceLastSession(change) => model.add_change_from_last_session(change),
+        Msg::Tick(now) => model.auto_review(now),
+        Msg::CommentsExported(path) => {
+            model.user_msg = Some(UserMsg::info(format!(
+                "exported comments to {}",
+                path.display()
+            )));
+        }
+        Msg::CommentsExportFailed(e) => {
+            model.user_msg = Some(UserMsg::error(format!("exporting comments failed: {e}")));
+        }
+        Msg::CopyingFailed(e) => {
+            model.user_msg = Some(UserMsg::error(format!("copying to clipboard failed: {e}")));
+        }
+        Msg::EditorClosed(result) => model.on_editor_closed(result, Instant::now()),
         // this is just to trigger a render of TUI
         Msg::PrepopulationFinished => {}
         Msg::PrepopulationFailed(e) => {
@@ -70,14 +124,14 @@ pub fn update(model: &mut Model, msg: Msg) -> Vec<Cmd> {
             model.behaviours.watch = false;
             model.user_msg = Some(UserMsg::error(format!("prepopulating changes failed: {e}")));
         }
-        Msg::WatchingFailed(e) => {
+        Msg::WatchingFailed(root_index, e) => {
             #[cfg(feature = "sound")]
             if model.behaviours.play_sound {
                 model.play_error_sound();
             }
-            model.behaviours.watch = false;
-            model.user_msg = Some(UserMsg::error(format!("watching for changes failed: {e}")));
+            model.on_watching_failed(root_index, e);
         }
+        Msg::PollingSuggested(description) => model.on_polling_suggested(description),
     }
 
     if let Some(message) = &mut model.user_msg {
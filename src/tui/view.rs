@@ -1,35 +1,37 @@
 use super::common::*;
-use super::model::{MessageKind, Model};
-use crate::domain::{ChangeKind, Diff, DiffOperation, Modification};
+use super::filter::ChangeKindFilter;
+use super::model::{MAX_DISPLAYED_DIFF_LINES, MessageKind, Model, Prompt, PromptKind};
+use super::search::DiffSearch;
+use super::theme::Theme;
+use super::tree::TreeRow;
+use crate::domain::{
+    ChangeKind, DiffLine, DiffOperation, Fingerprint, FingerprintChange, Modification,
+    gutter_width, line_num_padding,
+};
+use crate::utils::format_bytes;
 use ratatui::style::{Color, Modifier};
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Layout, Rect},
     style::{Style, Stylize},
     text::{Line, Span},
-    widgets::{Block, List, ListDirection, ListItem, Padding, Paragraph, Wrap},
+    widgets::{Block, Cell, List, ListDirection, ListItem, Padding, Paragraph, Row, Table, Wrap},
 };
+use std::ops::Range;
+use unicode_width::UnicodeWidthChar;
 
-const PANE_TITLE_FG_COLOR: Color = Color::from_u32(0x151515);
-const PRIMARY_COLOR: Color = Color::from_u32(0xa6d189);
-const INACTIVE_PANE_TITLE_BG_COLOR: Color = Color::from_u32(0x838ba7);
-const INACTIVE_PANE_BORDER_COLOR: Color = Color::from_u32(0x737994);
-const INFO_MESSAGE_COLOR: Color = Color::from_u32(0x83a598);
-const ERROR_MESSAGE_COLOR: Color = Color::from_u32(0xfb4934);
-const WATCHING_COLOR: Color = Color::from_u32(0xbabbf1);
-const PAUSED_COLOR: Color = Color::from_u32(0xe5c890);
 const WATCHING_LABEL: &str = " [watching]";
 const PAUSED_LABEL: &str = " [ paused ]";
-const SNAPSHOTS_COLOR: Color = Color::from_u32(0xca9ee6);
-const FOLLOWING_CHANGES_COLOR: Color = Color::from_u32(0x99d1db);
-const HELP_COLOR: Color = Color::from_u32(0xbabbf1);
-const DIM_COLOR: Color = Color::Gray;
-#[cfg(feature = "sound")]
-const SOUND_UNAVAILABLE_COLOR: Color = Color::from_u32(0xe78284);
-#[cfg(feature = "sound")]
-const SOUND_ON_COLOR: Color = Color::from_u32(0xf5a97f);
 
 const TITLE: &str = " dfft ";
+const FULL_FILE_VIEW_LABEL: &str = "[full file] ";
+const LARGE_FILE_LABEL: &str = "[large file] ";
+const USER_EDIT_LABEL: &str = "[edited by you] ";
+const LAST_SESSION_LABEL: &str = "[changed since last session] ";
+const TREE_COLLAPSED_MARKER: &str = "▸ ";
+const TREE_EXPANDED_MARKER: &str = "▾ ";
+const TREE_CHANGE_INDENT: &str = "    ";
+const WRAP_LABEL: &str = "[wrap] ";
 const BANNER_LARGE: &str = r#"
 
 
@@ -64,17 +66,23 @@ will show you changes in this directory as they happen
 
 pub fn view(model: &mut Model, frame: &mut Frame) {
     if model.terminal_too_small {
-        render_terminal_too_small_view(&model.terminal_dimensions, frame);
+        render_terminal_too_small_view(&model.terminal_dimensions, &model.theme, frame);
         return;
     }
 
     match model.active_pane {
         Pane::Changes | Pane::Diff => render_main_view(model, frame),
         Pane::Help => render_help_pane(model, frame),
+        Pane::Summary => render_summary_pane(model, frame),
+        Pane::Comments => render_comments_pane(model, frame),
     }
 }
 
-fn render_terminal_too_small_view(dimensions: &TerminalDimensions, frame: &mut Frame) {
+fn render_terminal_too_small_view(
+    dimensions: &TerminalDimensions,
+    theme: &Theme,
+    frame: &mut Frame,
+) {
     let message = format!(
         r#"
 Terminal size too small:
@@ -90,7 +98,7 @@ Press (q/<ctrl+c>/<esc> to exit)
 
     let p = Paragraph::new(message)
         .block(Block::bordered())
-        .style(Style::new().fg(PRIMARY_COLOR))
+        .style(Style::new().fg(theme.primary))
         .wrap(Wrap { trim: false })
         .alignment(Alignment::Center);
 
@@ -113,58 +121,93 @@ fn render_main_view(model: &mut Model, frame: &mut Frame) {
 }
 
 fn render_diff_pane(model: &mut Model, frame: &mut Frame, rect: Rect) {
+    let theme = &model.theme;
     let (color, border_color, title_color) = if model.active_pane == Pane::Diff {
-        (PRIMARY_COLOR, PRIMARY_COLOR, PRIMARY_COLOR)
+        (theme.primary, theme.primary, theme.primary)
     } else {
         (
-            INACTIVE_PANE_BORDER_COLOR,
-            INACTIVE_PANE_BORDER_COLOR,
-            INACTIVE_PANE_TITLE_BG_COLOR,
+            theme.inactive_border,
+            theme.inactive_border,
+            theme.inactive_title,
         )
     };
 
     let pane_name = " diff ";
 
-    let maybe_selected_index = model.changes.state.selected();
-    let details = match maybe_selected_index {
-        Some(selected_index) => {
-            let maybe_change = model.changes.items.get(selected_index);
-            let lines = match maybe_change {
-                Some(change) => match &change.change.kind {
-                    ChangeKind::Created(Ok(contents)) => contents
-                        .lines()
-                        .skip(model.diff_scroll)
-                        .map(Line::raw)
-                        .collect(),
-                    ChangeKind::Created(Err(e)) => {
+    let details = match model.selected_change() {
+        Some(change) => {
+            let maybe_change = Some(change);
+            let rows = model.displayed_diff_lines().map(|diff_lines| {
+                let comments = model
+                    .displayed_comments(&diff_lines)
+                    .into_iter()
+                    .map(|(i, c)| (i, c.display_text()))
+                    .collect::<Vec<_>>();
+                let mut rows = get_diff_pane_rows(
+                    &diff_lines,
+                    model.full_file_view,
+                    model.diff_pane_shows_line_numbers(),
+                    model.diff_search.as_ref(),
+                    &comments,
+                    theme,
+                );
+
+                let num_hidden = model.num_hidden_diff_lines();
+                if num_hidden > 0 {
+                    rows.push(DiffPaneRow {
+                        gutter: vec![],
+                        content: vec![Span::styled(
+                            format!(
+                                "... {num_hidden} more lines not shown (only the first {MAX_DISPLAYED_DIFF_LINES} are)"
+                            ),
+                            Style::default().fg(theme.dim),
+                        )],
+                    });
+                }
+
+                rows
+            });
+
+            let lines = match rows {
+                Some(rows) => fit_rows_to_pane(
+                    rows,
+                    model
+                        .wrap_diff_lines
+                        .then_some(rect.width.saturating_sub(3) as usize),
+                    model.diff_horizontal_scroll,
+                )
+                .into_iter()
+                .skip(model.diff_scroll)
+                .collect(),
+                None => match maybe_change.map(|c| &c.change.kind) {
+                    Some(ChangeKind::Created(Err(e))) | Some(ChangeKind::Modified(Err(e))) => {
                         vec![Line::raw(format!("error reading file contents: {e}"))]
                     }
-                    ChangeKind::Modified(Ok(result)) => match result {
-                        Modification::InitialSnapshot => {
-                            vec![Line::raw(
-                                "initial snapshot captured; diffs will be available from now onwards",
-                            )]
-                        }
-                        Modification::Diff(diff) => get_diff_lines(diff)
-                            .into_iter()
-                            .skip(model.diff_scroll)
-                            .collect(),
-                    },
-                    ChangeKind::Modified(Err(e)) => {
-                        vec![Line::raw(format!("error reading file contents: {e}"))]
+                    Some(ChangeKind::Modified(Ok(Modification::NoNetChange))) => {
+                        vec![Line::raw(
+                            "no net changes; contents are the same as they were at the start",
+                        )]
                     }
-                    ChangeKind::RemovedFile => vec![Line::raw("file removed")],
-                    ChangeKind::RemovedDir => vec![Line::raw("directory removed")],
+                    Some(ChangeKind::Modified(Ok(Modification::InitialSnapshot))) => {
+                        vec![Line::raw(
+                            "initial snapshot captured; diffs will be available from now onwards",
+                        )]
+                    }
+                    Some(ChangeKind::RemovedFile) => vec![Line::raw("file removed")],
+                    Some(ChangeKind::RemovedDir) => vec![Line::raw("directory removed")],
+                    Some(ChangeKind::Binary(change)) => get_binary_change_lines(change),
+                    Some(ChangeKind::TooLarge(change)) => get_too_large_change_lines(change),
+                    Some(ChangeKind::Reencoded(change)) => vec![Line::raw(format!(
+                        "encoding changed from {} to {}",
+                        change.before, change.after
+                    ))],
+                    _ => vec![Line::raw("something went wrong")],
                 },
-                None => vec![Line::raw("something went wrong")],
             };
 
-            let section_title_span = Span::from(pane_name)
-                .bold()
-                .bg(title_color)
-                .fg(PANE_TITLE_FG_COLOR);
+            let section_title_span = Span::from(pane_name).style(theme.label(title_color));
 
-            let title_spans = if let Some(fp) = model.current_file_path() {
+            let mut title_spans = if let Some(fp) = model.current_file_path() {
                 vec![
                     section_title_span,
                     Span::from(" "),
@@ -175,6 +218,56 @@ fn render_diff_pane(model: &mut Model, frame: &mut Frame, rect: Rect) {
                 vec![section_title_span.clone()]
             };
 
+            if let Some((current, total)) = model.hunk_position() {
+                let label = match (current, total) {
+                    (Some(i), n) => format!("[hunk {}/{n}] ", i + 1),
+                    (None, 1) => "[1 hunk] ".to_string(),
+                    (None, n) => format!("[{n} hunks] "),
+                };
+                title_spans.push(Span::from(label).fg(title_color));
+            }
+
+            if let Some(n) = model.num_changes_in_net_change() {
+                let label = match n {
+                    1 => "[net: 1 change] ".to_string(),
+                    n => format!("[net: {n} changes] "),
+                };
+                title_spans.push(Span::from(label).fg(title_color));
+            }
+
+            if let Some(encoding) = model
+                .selected_change()
+                .and_then(|c| c.change.encoding)
+                .filter(|e| !e.is_default())
+            {
+                title_spans.push(Span::from(format!("[{encoding}] ")).fg(title_color));
+            }
+
+            if model.selected_change().is_some_and(|c| c.by_user) {
+                title_spans.push(Span::from(USER_EDIT_LABEL).fg(theme.user_edit));
+            }
+
+            if model
+                .selected_change()
+                .is_some_and(|c| c.since_last_session)
+            {
+                title_spans.push(Span::from(LAST_SESSION_LABEL).fg(theme.info));
+            }
+
+            if model.full_file_view {
+                title_spans.push(Span::from(FULL_FILE_VIEW_LABEL).fg(title_color));
+            }
+
+            if model.selected_change().is_some_and(|c| {
+                matches!(&c.change.kind, ChangeKind::Modified(Ok(Modification::Diff(diff))) if diff.large_file)
+            }) {
+                title_spans.push(Span::from(LARGE_FILE_LABEL).fg(title_color));
+            }
+
+            if model.wrap_diff_lines {
+                title_spans.push(Span::from(WRAP_LABEL).fg(title_color));
+            }
+
             Paragraph::new(lines)
                 .block(
                     Block::bordered()
@@ -192,7 +285,7 @@ fn render_diff_pane(model: &mut Model, frame: &mut Frame, rect: Rect) {
         .block(
             Block::bordered()
                 .border_style(Style::default().fg(border_color))
-                .title_style(Style::new().bold().bg(title_color).fg(PANE_TITLE_FG_COLOR))
+                .title_style(theme.label(title_color))
                 .title(pane_name)
                 .padding(Padding::new(1, 0, 1, 0)),
         )
@@ -204,22 +297,41 @@ fn render_diff_pane(model: &mut Model, frame: &mut Frame, rect: Rect) {
 }
 
 fn render_changes_pane(model: &mut Model, frame: &mut Frame, rect: Rect) {
-    let items: Vec<ListItem> = model.changes.items.iter().map(ListItem::from).collect();
+    let items: Vec<ListItem> = if model.grouped_view {
+        model
+            .change_tree
+            .rows
+            .iter()
+            .filter_map(|row| get_tree_row_item(model, row))
+            .collect()
+    } else {
+        model
+            .changes
+            .visible
+            .iter()
+            .filter_map(|&i| model.changes.items.get(i))
+            .map(|item| item.list_item(&model.theme, model.roots.len() > 1))
+            .collect()
+    };
 
     let pane_name = " changes ";
+    let theme = &model.theme;
 
     let (border_color, title_color) = if model.active_pane == Pane::Changes {
-        (PRIMARY_COLOR, PRIMARY_COLOR)
+        (theme.primary, theme.primary)
     } else {
-        (INACTIVE_PANE_BORDER_COLOR, INACTIVE_PANE_TITLE_BG_COLOR)
+        (theme.inactive_border, theme.inactive_title)
     };
 
-    let section_title_span = Span::from(pane_name)
-        .bold()
-        .bg(title_color)
-        .fg(PANE_TITLE_FG_COLOR);
+    let section_title_span = Span::from(pane_name).style(theme.label(title_color));
+
+    let selected = if model.grouped_view {
+        model.change_tree.state.selected()
+    } else {
+        model.changes.state.selected()
+    };
 
-    let title_spans = if let Some(i) = model.changes.state.selected() {
+    let mut title_spans = if let Some(i) = selected {
         vec![
             section_title_span,
             Span::from(format!(" ({}/{}) ", i + 1, items.len())).fg(title_color),
@@ -228,13 +340,44 @@ fn render_changes_pane(model: &mut Model, frame: &mut Frame, rect: Rect) {
         vec![section_title_span]
     };
 
+    if model.grouped_view {
+        if selected.is_none() {
+            title_spans.push(Span::from(" "));
+        }
+        let label = match model.change_tree.num_files() {
+            1 => "[grouped: 1 file] ".to_string(),
+            n => format!("[grouped: {n} files] "),
+        };
+        title_spans.push(Span::from(label).fg(title_color));
+    }
+
+    let filter = &model.changes.filter;
+    if filter.is_active() {
+        if selected.is_none() && !model.grouped_view {
+            title_spans.push(Span::from(" "));
+        }
+        if !filter.query.is_empty() {
+            title_spans.push(Span::from(format!("[/{}] ", filter.query)).fg(theme.filter));
+        }
+        if filter.kind != ChangeKindFilter::All {
+            title_spans.push(Span::from(format!("[{}] ", filter.kind)).fg(theme.filter));
+        }
+        // rows in the grouped view don't map to changes one to one
+        let num_hidden = model.changes.items.len() - model.changes.visible.len();
+        title_spans.push(Span::from(format!("[{num_hidden} hidden] ")).fg(theme.filter));
+    }
+
     if items.is_empty() {
-        let p = Paragraph::new("changes will appear here")
+        let message = if model.changes.items.is_empty() {
+            "changes will appear here"
+        } else {
+            "no changes match the filter"
+        };
+        let p = Paragraph::new(message)
             .block(
                 Block::bordered()
                     .border_style(Style::default().fg(border_color))
-                    .title_style(Style::new().bold().bg(title_color).fg(PANE_TITLE_FG_COLOR))
-                    .title(pane_name)
+                    .title(Line::from(title_spans))
                     .padding(Padding::new(1, 0, 1, 0)),
             )
             .wrap(Wrap { trim: false })
@@ -253,17 +396,64 @@ fn render_changes_pane(model: &mut Model, frame: &mut Frame, rect: Rect) {
         .highlight_symbol("> ")
         .direction(ListDirection::TopToBottom);
 
-    frame.render_stateful_widget(list, rect, &mut model.changes.state);
+    let state = if model.grouped_view {
+        &mut model.change_tree.state
+    } else {
+        &mut model.changes.state
+    };
+
+    frame.render_stateful_widget(list, rect, state);
+}
+
+fn get_tree_row_item<'a>(model: &Model, row: &TreeRow) -> Option<ListItem<'a>> {
+    let theme = &model.theme;
+    let line = match row {
+        TreeRow::File { path, changes } => {
+            let marker = if model.change_tree.is_expanded(path) {
+                TREE_EXPANDED_MARKER
+            } else {
+                TREE_COLLAPSED_MARKER
+            };
+            let count = match changes.len() {
+                1 => "  1 change".to_string(),
+                n => format!("  {n} changes"),
+            };
+
+            let path_style = if model.are_reviewed(changes) {
+                Style::default().fg(theme.reviewed)
+            } else {
+                Style::default()
+            };
+
+            Line::from(vec![
+                Span::from(marker).fg(theme.dim),
+                Span::styled(path.clone(), path_style),
+                Span::from(count).fg(theme.dim),
+            ])
+        }
+        TreeRow::Change(i) => {
+            let item = model.changes.items.get(*i)?;
+            let mut spans = vec![Span::from(TREE_CHANGE_INDENT), item.kind_span(theme)];
+            spans.extend(item.stats_spans(theme));
+            spans.extend(item.by_user_span(theme));
+            spans.extend(item.last_session_span(theme));
+
+            Line::from(spans)
+        }
+    };
+
+    Some(ListItem::new(line))
 }
 
 fn render_help_pane(model: &Model, frame: &mut Frame) {
+    let theme = &model.theme;
     let rect = Layout::default()
         .direction(ratatui::layout::Direction::Vertical)
         .constraints(vec![Constraint::Fill(1), Constraint::Length(1)])
         .split(frame.area());
 
-    let help_content = get_help_content();
-    let lines: Vec<Line> = help_content
+    let lines: Vec<Line> = model
+        .help_content
         .lines()
         .skip(model.help_scroll)
         .map(Line::raw)
@@ -274,12 +464,12 @@ fn render_help_pane(model: &Model, frame: &mut Frame) {
     let help_widget = Paragraph::new(lines)
         .block(
             Block::bordered()
-                .border_style(Style::default().fg(HELP_COLOR))
-                .title_style(Style::new().bold().bg(HELP_COLOR).fg(PANE_TITLE_FG_COLOR))
+                .border_style(Style::default().fg(theme.help))
+                .title_style(theme.label(theme.help))
                 .title(title)
                 .padding(Padding::new(1, 0, 1, 0)),
         )
-        .style(Style::new().white().on_black())
+        .style(Style::new().fg(theme.text).bg(theme.background))
         .wrap(Wrap { trim: false })
         .alignment(Alignment::Left);
 
@@ -287,69 +477,241 @@ fn render_help_pane(model: &Model, frame: &mut Frame) {
     render_status_line(model, frame, rect[1]);
 }
 
+fn render_summary_pane(model: &Model, frame: &mut Frame) {
+    let theme = &model.theme;
+    let rect = Layout::default()
+        .direction(ratatui::layout::Direction::Vertical)
+        .constraints(vec![Constraint::Fill(1), Constraint::Length(1)])
+        .split(frame.area());
+
+    let files = model.session_summary.sorted(model.summary_sort_key);
+    let (insertions, deletions) = model.session_summary.totals();
+
+    let title_spans = vec![
+        Span::from(" summary ").style(theme.label(theme.summary)),
+        Span::from(format!(
+            " ({} files, +{insertions} -{deletions}) [sorted by {}] ",
+            files.len(),
+            model.summary_sort_key
+        ))
+        .fg(theme.summary),
+    ];
+
+    let block = Block::bordered()
+        .border_style(Style::default().fg(theme.summary))
+        .title(Line::from(title_spans))
+        .padding(Padding::new(1, 0, 1, 0));
+
+    if files.is_empty() {
+        let p = Paragraph::new("files changed during this session will appear here").block(block);
+        frame.render_widget(&p, rect[0]);
+        render_status_line(model, frame, rect[1]);
+        return;
+    }
+
+    let rows = files
+        .iter()
+        .skip(model.summary_scroll)
+        .map(|file| {
+            Row::new(vec![
+                Cell::from(file.path.clone()),
+                Cell::from(format!("+{}", file.insertions)).style(theme.insertion()),
+                Cell::from(format!("-{}", file.deletions)).style(theme.deletion()),
+                Cell::from(file.num_changes.to_string()),
+            ])
+        })
+        .collect::<Vec<_>>();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Fill(1),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Length(8),
+        ],
+    )
+    .header(Row::new(vec!["file", "added", "removed", "changes"]).bold())
+    .block(block);
+
+    frame.render_widget(&table, rect[0]);
+    render_status_line(model, frame, rect[1]);
+}
+
+fn render_comments_pane(model: &mut Model, frame: &mut Frame) {
+    let theme = &model.theme;
+    let rect = Layout::default()
+        .direction(ratatui::layout::Direction::Vertical)
+        .constraints(vec![Constraint::Fill(1), Constraint::Length(1)])
+        .split(frame.area());
+
+    let mut title_spans = vec![Span::from(" comments ").style(theme.label(theme.comment))];
+    if let Some(i) = model.comments_state.selected() {
+        title_spans
+            .push(Span::from(format!(" ({}/{}) ", i + 1, model.comments.len())).fg(theme.comment));
+    }
+
+    let block = Block::bordered()
+        .border_style(Style::default().fg(theme.comment))
+        .title(Line::from(title_spans))
+        .padding(Padding::new(0, 0, 1, 0));
+
+    if model.comments.is_empty() {
+        let p = Paragraph::new(
+            "comments will appear here; press c in the diff pane to comment on the line in focus",
+        )
+        .block(block.padding(Padding::new(1, 0, 1, 0)))
+        .wrap(Wrap { trim: false });
+        frame.render_widget(&p, rect[0]);
+        render_status_line(model, frame, rect[1]);
+        return;
+    }
+
+    let items = model
+        .comments
+        .iter()
+        .map(|comment| {
+            ListItem::new(Line::from(vec![
+                Span::from(comment.location()).fg(theme.comment),
+                Span::from(format!(" [{}]", comment.target)).fg(theme.dim),
+                Span::from("  "),
+                Span::from(comment.text.clone()),
+            ]))
+        })
+        .collect::<Vec<_>>();
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_symbol("> ")
+        .direction(ListDirection::TopToBottom);
+
+    frame.render_stateful_widget(list, rect[0], &mut model.comments_state);
+    render_status_line(model, frame, rect[1]);
+}
+
 fn render_status_line(model: &Model, frame: &mut Frame, rect: Rect) {
-    let mut status_bar_lines = vec![Span::styled(
-        TITLE,
-        Style::new()
-            .bold()
-            .bg(PRIMARY_COLOR)
-            .fg(PANE_TITLE_FG_COLOR),
-    )];
-
-    if let Some(n) = model.snapshots_in_memory()
-        && n > 0
+    if let Some(prompt) = &model.prompt {
+        return render_prompt(model, prompt, frame, rect);
+    }
+
+    let theme = &model.theme;
+    let mut status_bar_lines = vec![Span::styled(TITLE, theme.label(theme.primary))];
+
+    if let Some(usage) = model.cache_usage()
+        && usage.num_files > 0
     {
+        let snapshots = if usage.num_files == 1 {
+            "1 snapshot".to_string()
+        } else {
+            format!("{} snapshots", usage.num_files)
+        };
+        let memory = match usage.budget {
+            Some(budget) => format!(
+                "{} / {}",
+                format_bytes(usage.bytes_used),
+                format_bytes(budget)
+            ),
+            None => format_bytes(usage.bytes_used),
+        };
         status_bar_lines.push(Span::styled(
-            if n == 1 {
-                " [1 snapshot in memory]".to_string()
-            } else {
-                format!(" [{n} snapshots in memory]")
-            },
-            Style::default().fg(SNAPSHOTS_COLOR).bold(),
+            format!(" [{snapshots} in memory, {memory}]"),
+            Style::default().fg(theme.snapshots).bold(),
         ));
     }
 
-    let (watching_label, watching_color) = if model.behaviours.watch {
-        (WATCHING_LABEL, WATCHING_COLOR)
+    if model.roots.len() > 1 {
+        for (index, r) in model.roots.iter().enumerate() {
+            let name = r.root.name.as_deref().unwrap_or_default();
+            let (state, color) = if model.is_watching_root(index) {
+                ("watching", theme.watching)
+            } else {
+                ("paused", theme.paused)
+            };
+            status_bar_lines.push(Span::styled(
+                format!(" [{name}: {state}]"),
+                Style::default().fg(color).bold(),
+            ));
+        }
     } else {
-        (PAUSED_LABEL, PAUSED_COLOR)
-    };
+        let (watching_label, watching_color) = if model.is_watching_root(0) {
+            (WATCHING_LABEL, theme.watching)
+        } else {
+            (PAUSED_LABEL, theme.paused)
+        };
+
+        status_bar_lines.push(Span::styled(
+            watching_label,
+            Style::default().fg(watching_color).bold(),
+        ));
+    }
+
+    if model.is_polling() {
+        status_bar_lines.push(Span::styled(
+            " [polling]",
+            Style::default().fg(theme.watching).bold(),
+        ));
+    }
 
-    status_bar_lines.push(Span::styled(
-        watching_label,
-        Style::default().fg(watching_color).bold(),
-    ));
+    for suggestion in &model.poll_suggestions {
+        status_bar_lines.push(Span::styled(
+            format!(" [{suggestion}, try --poll]"),
+            Style::default().fg(theme.error).bold(),
+        ));
+    }
 
     #[cfg(feature = "sound")]
     if model.is_sound_unavailable() {
         status_bar_lines.push(Span::styled(
             " [sound unavailable]",
-            Style::default().fg(SOUND_UNAVAILABLE_COLOR).bold(),
+            Style::default().fg(theme.sound_unavailable).bold(),
         ));
     } else if model.behaviours.play_sound {
         status_bar_lines.push(Span::styled(
             " [sound on]",
-            Style::default().fg(SOUND_ON_COLOR).bold(),
+            Style::default().fg(theme.sound_on).bold(),
+        ));
+    }
+
+    if let Some(filter) = model.path_filter() {
+        status_bar_lines.push(Span::styled(
+            format!(" [globs: {filter}]"),
+            Style::default().fg(theme.filter).bold(),
         ));
     }
 
     if model.behaviours.follow_changes {
         status_bar_lines.push(Span::styled(
             " [following changes]",
-            Style::default().fg(FOLLOWING_CHANGES_COLOR).bold(),
+            Style::default().fg(theme.following).bold(),
+        ));
+    }
+
+    if !model.changes.items.is_empty() {
+        let label = match model.num_unreviewed() {
+            0 => " [all reviewed]".to_string(),
+            n => format!(" [{n} unreviewed]"),
+        };
+        status_bar_lines.push(Span::styled(
+            label,
+            Style::default().fg(theme.unreviewed).bold(),
+        ));
+    }
+
+    if let Some(search) = &model.diff_search {
+        status_bar_lines.push(Span::styled(
+            format!(" [/{}: {}]", search.query, get_search_summary(search)),
+            Style::default().fg(theme.search).bold(),
         ));
     }
 
     if let Some(msg) = &model.user_msg {
         let span = match msg.kind {
-            MessageKind::Info => Span::styled(
-                format!(" {}", msg.value),
-                Style::new().fg(INFO_MESSAGE_COLOR),
-            ),
-            MessageKind::Error => Span::styled(
-                format!(" {}", msg.value),
-                Style::new().fg(ERROR_MESSAGE_COLOR),
-            ),
+            MessageKind::Info => {
+                Span::styled(format!(" {}", msg.value), Style::new().fg(theme.info))
+            }
+            MessageKind::Error => {
+                Span::styled(format!(" {}", msg.value), Style::new().fg(theme.error))
+            }
         };
 
         status_bar_lines.push(span);
@@ -363,6 +725,12 @@ fn render_status_line(model: &Model, frame: &mut Frame, rect: Rect) {
             " [dimensions: {}x{}] ",
             model.terminal_dimensions.width, model.terminal_dimensions.height
         )));
+        let metrics = model.watch_metrics();
+        status_bar_lines.push(Span::from(format!(
+            "[queue: {}, max {}] ",
+            metrics.queued(),
+            metrics.max_queued()
+        )));
     }
 
     let status_bar_text = Line::from(status_bar_lines);
@@ -372,56 +740,400 @@ fn render_status_line(model: &Model, frame: &mut Frame, rect: Rect) {
     frame.render_widget(&status_bar, rect);
 }
 
-// inspired by https://github.com/mitsuhiko/similar/blob/main/examples/terminal-inline.rs
-fn get_diff_lines(diff: &Diff) -> Vec<Line<'static>> {
-    let mut lines = Vec::new();
+fn render_prompt(model: &Model, prompt: &Prompt, frame: &mut Frame, rect: Rect) {
+    let theme = &model.theme;
+    let mut spans = match prompt.kind {
+        PromptKind::DiffSearch(scope) => vec![
+            Span::styled(" search ", theme.label(theme.search)),
+            Span::styled(format!(" [{scope}]"), Style::default().fg(theme.search)),
+            Span::raw(format!(" /{}", prompt.input)),
+        ],
+        PromptKind::ChangesFilter(kind) => vec![
+            Span::styled(" filter ", theme.label(theme.filter)),
+            Span::styled(format!(" [{kind}]"), Style::default().fg(theme.filter)),
+            Span::raw(format!(" /{}", prompt.input)),
+            Span::styled(
+                format!(
+                    "  ({}/{} changes)",
+                    model.changes.visible.len(),
+                    model.changes.items.len()
+                ),
+                Style::default().fg(theme.dim),
+            ),
+        ],
+        PromptKind::Comment(target) => vec![
+            Span::styled(" comment ", theme.label(theme.comment)),
+            Span::styled(
+                format!(
+                    " [{target}: {}]",
+                    model.comment_draft_location(target).unwrap_or_default()
+                ),
+                Style::default().fg(theme.comment),
+            ),
+            Span::raw(format!(" {}", prompt.input)),
+        ],
+        PromptKind::PathFilter => vec![
+            Span::styled(" globs ", theme.label(theme.filter)),
+            Span::raw(format!(" {}", prompt.input)),
+            Span::styled(
+                "  (separated by spaces, ! to exclude)",
+                Style::default().fg(theme.dim),
+            ),
+        ],
+    };
 
-    let line_number_padding = diff.line_num_padding();
+    if let PromptKind::DiffSearch(_) = prompt.kind
+        && let Some(search) = &model.diff_search
+    {
+        spans.push(Span::styled(
+            format!("  ({})", get_search_summary(search)),
+            Style::default().fg(theme.dim),
+        ));
+    }
 
-    for (idx, hunk) in diff.hunks.iter().enumerate() {
-        if idx > 0 {
-            lines.push(Line::from(vec![Span::styled(
-                format!("{:-^80}", "-"),
-                Style::new().fg(DIM_COLOR),
-            )]));
+    let prompt_widget = Paragraph::new(Line::from(spans)).block(Block::default());
+
+    frame.render_widget(&prompt_widget, rect);
+}
+
+fn get_search_summary(search: &DiffSearch) -> String {
+    match (search.current, search.matches.len()) {
+        (_, 0) => "no matches".to_string(),
+        (Some(i), n) => format!("match {}/{n}", i + 1),
+        (None, 1) => "1 match".to_string(),
+        (None, n) => format!("{n} matches"),
+    }
+}
+
+fn get_binary_change_lines(change: &FingerprintChange) -> Vec<Line<'static>> {
+    let describe = |info: &Fingerprint| format!("{} bytes (hash {})", info.size, info.short_hash());
+    let before = change
+        .before
+        .as_ref()
+        .map(describe)
+        .unwrap_or_else(|| "not seen before".to_string());
+
+    vec![
+        Line::raw("binary file; contents aren't shown"),
+        Line::raw(""),
+        Line::raw(format!("before: {before}")),
+        Line::raw(format!("after:  {}", describe(&change.after))),
+    ]
+}
+
+fn get_too_large_change_lines(change: &FingerprintChange) -> Vec<Line<'static>> {
+    let summary = match change.before {
+        Some(before) => format!(
+            "changed, {} bytes → {} bytes",
+            before.size, change.after.size
+        ),
+        None => format!("changed, {} bytes", change.after.size),
+    };
+
+    vec![
+        Line::raw("file is too large to be diffed"),
+        Line::raw(""),
+        Line::raw(summary),
+    ]
+}
+
+// A line in the diff pane, split into a "gutter" (line numbers, markers) and the actual content.
+// Only the content is wrapped or scrolled horizontally.
+struct DiffPaneRow {
+    gutter: Vec<Span<'static>>,
+    content: Vec<Span<'static>>,
+}
+
+impl DiffPaneRow {
+    fn plain(line: &str) -> Self {
+        Self {
+            gutter: vec![],
+            content: vec![Span::raw(line.to_string())],
         }
+    }
+}
+
+// Builds a row for each line shown in the diff pane, with search matches highlighted, followed
+// by rows for the comments on it.
+//
+// Unlike the hunks view, the full file view doesn't dim unchanged lines, since they make up most
+// of the file, while deleted lines are shown as dimmed "ghost" lines
+fn get_diff_pane_rows(
+    diff_lines: &[Option<DiffLine>],
+    full_file_view: bool,
+    show_line_numbers: bool,
+    search: Option<&DiffSearch>,
+    comments: &[(usize, String)],
+    theme: &Theme,
+) -> Vec<DiffPaneRow> {
+    let line_number_padding = line_num_padding(diff_lines.iter().flatten());
+    let comment_gutter = if show_line_numbers {
+        " ".repeat(gutter_width(line_number_padding))
+    } else {
+        String::new()
+    };
+    let unchanged_color = if full_file_view {
+        Color::Reset
+    } else {
+        theme.dim
+    };
 
-        for diff_line in &hunk.lines {
-            let sign = diff_line.kind.sign();
-            let style = match diff_line.kind {
-                DiffOperation::Delete => Style::new().fg(SUBTRACTION_COLOR),
-                DiffOperation::Insert => Style::new().fg(ADDITION_COLOR),
-                DiffOperation::Equal => Style::new().fg(DIM_COLOR),
+    diff_lines
+        .iter()
+        .enumerate()
+        .flat_map(|(idx, diff_line)| {
+            let Some(diff_line) = diff_line else {
+                return vec![DiffPaneRow {
+                    gutter: vec![Span::styled(
+                        format!("{:-^80}", "-"),
+                        Style::new().fg(theme.dim),
+                    )],
+                    content: vec![],
+                }];
             };
 
-            let old_line = diff_line
-                .old_line_num
-                .map(|n| format!("{:<padding$}", n + 1, padding = line_number_padding))
-                .unwrap_or_else(|| " ".repeat(line_number_padding));
-
-            let new_line = diff_line
-                .new_line_num
-                .map(|n| format!("{:<padding$}", n + 1, padding = line_number_padding))
-                .unwrap_or_else(|| " ".repeat(line_number_padding));
-
-            let mut line_spans = vec![
-                Span::styled(old_line, Style::new().fg(DIM_COLOR)),
-                Span::styled(new_line, Style::new().fg(DIM_COLOR)),
-                Span::styled(format!("|{sign}"), style.add_modifier(Modifier::BOLD)),
-            ];
-
-            for inline_change in &diff_line.inline_changes {
-                let final_style = if inline_change.emphasized {
-                    style.add_modifier(Modifier::UNDERLINED)
+            let mut row = if show_line_numbers {
+                get_diff_row(diff_line, line_number_padding, unchanged_color, theme)
+            } else {
+                DiffPaneRow::plain(&diff_line.content())
+            };
+
+            if full_file_view && diff_line.kind == DiffOperation::Delete {
+                row.content = row
+                    .content
+                    .into_iter()
+                    .map(|span| span.dim().crossed_out())
+                    .collect();
+            }
+
+            if let Some(search) = search {
+                let highlights = search
+                    .matches_for_line(idx)
+                    .map(|(match_idx, m)| {
+                        (
+                            m.range.clone(),
+                            theme.search_highlight(search.current == Some(match_idx)),
+                        )
+                    })
+                    .collect::<Vec<_>>();
+
+                if !highlights.is_empty() {
+                    row.content = highlight_ranges(row.content, &highlights);
+                }
+            }
+
+            let comment_rows = comments
+                .iter()
+                .filter(|(line_idx, _)| *line_idx == idx)
+                .map(|(_, text)| DiffPaneRow {
+                    gutter: vec![Span::raw(comment_gutter.clone())],
+                    content: vec![Span::styled(
+                        text.clone(),
+                        Style::new().fg(theme.comment).italic(),
+                    )],
+                });
+
+            std::iter::once(row).chain(comment_rows).collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+fn get_diff_row(
+    diff_line: &DiffLine,
+    line_number_padding: usize,
+    unchanged_color: Color,
+    theme: &Theme,
+) -> DiffPaneRow {
+    let sign = diff_line.kind.sign();
+    let style = match diff_line.kind {
+        DiffOperation::Delete => theme.deletion(),
+        DiffOperation::Insert => theme.insertion(),
+        DiffOperation::Equal => Style::new().fg(unchanged_color),
+    };
+
+    let old_line = diff_line
+        .old_line_num
+        .map(|n| format!("{:<padding$}", n + 1, padding = line_number_padding))
+        .unwrap_or_else(|| " ".repeat(line_number_padding));
+
+    let new_line = diff_line
+        .new_line_num
+        .map(|n| format!("{:<padding$}", n + 1, padding = line_number_padding))
+        .unwrap_or_else(|| " ".repeat(line_number_padding));
+
+    let gutter = vec![
+        Span::styled(old_line, Style::new().fg(theme.dim)),
+        Span::styled(new_line, Style::new().fg(theme.dim)),
+        Span::styled(format!("|{sign}"), style.add_modifier(Modifier::BOLD)),
+    ];
+
+    let content = diff_line
+        .inline_changes
+        .iter()
+        .map(|inline_change| {
+            let final_style = if inline_change.emphasized {
+                style.add_modifier(theme.inline_emphasis())
+            } else {
+                style
+            };
+            Span::styled(
+                inline_change
+                    .value
+                    .trim_end_matches(['\n', '\r'])
+                    .to_string(),
+                final_style,
+            )
+        })
+        .collect();
+
+    DiffPaneRow { gutter, content }
+}
+
+// Turns rows into lines that fit the diff pane, either by wrapping their content to the given
+// width, or by cutting off the first few columns of their content when scrolled horizontally.
+// This needs to stay in sync with how the model computes scroll limits.
+fn fit_rows_to_pane(
+    rows: Vec<DiffPaneRow>,
+    wrap_width: Option<usize>,
+    horizontal_scroll: usize,
+) -> Vec<Line<'static>> {
+    let mut lines = Vec::with_capacity(rows.len());
+
+    for row in rows {
+        let gutter_width = row.gutter.iter().map(|s| s.width()).sum::<usize>();
+
+        match wrap_width {
+            Some(width) => {
+                let mut chunks = wrap_spans(row.content, width.saturating_sub(gutter_width));
+                let first_chunk = if chunks.is_empty() {
+                    vec![]
                 } else {
-                    style
+                    chunks.remove(0)
                 };
-                line_spans.push(Span::styled(inline_change.value.clone(), final_style));
-            }
 
-            lines.push(Line::from(line_spans));
+                let mut first_line = row.gutter;
+                first_line.extend(first_chunk);
+                lines.push(Line::from(first_line));
+
+                for chunk in chunks {
+                    let mut line = vec![Span::raw(" ".repeat(gutter_width))];
+                    line.extend(chunk);
+                    lines.push(Line::from(line));
+                }
+            }
+            None => {
+                let mut line = row.gutter;
+                line.extend(skip_columns(row.content, horizontal_scroll));
+                lines.push(Line::from(line));
+            }
         }
     }
 
     lines
 }
+
+// Splits spans at the boundaries of the given byte ranges (relative to the concatenated content
+// of all spans), and applies the corresponding style to the parts within each range
+fn highlight_ranges(
+    spans: Vec<Span<'static>>,
+    highlights: &[(Range<usize>, Style)],
+) -> Vec<Span<'static>> {
+    let mut result = vec![];
+    let mut offset = 0;
+
+    for span in spans {
+        let span_start = offset;
+        let span_end = offset + span.content.len();
+        offset = span_end;
+
+        let mut boundaries = vec![span_start, span_end];
+        for (range, _) in highlights {
+            for boundary in [range.start, range.end] {
+                if boundary > span_start && boundary < span_end {
+                    boundaries.push(boundary);
+                }
+            }
+        }
+        boundaries.sort_unstable();
+        boundaries.dedup();
+
+        for window in boundaries.windows(2) {
+            let (start, end) = (window[0], window[1]);
+            let style = highlights
+                .iter()
+                .find(|(range, _)| range.start <= start && end <= range.end)
+                .map(|(_, style)| span.style.patch(*style))
+                .unwrap_or(span.style);
+
+            result.push(Span::styled(
+                span.content[start - span_start..end - span_start].to_string(),
+                style,
+            ));
+        }
+    }
+
+    result
+}
+
+// mirrors crate::domain::wrapped_line_count
+fn wrap_spans(spans: Vec<Span<'static>>, width: usize) -> Vec<Vec<Span<'static>>> {
+    let mut rows = vec![vec![]];
+    let mut row_width = 0;
+
+    for span in spans {
+        let mut current = String::new();
+        for c in span.content.chars() {
+            let char_width = c.width().unwrap_or_default();
+            if width > 0 && row_width + char_width > width && row_width > 0 {
+                if let Some(row) = rows.last_mut()
+                    && !current.is_empty()
+                {
+                    row.push(Span::styled(std::mem::take(&mut current), span.style));
+                }
+                rows.push(vec![]);
+                row_width = 0;
+            }
+            current.push(c);
+            row_width += char_width;
+        }
+
+        if let Some(row) = rows.last_mut()
+            && !current.is_empty()
+        {
+            row.push(Span::styled(current, span.style));
+        }
+    }
+
+    rows
+}
+
+fn skip_columns(spans: Vec<Span<'static>>, columns: usize) -> Vec<Span<'static>> {
+    if columns == 0 {
+        return spans;
+    }
+
+    let mut skipped = 0;
+    let mut result = vec![];
+
+    for span in spans {
+        if skipped >= columns {
+            result.push(span);
+            continue;
+        }
+
+        let mut remaining = String::new();
+        for c in span.content.chars() {
+            if skipped < columns {
+                skipped += c.width().unwrap_or_default();
+            } else {
+                remaining.push(c);
+            }
+        }
+
+        if !remaining.is_empty() {
+            result.push(Span::styled(remaining, span.style));
+        }
+    }
+
+    result
+}
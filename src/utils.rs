@@ -1,3 +1,6 @@
+use etcetera::{BaseStrategy, choose_base_strategy};
+use std::path::PathBuf;
+
 pub fn num_digits(mut n: usize) -> usize {
     if n == 0 {
         1
@@ -11,6 +14,58 @@ pub fn num_digits(mut n: usize) -> usize {
     }
 }
 
+// Sizes in a form that's easy to read at a glance, eg. "1.5 MB"
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = UNITS[0];
+    for next_unit in &UNITS[1..] {
+        if size < 1024.0 {
+            break;
+        }
+        size /= 1024.0;
+        unit = next_unit;
+    }
+
+    format!("{size:.1} {unit}")
+}
+
+// Directory for the files dfft writes (logs, exported review comments, etc.)
+#[cfg(not(target_os = "windows"))]
+pub fn get_state_dir() -> anyhow::Result<PathBuf> {
+    let strategy = choose_base_strategy()?;
+
+    // XDG spec suggests using XDG_STATE_HOME for logs
+    // https://specifications.freedesktop.org/basedir-spec/latest/#variables
+    let state_dir = strategy
+        .state_dir() // this always returns Some on unix, but adding a fallback regardless
+        .map(|d| d.join("dfft"))
+        .unwrap_or_else(|| strategy.home_dir().join(".dfft"));
+
+    Ok(state_dir)
+}
+
+#[cfg(target_os = "windows")]
+pub fn get_state_dir() -> anyhow::Result<PathBuf> {
+    let strategy = choose_base_strategy()?;
+
+    let state_dir = strategy.cache_dir().join("dfft");
+
+    Ok(state_dir)
+}
+
+// Directory for dfft's config file
+pub fn get_config_dir() -> anyhow::Result<PathBuf> {
+    let strategy = choose_base_strategy()?;
+
+    Ok(strategy.config_dir().join("dfft"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -24,4 +79,13 @@ mod tests {
         assert_eq!(num_digits(9999), 4);
         assert_eq!(num_digits(1000000), 7);
     }
+
+    #[test]
+    fn formatting_bytes_works() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+        assert_eq!(format_bytes(256 * 1024 * 1024), "256.0 MB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.0 GB");
+    }
 }
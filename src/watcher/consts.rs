@@ -1,173 +0,0 @@
-pub(super) const EXTENSIONS_TO_IGNORE: [&str; 171] = [
-    "3ds",
-    "3gp",
-    "7z",
-    "a",
-    "aac",
-    "ac3",
-    "accdb",
-    "ace",
-    "aiff",
-    "amr",
-    "apk",
-    "arj",
-    "art",
-    "asf",
-    "au",
-    "avi",
-    "avif",
-    "bak",
-    "bin",
-    "bmp",
-    "bsp",
-    "bundle",
-    "bz",
-    "bz2",
-    "cab",
-    "ccd",
-    "class",
-    "cpio",
-    "crx",
-    "dat",
-    "db",
-    "dbf",
-    "dds",
-    "deb",
-    "dex",
-    "dfont",
-    "divx",
-    "djvu",
-    "dll",
-    "dmg",
-    "doc",
-    "docx",
-    "drv",
-    "dts",
-    "dylib",
-    "efi",
-    "elf",
-    "emf",
-    "eot",
-    "epub",
-    "exe",
-    "exr",
-    "f4v",
-    "fb2",
-    "flac",
-    "flv",
-    "fon",
-    "framework",
-    "gba",
-    "gif",
-    "gz",
-    "hdr",
-    "heic",
-    "ico",
-    "img",
-    "indd",
-    "ipa",
-    "iso",
-    "it",
-    "jar",
-    "jp2",
-    "jpeg",
-    "jpg",
-    "jxl",
-    "ldb",
-    "lib",
-    "lz",
-    "lzh",
-    "lzma",
-    "m2ts",
-    "m4a",
-    "m4v",
-    "mdb",
-    "mdf",
-    "mds",
-    "mkv",
-    "mov",
-    "mp3",
-    "mp4",
-    "mpeg",
-    "mpg",
-    "msi",
-    "mts",
-    "nds",
-    "o",
-    "obj",
-    "odex",
-    "odp",
-    "ods",
-    "odt",
-    "ogg",
-    "ogv",
-    "opus",
-    "otf",
-    "pak",
-    "pcx",
-    "pdb",
-    "pdf",
-    "pfa",
-    "pfb",
-    "png",
-    "ppt",
-    "pptx",
-    "psd",
-    "pub",
-    "pyd",
-    "qcow2",
-    "rar",
-    "raw",
-    "rdb",
-    "rm",
-    "rmvb",
-    "rom",
-    "rpm",
-    "s3m",
-    "sav",
-    "sea",
-    "sit",
-    "snd",
-    "so",
-    "sqlite",
-    "sqlite3",
-    "swf",
-    "sys",
-    "tar.bz2",
-    "tar.gz",
-    "tar.xz",
-    "tar",
-    "tbz2",
-    "tga",
-    "tgz",
-    "tif",
-    "tiff",
-    "ts",
-    "ttc",
-    "ttf",
-    "uue",
-    "vdex",
-    "vdi",
-    "vhd",
-    "vmdk",
-    "vob",
-    "voc",
-    "vpk",
-    "wad",
-    "wasm",
-    "wav",
-    "webm",
-    "webp",
-    "wma",
-    "wmf",
-    "wmv",
-    "woff",
-    "woff2",
-    "xls",
-    "xlsx",
-    "xm",
-    "xpi",
-    "xz",
-    "z",
-    "zip",
-];
@@ -0,0 +1,108 @@
+use std::path::Path;
+
+#[cfg(target_os = "linux")]
+const MOUNTS_PATH: &str = "/proc/mounts";
+
+// File systems on which file system events usually don't fire for changes made elsewhere (eg. on
+// the host of a Docker bind mount, or on an NFS server), so changes are only noticed by polling
+const UNWATCHABLE_FILESYSTEMS: [&str; 13] = [
+    "nfs",
+    "nfs4",
+    "cifs",
+    "smb3",
+    "smbfs",
+    "fuse.sshfs",
+    "9p",
+    "drvfs",
+    "virtiofs",
+    "fakeowner",
+    "fuse.grpcfuse",
+    "vboxsf",
+    "fuse.osxfs",
+];
+
+// The type of the file system the path is on, if it's one whose changes can't be relied on to
+// produce events
+#[cfg(target_os = "linux")]
+pub(super) fn unwatchable_filesystem(path: &Path) -> Option<String> {
+    let mounts = std::fs::read_to_string(MOUNTS_PATH).ok()?;
+
+    filesystem_type(&mounts, path)
+        .filter(|fs_type| UNWATCHABLE_FILESYSTEMS.contains(fs_type))
+        .map(str::to_string)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub(super) fn unwatchable_filesystem(_path: &Path) -> Option<String> {
+    None
+}
+
+// Goes by the mount point that's closest to the path, given the contents of /proc/mounts
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn filesystem_type<'a>(mounts: &'a str, path: &Path) -> Option<&'a str> {
+    mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let _device = fields.next()?;
+            let mount_point = fields.next()?;
+            let fs_type = fields.next()?;
+            // spaces in mount points are escaped as octal
+            let mount_point = mount_point.replace("\\040", " ");
+            path.starts_with(&mount_point)
+                .then_some((mount_point.len(), fs_type))
+        })
+        .max_by_key(|(len, _)| *len)
+        .map(|(_, fs_type)| fs_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MOUNTS: &str = r#"
+overlay / overlay rw,relatime 0 0
+proc /proc proc rw,nosuid,nodev,noexec,relatime 0 0
+server:/exports/home /home/user/nfs nfs4 rw,relatime 0 0
+/run/host_mark/Users /workspace fakeowner rw,nosuid,nodev,relatime 0 0
+C:\134 /mnt/c 9p rw,noatime 0 0
+/dev/sda1 /home/user/my\040files ext4 rw,relatime 0 0
+"#;
+
+    #[test]
+    fn the_closest_mount_point_decides_the_filesystem_type() {
+        // GIVEN
+        // WHEN
+        // THEN
+        assert_eq!(
+            filesystem_type(MOUNTS, Path::new("/home/user/nfs/project")),
+            Some("nfs4")
+        );
+        assert_eq!(
+            filesystem_type(MOUNTS, Path::new("/workspace/app")),
+            Some("fakeowner")
+        );
+        assert_eq!(
+            filesystem_type(MOUNTS, Path::new("/mnt/c/Users/me/app")),
+            Some("9p")
+        );
+        assert_eq!(
+            filesystem_type(MOUNTS, Path::new("/home/user/my files/app")),
+            Some("ext4")
+        );
+        assert_eq!(
+            filesystem_type(MOUNTS, Path::new("/home/user/project")),
+            Some("overlay")
+        );
+    }
+
+    #[test]
+    fn mount_points_only_match_whole_path_components() {
+        // GIVEN
+        // WHEN
+        let fs_type = filesystem_type(MOUNTS, Path::new("/workspace-2/app"));
+
+        // THEN
+        assert_eq!(fs_type, Some("overlay"));
+    }
+}
@@ -1,13 +1,15 @@
-use super::consts::EXTENSIONS_TO_IGNORE;
+use super::settings::WatchSettings;
+use crate::domain::{FileContents, Fingerprint, FingerprintHasher, SNIFF_LEN, is_binary};
 use anyhow::Context;
 use ignore::gitignore::Gitignore;
 use ignore::gitignore::GitignoreBuilder;
+use std::io::Read;
 use std::path::Path;
 
 const GITIGNORE_PATH: &str = ".gitignore";
 const DFFTIGNORE_PATH: &str = ".dfftignore";
-const MAX_FILE_SIZE: u64 = 1024 * 1024; // 1MB
 const VCS_DIRS: [&str; 4] = [".git", ".jj", ".hg", ".svn"];
+const FINGERPRINT_CHUNK_SIZE: usize = 64 * 1024;
 
 pub(super) fn get_ignore<P>(root: P) -> anyhow::Result<Option<Gitignore>>
 where
@@ -49,7 +51,11 @@ where
     ))
 }
 
-pub(super) async fn is_path_to_be_ignored<P>(path: P, gitignore: &Option<Gitignore>) -> bool
+pub(super) fn is_path_to_be_ignored<P>(
+    path: P,
+    gitignore: &Option<Gitignore>,
+    settings: &WatchSettings,
+) -> bool
 where
     P: AsRef<Path>,
 {
@@ -60,31 +66,142 @@ where
         return true;
     }
 
-    if is_extension_to_be_ignored(&path) {
+    if has_extension_in(&path, &settings.ignored_extensions) {
         return true;
     }
 
     false
 }
 
-fn is_extension_to_be_ignored<P>(path: P) -> bool
+// Extensions are matched against the end of the file name, so that ones like "tar.gz" work too
+fn has_extension_in<P>(path: P, extensions: &[String]) -> bool
 where
     P: AsRef<Path>,
 {
-    if let Some(ext) = path.as_ref().extension() {
-        let ext = ext.to_string_lossy().to_lowercase();
-        EXTENSIONS_TO_IGNORE.contains(&ext.as_str())
-    } else {
-        false
+    let Some(file_name) = path.as_ref().file_name() else {
+        return false;
+    };
+    let file_name = file_name.to_string_lossy().to_lowercase();
+
+    extensions.iter().any(|ext| {
+        let ext = ext.trim_start_matches('.').to_lowercase();
+        file_name.ends_with(&format!(".{ext}"))
+    })
+}
+
+// Files above the size limit for diffing, and binary files, are only fingerprinted, without reading
+// all of them into memory at once; whether a file is binary is decided by its first block. Reading
+// is blocking, so this is meant to be called from a blocking thread.
+pub(super) fn read_file<P>(path: P, settings: &WatchSettings) -> Result<FileContents, String>
+where
+    P: AsRef<Path>,
+{
+    let size = std::fs::metadata(&path).map_err(|e| e.to_string())?.len();
+
+    if size > settings.max_diffed_file_size {
+        return fingerprint_file(&path)
+            .map(FileContents::TooLarge)
+            .map_err(|e| e.to_string());
     }
+
+    let mut file = std::fs::File::open(&path).map_err(|e| e.to_string())?;
+    let mut bytes = Vec::new();
+    (&mut file)
+        .take(SNIFF_LEN as u64)
+        .read_to_end(&mut bytes)
+        .map_err(|e| e.to_string())?;
+
+    let force_text = has_extension_in(&path, &settings.text_extensions);
+    if !force_text && is_binary(&bytes) {
+        let mut hasher = FingerprintHasher::default();
+        hasher.update(&bytes);
+        return fingerprint_rest(file, hasher)
+            .map(FileContents::Binary)
+            .map_err(|e| e.to_string());
+    }
+
+    file.read_to_end(&mut bytes).map_err(|e| e.to_string())?;
+    FileContents::from_bytes(bytes, force_text)
 }
 
-pub async fn is_file_too_large<P>(path: P) -> bool
+fn fingerprint_file<P>(path: P) -> std::io::Result<Fingerprint>
 where
     P: AsRef<Path>,
 {
-    tokio::fs::metadata(path)
-        .await
-        .map(|m| m.len() > MAX_FILE_SIZE)
-        .unwrap_or(true)
+    fingerprint_rest(std::fs::File::open(path)?, FingerprintHasher::default())
+}
+
+// Adds what's left of the file to the fingerprint
+fn fingerprint_rest(
+    mut file: std::fs::File,
+    mut hasher: FingerprintHasher,
+) -> std::io::Result<Fingerprint> {
+    let mut buffer = vec![0; FINGERPRINT_CHUNK_SIZE];
+
+    loop {
+        let num_read = file.read(&mut buffer)?;
+        if num_read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..num_read]);
+    }
+
+    Ok(hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matching_extensions_works() {
+        // GIVEN
+        let extensions = vec!["DAT".to_string(), ".tar.gz".to_string()];
+
+        // WHEN
+        // THEN
+        assert!(has_extension_in("data/cities.dat", &extensions));
+        assert!(has_extension_in("dist/release.tar.gz", &extensions));
+        assert!(!has_extension_in("dist/release.gz", &extensions));
+        assert!(!has_extension_in("dat", &extensions));
+        assert!(!has_extension_in("src/main.rs", &[]));
+    }
+
+    #[test]
+    fn binary_files_are_fingerprinted_whole() {
+        // GIVEN
+        let dir = tempfile::tempdir().expect("temporary directory should've been created");
+        let path = dir.path().join("image.png");
+        let bytes = (0..=255).cycle().take(3 * SNIFF_LEN).collect::<Vec<u8>>();
+        std::fs::write(&path, &bytes).expect("file should've been written");
+
+        // WHEN
+        let result = read_file(&path, &WatchSettings::default());
+
+        // THEN
+        assert!(
+            matches!(result, Ok(FileContents::Binary(fingerprint)) if fingerprint == Fingerprint::new(&bytes))
+        );
+    }
+
+    #[test]
+    fn files_with_text_extensions_are_read_whole_as_text() {
+        // GIVEN
+        let dir = tempfile::tempdir().expect("temporary directory should've been created");
+        let path = dir.path().join("data.log");
+        let contents = format!("a\0b\n{}", "c".repeat(SNIFF_LEN));
+        std::fs::write(&path, &contents).expect("file should've been written");
+        let settings = WatchSettings {
+            text_extensions: vec!["log".to_string()],
+            ..WatchSettings::default()
+        };
+
+        // WHEN
+        let result = read_file(&path, &settings);
+
+        // THEN
+        assert!(
+            matches!(result, Ok(FileContents::Text { contents: read, .. }) if read == contents)
+        );
+    }
 }
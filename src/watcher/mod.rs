@@ -1,5 +1,11 @@
-mod consts;
+mod filesystem;
 mod helpers;
+mod pool;
+mod root;
+mod settings;
+mod snapshots;
 mod watch;
 
+pub use root::WatchRoot;
+pub use settings::WatchSettings;
 pub use watch::watch_for_changes;
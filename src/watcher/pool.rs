@@ -0,0 +1,239 @@
+use crate::domain::WatchMetrics;
+use notify::event::ModifyKind;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::num::NonZero;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::mpsc::{Sender, channel};
+use tokio::sync::oneshot;
+
+const MAX_WORKERS: usize = 8;
+const JOB_QUEUE_BUFFER: usize = 256;
+
+// A file system event for a single path, to be turned into changes by a worker
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Job {
+    pub event_path: PathBuf,
+    // relative to the root
+    pub path: String,
+    pub kind: JobKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum JobKind {
+    Created,
+    Modified(ModifyKind),
+    RemovedFile,
+    RemovedDir,
+    // the polling watcher can't tell whether a file or a directory was removed
+    Removed,
+}
+
+impl JobKind {
+    // whether the job might affect other paths than its own, ie. the files in a removed directory
+    fn affects_directory(&self) -> bool {
+        matches!(self, JobKind::RemovedDir | JobKind::Removed)
+    }
+}
+
+enum WorkerMsg {
+    Job(Job),
+    // acknowledged once the jobs queued before it are done
+    Flush(oneshot::Sender<()>),
+}
+
+// Reading and diffing files happens on a fixed number of workers, so that a burst of events (eg. an
+// agent rewriting hundreds of files at once) doesn't hold up the loop receiving them. Workers run
+// on blocking threads, since all they do is read files, diff them, and update the cache. Jobs for a
+// path always go to the same worker, which handles them one at a time, so that changes to a file
+// are reported in the order they happened. A removed directory takes the files in it along, and
+// jobs for those might be on any worker, so it's only handled once every job before it is done,
+// and jobs after it wait until it is.
+pub(super) struct WorkerPool {
+    senders: Vec<Sender<WorkerMsg>>,
+    metrics: Arc<WatchMetrics>,
+}
+
+impl WorkerPool {
+    pub(super) fn new<F>(num_workers: usize, metrics: Arc<WatchMetrics>, handler: F) -> Self
+    where
+        F: Fn(Job) + Clone + Send + 'static,
+    {
+        let senders = (0..num_workers.max(1))
+            .map(|_| {
+                let (tx, mut rx) = channel::<WorkerMsg>(JOB_QUEUE_BUFFER);
+                let handler = handler.clone();
+                let metrics = Arc::clone(&metrics);
+                tokio::task::spawn_blocking(move || {
+                    while let Some(msg) = rx.blocking_recv() {
+                        match msg {
+                            WorkerMsg::Job(job) => {
+                                handler(job);
+                                metrics.job_done();
+                            }
+                            WorkerMsg::Flush(done) => {
+                                let _ = done.send(());
+                            }
+                        }
+                    }
+                });
+
+                tx
+            })
+            .collect();
+
+        Self { senders, metrics }
+    }
+
+    // Waits for room in the worker's queue if it's full
+    pub(super) async fn submit(&self, job: Job) {
+        let mut hasher = DefaultHasher::new();
+        job.path.hash(&mut hasher);
+        let worker = (hasher.finish() % self.senders.len() as u64) as usize;
+
+        if !job.kind.affects_directory() {
+            self.send(worker, job).await;
+            return;
+        }
+
+        for i in 0..self.senders.len() {
+            self.flush(i).await;
+        }
+        self.send(worker, job).await;
+        self.flush(worker).await;
+    }
+
+    async fn send(&self, worker: usize, job: Job) {
+        self.metrics.job_queued();
+        if self.senders[worker]
+            .send(WorkerMsg::Job(job))
+            .await
+            .is_err()
+        {
+            self.metrics.job_done();
+        }
+    }
+
+    // Waits until the jobs queued on the worker so far are done
+    async fn flush(&self, worker: usize) {
+        let (done_tx, done_rx) = oneshot::channel();
+        if self.senders[worker]
+            .send(WorkerMsg::Flush(done_tx))
+            .await
+            .is_ok()
+        {
+            let _ = done_rx.await;
+        }
+    }
+}
+
+pub(super) fn default_num_workers() -> usize {
+    std::thread::available_parallelism()
+        .map_or(1, NonZero::get)
+        .min(MAX_WORKERS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    fn job(path: &str, kind: JobKind) -> Job {
+        Job {
+            event_path: PathBuf::from(path),
+            path: path.to_string(),
+            kind,
+        }
+    }
+
+    #[tokio::test]
+    async fn jobs_for_a_path_are_handled_in_order() {
+        // GIVEN
+        let handled = Arc::new(Mutex::new(vec![]));
+        let metrics = Arc::new(WatchMetrics::default());
+        let pool = {
+            let handled = Arc::clone(&handled);
+            WorkerPool::new(4, Arc::clone(&metrics), move |job: Job| {
+                // the first job for a file takes the longest
+                if job.kind == JobKind::Created {
+                    std::thread::sleep(Duration::from_millis(20));
+                }
+                handled
+                    .lock()
+                    .expect("lock should've been acquired")
+                    .push(job);
+            })
+        };
+        let paths = ["a.txt", "b.txt", "c.txt"];
+
+        // WHEN
+        for kind in [
+            JobKind::Created,
+            JobKind::Modified(ModifyKind::Any),
+            JobKind::RemovedFile,
+        ] {
+            for path in paths {
+                pool.submit(job(path, kind)).await;
+            }
+        }
+        while metrics.queued() > 0 {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+
+        // THEN
+        let handled = handled.lock().expect("lock should've been acquired");
+        for path in paths {
+            let kinds = handled
+                .iter()
+                .filter(|job| job.path == path)
+                .map(|job| job.kind)
+                .collect::<Vec<_>>();
+            assert_eq!(
+                kinds,
+                vec![
+                    JobKind::Created,
+                    JobKind::Modified(ModifyKind::Any),
+                    JobKind::RemovedFile,
+                ]
+            );
+        }
+        assert_eq!(handled.len(), 9);
+        assert!(metrics.max_queued() > 0);
+    }
+
+    #[tokio::test]
+    async fn a_removed_directory_is_handled_in_order_with_jobs_for_files_in_it() {
+        // GIVEN
+        let handled = Arc::new(Mutex::new(vec![]));
+        let metrics = Arc::new(WatchMetrics::default());
+        let pool = {
+            let handled = Arc::clone(&handled);
+            WorkerPool::new(4, Arc::clone(&metrics), move |job: Job| {
+                if job.kind == JobKind::Created {
+                    std::thread::sleep(Duration::from_millis(20));
+                }
+                handled
+                    .lock()
+                    .expect("lock should've been acquired")
+                    .push(job.path);
+            })
+        };
+
+        // WHEN
+        for path in ["src/a.rs", "src/b.rs", "src/c.rs"] {
+            pool.submit(job(path, JobKind::Created)).await;
+        }
+        pool.submit(job("src", JobKind::RemovedDir)).await;
+        pool.submit(job("src/d.rs", JobKind::Created)).await;
+        while metrics.queued() > 0 {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+
+        // THEN
+        let handled = handled.lock().expect("lock should've been acquired");
+        assert_eq!(handled.len(), 5);
+        assert_eq!(handled[3], "src");
+        assert_eq!(handled[4], "src/d.rs");
+    }
+}
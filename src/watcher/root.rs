@@ -0,0 +1,127 @@
+use std::path::{Path, PathBuf};
+
+// A directory being watched. When several directories are watched at once, each one gets a short
+// name, which is prepended to the paths of its files; that keeps the paths of files from different
+// directories apart (eg. both might have a package.json), and shows which directory a change is in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchRoot {
+    pub path: PathBuf,
+    pub name: Option<String>,
+}
+
+impl WatchRoot {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, name: None }
+    }
+
+    // Names the roots after their directories, adding a number to names that'd otherwise be the same
+    // (eg. for "~/work/app" and "~/personal/app"). A single root isn't named.
+    pub fn from_paths(paths: Vec<PathBuf>) -> Vec<Self> {
+        if paths.len() < 2 {
+            return paths.into_iter().map(Self::new).collect();
+        }
+
+        let mut names: Vec<String> = vec![];
+        paths
+            .into_iter()
+            .map(|path| {
+                let base = path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_else(|| "root".to_string());
+                let mut name = base.clone();
+                let mut suffix = 2;
+                while names.contains(&name) {
+                    name = format!("{base}-{suffix}");
+                    suffix += 1;
+                }
+                names.push(name.clone());
+
+                Self {
+                    path,
+                    name: Some(name),
+                }
+            })
+            .collect()
+    }
+
+    // The path that changes to a file in this root are reported with
+    pub fn path_of(&self, file_path: &Path) -> String {
+        let relative = file_path
+            .strip_prefix(&self.path)
+            .unwrap_or(file_path)
+            .to_string_lossy();
+
+        match &self.name {
+            Some(name) => format!("{name}/{relative}"),
+            None => relative.to_string(),
+        }
+    }
+
+    // The inverse of path_of; None if the path isn't for a file in this root
+    pub fn relative_path<'a>(&self, path: &'a str) -> Option<&'a str> {
+        match &self.name {
+            Some(name) => path.strip_prefix(name.as_str())?.strip_prefix('/'),
+            None => Some(path),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roots_with_the_same_directory_name_get_different_names() {
+        // GIVEN
+        let paths = vec![
+            PathBuf::from("/work/app"),
+            PathBuf::from("/work/api"),
+            PathBuf::from("/personal/app"),
+        ];
+
+        // WHEN
+        let roots = WatchRoot::from_paths(paths);
+
+        // THEN
+        let names = roots
+            .iter()
+            .map(|r| r.name.as_deref().unwrap_or_default())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["app", "api", "app-2"]);
+    }
+
+    #[test]
+    fn a_single_root_isnt_named() {
+        // GIVEN
+        let paths = vec![PathBuf::from("/work/app")];
+
+        // WHEN
+        let roots = WatchRoot::from_paths(paths);
+
+        // THEN
+        assert_eq!(roots, vec![WatchRoot::new(PathBuf::from("/work/app"))]);
+        assert_eq!(
+            roots[0].path_of(Path::new("/work/app/src/main.rs")),
+            "src/main.rs"
+        );
+    }
+
+    #[test]
+    fn paths_of_named_roots_are_prefixed_with_the_name() {
+        // GIVEN
+        let root = WatchRoot {
+            path: PathBuf::from("/work/frontend"),
+            name: Some("frontend".to_string()),
+        };
+
+        // WHEN
+        let path = root.path_of(Path::new("/work/frontend/src/app.ts"));
+
+        // THEN
+        assert_eq!(path, "frontend/src/app.ts");
+        assert_eq!(root.relative_path(&path), Some("src/app.ts"));
+        assert_eq!(root.relative_path("backend/src/app.ts"), None);
+        assert_eq!(root.relative_path("frontend-2/src/app.ts"), None);
+    }
+}
@@ -0,0 +1,58 @@
+use std::time::Duration;
+
+const DEFAULT_MAX_FILE_SIZE: u64 = 1024 * 1024; // 1MB
+const DEFAULT_MAX_DIFFED_FILE_SIZE: u64 = 16 * 1024 * 1024; // 16MB
+const DEFAULT_MAX_PREPOPULATED_FILES: usize = 10000;
+const DEFAULT_DEBOUNCE_MILLIS: u64 = 500;
+const DEFAULT_MAX_CACHE_SIZE: u64 = 256 * 1024 * 1024; // 256MB
+
+// Limits that control how the watcher treats the files under the root
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchSettings {
+    // files larger than this (in bytes) are diffed in a cheaper way, meant for large files
+    pub max_file_size: u64,
+    // files larger than this (in bytes) aren't diffed at all; only their size and hash is compared
+    pub max_diffed_file_size: u64,
+    // prepopulation stops after caching this many files
+    pub max_prepopulated_files: usize,
+    // snapshots of files are compressed or evicted to keep their contents within this many bytes
+    pub max_cache_size: u64,
+    // whether snapshots are compressed before any are evicted
+    pub compress_snapshots: bool,
+    // whether snapshots are stored on disk, so that they're still around after a restart; off by
+    // default, as that copies the contents of watched files (secrets included) out of the root
+    pub persist_snapshots: bool,
+    // file system events that happen within this window are processed together
+    pub debounce: Duration,
+    // files with these extensions are always read as text, without checking if they're binary
+    pub text_extensions: Vec<String>,
+    // files with these extensions are never watched
+    pub ignored_extensions: Vec<String>,
+    // when set, the root is scanned for changes this often, instead of relying on file system
+    // events (which might not fire on network or container file systems)
+    pub poll_interval: Option<Duration>,
+}
+
+impl WatchSettings {
+    // The longest it can take for a change to a file to be noticed, going by the settings
+    pub fn max_event_delay(&self) -> Duration {
+        self.debounce + self.poll_interval.unwrap_or_default()
+    }
+}
+
+impl Default for WatchSettings {
+    fn default() -> Self {
+        Self {
+            max_file_size: DEFAULT_MAX_FILE_SIZE,
+            max_diffed_file_size: DEFAULT_MAX_DIFFED_FILE_SIZE,
+            max_prepopulated_files: DEFAULT_MAX_PREPOPULATED_FILES,
+            max_cache_size: DEFAULT_MAX_CACHE_SIZE,
+            compress_snapshots: true,
+            persist_snapshots: false,
+            debounce: Duration::from_millis(DEFAULT_DEBOUNCE_MILLIS),
+            text_extensions: vec![],
+            ignored_extensions: vec![],
+            poll_interval: None,
+        }
+    }
+}
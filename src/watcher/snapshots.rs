@@ -0,0 +1,413 @@
+use super::root::WatchRoot;
+use crate::domain::{FileCache, Fingerprint, Snapshot, TextEncoding, stable_hash};
+use crate::utils::get_state_dir;
+use anyhow::Context;
+use encoding_rs::Encoding;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+const INDEX_FILE_NAME: &str = "index.json";
+const BLOBS_DIR_NAME: &str = "blobs";
+
+// The snapshots of a directory's files as of the last time dfft watched it, so that changes made
+// after a restart can still be diffed against what the files looked like before it. Each watched
+// directory gets a directory of its own in dfft's state directory, with an index of what's cached
+// for each file, and the (compressed) contents of text files stored once per unique contents.
+pub(super) struct SnapshotStore {
+    dir: PathBuf,
+    root: String,
+    // the root's name followed by a slash, for a root that's watched alongside others; paths are
+    // stored without it, so that snapshots are shared with sessions watching the root on its own
+    prefix: String,
+    // contents that have been written to the blobs directory
+    on_disk: HashSet<u64>,
+    synced_generation: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Index {
+    root: String,
+    files: BTreeMap<String, IndexEntry>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum IndexEntry {
+    Text {
+        blob: String,
+        encoding: String,
+        bom: bool,
+    },
+    Fingerprint {
+        size: u64,
+        hash: u64,
+    },
+}
+
+impl SnapshotStore {
+    pub(super) fn for_root(root: &WatchRoot) -> anyhow::Result<Self> {
+        let path = root.path.to_string_lossy().to_string();
+        let dir = get_state_dir()?
+            .join("snapshots")
+            .join(format!("{:016x}", stable_hash(path.as_bytes())));
+
+        let mut store = Self::new(dir, path);
+        if let Some(name) = &root.name {
+            store.prefix = format!("{name}/");
+        }
+
+        Ok(store)
+    }
+
+    fn new(dir: PathBuf, root: String) -> Self {
+        Self {
+            dir,
+            root,
+            prefix: String::new(),
+            on_disk: HashSet::new(),
+            synced_generation: None,
+        }
+    }
+
+    // Adds the snapshots from the last session to the cache, returning the paths they were for, or
+    // None if there was no last session
+    pub(super) async fn load_into(
+        &mut self,
+        cache: &RwLock<FileCache>,
+    ) -> anyhow::Result<Option<HashSet<String>>> {
+        let index = match tokio::fs::read(self.dir.join(INDEX_FILE_NAME)).await {
+            Ok(bytes) => serde_json::from_slice::<Index>(&bytes)
+                .context("couldn't parse index of stored snapshots")?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e).context("couldn't read index of stored snapshots"),
+        };
+
+        // eg. two directories whose paths have the same hash
+        if index.root != self.root {
+            return Ok(None);
+        }
+
+        self.on_disk = self.blobs_on_disk().await;
+
+        let mut blobs: HashMap<String, Option<Arc<str>>> = HashMap::new();
+        let mut loaded = HashSet::new();
+        for (path, entry) in index.files {
+            let path = format!("{}{path}", self.prefix);
+            match entry {
+                IndexEntry::Text {
+                    blob,
+                    encoding,
+                    bom,
+                } => {
+                    let Some(encoding) = Encoding::for_label(encoding.as_bytes()) else {
+                        continue;
+                    };
+                    let contents = match blobs.get(&blob) {
+                        Some(contents) => contents.clone(),
+                        None => {
+                            let contents = self.read_blob(&blob).await;
+                            blobs.insert(blob, contents.clone());
+                            contents
+                        }
+                    };
+                    let Some(contents) = contents else {
+                        continue;
+                    };
+
+                    cache
+                        .write()
+                        .await
+                        .insert(&path, contents, TextEncoding { encoding, bom });
+                }
+                IndexEntry::Fingerprint { size, hash } => {
+                    cache
+                        .write()
+                        .await
+                        .insert_fingerprint(&path, Fingerprint { size, hash });
+                }
+            }
+            loaded.insert(path);
+        }
+
+        Ok(Some(loaded))
+    }
+
+    // Writes the cache's snapshots to disk, if the cache changed since the last time this was done
+    pub(super) async fn sync(&mut self, cache: &RwLock<FileCache>) -> anyhow::Result<()> {
+        let (generation, snapshots, missing) = {
+            let cache = cache.read().await;
+            let generation = cache.generation();
+            if self.synced_generation == Some(generation) {
+                return Ok(());
+            }
+
+            let snapshots = cache.snapshots();
+            let missing = snapshots
+                .iter()
+                .filter_map(|(_, snapshot)| match snapshot {
+                    Snapshot::Text { blob, .. } if !self.on_disk.contains(blob) => Some(*blob),
+                    _ => None,
+                })
+                .collect::<HashSet<_>>()
+                .into_iter()
+                .filter_map(|key| cache.blob(key).map(|contents| (key, contents)))
+                .collect::<Vec<_>>();
+
+            (generation, snapshots, missing)
+        };
+
+        let blobs_dir = self.dir.join(BLOBS_DIR_NAME);
+        tokio::fs::create_dir_all(&blobs_dir)
+            .await
+            .context("couldn't create directory for stored snapshots")?;
+
+        for (key, contents) in missing {
+            let compressed = tokio::task::spawn_blocking(move || {
+                lz4_flex::compress_prepend_size(contents.as_bytes())
+            })
+            .await
+            .context("couldn't compress snapshot")?;
+            tokio::fs::write(blobs_dir.join(blob_name(key)), compressed)
+                .await
+                .context("couldn't write snapshot")?;
+            self.on_disk.insert(key);
+        }
+
+        let mut referenced = HashSet::new();
+        let files = snapshots
+            .into_iter()
+            .map(|(path, snapshot)| {
+                let entry = match snapshot {
+                    Snapshot::Text { blob, encoding } => {
+                        referenced.insert(blob);
+                        IndexEntry::Text {
+                            blob: blob_name(blob),
+                            encoding: encoding.encoding.name().to_string(),
+                            bom: encoding.bom,
+                        }
+                    }
+                    Snapshot::Fingerprint(Fingerprint { size, hash }) => {
+                        IndexEntry::Fingerprint { size, hash }
+                    }
+                };
+                let path = match path.strip_prefix(&self.prefix) {
+                    Some(relative) => relative.to_string(),
+                    None => path,
+                };
+                (path, entry)
+            })
+            .collect();
+        let index = Index {
+            root: self.root.clone(),
+            files,
+        };
+
+        // written to a temporary file first, so that the index is never left half written
+        let index_path = self.dir.join(INDEX_FILE_NAME);
+        let temp_path = self.dir.join(format!("{INDEX_FILE_NAME}.tmp"));
+        let contents = serde_json::to_vec(&index).context("couldn't serialize snapshot index")?;
+        tokio::fs::write(&temp_path, contents)
+            .await
+            .context("couldn't write snapshot index")?;
+        tokio::fs::rename(&temp_path, &index_path)
+            .await
+            .context("couldn't replace snapshot index")?;
+
+        let unreferenced = self
+            .on_disk
+            .difference(&referenced)
+            .copied()
+            .collect::<Vec<_>>();
+        for key in unreferenced {
+            let _ = tokio::fs::remove_file(blobs_dir.join(blob_name(key))).await;
+            self.on_disk.remove(&key);
+        }
+
+        self.synced_generation = Some(generation);
+
+        Ok(())
+    }
+
+    async fn read_blob(&self, name: &str) -> Option<Arc<str>> {
+        let bytes = tokio::fs::read(self.dir.join(BLOBS_DIR_NAME).join(name))
+            .await
+            .ok()?;
+        let bytes = lz4_flex::decompress_size_prepended(&bytes).ok()?;
+
+        String::from_utf8(bytes).ok().map(Arc::from)
+    }
+
+    async fn blobs_on_disk(&self) -> HashSet<u64> {
+        let mut on_disk = HashSet::new();
+        let Ok(mut entries) = tokio::fs::read_dir(self.dir.join(BLOBS_DIR_NAME)).await else {
+            return on_disk;
+        };
+
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            if let Some(key) = entry
+                .file_name()
+                .to_str()
+                .and_then(|name| u64::from_str_radix(name, 16).ok())
+            {
+                on_disk.insert(key);
+            }
+        }
+
+        on_disk
+    }
+}
+
+fn blob_name(key: u64) -> String {
+    format!("{key:016x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use encoding_rs::UTF_16LE;
+    use std::path::Path;
+
+    fn store(dir: &Path) -> SnapshotStore {
+        SnapshotStore::new(dir.to_path_buf(), "/path/to/project".to_string())
+    }
+
+    #[tokio::test]
+    async fn snapshots_are_restored_in_the_next_session() {
+        // GIVEN
+        let dir = tempfile::tempdir().expect("temporary directory should've been created");
+        let utf16 = TextEncoding {
+            encoding: UTF_16LE,
+            bom: true,
+        };
+        let cache = RwLock::new(FileCache::new());
+        {
+            let mut cache = cache.write().await;
+            cache.insert("src/main.rs", "fn main() {}\n", TextEncoding::UTF8);
+            cache.insert("notes.txt", "hello\n", utf16);
+            cache.insert_fingerprint(
+                "logo.png",
+                Fingerprint {
+                    size: 2048,
+                    hash: 0x1a2b3c4d5e6f7a8b,
+                },
+            );
+        }
+        store(dir.path())
+            .sync(&cache)
+            .await
+            .expect("snapshots should've been stored");
+
+        // WHEN
+        let restored = RwLock::new(FileCache::new());
+        let loaded = store(dir.path())
+            .load_into(&restored)
+            .await
+            .expect("snapshots should've been loaded");
+
+        // THEN
+        let mut loaded = loaded
+            .expect("snapshots should've been found")
+            .into_iter()
+            .collect::<Vec<_>>();
+        loaded.sort();
+        assert_eq!(loaded, vec!["logo.png", "notes.txt", "src/main.rs"]);
+        let mut restored = restored.write().await;
+        let main = restored
+            .remove("src/main.rs")
+            .expect("snapshot should've been restored");
+        assert_eq!(main.text(), Some("fn main() {}\n"));
+        let notes = restored
+            .remove("notes.txt")
+            .expect("snapshot should've been restored");
+        assert!(matches!(
+            notes,
+            crate::domain::CachedFile::Text { encoding, .. } if encoding == utf16
+        ));
+        let logo = restored
+            .remove("logo.png")
+            .expect("snapshot should've been restored");
+        assert_eq!(
+            logo.fingerprint(),
+            Fingerprint {
+                size: 2048,
+                hash: 0x1a2b3c4d5e6f7a8b,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn loading_without_a_previous_session_finds_nothing() {
+        // GIVEN
+        let dir = tempfile::tempdir().expect("temporary directory should've been created");
+        let cache = RwLock::new(FileCache::new());
+
+        // WHEN
+        let loaded = store(dir.path())
+            .load_into(&cache)
+            .await
+            .expect("loading should've succeeded");
+
+        // THEN
+        assert!(loaded.is_none());
+    }
+
+    #[tokio::test]
+    async fn contents_that_are_no_longer_referenced_are_removed() {
+        // GIVEN
+        let dir = tempfile::tempdir().expect("temporary directory should've been created");
+        let cache = RwLock::new(FileCache::new());
+        let mut store = store(dir.path());
+        cache
+            .write()
+            .await
+            .insert("a.txt", "old contents\n", TextEncoding::UTF8);
+        store.sync(&cache).await.expect("sync should've succeeded");
+
+        // WHEN
+        cache
+            .write()
+            .await
+            .insert("a.txt", "new contents\n", TextEncoding::UTF8);
+        store.sync(&cache).await.expect("sync should've succeeded");
+
+        // THEN
+        let num_blobs = std::fs::read_dir(dir.path().join(BLOBS_DIR_NAME))
+            .expect("blobs directory should've been readable")
+            .count();
+        assert_eq!(num_blobs, 1);
+    }
+
+    #[tokio::test]
+    async fn snapshots_are_shared_by_sessions_watching_a_directory_on_its_own_or_with_others() {
+        // GIVEN
+        let dir = tempfile::tempdir().expect("temporary directory should've been created");
+        let cache = RwLock::new(FileCache::new());
+        cache
+            .write()
+            .await
+            .insert("frontend/src/app.ts", "render();\n", TextEncoding::UTF8);
+        let mut named = store(dir.path());
+        named.prefix = "frontend/".to_string();
+        named
+            .sync(&cache)
+            .await
+            .expect("snapshots should've been stored");
+
+        // WHEN
+        let restored = RwLock::new(FileCache::new());
+        let loaded = store(dir.path())
+            .load_into(&restored)
+            .await
+            .expect("snapshots should've been loaded");
+
+        // THEN
+        let loaded = loaded
+            .expect("snapshots should've been found")
+            .into_iter()
+            .collect::<Vec<_>>();
+        assert_eq!(loaded, vec!["src/app.ts"]);
+    }
+}
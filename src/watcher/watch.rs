@@ -1,23 +1,31 @@
-use super::helpers::{get_ignore, is_file_too_large, is_path_to_be_ignored};
-use crate::domain::{Change, ChangeKind, Diff, FileCache, Modification, WatchUpdate};
+use super::filesystem::unwatchable_filesystem;
+use super::helpers::{get_ignore, is_path_to_be_ignored, read_file};
+use super::pool::{Job, JobKind, WorkerPool, default_num_workers};
+use super::root::WatchRoot;
+use super::settings::WatchSettings;
+use super::snapshots::SnapshotStore;
+use crate::domain::{
+    CachedFile, Change, ChangeKind, Diff, EncodingChange, FileCache, FileContents, Fingerprint,
+    FingerprintChange, Modification, PathFilter, TextEncoding, WatchMetrics, WatchUpdate,
+};
 use anyhow::Context;
 use ignore::{Walk, gitignore::Gitignore};
-use notify::EventKind;
-use notify::RecursiveMode;
 use notify::event::{CreateKind, ModifyKind, RemoveKind};
-use notify_debouncer_full::new_debouncer;
+use notify::{EventKind, PollWatcher, RecommendedWatcher, RecursiveMode};
+use notify_debouncer_full::{
+    DebounceEventResult, Debouncer, RecommendedCache, new_debouncer, new_debouncer_opt,
+};
+use std::collections::HashSet;
 use std::path::Path;
-use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::RwLock;
 use tokio::sync::mpsc::{Sender, channel};
+use tokio::sync::{RwLock, watch};
 use tokio_util::sync::CancellationToken;
 use tracing::debug;
 
 const EVENT_CHANNEL_BUFFER: usize = 100;
-const PREPOPULATION_MAX_THRESHOLD: usize = 10000;
-const FS_EVENTS_DEBOUNCE_MILLIS: u64 = 500;
+const SNAPSHOT_SYNC_INTERVAL: Duration = Duration::from_secs(5);
 
 // How this function behaves
 // - touch file.txt                                                            = CREATED
@@ -29,24 +37,71 @@ const FS_EVENTS_DEBOUNCE_MILLIS: u64 = 500;
 // - modification via a temp file (existing.txt.tmp -> rename to existing.txt) = MODIFIED
 //
 // the last two are how files are sometimes created/modified by agents/editors
+//
+// The same goes for when the root is polled for changes (with settings.poll_interval), except that
+// changes are only noticed on the next scan
+#[allow(clippy::too_many_arguments)]
 pub async fn watch_for_changes(
-    root: PathBuf,
+    root: WatchRoot,
     cache: Arc<RwLock<FileCache>>,
     updates_tx: Sender<WatchUpdate>,
     cancellation_token: CancellationToken,
     prepopulate_cache: bool,
+    settings: WatchSettings,
+    metrics: Arc<WatchMetrics>,
+    // can be changed while watching
+    filter: watch::Receiver<PathFilter>,
 ) -> anyhow::Result<()> {
-    let gitignore = get_ignore(&root)?;
+    let gitignore = get_ignore(&root.path)?;
+
+    let mut store = None;
+    let mut previous_session = None;
+    if settings.persist_snapshots {
+        match SnapshotStore::for_root(&root) {
+            Ok(mut s) => {
+                previous_session = s.load_into(&cache).await.unwrap_or_else(|e| {
+                    debug!("couldn't load stored snapshots: {e}");
+                    None
+                });
+                store = Some(s);
+            }
+            Err(e) => debug!("couldn't set up snapshot store: {e}"),
+        }
+    }
+
+    let worker_context = WorkerContext {
+        cache: Arc::clone(&cache),
+        updates_tx: updates_tx.clone(),
+        settings: Arc::new(settings.clone()),
+    };
 
     if prepopulate_cache {
-        match populate_cache(&cache, &gitignore, &root, PREPOPULATION_MAX_THRESHOLD).await {
+        let context = worker_context.clone();
+        let gitignore = gitignore.clone();
+        let root = root.clone();
+        let initial_filter = filter.borrow().clone();
+        let result = tokio::task::spawn_blocking(move || {
+            populate_cache(
+                &context,
+                &gitignore,
+                &root,
+                &initial_filter,
+                previous_session.as_ref(),
+            )
+        })
+        .await
+        .unwrap_or_else(|e| Err(anyhow::anyhow!(e)));
+
+        match result {
             Ok(count) => {
                 debug!("prepopulated cache with {} files", count);
-                let _ = updates_tx.try_send(WatchUpdate::PrepopulationFinished);
+                let _ = updates_tx.send(WatchUpdate::PrepopulationFinished).await;
             }
             Err(e) => {
                 debug!("prepopulation failed: {}, continuing without cache", e);
-                let _ = updates_tx.try_send(WatchUpdate::PrepopulationFailed(e.to_string()));
+                let _ = updates_tx
+                    .send(WatchUpdate::PrepopulationFailed(e.to_string()))
+                    .await;
             }
         }
     }
@@ -54,251 +109,109 @@ pub async fn watch_for_changes(
     let (tx, mut rx) = channel(EVENT_CHANNEL_BUFFER);
 
     let runtime_handle = tokio::runtime::Handle::current();
-    let mut debouncer = new_debouncer(
-        Duration::from_millis(FS_EVENTS_DEBOUNCE_MILLIS),
-        None,
-        move |res| {
-            let tx = tx.clone();
-            let runtime_handle = runtime_handle.clone();
-            runtime_handle.spawn(async move {
-                let _ = tx.send(res).await;
-            });
-        },
-    )
-    .context("couldn't create notifications debouncer")?;
-
-    debouncer
-        .watch(&root, RecursiveMode::Recursive)
-        .context("couldn't start watching for changes")?;
-    debug!("watching for changes...");
+    let handler = move |res: DebounceEventResult| {
+        let tx = tx.clone();
+        let runtime_handle = runtime_handle.clone();
+        runtime_handle.spawn(async move {
+            let _ = tx.send(res).await;
+        });
+    };
+
+    let _watcher = match settings.poll_interval {
+        Some(interval) => {
+            let mut debouncer = new_debouncer_opt::<_, PollWatcher, _>(
+                settings.debounce,
+                None,
+                handler,
+                RecommendedCache::new(),
+                notify::Config::default().with_poll_interval(interval),
+            )
+            .context("couldn't create notifications debouncer")?;
+            debouncer
+                .watch(&root.path, RecursiveMode::Recursive)
+                .context("couldn't start polling for changes")?;
+            debug!("polling for changes every {}ms...", interval.as_millis());
+            FileWatcher::Polling {
+                _debouncer: debouncer,
+            }
+        }
+        None => {
+            let mut debouncer = new_debouncer(settings.debounce, None, handler)
+                .context("couldn't create notifications debouncer")?;
+            debouncer
+                .watch(&root.path, RecursiveMode::Recursive)
+                .context("couldn't start watching for changes")?;
+            debug!("watching for changes...");
+
+            if let Some(fs_type) = unwatchable_filesystem(&root.path) {
+                let description = match &root.name {
+                    Some(name) => format!("{name} is on {fs_type}"),
+                    None => format!("on {fs_type}"),
+                };
+                let _ = updates_tx
+                    .send(WatchUpdate::PollingSuggested(description))
+                    .await;
+            }
+            FileWatcher::Native {
+                _debouncer: debouncer,
+            }
+        }
+    };
+
+    let mut sync_ticker = tokio::time::interval(SNAPSHOT_SYNC_INTERVAL);
+    let pool = WorkerPool::new(default_num_workers(), metrics, move |job| {
+        for change in handle_job(&worker_context, job) {
+            let _ = worker_context
+                .updates_tx
+                .blocking_send(WatchUpdate::ChangeReceived(change));
+        }
+    });
 
     loop {
         tokio::select! {
             _ = cancellation_token.cancelled() => {
+                if let Some(store) = &mut store
+                    && let Err(e) = store.sync(&cache).await
+                {
+                    debug!("couldn't store snapshots: {e}");
+                }
                 break;
             }
+            _ = sync_ticker.tick(), if store.is_some() => {
+                if let Some(store) = &mut store
+                    && let Err(e) = store.sync(&cache).await
+                {
+                    debug!("couldn't store snapshots: {e}");
+                }
+            }
             Some(result) = rx.recv() => {
                 #[allow(clippy::single_match)]
                 match result {
                     Ok(events) => {
                         for event in events {
-                            match event.kind {
-                                EventKind::Create(CreateKind::File) => {
-                                    for event_path in &event.paths {
-                                        debug!("got create event, path: {}", &event_path.to_string_lossy());
-                                        if is_path_to_be_ignored(event_path, &gitignore).await {
-                                            continue;
-                                        }
-
-                                        if is_file_too_large(event_path).await {
-                                            continue;
-                                        }
-
-                                        let path = event_path
-                                            .strip_prefix(&root)
-                                            .unwrap_or(event_path)
-                                            .to_string_lossy()
-                                            .to_string();
-
-                                        let change = match tokio::fs::read_to_string(event_path).await {
-                                            Ok(contents) => {
-                                                let was_held = {
-                                                    let mut cache_guard = cache.write().await;
-                                                    cache_guard.insert(&path, &contents)
-                                                };
-                                                match was_held {
-                                                    Some(old) => {
-                                                        debug!("got create event, but was already in cache, path: {}", &event_path.to_string_lossy());
-                                                        Diff::new(&old, &contents).map(|diff| Change {
-                                                                path,
-                                                                kind: ChangeKind::Modified(Ok(
-                                                                    Modification::Diff(diff),
-                                                                )),
-                                                            })
-                                                    }
-                                                    None => Some(Change {
-                                                        path,
-                                                        kind: ChangeKind::Created(Ok(contents)),
-                                                    }),
-                                                }
-                                            }
-                                            Err(e) => Some(Change {
-                                                path,
-                                                kind: ChangeKind::Created(Err(e.to_string())),
-                                            }),
-                                        };
-
-                                        if let Some(c) = change {
-                                            let _ = updates_tx.send(WatchUpdate::ChangeReceived(c)).await;
-                                        }
-                                    }
+                            let (kind, label) = match event.kind {
+                                EventKind::Create(CreateKind::File) => (JobKind::Created, "create"),
+                                // the polling watcher doesn't say what was created
+                                EventKind::Create(CreateKind::Any) => (JobKind::Created, "create"),
+                                EventKind::Modify(modify_kind) => (JobKind::Modified(modify_kind), "modify"),
+                                EventKind::Remove(RemoveKind::File) => (JobKind::RemovedFile, "delete"),
+                                EventKind::Remove(RemoveKind::Folder) => (JobKind::RemovedDir, "delete (folder)"),
+                                EventKind::Remove(RemoveKind::Any) => (JobKind::Removed, "delete"),
+                                _ => continue,
+                            };
+
+                            for event_path in &event.paths {
+                                debug!("got {label} event, path: {}", &event_path.to_string_lossy());
+                                if is_path_to_be_ignored(event_path, &gitignore, &settings) {
+                                    continue;
                                 }
-                                EventKind::Modify(modify_kind) => {
-                                    for event_path in &event.paths {
-                                        debug!("got modify event, path: {}", &event_path.to_string_lossy());
-                                        if is_path_to_be_ignored(event_path, &gitignore).await {
-                                            continue;
-                                        }
-
-                                        let path = event_path
-                                            .strip_prefix(&root)
-                                            .unwrap_or(event_path)
-                                            .to_string_lossy()
-                                            .to_string();
-
-                                        // Renames are tricky to handle
-                                        // Two events might show up for a rename, with the modify kind
-                                        // ModifyKind::Any, in which case it's tricky to determine
-                                        // which path no longer exists
-                                        // Sometimes a file removal also shows up as a modification
-                                        // So, we check if the path no longer exists, and if so,
-                                        // send out a removed event.
-                                        // Also, if the modification event is for a directory, we
-                                        // ignore it
-                                        match tokio::fs::metadata(event_path).await {
-                                            Ok(metadata) => {
-                                                if metadata.is_dir() {
-                                                    continue;
-                                                }
-                                            }
-                                            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
-                                                    let was_held = {
-                                                        let mut cache_guard = cache.write().await;
-                                                        cache_guard.remove(&path).is_some()
-                                                    };
-
-                                                    if was_held {
-                                                        let change = Change {
-                                                            path,
-                                                            kind: ChangeKind::RemovedFile,
-                                                        };
-
-                                                        let _ = updates_tx.send(WatchUpdate::ChangeReceived(change)).await;
-                                                    }
-
-                                                continue;
-                                            }
-                                            Err(e) => {
-                                                debug!("couldn't get metadata for path {}: {e}", &event_path.to_string_lossy());
-                                                continue;
-                                            }
-                                        }
-
-                                        if is_file_too_large(event_path).await {
-                                            continue;
-                                        }
-
-                                        let change = match tokio::fs::read_to_string(event_path).await {
-                                            Ok(contents) => {
-                                                let was_held = {
-                                                    let mut cache_guard = cache.write().await;
-                                                    cache_guard.insert(&path, &contents)
-                                                };
-                                                match was_held {
-                                                    Some(old) => {
-                                                        Diff::new(&old, &contents).map(|diff| Change {
-                                                                path,
-                                                                kind: ChangeKind::Modified(Ok(
-                                                                    Modification::Diff(diff),
-                                                                )),
-                                                            })
-                                                    }
-                                                    None => {
-                                                        match modify_kind {
-                                                            ModifyKind::Name(_) => {
-                                                                // Some agents will create a temporary
-                                                                // file and then rename it to the
-                                                                // target file, registering a MODIFY
-                                                                // event instead of a CREATE, but for
-                                                                // our purposes, the file was CREATED
-                                                                Some(Change {
-                                                                    path,
-                                                                    kind: ChangeKind::Created(Ok(contents)),
-                                                                })
-                                                            }
-                                                            _ => Some(Change {
-                                                                path,
-                                                                kind: ChangeKind::Modified(Ok(Modification::InitialSnapshot)),
-                                                            })
-                                                        }
-                                                    }
-                                                }
-                                            }
-                                            Err(e) => Some(Change {
-                                                path,
-                                                kind: ChangeKind::Modified(Err(e.to_string())),
-                                            }),
-                                        };
-
-                                        if let Some(c) = change {
-                                            let _ = updates_tx.send(WatchUpdate::ChangeReceived(c)).await;
-                                        }
-                                    }
-                                }
-                                EventKind::Remove(RemoveKind::File) => {
-                                    for event_path in &event.paths {
-                                        debug!("got delete event, path: {}", &event_path.to_string_lossy());
-                                        if is_path_to_be_ignored(event_path, &gitignore).await {
-                                            continue;
-                                        }
-
-                                        // "git checkout" emits a DELETE followed by a CREATE
-                                        // if file still exists when we receive this event, we can
-                                        // skip it. The arm handling CREATE events already checks
-                                        // if the path in question is in the cache, ultimately
-                                        // making git checkouts appear as MODIFICATIONS
-                                        if tokio::fs::try_exists(event_path).await.unwrap_or(false) {
-                                            continue;
-                                        };
-
-                                        let path = event_path
-                                            .strip_prefix(&root)
-                                            .unwrap_or(event_path)
-                                            .to_string_lossy()
-                                            .to_string();
-                                        {
-                                            let mut cache_guard = cache.write().await;
-                                            cache_guard.remove(&path);
-                                        }
-                                        let change = Change {
-                                            path,
-                                            kind: ChangeKind::RemovedFile,
-                                        };
-
-                                        let _ = updates_tx.send(WatchUpdate::ChangeReceived(change)).await;
-                                    }
-                                }
-                                EventKind::Remove(RemoveKind::Folder) => {
-                                    for event_path in &event.paths {
-                                        debug!("got delete event for a folder, path: {}", &event_path.to_string_lossy());
-                                        if is_path_to_be_ignored(event_path, &gitignore).await {
-                                            continue;
-                                        }
-
-                                        let path = event_path
-                                            .strip_prefix(&root)
-                                            .unwrap_or(event_path)
-                                            .to_string_lossy()
-                                            .to_string();
-
-                                        let were_files_removed = {
-                                            let mut cache_guard = cache.write().await;
-                                            cache_guard.remove_directory(&path)
-                                        };
-
-                                        debug!("removed files from cache for deleted directory: {}", &path);
-
-                                        if were_files_removed {
-                                            let change = Change {
-                                                path,
-                                                kind: ChangeKind::RemovedDir,
-                                            };
-
-                                            let _ = updates_tx.send(WatchUpdate::ChangeReceived(change)).await;
-                                        }
-                                    }
+
+                                let path = root.path_of(event_path);
+                                if !filter.borrow().matches(root.relative_path(&path).unwrap_or(&path)) {
+                                    continue;
                                 }
-                                _ => {}
+
+                                pool.submit(Job { event_path: event_path.clone(), path, kind }).await;
                             }
                         }
                     }
@@ -313,21 +226,251 @@ pub async fn watch_for_changes(
     Ok(())
 }
 
-async fn populate_cache<P>(
-    cache: &Arc<RwLock<FileCache>>,
+// The watcher used to get file system events; either one produces events that mean the same thing
+// once debounced. The debouncer is only held on to, since watching stops once it's dropped.
+enum FileWatcher {
+    Native {
+        _debouncer: Debouncer<RecommendedWatcher, RecommendedCache>,
+    },
+    Polling {
+        _debouncer: Debouncer<PollWatcher, RecommendedCache>,
+    },
+}
+
+// What workers need to turn file system events into changes
+#[derive(Clone)]
+struct WorkerContext {
+    cache: Arc<RwLock<FileCache>>,
+    updates_tx: Sender<WatchUpdate>,
+    settings: Arc<WatchSettings>,
+}
+
+// Reads and diffs files, so it blocks; it's run on the pool's workers, or on a blocking thread
+// while prepopulating
+fn handle_job(context: &WorkerContext, job: Job) -> Vec<Change> {
+    let Job {
+        event_path,
+        path,
+        kind,
+    } = job;
+    let cache = &context.cache;
+
+    match kind {
+        JobKind::Created => {
+            // eg. a temporary file that's already been renamed, or a directory (which the polling
+            // watcher reports creations of too)
+            match std::fs::metadata(&event_path) {
+                Ok(metadata) if !metadata.is_dir() => {}
+                _ => return vec![],
+            }
+
+            let unseen = |contents| ChangeKind::Created(Ok(contents));
+            read_change(context, &event_path, path, unseen, |e| {
+                ChangeKind::Created(Err(e))
+            })
+        }
+        JobKind::Modified(modify_kind) => {
+            // Renames are tricky to handle
+            // Two events might show up for a rename, with the modify kind
+            // ModifyKind::Any, in which case it's tricky to determine
+            // which path no longer exists
+            // Sometimes a file removal also shows up as a modification
+            // So, we check if the path no longer exists, and if so,
+            // send out a removed event.
+            // Also, if the modification event is for a directory, we
+            // ignore it
+            match std::fs::metadata(&event_path) {
+                Ok(metadata) => {
+                    if metadata.is_dir() {
+                        return vec![];
+                    }
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    let was_held = {
+                        let mut cache_guard = cache.blocking_write();
+                        cache_guard.remove(&path).is_some()
+                    };
+
+                    if !was_held {
+                        return vec![];
+                    }
+
+                    return vec![Change {
+                        path,
+                        kind: ChangeKind::RemovedFile,
+                        encoding: None,
+                    }];
+                }
+                Err(e) => {
+                    debug!(
+                        "couldn't get metadata for path {}: {e}",
+                        &event_path.to_string_lossy()
+                    );
+                    return vec![];
+                }
+            }
+
+            // Some agents will create a temporary file and then rename it to the target file,
+            // registering a MODIFY event instead of a CREATE, but for our purposes, the file was
+            // CREATED
+            let unseen = |contents| match modify_kind {
+                ModifyKind::Name(_) => ChangeKind::Created(Ok(contents)),
+                _ => ChangeKind::Modified(Ok(Modification::InitialSnapshot)),
+            };
+            read_change(context, &event_path, path, unseen, |e| {
+                ChangeKind::Modified(Err(e))
+            })
+        }
+        JobKind::RemovedFile => {
+            // "git checkout" emits a DELETE followed by a CREATE
+            // if file still exists when we receive this event, we can
+            // skip it. The arm handling CREATE events already checks
+            // if the path in question is in the cache, ultimately
+            // making git checkouts appear as MODIFICATIONS
+            if event_path.try_exists().unwrap_or(false) {
+                return vec![];
+            };
+
+            {
+                let mut cache_guard = cache.blocking_write();
+                cache_guard.remove(&path);
+            }
+
+            vec![Change {
+                path,
+                kind: ChangeKind::RemovedFile,
+                encoding: None,
+            }]
+        }
+        JobKind::RemovedDir => {
+            let were_files_removed = {
+                let mut cache_guard = cache.blocking_write();
+                cache_guard.remove_directory(&path)
+            };
+
+            debug!("removed files from cache for deleted directory: {}", &path);
+
+            if !were_files_removed {
+                return vec![];
+            }
+
+            vec![Change {
+                path,
+                kind: ChangeKind::RemovedDir,
+                encoding: None,
+            }]
+        }
+        JobKind::Removed => {
+            if event_path.try_exists().unwrap_or(false) {
+                return vec![];
+            };
+
+            let kind = {
+                let mut cache_guard = cache.blocking_write();
+                if cache_guard.remove(&path).is_some() {
+                    Some(ChangeKind::RemovedFile)
+                } else if cache_guard.remove_directory(&path) {
+                    Some(ChangeKind::RemovedDir)
+                } else {
+                    None
+                }
+            };
+
+            kind.map(|kind| Change {
+                path,
+                kind,
+                encoding: None,
+            })
+            .into_iter()
+            .collect()
+        }
+    }
+}
+
+// Reads a file that was created or modified, and compares it against what the cache held for it
+// before. `unseen` gives the kind of change for text files the cache didn't hold.
+fn read_change(
+    context: &WorkerContext,
+    event_path: &Path,
+    path: String,
+    unseen: impl FnOnce(String) -> ChangeKind,
+    on_error: fn(String) -> ChangeKind,
+) -> Vec<Change> {
+    let cache = &context.cache;
+    let settings = &context.settings;
+
+    match read_file(event_path, settings) {
+        Ok(FileContents::Text { contents, encoding }) => {
+            let (previous, snapshot) = {
+                let mut cache_guard = cache.blocking_write();
+                let previous = cache_guard.insert(&path, &contents, encoding);
+                (previous, cache_guard.text_contents(&path))
+            };
+            let encoding_change = get_encoding_change(&path, previous.as_ref(), encoding);
+            let kind = match previous {
+                Some(CachedFile::Text { contents: old, .. }) => {
+                    let new = snapshot.unwrap_or_else(|| contents.into());
+                    compute_diff(&old, new, settings)
+                        .map(|diff| ChangeKind::Modified(Ok(Modification::Diff(diff))))
+                }
+                // the file used to be binary, so there's nothing to diff against
+                Some(CachedFile::Fingerprint(_)) => {
+                    Some(ChangeKind::Modified(Ok(Modification::InitialSnapshot)))
+                }
+                None => Some(unseen(contents)),
+            };
+            let change = kind.map(|kind| Change {
+                path,
+                kind,
+                encoding: Some(encoding),
+            });
+
+            change.into_iter().chain(encoding_change).collect()
+        }
+        Ok(FileContents::Binary(fingerprint)) => {
+            record_fingerprint(cache, path, fingerprint, ChangeKind::Binary)
+                .into_iter()
+                .collect()
+        }
+        Ok(FileContents::TooLarge(fingerprint)) => {
+            record_fingerprint(cache, path, fingerprint, ChangeKind::TooLarge)
+                .into_iter()
+                .collect()
+        }
+        Err(e) => vec![Change {
+            path,
+            kind: on_error(e),
+            encoding: None,
+        }],
+    }
+}
+
+fn compute_diff(old: &str, new: Arc<str>, settings: &WatchSettings) -> Option<Diff> {
+    let large_file = is_large_file(old, &new, settings);
+    Diff::new_for(old, new, large_file)
+}
+
+// When there are snapshots from the last session, files are compared against them as they're read,
+// and the ones that were changed (or created, or removed) while dfft wasn't running are reported.
+// Walking the root and reading files blocks, so this is meant to be run on a blocking thread.
+fn populate_cache(
+    context: &WorkerContext,
     gitignore: &Option<Gitignore>,
-    root: P,
-    max_files: usize,
-) -> anyhow::Result<usize>
-where
-    P: AsRef<Path>,
-{
+    root: &WatchRoot,
+    filter: &PathFilter,
+    previous_session: Option<&HashSet<String>>,
+) -> anyhow::Result<usize> {
+    let cache = &context.cache;
+    let settings = &context.settings;
     let mut file_count = 0;
+    let mut seen = HashSet::new();
+    let mut walked_everything = true;
 
     // TODO: build this Walk with the same ignore paths as super::helpers::get_ignore
-    for result in Walk::new(&root) {
-        if file_count >= max_files {
+    for result in Walk::new(&root.path) {
+        if file_count >= settings.max_prepopulated_files {
             debug!("prepopulate threshold exceeded");
+            walked_everything = false;
             break;
         }
 
@@ -342,24 +485,53 @@ where
             continue;
         }
 
-        if is_path_to_be_ignored(path, gitignore).await {
+        if is_path_to_be_ignored(path, gitignore, settings) {
             continue;
         }
 
-        if is_file_too_large(path).await {
+        let file_path = root.path_of(path);
+        if !filter.matches(root.relative_path(&file_path).unwrap_or(&file_path)) {
             continue;
         }
 
-        match tokio::fs::read_to_string(path).await {
+        if let Some(previous_session) = previous_session {
+            seen.insert(file_path.clone());
+            let known = cache.blocking_read().contains(&file_path);
+            // a file that was in the last session, but whose snapshot didn't fit in the cache, is
+            // just read in again
+            if known || !previous_session.contains(&file_path) {
+                let kind = if known {
+                    JobKind::Modified(ModifyKind::Any)
+                } else {
+                    JobKind::Created
+                };
+                let job = Job {
+                    event_path: path.to_path_buf(),
+                    path: file_path,
+                    kind,
+                };
+                for change in handle_job(context, job) {
+                    let _ = context
+                        .updates_tx
+                        .blocking_send(WatchUpdate::ChangedSinceLastSession(change));
+                }
+                file_count += 1;
+                continue;
+            }
+        }
+
+        match read_file(path, settings) {
             Ok(contents) => {
-                let file_path = path
-                    .strip_prefix(&root)
-                    .unwrap_or(path)
-                    .to_string_lossy()
-                    .to_string();
                 {
-                    let mut cache_guard = cache.write().await;
-                    cache_guard.insert(&file_path, &contents);
+                    let mut cache_guard = cache.blocking_write();
+                    match contents {
+                        FileContents::Text { contents, encoding } => {
+                            cache_guard.insert(&file_path, &contents, encoding)
+                        }
+                        FileContents::Binary(fingerprint) | FileContents::TooLarge(fingerprint) => {
+                            cache_guard.insert_fingerprint(&file_path, fingerprint)
+                        }
+                    };
                 }
                 file_count += 1;
                 debug!("added to cache: {:?}", &file_path);
@@ -368,5 +540,199 @@ where
         }
     }
 
+    // files that were only left out of the walk (eg. because they're ignored now) aren't gone
+    if let Some(previous_session) = previous_session
+        && walked_everything
+    {
+        for file_path in previous_session.difference(&seen) {
+            cache.blocking_write().remove(file_path);
+            let relative_path = root.relative_path(file_path).unwrap_or(file_path);
+            let exists = root.path.join(relative_path).try_exists().unwrap_or(true);
+            if !exists {
+                let change = Change {
+                    path: file_path.clone(),
+                    kind: ChangeKind::RemovedFile,
+                    encoding: None,
+                };
+                let _ = context
+                    .updates_tx
+                    .blocking_send(WatchUpdate::ChangedSinceLastSession(change));
+            }
+        }
+    }
+
     Ok(file_count)
 }
+
+// Files larger than the max file size are diffed in a cheaper way
+fn is_large_file(old: &str, new: &str, settings: &WatchSettings) -> bool {
+    old.len().max(new.len()) as u64 > settings.max_file_size
+}
+
+// Files that can't be diffed (binary files, or ones that are too large) are only compared by their
+// size and hash, so a change to one only records those before and after; there's no change if
+// neither of them changed
+fn record_fingerprint(
+    cache: &Arc<RwLock<FileCache>>,
+    path: String,
+    fingerprint: Fingerprint,
+    to_kind: fn(FingerprintChange) -> ChangeKind,
+) -> Option<Change> {
+    let previous = {
+        let mut cache_guard = cache.blocking_write();
+        cache_guard.insert_fingerprint(&path, fingerprint)
+    };
+
+    let before = previous.map(|p| p.fingerprint());
+    if before == Some(fingerprint) {
+        return None;
+    }
+
+    Some(Change {
+        path,
+        kind: to_kind(FingerprintChange {
+            before,
+            after: fingerprint,
+        }),
+        encoding: None,
+    })
+}
+
+// A text file whose encoding (or BOM) changed gets a change of its own, so that it doesn't go
+// unnoticed, eg. when the contents stay the same
+fn get_encoding_change(
+    path: &str,
+    previous: Option<&CachedFile>,
+    encoding: TextEncoding,
+) -> Option<Change> {
+    let Some(CachedFile::Text {
+        encoding: before, ..
+    }) = previous
+    else {
+        return None;
+    };
+
+    if *before == encoding {
+        return None;
+    }
+
+    Some(Change {
+        path: path.to_string(),
+        kind: ChangeKind::Reencoded(EncodingChange {
+            before: *before,
+            after: encoding,
+        }),
+        encoding: Some(encoding),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::DiffContents;
+    use std::path::Path;
+
+    fn context() -> WorkerContext {
+        let (updates_tx, _) = channel(EVENT_CHANNEL_BUFFER);
+        WorkerContext {
+            cache: Arc::new(RwLock::new(FileCache::new())),
+            updates_tx,
+            settings: Arc::new(WatchSettings::default()),
+        }
+    }
+
+    fn job(dir: &Path, path: &str, kind: JobKind) -> Job {
+        Job {
+            event_path: dir.join(path),
+            path: path.to_string(),
+            kind,
+        }
+    }
+
+    #[test]
+    fn diffs_share_contents_with_the_snapshot() {
+        // GIVEN
+        let dir = tempfile::tempdir().expect("temporary directory should've been created");
+        std::fs::write(dir.path().join("notes.txt"), "hi\n").expect("file should've been written");
+        let context = context();
+        context
+            .cache
+            .blocking_write()
+            .insert("notes.txt", "hello\n", TextEncoding::UTF8);
+
+        // WHEN
+        let changes = handle_job(
+            &context,
+            job(dir.path(), "notes.txt", JobKind::Modified(ModifyKind::Any)),
+        );
+
+        // THEN
+        let [
+            Change {
+                kind: ChangeKind::Modified(Ok(Modification::Diff(diff))),
+                ..
+            },
+        ] = changes.as_slice()
+        else {
+            panic!("a single modification should've been reported");
+        };
+        let snapshot = context
+            .cache
+            .blocking_read()
+            .text_contents("notes.txt")
+            .expect("snapshot should've been cached");
+        let DiffContents::Full(contents) = &diff.contents else {
+            panic!("the diff should've had the whole file");
+        };
+        assert!(Arc::ptr_eq(contents, &snapshot));
+    }
+
+    #[test]
+    fn removals_without_a_kind_are_reported_for_files_and_directories() {
+        // GIVEN
+        let dir = tempfile::tempdir().expect("temporary directory should've been created");
+        let context = context();
+        {
+            let mut cache = context.cache.blocking_write();
+            cache.insert("notes.txt", "hello\n", TextEncoding::UTF8);
+            cache.insert("src/main.rs", "fn main() {}\n", TextEncoding::UTF8);
+        }
+
+        // WHEN
+        let file_changes = handle_job(&context, job(dir.path(), "notes.txt", JobKind::Removed));
+        let dir_changes = handle_job(&context, job(dir.path(), "src", JobKind::Removed));
+        let unknown_changes = handle_job(&context, job(dir.path(), "docs", JobKind::Removed));
+
+        // THEN
+        assert!(matches!(
+            file_changes.as_slice(),
+            [Change {
+                kind: ChangeKind::RemovedFile,
+                ..
+            }]
+        ));
+        assert!(matches!(
+            dir_changes.as_slice(),
+            [Change {
+                kind: ChangeKind::RemovedDir,
+                ..
+            }]
+        ));
+        assert!(unknown_changes.is_empty());
+        assert_eq!(context.cache.blocking_read().len(), 0);
+    }
+
+    #[test]
+    fn creation_of_a_directory_isnt_reported() {
+        // GIVEN
+        let dir = tempfile::tempdir().expect("temporary directory should've been created");
+        std::fs::create_dir(dir.path().join("src")).expect("directory should've been created");
+        let context = context();
+
+        // WHEN
+        let changes = handle_job(&context, job(dir.path(), "src", JobKind::Created));
+
+        // THEN
+        assert!(changes.is_empty());
+    }
+}
@@ -22,6 +22,11 @@ impl Fixture {
     {
         let mut command = Command::new(&self._bin_path);
         command.args(args);
+        // keeps the config file in the user's config directory from affecting tests
+        command.env(
+            "XDG_CONFIG_HOME",
+            std::env::temp_dir().join("dfft-tests-8163ef59"),
+        );
         command
     }
 }
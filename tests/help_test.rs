@@ -53,13 +53,27 @@ fn showing_help_for_run_works() {
     Usage: dfft run [OPTIONS]
 
     Options:
-      -p, --path <PATH>     Path of the directory to watch (defaults to current directory)
-          --debug           Output debug information without doing anything
-      -f, --follow-changes  Start with the setting "follow changes" enabled
-          --no-prepop       Skip prepopulating cache with file snapshots
-          --no-watch        Start with file watching disabled
-          --no-sound        Start with sound notifications disabled
-      -h, --help            Print help
+      -p, --path <PATH>            Path of a directory to watch (defaults to current directory); repeat to watch several
+          --debug                  Output debug information without doing anything
+          --include <GLOB>         Only watch files matching this glob (eg. "src/**/*.rs"); can be repeated
+          --exclude <GLOB>         Don't watch files matching this glob (eg. "docs"); can be repeated
+          --poll [<MILLIS>]        Poll for changes every MILLIS (defaults to 1000), eg. on network or container file systems
+      -f, --follow-changes         Start with the setting "follow changes" enabled
+          --no-follow-changes      Start with the setting "follow changes" disabled, eg. if the config file enables it
+          --collapse-appends       Show repeated appends to a file (eg. a log) as a single, updating change
+          --no-collapse-appends    Show every append to a file as a change of its own, eg. if the config file collapses them
+          --prepop                 Prepopulate cache with file snapshots, eg. if the config file skips it
+          --no-prepop              Skip prepopulating cache with file snapshots
+          --watch                  Start with file watching enabled, eg. if the config file disables it
+          --no-watch               Start with file watching disabled
+          --auto-review <SECONDS>  Mark a change as reviewed after viewing it for this many seconds
+          --config <PATH>          Path of the config file (defaults to config.toml in dfft's config directory)
+          --theme <NAME>           Color theme (auto, dark, light, high-contrast, no-color, or one from the config file)
+          --mouse                  Capture mouse events, eg. if the config file turns them off
+          --no-mouse               Don't capture mouse events (leaves text selection to the terminal)
+          --sound                  Start with sound notifications enabled, eg. if the config file disables them
+          --no-sound               Start with sound notifications disabled
+      -h, --help                   Print help
 
     ----- stderr -----
     "#);
@@ -16,7 +16,7 @@ fn debug_flag_works() {
 
     // WHEN
     // THEN
-    assert_cmd_snapshot!(cmd, @r"
+    assert_cmd_snapshot!(cmd, @"
     success: true
     exit_code: 0
     ----- stdout -----
@@ -24,10 +24,39 @@ fn debug_flag_works() {
 
     command:            run TUI
     path:               current directory
-    follow changes:     false
-    no prepopulation:   false
-    no watch:           false
-    no sound:           false
+    include:            none
+    exclude:            none
+    poll:               off
+    follow changes:     not set
+    collapse appends:   not set
+    prepopulate:        not set
+    watch:              not set
+    auto review:        off
+    config file:        default location
+    theme:              not set
+    mouse:              not set
+    sound:              not set
+
+    EFFECTIVE CONFIG:
+
+    config files read:  none
+    follow changes:     false (default)
+    collapse appends:   false (default)
+    prepopulate:        true (default)
+    watch:              true (default)
+    auto review:        off (default)
+    mouse:              true (default)
+    sound:              true (default)
+    theme:              auto (default)
+    max file size:      1048576 bytes (default)
+    max diffed size:    16777216 bytes (default)
+    max prepop files:   10000 (default)
+    max cache size:     268435456 bytes (default)
+    compress snapshots: true (default)
+    persist snapshots:  false (default)
+    debounce:           500ms (default)
+    text extensions:    none (default)
+    ignored extensions: none (default)
 
     ----- stderr -----
     ");
@@ -42,7 +71,7 @@ fn changing_path_works() {
 
     // WHEN
     // THEN
-    assert_cmd_snapshot!(cmd, @r"
+    assert_cmd_snapshot!(cmd, @"
     success: true
     exit_code: 0
     ----- stdout -----
@@ -50,10 +79,275 @@ fn changing_path_works() {
 
     command:            run TUI
     path:               path/to/a/directory
-    follow changes:     false
-    no prepopulation:   false
-    no watch:           false
-    no sound:           false
+    include:            none
+    exclude:            none
+    poll:               off
+    follow changes:     not set
+    collapse appends:   not set
+    prepopulate:        not set
+    watch:              not set
+    auto review:        off
+    config file:        default location
+    theme:              not set
+    mouse:              not set
+    sound:              not set
+
+    EFFECTIVE CONFIG:
+
+    config files read:  none
+    follow changes:     false (default)
+    collapse appends:   false (default)
+    prepopulate:        true (default)
+    watch:              true (default)
+    auto review:        off (default)
+    mouse:              true (default)
+    sound:              true (default)
+    theme:              auto (default)
+    max file size:      1048576 bytes (default)
+    max diffed size:    16777216 bytes (default)
+    max prepop files:   10000 (default)
+    max cache size:     268435456 bytes (default)
+    compress snapshots: true (default)
+    persist snapshots:  false (default)
+    debounce:           500ms (default)
+    text extensions:    none (default)
+    ignored extensions: none (default)
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+#[cfg(feature = "sound")]
+fn watching_multiple_paths_works() {
+    // GIVEN
+    let fx = Fixture::new();
+    let mut cmd = fx.cmd([
+        "run",
+        "--path",
+        "path/to/frontend",
+        "--path",
+        "path/to/backend",
+        "--debug",
+    ]);
+
+    // WHEN
+    // THEN
+    assert_cmd_snapshot!(cmd, @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    DEBUG INFO:
+
+    command:            run TUI
+    path:               path/to/frontend, path/to/backend
+    include:            none
+    exclude:            none
+    poll:               off
+    follow changes:     not set
+    collapse appends:   not set
+    prepopulate:        not set
+    watch:              not set
+    auto review:        off
+    config file:        default location
+    theme:              not set
+    mouse:              not set
+    sound:              not set
+
+    EFFECTIVE CONFIG:
+
+    config files read:  none
+    follow changes:     false (default)
+    collapse appends:   false (default)
+    prepopulate:        true (default)
+    watch:              true (default)
+    auto review:        off (default)
+    mouse:              true (default)
+    sound:              true (default)
+    theme:              auto (default)
+    max file size:      1048576 bytes (default)
+    max diffed size:    16777216 bytes (default)
+    max prepop files:   10000 (default)
+    max cache size:     268435456 bytes (default)
+    compress snapshots: true (default)
+    persist snapshots:  false (default)
+    debounce:           500ms (default)
+    text extensions:    none (default)
+    ignored extensions: none (default)
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+#[cfg(feature = "sound")]
+fn setting_globs_works() {
+    // GIVEN
+    let fx = Fixture::new();
+    let mut cmd = fx.cmd([
+        "run",
+        "--include",
+        "src/**/*.rs",
+        "--include",
+        "Cargo.toml",
+        "--exclude",
+        "src/generated",
+        "--debug",
+    ]);
+
+    // WHEN
+    // THEN
+    assert_cmd_snapshot!(cmd, @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    DEBUG INFO:
+
+    command:            run TUI
+    path:               current directory
+    include:            src/**/*.rs, Cargo.toml
+    exclude:            src/generated
+    poll:               off
+    follow changes:     not set
+    collapse appends:   not set
+    prepopulate:        not set
+    watch:              not set
+    auto review:        off
+    config file:        default location
+    theme:              not set
+    mouse:              not set
+    sound:              not set
+
+    EFFECTIVE CONFIG:
+
+    config files read:  none
+    follow changes:     false (default)
+    collapse appends:   false (default)
+    prepopulate:        true (default)
+    watch:              true (default)
+    auto review:        off (default)
+    mouse:              true (default)
+    sound:              true (default)
+    theme:              auto (default)
+    max file size:      1048576 bytes (default)
+    max diffed size:    16777216 bytes (default)
+    max prepop files:   10000 (default)
+    max cache size:     268435456 bytes (default)
+    compress snapshots: true (default)
+    persist snapshots:  false (default)
+    debounce:           500ms (default)
+    text extensions:    none (default)
+    ignored extensions: none (default)
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+#[cfg(feature = "sound")]
+fn polling_works() {
+    // GIVEN
+    let fx = Fixture::new();
+    let mut cmd = fx.cmd(["run", "--poll", "--debug"]);
+
+    // WHEN
+    // THEN
+    assert_cmd_snapshot!(cmd, @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    DEBUG INFO:
+
+    command:            run TUI
+    path:               current directory
+    include:            none
+    exclude:            none
+    poll:               every 1000ms
+    follow changes:     not set
+    collapse appends:   not set
+    prepopulate:        not set
+    watch:              not set
+    auto review:        off
+    config file:        default location
+    theme:              not set
+    mouse:              not set
+    sound:              not set
+
+    EFFECTIVE CONFIG:
+
+    config files read:  none
+    follow changes:     false (default)
+    collapse appends:   false (default)
+    prepopulate:        true (default)
+    watch:              true (default)
+    auto review:        off (default)
+    mouse:              true (default)
+    sound:              true (default)
+    theme:              auto (default)
+    max file size:      1048576 bytes (default)
+    max diffed size:    16777216 bytes (default)
+    max prepop files:   10000 (default)
+    max cache size:     268435456 bytes (default)
+    compress snapshots: true (default)
+    persist snapshots:  false (default)
+    debounce:           500ms (default)
+    text extensions:    none (default)
+    ignored extensions: none (default)
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+#[cfg(feature = "sound")]
+fn setting_poll_interval_works() {
+    // GIVEN
+    let fx = Fixture::new();
+    let mut cmd = fx.cmd(["run", "--poll", "250", "--debug"]);
+
+    // WHEN
+    // THEN
+    assert_cmd_snapshot!(cmd, @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    DEBUG INFO:
+
+    command:            run TUI
+    path:               current directory
+    include:            none
+    exclude:            none
+    poll:               every 250ms
+    follow changes:     not set
+    collapse appends:   not set
+    prepopulate:        not set
+    watch:              not set
+    auto review:        off
+    config file:        default location
+    theme:              not set
+    mouse:              not set
+    sound:              not set
+
+    EFFECTIVE CONFIG:
+
+    config files read:  none
+    follow changes:     false (default)
+    collapse appends:   false (default)
+    prepopulate:        true (default)
+    watch:              true (default)
+    auto review:        off (default)
+    mouse:              true (default)
+    sound:              true (default)
+    theme:              auto (default)
+    max file size:      1048576 bytes (default)
+    max diffed size:    16777216 bytes (default)
+    max prepop files:   10000 (default)
+    max cache size:     268435456 bytes (default)
+    compress snapshots: true (default)
+    persist snapshots:  false (default)
+    debounce:           500ms (default)
+    text extensions:    none (default)
+    ignored extensions: none (default)
 
     ----- stderr -----
     ");
@@ -68,7 +362,7 @@ fn turning_off_following_works() {
 
     // WHEN
     // THEN
-    assert_cmd_snapshot!(cmd, @r"
+    assert_cmd_snapshot!(cmd, @"
     success: true
     exit_code: 0
     ----- stdout -----
@@ -76,10 +370,39 @@ fn turning_off_following_works() {
 
     command:            run TUI
     path:               current directory
+    include:            none
+    exclude:            none
+    poll:               off
     follow changes:     true
-    no prepopulation:   false
-    no watch:           false
-    no sound:           false
+    collapse appends:   not set
+    prepopulate:        not set
+    watch:              not set
+    auto review:        off
+    config file:        default location
+    theme:              not set
+    mouse:              not set
+    sound:              not set
+
+    EFFECTIVE CONFIG:
+
+    config files read:  none
+    follow changes:     true (flag)
+    collapse appends:   false (default)
+    prepopulate:        true (default)
+    watch:              true (default)
+    auto review:        off (default)
+    mouse:              true (default)
+    sound:              true (default)
+    theme:              auto (default)
+    max file size:      1048576 bytes (default)
+    max diffed size:    16777216 bytes (default)
+    max prepop files:   10000 (default)
+    max cache size:     268435456 bytes (default)
+    compress snapshots: true (default)
+    persist snapshots:  false (default)
+    debounce:           500ms (default)
+    text extensions:    none (default)
+    ignored extensions: none (default)
 
     ----- stderr -----
     ");
@@ -94,7 +417,7 @@ fn turning_off_prepopulation_works() {
 
     // WHEN
     // THEN
-    assert_cmd_snapshot!(cmd, @r"
+    assert_cmd_snapshot!(cmd, @"
     success: true
     exit_code: 0
     ----- stdout -----
@@ -102,10 +425,39 @@ fn turning_off_prepopulation_works() {
 
     command:            run TUI
     path:               current directory
-    follow changes:     false
-    no prepopulation:   true
-    no watch:           false
-    no sound:           false
+    include:            none
+    exclude:            none
+    poll:               off
+    follow changes:     not set
+    collapse appends:   not set
+    prepopulate:        false
+    watch:              not set
+    auto review:        off
+    config file:        default location
+    theme:              not set
+    mouse:              not set
+    sound:              not set
+
+    EFFECTIVE CONFIG:
+
+    config files read:  none
+    follow changes:     false (default)
+    collapse appends:   false (default)
+    prepopulate:        false (flag)
+    watch:              true (default)
+    auto review:        off (default)
+    mouse:              true (default)
+    sound:              true (default)
+    theme:              auto (default)
+    max file size:      1048576 bytes (default)
+    max diffed size:    16777216 bytes (default)
+    max prepop files:   10000 (default)
+    max cache size:     268435456 bytes (default)
+    compress snapshots: true (default)
+    persist snapshots:  false (default)
+    debounce:           500ms (default)
+    text extensions:    none (default)
+    ignored extensions: none (default)
 
     ----- stderr -----
     ");
@@ -120,7 +472,7 @@ fn turning_off_watching_works() {
 
     // WHEN
     // THEN
-    assert_cmd_snapshot!(cmd, @r"
+    assert_cmd_snapshot!(cmd, @"
     success: true
     exit_code: 0
     ----- stdout -----
@@ -128,10 +480,204 @@ fn turning_off_watching_works() {
 
     command:            run TUI
     path:               current directory
-    follow changes:     false
-    no prepopulation:   false
-    no watch:           true
-    no sound:           false
+    include:            none
+    exclude:            none
+    poll:               off
+    follow changes:     not set
+    collapse appends:   not set
+    prepopulate:        not set
+    watch:              false
+    auto review:        off
+    config file:        default location
+    theme:              not set
+    mouse:              not set
+    sound:              not set
+
+    EFFECTIVE CONFIG:
+
+    config files read:  none
+    follow changes:     false (default)
+    collapse appends:   false (default)
+    prepopulate:        true (default)
+    watch:              false (flag)
+    auto review:        off (default)
+    mouse:              true (default)
+    sound:              true (default)
+    theme:              auto (default)
+    max file size:      1048576 bytes (default)
+    max diffed size:    16777216 bytes (default)
+    max prepop files:   10000 (default)
+    max cache size:     268435456 bytes (default)
+    compress snapshots: true (default)
+    persist snapshots:  false (default)
+    debounce:           500ms (default)
+    text extensions:    none (default)
+    ignored extensions: none (default)
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+#[cfg(feature = "sound")]
+fn setting_auto_review_works() {
+    // GIVEN
+    let fx = Fixture::new();
+    let mut cmd = fx.cmd(["run", "--auto-review", "5", "--debug"]);
+
+    // WHEN
+    // THEN
+    assert_cmd_snapshot!(cmd, @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    DEBUG INFO:
+
+    command:            run TUI
+    path:               current directory
+    include:            none
+    exclude:            none
+    poll:               off
+    follow changes:     not set
+    collapse appends:   not set
+    prepopulate:        not set
+    watch:              not set
+    auto review:        after 5s
+    config file:        default location
+    theme:              not set
+    mouse:              not set
+    sound:              not set
+
+    EFFECTIVE CONFIG:
+
+    config files read:  none
+    follow changes:     false (default)
+    collapse appends:   false (default)
+    prepopulate:        true (default)
+    watch:              true (default)
+    auto review:        after 5s (flag)
+    mouse:              true (default)
+    sound:              true (default)
+    theme:              auto (default)
+    max file size:      1048576 bytes (default)
+    max diffed size:    16777216 bytes (default)
+    max prepop files:   10000 (default)
+    max cache size:     268435456 bytes (default)
+    compress snapshots: true (default)
+    persist snapshots:  false (default)
+    debounce:           500ms (default)
+    text extensions:    none (default)
+    ignored extensions: none (default)
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+#[cfg(feature = "sound")]
+fn setting_theme_works() {
+    // GIVEN
+    let fx = Fixture::new();
+    let mut cmd = fx.cmd(["run", "--theme", "light", "--debug"]);
+
+    // WHEN
+    // THEN
+    assert_cmd_snapshot!(cmd, @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    DEBUG INFO:
+
+    command:            run TUI
+    path:               current directory
+    include:            none
+    exclude:            none
+    poll:               off
+    follow changes:     not set
+    collapse appends:   not set
+    prepopulate:        not set
+    watch:              not set
+    auto review:        off
+    config file:        default location
+    theme:              light
+    mouse:              not set
+    sound:              not set
+
+    EFFECTIVE CONFIG:
+
+    config files read:  none
+    follow changes:     false (default)
+    collapse appends:   false (default)
+    prepopulate:        true (default)
+    watch:              true (default)
+    auto review:        off (default)
+    mouse:              true (default)
+    sound:              true (default)
+    theme:              light (flag)
+    max file size:      1048576 bytes (default)
+    max diffed size:    16777216 bytes (default)
+    max prepop files:   10000 (default)
+    max cache size:     268435456 bytes (default)
+    compress snapshots: true (default)
+    persist snapshots:  false (default)
+    debounce:           500ms (default)
+    text extensions:    none (default)
+    ignored extensions: none (default)
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+#[cfg(feature = "sound")]
+fn turning_off_mouse_works() {
+    // GIVEN
+    let fx = Fixture::new();
+    let mut cmd = fx.cmd(["run", "--no-mouse", "--debug"]);
+
+    // WHEN
+    // THEN
+    assert_cmd_snapshot!(cmd, @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    DEBUG INFO:
+
+    command:            run TUI
+    path:               current directory
+    include:            none
+    exclude:            none
+    poll:               off
+    follow changes:     not set
+    collapse appends:   not set
+    prepopulate:        not set
+    watch:              not set
+    auto review:        off
+    config file:        default location
+    theme:              not set
+    mouse:              false
+    sound:              not set
+
+    EFFECTIVE CONFIG:
+
+    config files read:  none
+    follow changes:     false (default)
+    collapse appends:   false (default)
+    prepopulate:        true (default)
+    watch:              true (default)
+    auto review:        off (default)
+    mouse:              false (flag)
+    sound:              true (default)
+    theme:              auto (default)
+    max file size:      1048576 bytes (default)
+    max diffed size:    16777216 bytes (default)
+    max prepop files:   10000 (default)
+    max cache size:     268435456 bytes (default)
+    compress snapshots: true (default)
+    persist snapshots:  false (default)
+    debounce:           500ms (default)
+    text extensions:    none (default)
+    ignored extensions: none (default)
 
     ----- stderr -----
     ");
@@ -146,7 +692,7 @@ fn turning_off_sound_works() {
 
     // WHEN
     // THEN
-    assert_cmd_snapshot!(cmd, @r"
+    assert_cmd_snapshot!(cmd, @"
     success: true
     exit_code: 0
     ----- stdout -----
@@ -154,10 +700,39 @@ fn turning_off_sound_works() {
 
     command:            run TUI
     path:               current directory
-    follow changes:     false
-    no prepopulation:   false
-    no watch:           false
-    no sound:           true
+    include:            none
+    exclude:            none
+    poll:               off
+    follow changes:     not set
+    collapse appends:   not set
+    prepopulate:        not set
+    watch:              not set
+    auto review:        off
+    config file:        default location
+    theme:              not set
+    mouse:              not set
+    sound:              false
+
+    EFFECTIVE CONFIG:
+
+    config files read:  none
+    follow changes:     false (default)
+    collapse appends:   false (default)
+    prepopulate:        true (default)
+    watch:              true (default)
+    auto review:        off (default)
+    mouse:              true (default)
+    sound:              false (flag)
+    theme:              auto (default)
+    max file size:      1048576 bytes (default)
+    max diffed size:    16777216 bytes (default)
+    max prepop files:   10000 (default)
+    max cache size:     268435456 bytes (default)
+    compress snapshots: true (default)
+    persist snapshots:  false (default)
+    debounce:           500ms (default)
+    text extensions:    none (default)
+    ignored extensions: none (default)
 
     ----- stderr -----
     ");
@@ -172,7 +747,7 @@ fn sound_flag_is_not_shown_if_feature_is_off() {
 
     // WHEN
     // THEN
-    assert_cmd_snapshot!(cmd, @r"
+    assert_cmd_snapshot!(cmd, @"
     success: true
     exit_code: 0
     ----- stdout -----
@@ -180,14 +755,242 @@ fn sound_flag_is_not_shown_if_feature_is_off() {
 
     command:            run TUI
     path:               current directory
-    follow changes:     false
-    no prepopulation:   false
-    no watch:           false
+    include:            none
+    exclude:            none
+    poll:               off
+    follow changes:     not set
+    collapse appends:   not set
+    prepopulate:        not set
+    watch:              not set
+    auto review:        off
+    config file:        default location
+    theme:              not set
+    mouse:              not set
+
+    EFFECTIVE CONFIG:
+
+    config files read:  none
+    follow changes:     false (default)
+    collapse appends:   false (default)
+    prepopulate:        true (default)
+    watch:              true (default)
+    auto review:        off (default)
+    mouse:              true (default)
+    theme:              auto (default)
+    max file size:      1048576 bytes (default)
+    max diffed size:    16777216 bytes (default)
+    max prepop files:   10000 (default)
+    max cache size:     268435456 bytes (default)
+    compress snapshots: true (default)
+    persist snapshots:  false (default)
+    debounce:           500ms (default)
+    text extensions:    none (default)
+    ignored extensions: none (default)
 
     ----- stderr -----
     ");
 }
 
+#[test]
+#[cfg(feature = "sound")]
+fn config_files_and_flags_are_merged() {
+    // GIVEN
+    let fx = Fixture::new();
+    let mut cmd = fx.cmd([
+        "run",
+        "--path",
+        "tests/fixtures/project",
+        "--config",
+        "tests/fixtures/config.toml",
+        "--auto-review",
+        "5",
+        "--debug",
+    ]);
+
+    // WHEN
+    // THEN
+    assert_cmd_snapshot!(cmd, @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    DEBUG INFO:
+
+    command:            run TUI
+    path:               tests/fixtures/project
+    include:            none
+    exclude:            none
+    poll:               off
+    follow changes:     not set
+    collapse appends:   not set
+    prepopulate:        not set
+    watch:              not set
+    auto review:        after 5s
+    config file:        tests/fixtures/config.toml
+    theme:              not set
+    mouse:              not set
+    sound:              not set
+
+    EFFECTIVE CONFIG:
+
+    config files read:  tests/fixtures/config.toml, tests/fixtures/project/.dfft.toml
+    follow changes:     true (config file)
+    collapse appends:   false (default)
+    prepopulate:        true (default)
+    watch:              true (default)
+    auto review:        after 5s (flag)
+    mouse:              true (project config file)
+    sound:              true (default)
+    theme:              light (project config file)
+    max file size:      2048 bytes (project config file)
+    max diffed size:    16777216 bytes (default)
+    max prepop files:   10000 (default)
+    max cache size:     268435456 bytes (default)
+    compress snapshots: true (default)
+    persist snapshots:  false (default)
+    debounce:           200ms (config file)
+    text extensions:    none (default)
+    ignored extensions: none (default)
+    keymap overrides:
+        [keymap.diff] "<c-n>" = "next_hunk"
+        [keymap.diff] "<c-p>" = "previous_hunk"
+
+    ----- stderr -----
+    "#);
+}
+
+#[test]
+#[cfg(feature = "sound")]
+fn flags_can_turn_off_settings_the_config_files_turn_on() {
+    // GIVEN
+    let fx = Fixture::new();
+    let mut cmd = fx.cmd([
+        "run",
+        "--path",
+        "tests/fixtures/project",
+        "--config",
+        "tests/fixtures/config.toml",
+        "--no-follow-changes",
+        "--mouse",
+        "--no-mouse",
+        "--debug",
+    ]);
+
+    // WHEN
+    // THEN
+    assert_cmd_snapshot!(cmd, @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    DEBUG INFO:
+
+    command:            run TUI
+    path:               tests/fixtures/project
+    include:            none
+    exclude:            none
+    poll:               off
+    follow changes:     false
+    collapse appends:   not set
+    prepopulate:        not set
+    watch:              not set
+    auto review:        off
+    config file:        tests/fixtures/config.toml
+    theme:              not set
+    mouse:              false
+    sound:              not set
+
+    EFFECTIVE CONFIG:
+
+    config files read:  tests/fixtures/config.toml, tests/fixtures/project/.dfft.toml
+    follow changes:     false (flag)
+    collapse appends:   false (default)
+    prepopulate:        true (default)
+    watch:              true (default)
+    auto review:        after 10s (config file)
+    mouse:              false (flag)
+    sound:              true (default)
+    theme:              light (project config file)
+    max file size:      2048 bytes (project config file)
+    max diffed size:    16777216 bytes (default)
+    max prepop files:   10000 (default)
+    max cache size:     268435456 bytes (default)
+    compress snapshots: true (default)
+    persist snapshots:  false (default)
+    debounce:           200ms (config file)
+    text extensions:    none (default)
+    ignored extensions: none (default)
+    keymap overrides:
+        [keymap.diff] "<c-n>" = "next_hunk"
+        [keymap.diff] "<c-p>" = "previous_hunk"
+
+    ----- stderr -----
+    "#);
+}
+
+#[test]
+#[cfg(feature = "sound")]
+fn project_config_files_of_other_directories_are_skipped() {
+    // GIVEN
+    let fx = Fixture::new();
+    let mut cmd = fx.cmd([
+        "run",
+        "--path",
+        "tests/fixtures/project",
+        "--path",
+        "tests/fixtures/other_project",
+        "--debug",
+    ]);
+
+    // WHEN
+    // THEN
+    assert_cmd_snapshot!(cmd, @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    DEBUG INFO:
+
+    command:            run TUI
+    path:               tests/fixtures/project, tests/fixtures/other_project
+    include:            none
+    exclude:            none
+    poll:               off
+    follow changes:     not set
+    collapse appends:   not set
+    prepopulate:        not set
+    watch:              not set
+    auto review:        off
+    config file:        default location
+    theme:              not set
+    mouse:              not set
+    sound:              not set
+
+    EFFECTIVE CONFIG:
+
+    config files read:  tests/fixtures/project/.dfft.toml
+    skipped configs:    tests/fixtures/other_project/.dfft.toml
+    follow changes:     false (default)
+    collapse appends:   false (default)
+    prepopulate:        true (default)
+    watch:              true (default)
+    auto review:        off (default)
+    mouse:              true (project config file)
+    sound:              true (default)
+    theme:              light (project config file)
+    max file size:      2048 bytes (project config file)
+    max diffed size:    16777216 bytes (default)
+    max prepop files:   10000 (default)
+    max cache size:     268435456 bytes (default)
+    compress snapshots: true (default)
+    persist snapshots:  false (default)
+    debounce:           500ms (default)
+    text extensions:    none (default)
+    ignored extensions: none (default)
+    keymap overrides:
+        [keymap.diff] "<c-p>" = "previous_hunk"
+
+    ----- stderr -----
+    "#);
+}
+
 //------------//
 //  FAILURES  //
 //------------//
@@ -227,3 +1030,132 @@ fn fails_if_provided_path_is_not_a_directory() {
     Error: path is not a directory: tests/run_test.rs
     ");
 }
+
+#[test]
+fn fails_if_provided_paths_overlap() {
+    // GIVEN
+    let fx = Fixture::new();
+    let mut cmd = fx.cmd(["run", "--path", "src", "--path", "src/tui"]);
+
+    // WHEN
+    // THEN
+    assert_cmd_snapshot!(cmd, @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    Error: paths overlap: src and src/tui
+    ");
+}
+
+#[test]
+fn fails_if_provided_glob_is_invalid() {
+    // GIVEN
+    let fx = Fixture::new();
+    let mut cmd = fx.cmd(["run", "--exclude", "src/[a-"]);
+
+    // WHEN
+    // THEN
+    assert_cmd_snapshot!(cmd, @r#"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    Error: invalid glob "src/[a-"
+
+    Caused by:
+        error parsing glob 'src/[a-': unclosed character class; missing ']'
+    "#);
+}
+
+#[test]
+fn fails_if_provided_poll_interval_is_zero() {
+    // GIVEN
+    let fx = Fixture::new();
+    let mut cmd = fx.cmd(["run", "--poll", "0"]);
+
+    // WHEN
+    // THEN
+    assert_cmd_snapshot!(cmd, @"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: invalid value '0' for '--poll [<MILLIS>]': 0 is not in 1..18446744073709551615
+
+    For more information, try '--help'.
+    ");
+}
+
+#[test]
+fn fails_if_provided_config_file_doesnt_exist() {
+    // GIVEN
+    let fx = Fixture::new();
+    let mut cmd = fx.cmd(["run", "--config", "this/doesnt/exist/8163ef59.toml"]);
+
+    // WHEN
+    // THEN
+    assert_cmd_snapshot!(cmd, @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    Error: couldn't read config file this/doesnt/exist/8163ef59.toml
+
+    Caused by:
+        No such file or directory (os error 2)
+    ");
+}
+
+#[test]
+fn fails_if_keymap_in_config_file_is_invalid() {
+    // GIVEN
+    let fx = Fixture::new();
+    let mut cmd = fx.cmd(["run", "--config", "tests/fixtures/invalid_keymap.toml"]);
+
+    // WHEN
+    // THEN
+    assert_cmd_snapshot!(cmd, @r#"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    Error: invalid keymap in config file tests/fixtures/invalid_keymap.toml
+
+    Caused by:
+        [keymap.diff] "<c-" is not a valid key
+        [keymap.prompt] "a" can't be bound, since it's typed into the prompt
+    "#);
+}
+
+#[test]
+fn fails_if_theme_is_unknown() {
+    // GIVEN
+    let fx = Fixture::new();
+    let mut cmd = fx.cmd([
+        "run",
+        "--config",
+        "tests/fixtures/themes.toml",
+        "--theme",
+        "solarized",
+    ]);
+
+    // WHEN
+    // THEN
+    assert_cmd_snapshot!(cmd, @r#"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    Error: couldn't load theme
+
+    Caused by:
+        unknown theme "solarized"; available themes: auto, dark, light, high-contrast, no-color, mine
+    "#);
+}